@@ -13,7 +13,14 @@ use anyhow::bail;
 use anyhow::ensure;
 use holochain_conductor_api::AdminRequest;
 use holochain_conductor_api::AdminResponse;
+use holochain_conductor_api::AppStatusFilter;
+use holochain_conductor_api::AppStatusInfo;
+use holochain_conductor_api::CallLimit;
+use holochain_conductor_api::CellArchive;
+use holochain_conductor_api::FullStateDump;
 use holochain_conductor_api::InterfaceDriver;
+use holochain_conductor_api::InterfaceTlsConfig;
+use holochain_conductor_api::ZomeCallLimits;
 use holochain_conductor_api::{AdminInterfaceConfig, InstalledAppInfo};
 use holochain_p2p::kitsune_p2p;
 use holochain_p2p::kitsune_p2p::agent_store::AgentInfoSigned;
@@ -42,6 +49,10 @@ pub struct Call {
     /// If this is empty existing sandboxes will be used.
     /// Cannot be combined with existing sandboxes.
     pub running: Vec<u16>,
+    #[structopt(long = "socket", conflicts_with_all = &["running", "existing_paths", "indices"], value_delimiter = ",")]
+    /// Paths to the unix sockets of running conductor admin interfaces.
+    /// Cannot be combined with ports or existing sandboxes.
+    pub sockets: Vec<PathBuf>,
     #[structopt(flatten)]
     pub existing: Existing,
     #[structopt(subcommand)]
@@ -69,9 +80,13 @@ pub enum AdminRequestCli {
     ListCells,
     /// Calls AdminRequest::ListActiveApps.
     ListActiveApps,
+    ListApps(ListApps),
     ActivateApp(ActivateApp),
     DeactivateApp(DeactivateApp),
     DumpState(DumpState),
+    DumpFullState(DumpFullState),
+    ExportCell(ExportCell),
+    ImportCell(ImportCell),
     /// Calls AdminRequest::AddAgentInfo.
     /// [Unimplemented].
     AddAgents,
@@ -93,6 +108,35 @@ pub struct AddAppWs {
     /// Optional port number.
     /// Defaults to assigned by OS.
     pub port: Option<u16>,
+    /// Only accept signed zome calls on this interface.
+    #[structopt(long)]
+    pub require_signed_zome_calls: bool,
+    /// Optional address to bind to.
+    /// Defaults to the loopback address.
+    #[structopt(long)]
+    pub bind_address: Option<std::net::IpAddr>,
+    /// Path to a PEM certificate to serve the interface over wss.
+    #[structopt(long, requires = "tls-key")]
+    pub tls_cert: Option<PathBuf>,
+    /// Path to the PEM private key matching `--tls-cert`.
+    #[structopt(long, requires = "tls-cert")]
+    pub tls_key: Option<PathBuf>,
+    /// Only accept browser connections from this origin.
+    /// Can be given multiple times.
+    #[structopt(long = "allowed-origin")]
+    pub allowed_origins: Vec<String>,
+    /// Limit the zome calls per second each connection can make.
+    #[structopt(long)]
+    pub connection_calls_per_second: Option<u32>,
+    /// Limit the zome calls each connection can have running at once.
+    #[structopt(long)]
+    pub connection_max_concurrent_calls: Option<u32>,
+    /// Limit the zome calls per second made into each app.
+    #[structopt(long)]
+    pub app_calls_per_second: Option<u32>,
+    /// Limit the zome calls running at once in each app.
+    #[structopt(long)]
+    pub app_max_concurrent_calls: Option<u32>,
 }
 
 #[derive(Debug, StructOpt, Clone)]
@@ -190,6 +234,63 @@ pub struct DumpState {
     pub agent_key: AgentPubKey,
 }
 
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AdminRequest::ListApps
+/// and reports each app's status and the health of its cells.
+pub struct ListApps {
+    #[structopt(long, parse(try_from_str = parse_status_filter))]
+    /// Only list apps with this status: `active` or `inactive`.
+    pub status: Option<AppStatusFilter>,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AdminRequest::DumpFullState
+/// and prints the cell's state as json or yaml.
+pub struct DumpFullState {
+    #[structopt(parse(try_from_str = parse_dna_hash))]
+    /// The dna hash half of the cell id to dump.
+    pub dna: DnaHash,
+    #[structopt(parse(try_from_str = parse_agent_key))]
+    /// The agent half of the cell id to dump.
+    pub agent_key: AgentPubKey,
+    #[structopt(long)]
+    /// Only dump ops after this cursor,
+    /// which is part of the previous page
+    /// and also printed to stderr after it.
+    pub cursor: Option<u64>,
+    #[structopt(long)]
+    /// Dump at most this many ops,
+    /// which must be more than 0.
+    pub limit: Option<u32>,
+    #[structopt(long, default_value = "json", possible_values = &["json", "yaml"])]
+    /// The format to print the dump in.
+    pub format: String,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AdminRequest::ExportCell
+/// and writes the cell's source chain to a file as json.
+pub struct ExportCell {
+    #[structopt(parse(try_from_str = parse_dna_hash))]
+    /// The dna hash half of the cell id to export.
+    pub dna: DnaHash,
+    #[structopt(parse(try_from_str = parse_agent_key))]
+    /// The agent half of the cell id to export.
+    pub agent_key: AgentPubKey,
+    #[structopt(short, long)]
+    /// The file to write the archive to.
+    pub output: PathBuf,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+/// Calls AdminRequest::ImportCell
+/// and restores a source chain from a file made by `export-cell`.
+/// The dna must already be registered.
+pub struct ImportCell {
+    /// The archive file to import.
+    pub path: PathBuf,
+}
+
 #[derive(Debug, StructOpt, Clone)]
 /// Calls AdminRequest::RequestAgentInfo
 /// and pretty prints the agent info on
@@ -208,9 +309,16 @@ pub async fn call(holochain_path: &Path, req: Call) -> anyhow::Result<()> {
     let Call {
         existing,
         running,
+        sockets,
         call,
     } = req;
-    let cmds = if running.is_empty() {
+    let cmds = if !sockets.is_empty() {
+        let mut cmds = Vec::with_capacity(sockets.len());
+        for path in sockets {
+            cmds.push((CmdRunner::try_new_unix(&path).await?, None));
+        }
+        cmds
+    } else if running.is_empty() {
         let paths = if existing.is_empty() {
             crate::save::load(std::env::current_dir()?)?
         } else {
@@ -293,6 +401,10 @@ async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Resul
             let apps = list_active_apps(cmd).await?;
             msg!("Active Apps: {:?}", apps);
         }
+        AdminRequestCli::ListApps(args) => {
+            let apps = list_apps(cmd, args).await?;
+            msg!("Apps: {:#?}", apps);
+        }
         AdminRequestCli::ActivateApp(args) => {
             let app_id = args.app_id.clone();
             activate_app(cmd, args).await?;
@@ -307,6 +419,36 @@ async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Resul
             let state = dump_state(cmd, args).await?;
             msg!("DUMP STATE \n{}", state);
         }
+        AdminRequestCli::DumpFullState(args) => {
+            let format = args.format.clone();
+            let state = dump_full_state(cmd, args).await?;
+            let out = match format.as_str() {
+                "yaml" => serde_yaml::to_string(&state)?,
+                _ => serde_json::to_string_pretty(&state)?,
+            };
+            // Only the dump goes to stdout so it can be parsed.
+            println!("{}", out);
+            if let Some(cursor) = state.integration_dump.dht_ops_cursor {
+                eprintln!("More ops may remain, continue with --cursor {}", cursor);
+            }
+        }
+        AdminRequestCli::ExportCell(args) => {
+            let output = args.output.clone();
+            let archive = export_cell(cmd, args).await?;
+            std::fs::write(&output, serde_json::to_vec(&archive)?)?;
+            msg!(
+                "Exported {} elements of cell {:?} to {}",
+                archive.elements.len(),
+                archive.cell_id,
+                output.display()
+            );
+        }
+        AdminRequestCli::ImportCell(args) => {
+            let archive: CellArchive = serde_json::from_slice(&std::fs::read(&args.path)?)?;
+            let cell_id = archive.cell_id.clone();
+            import_cell(cmd, archive).await?;
+            msg!("Imported cell: {:?}", cell_id);
+        }
         AdminRequestCli::AddAgents => todo!("Adding agent info via cli is coming soon"),
         AdminRequestCli::ListAgents(args) => {
             use std::fmt::Write;
@@ -365,7 +507,7 @@ pub async fn add_admin_interface(cmd: &mut CmdRunner, args: AddAdminWs) -> anyho
     let resp = cmd
         .command(AdminRequest::AddAdminInterfaces(vec![
             AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port },
+                driver: InterfaceDriver::websocket(port),
             },
         ]))
         .await?;
@@ -526,6 +668,16 @@ pub async fn list_active_apps(cmd: &mut CmdRunner) -> anyhow::Result<Vec<String>
     Ok(expect_match!(resp => AdminResponse::ActiveAppsListed, "Failed to list active apps"))
 }
 
+/// Calls [`AdminRequest::ListApps`].
+pub async fn list_apps(cmd: &mut CmdRunner, args: ListApps) -> anyhow::Result<Vec<AppStatusInfo>> {
+    let resp = cmd
+        .command(AdminRequest::ListApps {
+            status_filter: args.status,
+        })
+        .await?;
+    Ok(expect_match!(resp => AdminResponse::AppsListed, "Failed to list apps"))
+}
+
 /// Calls [`AdminRequest::ActivateApp`] and activates the installed app.
 pub async fn activate_app(cmd: &mut CmdRunner, args: ActivateApp) -> anyhow::Result<()> {
     let resp = cmd
@@ -559,7 +711,33 @@ pub async fn deactivate_app(cmd: &mut CmdRunner, args: DeactivateApp) -> anyhow:
 /// Calls [`AdminRequest::AttachAppInterface`] and adds another app interface.
 pub async fn attach_app_interface(cmd: &mut CmdRunner, args: AddAppWs) -> anyhow::Result<u16> {
     let resp = cmd
-        .command(AdminRequest::AttachAppInterface { port: args.port })
+        .command(AdminRequest::AttachAppInterface {
+            port: args.port,
+            require_signed_zome_calls: args.require_signed_zome_calls,
+            bind_address: args.bind_address,
+            tls: args
+                .tls_cert
+                .zip(args.tls_key)
+                .map(|(cert_path, key_path)| InterfaceTlsConfig {
+                    cert_path,
+                    key_path,
+                }),
+            allowed_origins: if args.allowed_origins.is_empty() {
+                None
+            } else {
+                Some(args.allowed_origins)
+            },
+            zome_call_limits: ZomeCallLimits {
+                per_connection: CallLimit {
+                    calls_per_second: args.connection_calls_per_second,
+                    max_concurrent_calls: args.connection_max_concurrent_calls,
+                },
+                per_app: CallLimit {
+                    calls_per_second: args.app_calls_per_second,
+                    max_concurrent_calls: args.app_max_concurrent_calls,
+                },
+            },
+        })
         .await?;
     tracing::debug!(?resp);
     match resp {
@@ -584,6 +762,46 @@ pub async fn dump_state(cmd: &mut CmdRunner, args: DumpState) -> anyhow::Result<
     Ok(expect_match!(resp => AdminResponse::StateDumped, "Failed to dump state"))
 }
 
+/// Calls [`AdminRequest::DumpFullState`] and dumps the cell's state as structured data.
+pub async fn dump_full_state(
+    cmd: &mut CmdRunner,
+    args: DumpFullState,
+) -> anyhow::Result<FullStateDump> {
+    let resp = cmd
+        .command(AdminRequest::DumpFullState {
+            cell_id: Box::new(CellId::new(args.dna, args.agent_key)),
+            dht_ops_cursor: args.cursor,
+            dht_ops_limit: args.limit,
+        })
+        .await?;
+    Ok(*expect_match!(resp => AdminResponse::FullStateDumped, "Failed to dump full state"))
+}
+
+/// Calls [`AdminRequest::ExportCell`] and returns the cell's signed archive.
+pub async fn export_cell(cmd: &mut CmdRunner, args: ExportCell) -> anyhow::Result<CellArchive> {
+    let resp = cmd
+        .command(AdminRequest::ExportCell {
+            cell_id: Box::new(CellId::new(args.dna, args.agent_key)),
+        })
+        .await?;
+    Ok(*expect_match!(resp => AdminResponse::CellExported, "Failed to export cell"))
+}
+
+/// Calls [`AdminRequest::ImportCell`] and restores the archived source chain.
+pub async fn import_cell(cmd: &mut CmdRunner, archive: CellArchive) -> anyhow::Result<()> {
+    let resp = cmd
+        .command(AdminRequest::ImportCell {
+            archive: Box::new(archive),
+        })
+        .await?;
+    ensure!(
+        matches!(resp, AdminResponse::CellImported),
+        "Failed to import cell, got: {:?}",
+        resp
+    );
+    Ok(())
+}
+
 /// Calls [`AdminRequest::AddAgentInfo`] with and adds the list of agent info.
 pub async fn add_agent_info(cmd: &mut CmdRunner, args: Vec<AgentInfoSigned>) -> anyhow::Result<()> {
     let resp = cmd
@@ -618,6 +836,14 @@ fn parse_dna_hash(arg: &str) -> anyhow::Result<DnaHash> {
     DnaHash::try_from(arg).map_err(|e| anyhow::anyhow!("{:?}", e))
 }
 
+fn parse_status_filter(arg: &str) -> anyhow::Result<AppStatusFilter> {
+    match arg {
+        "active" => Ok(AppStatusFilter::Active),
+        "inactive" => Ok(AppStatusFilter::Inactive),
+        _ => bail!("Unknown app status {}, expected active or inactive", arg),
+    }
+}
+
 impl From<CellId> for DumpState {
     fn from(cell_id: CellId) -> Self {
         let (dna, agent_key) = cell_id.into_dna_and_agent();
@@ -117,6 +117,7 @@ use holochain_conductor_api::{AdminRequest, AdminResponse};
 use holochain_websocket::WebsocketResult;
 use holochain_websocket::WebsocketSender;
 use ports::get_admin_api;
+use ports::get_admin_api_unix;
 
 pub use ports::force_admin_port;
 
@@ -165,6 +166,13 @@ impl CmdRunner {
         Ok(Self { client })
     }
 
+    /// Create a new connection for calling admin interface commands
+    /// over the unix domain socket of an admin interface.
+    pub async fn try_new_unix(path: &Path) -> WebsocketResult<Self> {
+        let client = get_admin_api_unix(path).await?;
+        Ok(Self { client })
+    }
+
     /// Create a command runner from a sandbox path.
     /// This expects holochain to be on the path.
     pub async fn from_sandbox(
@@ -1,4 +1,5 @@
 //! Helpers for working with websockets and ports.
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -31,11 +32,8 @@ pub async fn get_admin_ports(paths: Vec<PathBuf>) -> anyhow::Result<Vec<u16>> {
         }
         if let Some(config) = read_config(p)? {
             if let Some(ai) = config.admin_interfaces {
-                if let Some(AdminInterfaceConfig {
-                    driver: InterfaceDriver::Websocket { port },
-                }) = ai.get(0)
-                {
-                    ports.push(*port)
+                if let Some(port) = ai.get(0).and_then(|ai| ai.driver.port()) {
+                    ports.push(port)
                 }
             }
         }
@@ -48,6 +46,23 @@ pub(crate) async fn get_admin_api(port: u16) -> WebsocketResult<WebsocketSender>
     websocket_client_by_port(port).await.map(|p| p.0)
 }
 
+#[cfg(unix)]
+pub(crate) async fn get_admin_api_unix(path: &Path) -> WebsocketResult<WebsocketSender> {
+    tracing::debug!(?path);
+    ws::connect_unix(path, Arc::new(WebsocketConfig::default()))
+        .await
+        .map(|p| p.0)
+}
+
+#[cfg(not(unix))]
+pub(crate) async fn get_admin_api_unix(_path: &Path) -> WebsocketResult<WebsocketSender> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Other,
+        "unix sockets are not supported on this platform",
+    )
+    .into())
+}
+
 async fn websocket_client_by_port(
     port: u16,
 ) -> WebsocketResult<(WebsocketSender, WebsocketReceiver)> {
@@ -60,17 +75,10 @@ async fn websocket_client_by_port(
 
 pub(crate) fn random_admin_port(config: &mut ConductorConfig) {
     match config.admin_interfaces.as_mut().and_then(|i| i.first_mut()) {
-        Some(AdminInterfaceConfig {
-            driver: InterfaceDriver::Websocket { port },
-        }) => {
-            if *port != 0 {
-                *port = 0;
-            }
-        }
+        Some(AdminInterfaceConfig { driver }) => driver.set_port(0),
         None => {
-            let port = 0;
             config.admin_interfaces = Some(vec![AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port },
+                driver: InterfaceDriver::websocket(0),
             }]);
         }
     }
@@ -79,7 +87,7 @@ pub(crate) fn random_admin_port(config: &mut ConductorConfig) {
 pub(crate) fn set_admin_port(config: &mut ConductorConfig, port: u16) {
     let p = port;
     let port = AdminInterfaceConfig {
-        driver: InterfaceDriver::Websocket { port },
+        driver: InterfaceDriver::websocket(port),
     };
     match config
         .admin_interfaces
@@ -39,6 +39,15 @@ pub async fn run(
             &mut cmd,
             AddAppWs {
                 port: Some(app_port),
+                require_signed_zome_calls: false,
+                bind_address: None,
+                tls_cert: None,
+                tls_key: None,
+                allowed_origins: Vec::new(),
+                connection_calls_per_second: None,
+                connection_max_concurrent_calls: None,
+                app_calls_per_second: None,
+                app_max_concurrent_calls: None,
             },
         )
         .await?;
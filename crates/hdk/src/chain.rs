@@ -17,11 +17,24 @@ pub fn get_agent_activity(
     })
 }
 
-/// Walks the source chain in reverse (latest to oldest) filtering by header and/or entry type
+/// Query the elements of this agent's source chain, filtered by [ `ChainQueryFilter` ].
 ///
-/// Given a header and entry type, returns an [ `Vec<Element>` ]
+/// Elements are returned oldest first unless the filter is `descending`.
+/// The filter can match a range of sequence numbers, any of several header types,
+/// entry types or entry hashes, and limit how many elements are returned.
+/// All of this is done in the database so e.g. the last 10 posts are cheap to get
+/// however long the chain is.
+///
+/// ```ignore
+/// let last_posts = query(
+///     ChainQueryFilter::new()
+///         .entry_type(EntryType::App(post_entry_type))
+///         .include_entries(true)
+///         .descending()
+///         .limit(10),
+/// )?;
+/// ```
 ///
-/// @todo document this better with examples
 /// @todo implement cap grant/claim usage in terms of query
 pub fn query(filter: ChainQueryFilter) -> ExternResult<Vec<Element>> {
     HDK.with(|h| h.borrow().query(filter))
@@ -174,9 +174,10 @@ where
 ///       @todo implement a 'get optimistic' that returns based on the current opinion of the world
 ///       and performs network calls in the background so they are available 'next time'.
 ///
-/// Note: Deletes are considered in the liveness but Updates are not currently followed
-///       automatically due to the need for the happ to disambiguate update logic.
-///       @todo implement 'redirect' logic so that updates are followed by [ `get` ].
+/// Note: Deletes are considered in the liveness but Updates are only followed when getting an
+///       entry by its hash and the entry def has a `crdt_type` other than [ `CrdtType::NoMerge` ].
+///       Then the updates are followed to the latest versions and the newest of them is returned.
+///       Use [ `get_merged` ] to merge the latest versions of a set or counter.
 ///
 /// Note: Updates typically point to a different entry hash than what they are updating but not
 ///       always, e.g. consider changing `foo` to `bar` back to `foo`. The entry hashes in a crud
@@ -187,7 +188,8 @@ where
 /// Note: "oldest live" only relates to disambiguating many creates and updates from many authors
 ///       pointing to a single entry, it is not the "current value" of an entry in a CRUD sense.
 ///       e.g. If "foo" is created then updated to "bar", a [ `get` ] on the hash of "foo" will return
-///            "foo" as part of an element with the "oldest live" header.
+///            "foo" as part of an element with the "oldest live" header, unless its entry def merges
+///            updates.
 ///            To discover "bar" the agent needs to call `get_details` and decide how it wants to
 ///            collapse many potential creates, updates and deletes down into a single or filtered
 ///            set of updates, to "walk the tree".
@@ -215,6 +217,36 @@ where
     })
 }
 
+/// Get the live element for a hash once several authorities have been asked,
+/// along with how many of them responded and agreed.
+///
+/// Use this for critical data like balances or membership, where the answer
+/// of a single authority isn't enough. Unlike [ `get` ] with a quorum in its
+/// [ `GetOptions` ], this doesn't fail if too few authorities agree, so the
+/// caller can decide what to do with a weaker answer.
+///
+/// Reads are currently only answered by the agents on this conductor,
+/// so only a quorum of one is accepted, see [ `Quorum` ].
+///
+/// ```ignore
+/// let QuorumOutput { output, report } =
+///     get_with_quorum(hash, Quorum::new(1).timeout_ms(5_000))?;
+/// if report.agreeing < 1 {
+///     // no authority answered yet
+/// }
+/// ```
+pub fn get_with_quorum<H>(hash: H, quorum: Quorum) -> ExternResult<QuorumOutput<Option<Element>>>
+where
+    AnyDhtHash: From<H>,
+{
+    HDK.with(|h| {
+        h.borrow().get_with_quorum(GetInput::new(
+            AnyDhtHash::from(hash),
+            GetOptions::latest().with_quorum(quorum),
+        ))
+    })
+}
+
 /// Get an element from the hash AND the details for the entry or header hash passed in.
 /// Returns [ `None` ] if the entry/header does not exist.
 /// The details returned are a contextual mix of elements and header hashes, see below.
@@ -266,6 +298,74 @@ pub fn get_details<H: Into<AnyDhtHash>>(
     HDK.with(|h| h.borrow().get_details(GetInput::new(hash.into(), options)))
 }
 
+/// Get the live elements for many hashes at once.
+/// The results are in the same order as the hashes, with [ `None` ] for any hash
+/// that has no live element, exactly as if [ `get` ] was called for each hash.
+///
+/// This is much faster than calling [ `get` ] in a loop, e.g. for a list view,
+/// because it is a single host call and the hashes missing locally are fetched
+/// with one network request per group of authorities rather than one per hash.
+///
+/// ```ignore
+/// let hashes: Vec<EntryHash> = links.into_iter().map(|link| link.target).collect();
+/// let elements = get_many(hashes, GetOptions::default())?;
+/// ```
+pub fn get_many<H>(hashes: Vec<H>, options: GetOptions) -> ExternResult<Vec<Option<Element>>>
+where
+    AnyDhtHash: From<H>,
+{
+    HDK.with(|h| {
+        h.borrow().get_many(GetManyInput::new(
+            hashes.into_iter().map(AnyDhtHash::from).collect(),
+            options,
+        ))
+    })
+}
+
+/// Get the latest versions of an entry merged according to the `crdt_type` of its entry def.
+///
+/// For an entry hash the updates to the entry are followed to its latest versions, like [ `get` ]
+/// does, and these are merged, e.g. taking the newest for [ `CrdtType::LastWriterWins` ] or the
+/// union of every version for [ `CrdtType::GrowOnlySet` ].
+/// The merged entry is returned along with the elements it was merged from.
+/// It doesn't match the entry hash of any of their headers when a set or counter was merged
+/// from more than one version, so it is kept out of those elements.
+///
+/// A header hash, or an entry that doesn't merge, gives the entry of the element [ `get` ]
+/// would return. [ `None` ] is returned if there is no such element or it has no entry.
+///
+/// ```ignore
+/// #[hdk_entry(id = "tags", crdt_type = "grow_only_set")]
+/// struct Tags(GrowOnlySet);
+///
+/// let tags: Option<Tags> = match get_merged(tags_entry_hash, GetOptions::default())? {
+///     Some(merged) => Some(Tags::try_from(&merged.entry)?),
+///     None => None,
+/// };
+/// ```
+pub fn get_merged<H: Into<AnyDhtHash>>(
+    hash: H,
+    options: GetOptions,
+) -> ExternResult<Option<MergedEntry>> {
+    HDK.with(|h| h.borrow().get_merged(GetInput::new(hash.into(), options)))
+}
+
+/// Get the [ `Details` ] for many hashes at once.
+/// The results are in the same order as the hashes, exactly as if
+/// [ `get_details` ] was called for each hash, but with a single host call
+/// and batched network requests like [ `get_many` ].
+pub fn get_details_many<H: Into<AnyDhtHash>>(
+    hashes: Vec<H>,
+    options: GetOptions,
+) -> ExternResult<Vec<Option<Details>>> {
+    HDK.with(|h| {
+        h.borrow().get_details_many(GetManyInput::new(
+            hashes.into_iter().map(Into::into).collect(),
+            options,
+        ))
+    })
+}
+
 /// Trait for binding static [ `EntryDef` ] property access for a type.
 /// See [ `register_entry` ]
 pub trait EntryDefRegistration {
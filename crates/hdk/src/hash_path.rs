@@ -53,3 +53,10 @@ pub mod path;
 ///
 /// The tests in the shard module include several examples of the DSL including multibyte characters.
 pub mod shard;
+
+/// Time ordered collections of links built on [ `path::Path` ].
+///
+/// Entries are linked from a UTC `year.month.day.hour` bucket path for the time they are indexed at,
+/// e.g. `feed.2021.03.04.05`, and the links between two times are found by walking down only the
+/// branches of the tree that overlap them.
+pub mod time_index;
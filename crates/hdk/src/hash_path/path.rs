@@ -1,3 +1,4 @@
+use crate::hash_path::shard::HashedShardStrategy;
 use crate::hash_path::shard::ShardStrategy;
 use crate::hash_path::shard::SHARDEND;
 use crate::prelude::*;
@@ -144,7 +145,7 @@ pub struct Path(Vec<Component>);
 
 entry_def!(Path EntryDef {
     id: core::str::from_utf8(&NAME).unwrap().into(),
-    crdt_type: CrdtType,
+    crdt_type: CrdtType::NoMerge,
     required_validations: RequiredValidations::default(),
     visibility: EntryVisibility::Public,
     required_validation_type: RequiredValidationType::default(),
@@ -196,6 +197,11 @@ impl AsRef<Vec<Component>> for Path {
 /// - foo.1:3#barbazii => width 1, depth 3, ["foo", "b", "a", "r", "barbazii"]
 /// - foo.2:3#barbazii => width 2, depth 3, ["foo", "ba", "rb", "az", "barbazii"]
 ///
+/// Put an `h` after the depth to shard the hash of the component instead, see [ `HashedShardStrategy` ].
+///
+/// e.g.
+/// - foo.1:2h#bar => width 1, depth 2, ["foo", "x", "y", "bar"] where "xy" starts the hex of the hash of "bar"
+///
 /// Note that this all works because the components and sharding for strings maps to fixed-width
 /// utf32 bytes under the hood rather than variable width bytes.
 impl From<&str> for Path {
@@ -203,18 +209,30 @@ impl From<&str> for Path {
         Self(
             s.split(DELIMITER)
                 .filter(|s| !s.is_empty())
-                .flat_map(|s| match ShardStrategy::from_str(s) {
-                    // Handle a strategy if one is found.
-                    Ok(strategy) => {
-                        let (_strategy, component) = s.split_at(s.find(SHARDEND).unwrap());
+                .flat_map(|s| {
+                    let sharded = s.find(SHARDEND).and_then(|end_index| {
+                        let (_strategy, component) = s.split_at(end_index);
                         let component = component.trim_start_matches(SHARDEND);
-                        let shard_path = Path::from((&strategy, component));
-                        let mut shard_components: Vec<Component> = shard_path.into();
-                        shard_components.push(Component::from(component));
-                        shard_components
+                        match (HashedShardStrategy::from_str(s), ShardStrategy::from_str(s)) {
+                            (Ok(strategy), _) => {
+                                Some((Path::from((&strategy, component)), component))
+                            }
+                            (_, Ok(strategy)) => {
+                                Some((Path::from((&strategy, component)), component))
+                            }
+                            _ => None,
+                        }
+                    });
+                    match sharded {
+                        // Handle a strategy if one is found.
+                        Some((shard_path, component)) => {
+                            let mut shard_components: Vec<Component> = shard_path.into();
+                            shard_components.push(Component::from(component));
+                            shard_components
+                        }
+                        // No strategy. Use the component directly.
+                        None => vec![Component::from(s)],
                     }
-                    // No strategy. Use the component directly.
-                    Err(_) => vec![Component::from(s)],
                 })
                 .collect(),
         )
@@ -305,6 +323,45 @@ impl Path {
         Ok(holochain_zome_types::link::Links::from(unwrapped))
     }
 
+    /// The paths one level below this one that have been ensured.
+    /// Unlike [ `Path::children` ] this never creates any entries.
+    pub fn existing_children(&self) -> ExternResult<Vec<Path>> {
+        let links = get_links(
+            self.hash()?,
+            Some(holochain_zome_types::link::LinkTag::new(NAME)),
+        )?;
+        let mut children: Vec<Path> = links
+            .into_inner()
+            .iter()
+            .filter_map(|link| Path::try_from(&link.tag).ok())
+            .collect();
+        children.sort_unstable_by(|a, b| {
+            a.as_ref()
+                .last()
+                .map(AsRef::<[u8]>::as_ref)
+                .cmp(&b.as_ref().last().map(AsRef::<[u8]>::as_ref))
+        });
+        children.dedup();
+        Ok(children)
+    }
+
+    /// Walk down the tree and list all the paths `depth` levels below this one.
+    ///
+    /// For a sharded path this lists the data below the shards,
+    /// e.g. the leaves of `foo` at depth 3 include `foo.a.b.abc`.
+    /// Every level is fetched from the DHT, so this is only suited to small trees.
+    pub fn leaves(&self, depth: usize) -> ExternResult<Vec<Path>> {
+        let mut level = vec![self.clone()];
+        for _ in 0..depth {
+            let mut next = vec![];
+            for path in level.iter() {
+                next.extend(path.existing_children()?);
+            }
+            level = next;
+        }
+        Ok(level)
+    }
+
     pub fn children_details(&self) -> ExternResult<holochain_zome_types::link::LinkDetails> {
         Self::ensure(&self)?;
         get_link_details(
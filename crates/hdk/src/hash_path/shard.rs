@@ -1,11 +1,14 @@
 use crate::hash_path::path::Component;
 use crate::hash_path::path::Path;
+use crate::prelude::ExternResult;
 use std::str::FromStr;
 
 /// Separates the shard width and depth.
 pub const SHARDSPLIT: &str = ":";
 /// Terminates the end of a shard shorthand.
 pub const SHARDEND: &str = "#";
+/// Follows the shard depth to shard a hash of the data, e.g. `2:3h#`.
+pub const SHARDHASHED: &str = "h";
 
 /// The width of a shard is how many bytes/characters to use for each path component in sharding.
 /// e.g. abcdef with width 1 shards to a.b.c.d.e.f.abcdef and 2 shards to ab.cd.ef.abcdef.
@@ -19,9 +22,44 @@ pub type ShardDepth = u32;
 /// At the moment sharding only works well for data that is reliably longer than width/depth.
 /// For example, sharding the username foo with width 4 doesn't make sense.
 /// There is no magic padding or extending of the provided data to make up undersized shards.
-/// @todo stretch short shards out in a nice balanced way (append some bytes from the hash?)
+/// Use a [ `HashedShardStrategy` ] for data that may be short or unevenly distributed.
 pub struct ShardStrategy(ShardWidth, ShardDepth);
 
+#[derive(Debug)]
+/// A strategy that shards a hash of the data instead of the data itself.
+///
+/// Data of any length can be sharded and, because the hash is uniformly distributed,
+/// the shards fill up evenly even when the data shares common prefixes.
+/// The price is that the shards no longer say anything about the data,
+/// so the tree can't be used for prefix searches.
+///
+/// Strings shard the lowercase hex of the blake2b-256 hash of their utf8 bytes,
+/// so each unit of width is one hex character and each level has up to `16^width` shards.
+/// Bytes shard the raw hash bytes.
+/// Width times depth beyond the size of the hash is truncated as for [ `ShardStrategy` ].
+pub struct HashedShardStrategy(ShardWidth, ShardDepth);
+
+impl HashedShardStrategy {
+    fn width(&self) -> ShardWidth {
+        self.0
+    }
+
+    fn depth(&self) -> ShardDepth {
+        self.1
+    }
+
+    /// The strategy applied to the hash.
+    fn inner(&self) -> ShardStrategy {
+        ShardStrategy(self.width(), self.depth())
+    }
+
+    /// List the paths of all the data sharded below `root`.
+    /// See [ `Path::leaves` ].
+    pub fn leaves(&self, root: &Path) -> ExternResult<Vec<Path>> {
+        root.leaves(self.depth() as usize + 1)
+    }
+}
+
 /// impl [ `ShardStrategy` ] as an immutable/read-only thingy.
 impl ShardStrategy {
     fn width(&self) -> ShardWidth {
@@ -31,6 +69,12 @@ impl ShardStrategy {
     fn depth(&self) -> ShardDepth {
         self.1
     }
+
+    /// List the paths of all the data sharded below `root`.
+    /// See [ `Path::leaves` ].
+    pub fn leaves(&self, root: &Path) -> ExternResult<Vec<Path>> {
+        root.leaves(self.depth() as usize + 1)
+    }
 }
 
 #[derive(Debug)]
@@ -47,6 +91,8 @@ pub enum ParseShardStrategyError {
     FirstCharNotADigit,
     /// The sharding definition is empty.
     EmptyString,
+    /// The sharding definition is not followed by the hashed marker.
+    NotHashed,
 }
 
 /// Attempt to parse a "width:depth#" shard out of a string.
@@ -98,6 +144,28 @@ impl FromStr for ShardStrategy {
     }
 }
 
+/// A hashed shard strategy is parsed as "width:depthh#..." at the start of a string.
+impl FromStr for HashedShardStrategy {
+    type Err = ParseShardStrategyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let end_index = s
+            .find(SHARDEND)
+            .ok_or(ParseShardStrategyError::ShardEndNotFound)?;
+        let (maybe_strategy, _) = s.split_at(end_index);
+        if !maybe_strategy.ends_with(SHARDHASHED) {
+            return Err(ParseShardStrategyError::NotHashed);
+        }
+        let unhashed = format!(
+            "{}{}",
+            maybe_strategy.trim_end_matches(SHARDHASHED),
+            SHARDEND
+        );
+        let ShardStrategy(width, depth) = ShardStrategy::from_str(&unhashed)?;
+        Ok(HashedShardStrategy(width, depth))
+    }
+}
+
 /// Builds a path for a shard strategy and some binary bytes.
 /// This is the trivial case, we just split the bytes out one by one and make a path from it.
 impl From<(&ShardStrategy, &[u8])> for Path {
@@ -177,6 +245,52 @@ impl From<(&ShardStrategy, String)> for Path {
     }
 }
 
+/// The hash a [ `HashedShardStrategy` ] shards.
+fn blake2b_256(bytes: &[u8]) -> blake2b_simd::Hash {
+    blake2b_simd::Params::new().hash_length(32).hash(bytes)
+}
+
+/// Builds a path from the shards of the hash of some binary bytes.
+impl From<(&HashedShardStrategy, &[u8])> for Path {
+    fn from((strategy, bytes): (&HashedShardStrategy, &[u8])) -> Path {
+        Path::from((&strategy.inner(), blake2b_256(bytes).as_bytes()))
+    }
+}
+/// Wrapper around &Vec<u8> to work the same as &[u8].
+impl From<(&HashedShardStrategy, &Vec<u8>)> for Path {
+    fn from((strategy, bytes): (&HashedShardStrategy, &Vec<u8>)) -> Path {
+        let bytes: &[u8] = bytes.as_ref();
+        Path::from((strategy, bytes))
+    }
+}
+/// Wrapper around Vec<u8> to work the same as &[u8].
+impl From<(&HashedShardStrategy, Vec<u8>)> for Path {
+    fn from((strategy, bytes): (&HashedShardStrategy, Vec<u8>)) -> Path {
+        let bytes: &[u8] = bytes.as_ref();
+        Path::from((strategy, bytes))
+    }
+}
+/// Builds a path from the shards of the hex of the hash of a string.
+/// The components are strings so they can be read back like any other string path.
+impl From<(&HashedShardStrategy, &str)> for Path {
+    fn from((strategy, s): (&HashedShardStrategy, &str)) -> Path {
+        let hex = blake2b_256(s.as_bytes()).to_hex();
+        Path::from((&strategy.inner(), hex.as_str()))
+    }
+}
+/// [ `&String` ] wrapper mimicing [ `&str` ] for [ `Path` ] building.
+impl From<(&HashedShardStrategy, &String)> for Path {
+    fn from((strategy, s): (&HashedShardStrategy, &String)) -> Path {
+        Path::from((strategy, s.as_str()))
+    }
+}
+// [ `String` ] wrapper mimicing [ `&str` ] for [ `Path` ] building.
+impl From<(&HashedShardStrategy, String)> for Path {
+    fn from((strategy, s): (&HashedShardStrategy, String)) -> Path {
+        Path::from((strategy, s.as_str()))
+    }
+}
+
 #[test]
 #[cfg(test)]
 fn hash_path_shard_bytes() {
@@ -262,3 +376,58 @@ fn hash_path_shard_string() {
         );
     }
 }
+
+#[test]
+#[cfg(test)]
+fn hash_path_shard_hashed() {
+    use std::convert::TryFrom;
+
+    fn is_hex(component: &Component, width: usize) -> bool {
+        let s = String::try_from(component).unwrap();
+        s.len() == width && s.chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    // Short data still fills every level.
+    for (width, depth, s) in vec![(1, 1, "a"), (2, 3, "a"), (4, 4, "foo"), (1, 2, "")] {
+        let strategy = HashedShardStrategy(width, depth);
+        let path = Path::from((&strategy, s));
+        let components: Vec<Component> = path.clone().into();
+        assert_eq!(depth as usize, components.len());
+        assert!(components.iter().all(|c| is_hex(c, width as usize)));
+        // Hashing is deterministic.
+        assert_eq!(path, Path::from((&strategy, s.to_string())));
+    }
+    assert_eq!(
+        2,
+        Vec::<Component>::from(Path::from((&HashedShardStrategy(3, 2), vec![1_u8]))).len()
+    );
+
+    // Data with a common prefix is spread across the shards.
+    let strategy = HashedShardStrategy(1, 1);
+    let mut counts = std::collections::HashMap::new();
+    for i in 0..1600 {
+        let path = Path::from((&strategy, format!("user{}", i).as_str()));
+        let shard: Vec<u8> = path.as_ref()[0].clone().into();
+        *counts.entry(shard).or_insert(0) += 1;
+    }
+    assert_eq!(16, counts.len());
+    assert!(counts.values().all(|count| *count > 50));
+
+    // The DSL keeps the full data as the last component.
+    let path = Path::from("foo.1:2h#a");
+    let components: Vec<Component> = path.into();
+    assert_eq!(4, components.len());
+    assert_eq!(Component::from("foo"), components[0]);
+    assert!(is_hex(&components[1], 1));
+    assert!(is_hex(&components[2], 1));
+    assert_eq!(Component::from("a"), components[3]);
+    assert_eq!(
+        Path::from(vec![components[1].clone(), components[2].clone()]),
+        Path::from((&HashedShardStrategy(1, 2), "a"))
+    );
+
+    // Unhashed strategies are parsed as before.
+    assert!(HashedShardStrategy::from_str("1:2#a").is_err());
+    assert!(ShardStrategy::from_str("1:2h#a").is_err());
+    assert_eq!(Path::from("foo.f.o.foo"), Path::from("foo.1:2#foo"));
+}
@@ -0,0 +1,539 @@
+use crate::hash_path::path::Component;
+use crate::hash_path::path::Path;
+use crate::prelude::*;
+use std::collections::VecDeque;
+
+/// "hdk.time" as utf8 bytes
+/// Starts the tag of every link from a time index bucket to an indexed entry.
+/// Keeps the indexed links apart from any other links on the bucket's path.
+pub const TIME_NAME: [u8; 8] = [0x68, 0x64, 0x6b, 0x2e, 0x74, 0x69, 0x6d, 0x65];
+
+/// The number of bytes used to encode the indexed time in a link tag.
+const TIME_BYTES: usize = 12;
+
+/// How finely a [ `TimeIndex` ] splits time into buckets.
+///
+/// Each bucket is a [ `Path` ] below the root of the index so a finer granularity
+/// means smaller buckets but a deeper tree to walk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TimeIndexGranularity {
+    /// `root.yyyy`
+    Year,
+    /// `root.yyyy.mm`
+    Month,
+    /// `root.yyyy.mm.dd`
+    Day,
+    /// `root.yyyy.mm.dd.hh`
+    Hour,
+}
+
+impl TimeIndexGranularity {
+    /// How many path components below the root the buckets are.
+    fn depth(&self) -> usize {
+        match self {
+            Self::Year => 1,
+            Self::Month => 2,
+            Self::Day => 3,
+            Self::Hour => 4,
+        }
+    }
+}
+
+impl Default for TimeIndexGranularity {
+    fn default() -> Self {
+        Self::Hour
+    }
+}
+
+/// A time ordered collection of links built on [ `Path` ].
+///
+/// Entries are indexed by linking to them from the bucket of the time they are indexed at.
+/// The buckets are UTC `year.month.day.hour` paths below the root so that a
+/// [ `TimeIndexRange` ] can walk only the part of the tree between two times.
+///
+/// e.g. indexing a post in a "feed" index:
+///
+/// ```ignore
+/// let feed = TimeIndex::new("feed");
+/// feed.index_now(hash_entry(&post)?, ())?;
+/// for indexed in feed.range(from, until).newest_first() {
+///     let indexed = indexed?;
+///     // indexed.timestamp, indexed.link.target ...
+/// }
+/// ```
+///
+/// As with all paths the index entries must be included in the zome's entry defs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeIndex {
+    root: Path,
+    granularity: TimeIndexGranularity,
+}
+
+/// A link found by walking a [ `TimeIndex` ].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeIndexedLink {
+    /// The time the target was indexed at.
+    pub timestamp: Timestamp,
+    /// The tag given when the target was indexed.
+    pub tag: LinkTag,
+    /// The link from the bucket to the target.
+    pub link: Link,
+}
+
+impl TimeIndex {
+    /// A time index below `root` with hourly buckets.
+    pub fn new<P: Into<Path>>(root: P) -> Self {
+        Self {
+            root: root.into(),
+            granularity: TimeIndexGranularity::default(),
+        }
+    }
+
+    /// Use buckets of this size.
+    /// Every agent using the same index must agree on the granularity.
+    pub fn with_granularity(mut self, granularity: TimeIndexGranularity) -> Self {
+        self.granularity = granularity;
+        self
+    }
+
+    /// The bucket path that `timestamp` falls into.
+    pub fn path_for(&self, timestamp: Timestamp) -> Path {
+        let mut components: Vec<Component> = self.root.clone().into();
+        let key = bucket_key(timestamp);
+        for (level, value) in key.iter().take(self.granularity.depth()).enumerate() {
+            components.push(Component::from(if level == 0 {
+                format!("{:04}", value)
+            } else {
+                format!("{:02}", value)
+            }));
+        }
+        components.into()
+    }
+
+    /// Index `target` at `timestamp`.
+    /// Ensures the bucket path exists then links from it to the target.
+    pub fn index<T: Into<LinkTag>>(
+        &self,
+        target: EntryHash,
+        timestamp: Timestamp,
+        tag: T,
+    ) -> ExternResult<HeaderHash> {
+        let path = self.path_for(timestamp);
+        path.ensure()?;
+        create_link(path.hash()?, target, encode_tag(timestamp, &tag.into()))
+    }
+
+    /// Index `target` at the current [ `sys_time` ].
+    pub fn index_now<T: Into<LinkTag>>(
+        &self,
+        target: EntryHash,
+        tag: T,
+    ) -> ExternResult<HeaderHash> {
+        let now = sys_time()?;
+        self.index(
+            target,
+            Timestamp(now.as_secs() as i64, now.subsec_nanos()),
+            tag,
+        )
+    }
+
+    /// Lazily walk the links indexed from `from` up to but not including `until`.
+    ///
+    /// Nothing is fetched until the range is iterated and only the
+    /// buckets overlapping the range are visited.
+    pub fn range(&self, from: Timestamp, until: Timestamp) -> TimeIndexRange {
+        TimeIndexRange {
+            index: self.clone(),
+            from,
+            until,
+            from_key: bucket_key(from),
+            until_key: bucket_key(until),
+            newest_first: false,
+            pending: vec![(self.root.clone(), vec![])],
+            links: VecDeque::new(),
+        }
+    }
+
+    /// The time indexed links of a single bucket in the range.
+    fn bucket_links(
+        &self,
+        path: &Path,
+        from: Timestamp,
+        until: Timestamp,
+    ) -> ExternResult<Vec<TimeIndexedLink>> {
+        let links = get_links(path.hash()?, Some(LinkTag::new(TIME_NAME)))?;
+        let mut links: Vec<TimeIndexedLink> = links
+            .into_inner()
+            .into_iter()
+            .filter_map(|link| {
+                let (timestamp, tag) = decode_tag(&link.tag)?;
+                Some(TimeIndexedLink {
+                    timestamp,
+                    tag,
+                    link,
+                })
+            })
+            .filter(|indexed| indexed.timestamp >= from && indexed.timestamp < until)
+            .collect();
+        links.sort_by(|a, b| {
+            (a.timestamp, &a.link.create_link_hash).cmp(&(b.timestamp, &b.link.create_link_hash))
+        });
+        Ok(links)
+    }
+}
+
+/// An iterator over the links of a [ `TimeIndex` ] between two times.
+///
+/// Buckets are fetched one at a time as the iterator is advanced.
+/// Links come oldest first unless [ `TimeIndexRange::newest_first` ] is set.
+/// The walk stops after the first error.
+pub struct TimeIndexRange {
+    index: TimeIndex,
+    from: Timestamp,
+    until: Timestamp,
+    from_key: Vec<i64>,
+    until_key: Vec<i64>,
+    newest_first: bool,
+    /// Paths still to visit, the next one last.
+    pending: Vec<(Path, Vec<i64>)>,
+    /// Links of the last visited bucket not yet returned.
+    links: VecDeque<TimeIndexedLink>,
+}
+
+impl TimeIndexRange {
+    /// Walk the range from the newest link to the oldest.
+    /// Must be set before iterating.
+    pub fn newest_first(mut self) -> Self {
+        self.newest_first = true;
+        self
+    }
+
+    fn visit(&mut self, path: Path, key: Vec<i64>) -> ExternResult<()> {
+        if key.len() == self.index.granularity.depth() {
+            let mut links = self.index.bucket_links(&path, self.from, self.until)?;
+            if self.newest_first {
+                links.reverse();
+            }
+            self.links.extend(links);
+        } else {
+            let level = key.len();
+            let from_key = &self.from_key[..=level];
+            let until_key = &self.until_key[..=level];
+            let mut children: Vec<(Path, Vec<i64>)> = child_buckets(&path)?
+                .into_iter()
+                .map(|(child, value)| {
+                    let mut child_key = key.clone();
+                    child_key.push(value);
+                    (child, child_key)
+                })
+                .filter(|(_, child_key)| {
+                    from_key <= child_key.as_slice() && child_key.as_slice() <= until_key
+                })
+                .collect();
+            children.sort_by(|a, b| a.1.cmp(&b.1));
+            // The stack is popped from the end.
+            if !self.newest_first {
+                children.reverse();
+            }
+            self.pending.extend(children);
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for TimeIndexRange {
+    type Item = ExternResult<TimeIndexedLink>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(link) = self.links.pop_front() {
+                return Some(Ok(link));
+            }
+            let (path, key) = self.pending.pop()?;
+            if let Err(e) = self.visit(path, key) {
+                self.pending.clear();
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+/// The existing buckets one level below `path` and their numeric values.
+fn child_buckets(path: &Path) -> ExternResult<Vec<(Path, i64)>> {
+    let mut children: Vec<(Path, i64)> = path
+        .existing_children()?
+        .into_iter()
+        .filter_map(|child| {
+            let value = String::try_from(child.as_ref().last()?)
+                .ok()?
+                .parse()
+                .ok()?;
+            Some((child, value))
+        })
+        .collect();
+    children.sort_by_key(|(_, value)| *value);
+    children.dedup_by_key(|(_, value)| *value);
+    Ok(children)
+}
+
+/// The UTC `[year, month, day, hour]` a timestamp falls into.
+fn bucket_key(timestamp: Timestamp) -> Vec<i64> {
+    let days = timestamp.0.div_euclid(86_400);
+    let hour = timestamp.0.rem_euclid(86_400) / 3_600;
+    let (year, month, day) = civil_from_days(days);
+    vec![year, month, day, hour]
+}
+
+/// The proleptic Gregorian date of a number of days since 1970-01-01.
+/// See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// The link tag is [ `TIME_NAME` ], the indexed time then the caller's tag.
+/// The time is encoded big endian with the sign flipped so tags sort by time.
+fn encode_tag(timestamp: Timestamp, tag: &LinkTag) -> LinkTag {
+    let secs = (timestamp.0 as u64) ^ (1 << 63);
+    LinkTag::new(
+        TIME_NAME
+            .iter()
+            .copied()
+            .chain(secs.to_be_bytes().iter().copied())
+            .chain(timestamp.1.to_be_bytes().iter().copied())
+            .chain(tag.as_ref().iter().copied())
+            .collect::<Vec<u8>>(),
+    )
+}
+
+fn decode_tag(tag: &LinkTag) -> Option<(Timestamp, LinkTag)> {
+    let bytes = tag.as_ref();
+    if !bytes.starts_with(&TIME_NAME) || bytes.len() < TIME_NAME.len() + TIME_BYTES {
+        return None;
+    }
+    let bytes = &bytes[TIME_NAME.len()..];
+    let (time, tag) = bytes.split_at(TIME_BYTES);
+    let secs = u64::from_be_bytes(time[..8].try_into().ok()?) ^ (1 << 63);
+    let nanos = u32::from_be_bytes(time[8..].try_into().ok()?);
+    Some((Timestamp(secs as i64, nanos), LinkTag::new(tag.to_vec())))
+}
+
+#[test]
+#[cfg(test)]
+fn hash_path_time_index_buckets() {
+    for (secs, key) in vec![
+        (0, vec![1970, 1, 1, 0]),
+        (86_399, vec![1970, 1, 1, 23]),
+        (951_782_400, vec![2000, 2, 29, 0]),
+        (1_609_459_199, vec![2020, 12, 31, 23]),
+        (1_609_459_200, vec![2021, 1, 1, 0]),
+        (-1, vec![1969, 12, 31, 23]),
+    ] {
+        assert_eq!(key, bucket_key(Timestamp(secs, 0)));
+    }
+
+    let index = TimeIndex::new("feed");
+    assert_eq!(
+        Path::from("feed.2021.03.04.05"),
+        index.path_for(Timestamp(1_614_834_000, 0)),
+    );
+    assert_eq!(
+        Path::from("feed.2021.03"),
+        index
+            .with_granularity(TimeIndexGranularity::Month)
+            .path_for(Timestamp(1_614_834_000, 0)),
+    );
+}
+
+#[test]
+#[cfg(test)]
+fn hash_path_time_index_tag() {
+    let tag = LinkTag::new(vec![1, 2, 3]);
+    for timestamp in vec![
+        Timestamp(0, 0),
+        Timestamp(1_614_834_000, 999_999_999),
+        Timestamp(-1, 5),
+    ] {
+        assert_eq!(
+            Some((timestamp, tag.clone())),
+            decode_tag(&encode_tag(timestamp, &tag))
+        );
+    }
+    // Tags sort in time order.
+    assert!(encode_tag(Timestamp(-1, 0), &tag) < encode_tag(Timestamp(0, 0), &tag));
+    assert!(encode_tag(Timestamp(1, 0), &tag) < encode_tag(Timestamp(1, 1), &tag));
+    // Other links are ignored.
+    assert_eq!(
+        None,
+        decode_tag(&LinkTag::new(crate::hash_path::path::NAME))
+    );
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod mock_tests {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashMap;
+    use std::hash::Hash;
+    use std::hash::Hasher;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    /// 2021-03-04T05:00:00Z
+    const HOUR: i64 = 1_614_834_000;
+
+    /// Stands in for the host's hashing so every path has a stable, distinct hash.
+    fn fake_hash(entry: &Entry) -> EntryHash {
+        let mut hasher = DefaultHasher::new();
+        format!("{:?}", entry).hash(&mut hasher);
+        EntryHash::from_raw_36(
+            hasher
+                .finish()
+                .to_le_bytes()
+                .iter()
+                .copied()
+                .cycle()
+                .take(36)
+                .collect(),
+        )
+    }
+
+    fn path_hash(path: &Path) -> EntryHash {
+        fake_hash(&Entry::try_from(path).unwrap())
+    }
+
+    /// Mock the links that indexing at each of `times` would have created.
+    /// The target of each indexed link is made from its position in `times`.
+    /// Returns the bases that `get_links` is called on.
+    fn mock_index(index: &TimeIndex, times: &[Timestamp]) -> Arc<Mutex<Vec<EntryHash>>> {
+        let mut links: HashMap<EntryHash, Vec<Link>> = HashMap::new();
+        for (i, timestamp) in times.iter().enumerate() {
+            let bucket = index.path_for(*timestamp);
+            links.entry(path_hash(&bucket)).or_default().push(Link {
+                target: EntryHash::from_raw_36(vec![i as u8; 36]),
+                timestamp: *timestamp,
+                tag: encode_tag(*timestamp, &LinkTag::new(vec![i as u8])),
+                create_link_hash: HeaderHash::from_raw_36(vec![i as u8; 36]),
+            });
+            let mut child = bucket;
+            while let Some(parent) = child.parent() {
+                links.entry(path_hash(&parent)).or_default().push(Link {
+                    target: path_hash(&child),
+                    timestamp: *timestamp,
+                    tag: LinkTag::try_from(&child).unwrap(),
+                    create_link_hash: HeaderHash::from_raw_36(vec![0; 36]),
+                });
+                child = parent;
+            }
+        }
+
+        let visited = Arc::new(Mutex::new(Vec::new()));
+        let visited_closure = visited.clone();
+        let mut mock_hdk = MockHdkT::new();
+        mock_hdk
+            .expect_hash_entry()
+            .returning(|entry| Ok(fake_hash(&entry)));
+        mock_hdk.expect_get_links().returning(move |input| {
+            visited_closure
+                .lock()
+                .unwrap()
+                .push(input.base_address.clone());
+            let prefix = input.tag_prefix.unwrap_or_else(|| LinkTag::new(vec![]));
+            Ok(links
+                .get(&input.base_address)
+                .cloned()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|link| link.tag.as_ref().starts_with(prefix.as_ref()))
+                .collect::<Vec<Link>>()
+                .into())
+        });
+        set_hdk(mock_hdk);
+        visited
+    }
+
+    fn targets(range: TimeIndexRange) -> Vec<u8> {
+        range
+            .map(|indexed| indexed.unwrap().link.target.get_raw_36()[0])
+            .collect()
+    }
+
+    #[test]
+    fn hash_path_time_index_range() {
+        let index = TimeIndex::new("feed");
+        let times = vec![
+            // 0: inside the first bucket
+            Timestamp(HOUR + 10, 0),
+            // 1: two hours later
+            Timestamp(HOUR + 2 * 3_600, 0),
+            // 2: earlier in the first bucket than 0
+            Timestamp(HOUR + 5, 0),
+            // 3: exactly at the end of the range
+            Timestamp(HOUR + 86_400, 0),
+            // 4: the hour before the range
+            Timestamp(HOUR - 1, 0),
+            // 5: the hour after the end of the range
+            Timestamp(HOUR + 86_400 + 3_600, 0),
+            // 6: a year before the range
+            Timestamp(HOUR - 400 * 86_400, 0),
+            // 7: the same time as 2, ordered by the link hash
+            Timestamp(HOUR + 5, 0),
+        ];
+        let visited = mock_index(&index, &times);
+        let from = Timestamp(HOUR, 0);
+        let until = Timestamp(HOUR + 86_400, 0);
+
+        // - Links come in time order and only from inside the range
+        assert_eq!(vec![2, 7, 0, 1], targets(index.range(from, until)));
+        assert_eq!(
+            vec![1, 0, 7, 2],
+            targets(index.range(from, until).newest_first())
+        );
+
+        // - Buckets and years outside the range are never fetched
+        let visited = visited.lock().unwrap();
+        for outside in vec![
+            index.path_for(times[4]),
+            index.path_for(times[5]),
+            Path::from("feed.2020"),
+        ] {
+            assert!(!visited.contains(&path_hash(&outside)));
+        }
+        // - The bucket holding the end of the range is checked
+        //   because it may contain links from before the end
+        assert!(visited.contains(&path_hash(&index.path_for(times[3]))));
+    }
+
+    #[test]
+    fn hash_path_time_index_range_is_lazy() {
+        let index = TimeIndex::new("feed").with_granularity(TimeIndexGranularity::Day);
+        let times = vec![Timestamp(HOUR, 0), Timestamp(HOUR + 2 * 86_400, 0)];
+        let visited = mock_index(&index, &times);
+
+        let mut range = index.range(Timestamp(HOUR - 86_400, 0), Timestamp(HOUR + 3 * 86_400, 0));
+        assert_eq!(
+            0,
+            range.next().unwrap().unwrap().link.target.get_raw_36()[0]
+        );
+        // Only the first bucket has been fetched so far.
+        assert!(!visited
+            .lock()
+            .unwrap()
+            .contains(&path_hash(&index.path_for(times[1]))));
+
+        assert_eq!(
+            1,
+            range.next().unwrap().unwrap().link.target.get_raw_36()[0]
+        );
+        assert!(range.next().is_none());
+    }
+}
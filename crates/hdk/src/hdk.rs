@@ -39,7 +39,11 @@ pub trait HdkT: Send + Sync {
     fn delete(&self, hash: HeaderHash) -> ExternResult<HeaderHash>;
     fn hash_entry(&self, entry: Entry) -> ExternResult<EntryHash>;
     fn get(&self, get_input: GetInput) -> ExternResult<Option<Element>>;
+    fn get_with_quorum(&self, get_input: GetInput) -> ExternResult<QuorumOutput<Option<Element>>>;
     fn get_details(&self, get_input: GetInput) -> ExternResult<Option<Details>>;
+    fn get_many(&self, get_many_input: GetManyInput) -> ExternResult<Vec<Option<Element>>>;
+    fn get_merged(&self, get_input: GetInput) -> ExternResult<Option<MergedEntry>>;
+    fn get_details_many(&self, get_many_input: GetManyInput) -> ExternResult<Vec<Option<Details>>>;
     // Info
     fn agent_info(&self, agent_info_input: ()) -> ExternResult<AgentInfo>;
     fn app_info(&self, app_info_input: ()) -> ExternResult<AppInfo>;
@@ -50,12 +54,21 @@ pub trait HdkT: Send + Sync {
     fn create_link(&self, create_link_input: CreateLinkInput) -> ExternResult<HeaderHash>;
     fn delete_link(&self, add_link_header: HeaderHash) -> ExternResult<HeaderHash>;
     fn get_links(&self, get_links_input: GetLinksInput) -> ExternResult<Links>;
+    fn count_links(&self, get_links_input: GetLinksInput) -> ExternResult<u32>;
+    fn get_links_with_quorum(
+        &self,
+        get_links_input: GetLinksInput,
+    ) -> ExternResult<QuorumOutput<Links>>;
     fn get_link_details(&self, get_links_input: GetLinksInput) -> ExternResult<LinkDetails>;
     // P2P
     fn call(&self, call: Call) -> ExternResult<ZomeCallResponse>;
     fn call_remote(&self, call_remote: CallRemote) -> ExternResult<ZomeCallResponse>;
     fn emit_signal(&self, app_signal: AppSignal) -> ExternResult<()>;
     fn remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()>;
+    fn remote_signal_with_receipts(
+        &self,
+        remote_signal: RemoteSignalWithReceipts,
+    ) -> ExternResult<Vec<RemoteSignalReceipt>>;
     // Random
     fn random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes>;
     // Time
@@ -126,9 +139,21 @@ impl HdkT for ErrHdk {
     fn get(&self, _: GetInput) -> ExternResult<Option<Element>> {
         Self::err()
     }
+    fn get_with_quorum(&self, _: GetInput) -> ExternResult<QuorumOutput<Option<Element>>> {
+        Self::err()
+    }
     fn get_details(&self, _: GetInput) -> ExternResult<Option<Details>> {
         Self::err()
     }
+    fn get_many(&self, _: GetManyInput) -> ExternResult<Vec<Option<Element>>> {
+        Self::err()
+    }
+    fn get_merged(&self, _: GetInput) -> ExternResult<Option<MergedEntry>> {
+        Self::err()
+    }
+    fn get_details_many(&self, _: GetManyInput) -> ExternResult<Vec<Option<Details>>> {
+        Self::err()
+    }
     fn agent_info(&self, _: ()) -> ExternResult<AgentInfo> {
         Self::err()
     }
@@ -154,6 +179,12 @@ impl HdkT for ErrHdk {
     fn get_links(&self, _: GetLinksInput) -> ExternResult<Links> {
         Self::err()
     }
+    fn count_links(&self, _: GetLinksInput) -> ExternResult<u32> {
+        Self::err()
+    }
+    fn get_links_with_quorum(&self, _: GetLinksInput) -> ExternResult<QuorumOutput<Links>> {
+        Self::err()
+    }
     fn get_link_details(&self, _: GetLinksInput) -> ExternResult<LinkDetails> {
         Self::err()
     }
@@ -170,6 +201,12 @@ impl HdkT for ErrHdk {
     fn remote_signal(&self, _: RemoteSignal) -> ExternResult<()> {
         Self::err()
     }
+    fn remote_signal_with_receipts(
+        &self,
+        _: RemoteSignalWithReceipts,
+    ) -> ExternResult<Vec<RemoteSignalReceipt>> {
+        Self::err()
+    }
     // Random
     fn random_bytes(&self, _: u32) -> ExternResult<Bytes> {
         Self::err()
@@ -265,9 +302,21 @@ impl HdkT for HostHdk {
     fn get(&self, get_input: GetInput) -> ExternResult<Option<Element>> {
         host_call::<GetInput, Option<Element>>(__get, get_input)
     }
+    fn get_with_quorum(&self, get_input: GetInput) -> ExternResult<QuorumOutput<Option<Element>>> {
+        host_call::<GetInput, QuorumOutput<Option<Element>>>(__get_with_quorum, get_input)
+    }
     fn get_details(&self, get_input: GetInput) -> ExternResult<Option<Details>> {
         host_call::<GetInput, Option<Details>>(__get_details, get_input)
     }
+    fn get_many(&self, get_many_input: GetManyInput) -> ExternResult<Vec<Option<Element>>> {
+        host_call::<GetManyInput, Vec<Option<Element>>>(__get_many, get_many_input)
+    }
+    fn get_merged(&self, get_input: GetInput) -> ExternResult<Option<MergedEntry>> {
+        host_call::<GetInput, Option<MergedEntry>>(__get_merged, get_input)
+    }
+    fn get_details_many(&self, get_many_input: GetManyInput) -> ExternResult<Vec<Option<Details>>> {
+        host_call::<GetManyInput, Vec<Option<Details>>>(__get_details_many, get_many_input)
+    }
 
     fn agent_info(&self, _: ()) -> ExternResult<AgentInfo> {
         host_call::<(), AgentInfo>(__agent_info, ())
@@ -294,6 +343,15 @@ impl HdkT for HostHdk {
     fn get_links(&self, get_links_input: GetLinksInput) -> ExternResult<Links> {
         host_call::<GetLinksInput, Links>(__get_links, get_links_input)
     }
+    fn count_links(&self, get_links_input: GetLinksInput) -> ExternResult<u32> {
+        host_call::<GetLinksInput, u32>(__count_links, get_links_input)
+    }
+    fn get_links_with_quorum(
+        &self,
+        get_links_input: GetLinksInput,
+    ) -> ExternResult<QuorumOutput<Links>> {
+        host_call::<GetLinksInput, QuorumOutput<Links>>(__get_links_with_quorum, get_links_input)
+    }
     fn get_link_details(&self, get_links_input: GetLinksInput) -> ExternResult<LinkDetails> {
         host_call::<GetLinksInput, LinkDetails>(__get_link_details, get_links_input)
     }
@@ -310,6 +368,15 @@ impl HdkT for HostHdk {
     fn remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()> {
         host_call::<RemoteSignal, ()>(__remote_signal, remote_signal)
     }
+    fn remote_signal_with_receipts(
+        &self,
+        remote_signal: RemoteSignalWithReceipts,
+    ) -> ExternResult<Vec<RemoteSignalReceipt>> {
+        host_call::<RemoteSignalWithReceipts, Vec<RemoteSignalReceipt>>(
+            __remote_signal_with_receipts,
+            remote_signal,
+        )
+    }
 
     fn random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes> {
         host_call::<u32, Bytes>(__random_bytes, number_of_bytes)
@@ -171,6 +171,7 @@
 //!   - <entry_id> is the entry id defined by entry defs e.g. "comment"
 //!   - Only the originating zome is called
 //!   - Failure overrides retry
+//!   - `#[hdk_entry(id = "comment", validate = true)]` generates these callbacks for "comment" from its [ `validate::ValidateEntry` ] impl
 //! - `fn validation_package_{{ <entry_id> }}(entry_type: AppEntryType) -> ExternResult<ValidationPackageCallbackResult>`:
 //!   - Allows the guest to build a validation package for the given entry type
 //!   - Can pass/retry/fail/not-implemented in reverse override order
@@ -480,6 +481,12 @@ pub mod time;
 /// The host provides the random bytes because any/all wasm implementations of randomness is flawed and insecure.
 pub mod random;
 
+/// Typed validation callbacks for app entries.
+///
+/// `#[hdk_entry( .. , validate = true)]` generates the entry validation callbacks for a type,
+/// which deserialize the entries and call its [ `validate::ValidateEntry` ] methods.
+pub mod validate;
+
 /// The interface between the host and guest is implemented as an `HdkT` trait.
 ///
 /// The `set_hdk` function globally sets a `RefCell` to track the current HDK implementation.
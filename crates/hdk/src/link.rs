@@ -119,6 +119,64 @@ pub fn get_links(base: EntryHash, link_tag: Option<LinkTag>) -> ExternResult<Lin
     HDK.with(|h| h.borrow().get_links(GetLinksInput::new(base, link_tag)))
 }
 
+/// Returns the links that reference a base entry hash and match a [ `LinkFilter` ].
+///
+/// The filter is applied by the authorities so only the matching links
+/// are sent over the network. Tag filtering is the same as [ `get_links` ].
+///
+/// To page through many links set a limit, then pass the last link of
+/// each page as the cursor for the next one:
+///
+/// ```ignore
+/// let first = get_links_filtered(base.clone(), None, LinkFilter::default().page(10, None))?;
+/// if let Some(last) = first.into_inner().last() {
+///     let next = get_links_filtered(base, None, LinkFilter::default().page(10, Some(last.into())))?;
+/// }
+/// ```
+pub fn get_links_filtered(
+    base: EntryHash,
+    link_tag: Option<LinkTag>,
+    filter: LinkFilter,
+) -> ExternResult<Links> {
+    HDK.with(|h| {
+        h.borrow()
+            .get_links(GetLinksInput::new(base, link_tag).with_filter(filter))
+    })
+}
+
+/// Counts the links that reference a base entry hash and match a [ `LinkFilter` ]
+/// without fetching the links themselves.
+///
+/// Deleted links are not counted.
+/// Tag filtering is the same as [ `get_links` ].
+pub fn count_links(
+    base: EntryHash,
+    link_tag: Option<LinkTag>,
+    filter: LinkFilter,
+) -> ExternResult<u32> {
+    HDK.with(|h| {
+        h.borrow()
+            .count_links(GetLinksInput::new(base, link_tag).with_filter(filter))
+    })
+}
+
+/// Returns the links that reference a base entry hash once several authorities
+/// have been asked, along with how many of them responded and agreed.
+///
+/// Tag filtering is the same as [ `get_links` ].
+/// This doesn't fail if too few authorities agree,
+/// check [ `QuorumReport::is_reached` ] before trusting the links.
+pub fn get_links_with_quorum(
+    base: EntryHash,
+    link_tag: Option<LinkTag>,
+    quorum: Quorum,
+) -> ExternResult<QuorumOutput<Links>> {
+    HDK.with(|h| {
+        h.borrow()
+            .get_links_with_quorum(GetLinksInput::new(base, link_tag).with_quorum(quorum))
+    })
+}
+
 /// Get all link creates and deletes that reference a base entry hash, optionally filtered by tag
 ///
 /// Tag filtering is a simple bytes prefix.
@@ -144,3 +202,131 @@ pub fn get_link_details(base: EntryHash, link_tag: Option<LinkTag>) -> ExternRes
             .get_link_details(GetLinksInput::new(base, link_tag))
     })
 }
+
+/// Trait for a link type whose tag holds a typed payload.
+/// See [ `link_type!` ] and `#[hdk_link_type( .. )]`.
+///
+/// The tag of a typed link starts with the name of its type, so the links of one type can be
+/// found with [ `get_typed_links` ] and are validated by a `validate_create_link_<name>`
+/// callback as well as `validate_create_link`.
+/// Only names made of `a-z`, `0-9` and `_` get their own callback, e.g.
+///
+/// ```ignore
+/// #[hdk_link_type(id = "comment")]
+/// struct Comment {
+///     posted_at: Timestamp,
+/// }
+///
+/// #[hdk_extern]
+/// fn validate_create_link_comment(data: ValidateCreateLinkData) -> ExternResult<ValidateLinkCallbackResult> {
+///     match Comment::try_from_link_tag(&data.link_add.tag)? {
+///         Some(_comment) => Ok(ValidateLinkCallbackResult::Valid),
+///         None => Ok(ValidateLinkCallbackResult::Invalid("not a comment".into())),
+///     }
+/// }
+/// ```
+///
+/// Deletes are only validated by `validate_delete_link` as they don't include the tag.
+pub trait LinkType: Sized {
+    /// The name of this link type.
+    fn link_type_id() -> &'static str;
+
+    /// The tag of a link of this type with this payload.
+    fn link_tag(&self) -> ExternResult<LinkTag>;
+
+    /// The payload in the tag of a link of this type,
+    /// or `None` if the tag is for another type.
+    fn try_from_link_tag(tag: &LinkTag) -> ExternResult<Option<Self>>;
+
+    /// The start of the tag of every link of this type.
+    fn link_tag_prefix() -> ExternResult<LinkTag> {
+        LinkTag::typed_prefix(Self::link_type_id()).ok_or_else(|| {
+            WasmError::Guest(format!(
+                "Link type {} is longer than {} bytes",
+                Self::link_type_id(),
+                MAX_LINK_TYPE_LEN
+            ))
+        })
+    }
+}
+
+/// Implements [ `LinkType` ] for a struct or enum holding the payload of a link tag.
+///
+/// This requires that TryFrom and TryInto [ `derive@SerializedBytes` ] is implemented for the type.
+/// The `#[hdk_link_type( .. )]` attribute macro derives these and calls this macro.
+#[macro_export]
+macro_rules! link_type {
+    ( $t:ident $id:expr ) => {
+        impl $crate::prelude::LinkType for $t {
+            fn link_type_id() -> &'static str {
+                $id
+            }
+
+            fn link_tag(&self) -> $crate::prelude::ExternResult<$crate::prelude::LinkTag> {
+                let payload: Vec<u8> = $crate::prelude::UnsafeBytes::from(
+                    <$crate::prelude::SerializedBytes as $crate::prelude::TryFrom<&$t>>::try_from(
+                        self,
+                    )?,
+                )
+                .into();
+                Ok($crate::prelude::LinkTag::typed(
+                    <Self as $crate::prelude::LinkType>::link_type_id(),
+                    &payload,
+                )
+                .ok_or_else(|| {
+                    $crate::prelude::WasmError::Guest(format!(
+                        "Link type {} is longer than {} bytes",
+                        <Self as $crate::prelude::LinkType>::link_type_id(),
+                        $crate::prelude::MAX_LINK_TYPE_LEN
+                    ))
+                })?)
+            }
+
+            fn try_from_link_tag(
+                tag: &$crate::prelude::LinkTag,
+            ) -> $crate::prelude::ExternResult<Option<Self>> {
+                if tag.link_type() != Some(<Self as $crate::prelude::LinkType>::link_type_id()) {
+                    return Ok(None);
+                }
+                match tag.typed_payload() {
+                    Some(payload) => Ok(Some(<Self as $crate::prelude::TryFrom<
+                        $crate::prelude::SerializedBytes,
+                    >>::try_from(
+                        $crate::prelude::SerializedBytes::from($crate::prelude::UnsafeBytes::from(
+                            payload.to_vec(),
+                        )),
+                    )?)),
+                    None => Ok(None),
+                }
+            }
+        }
+    };
+}
+
+/// Create a link of a [ `LinkType` ] from a base entry to a target entry.
+/// The payload is serialized into the tag after the name of the link type.
+///
+/// See [ `create_link` ].
+pub fn create_typed_link<L: LinkType>(
+    base_address: EntryHash,
+    target_address: EntryHash,
+    link: L,
+) -> ExternResult<HeaderHash> {
+    create_link(base_address, target_address, link.link_tag()?)
+}
+
+/// Returns the live links of a [ `LinkType` ] that reference a base entry hash,
+/// each with the payload from its tag.
+///
+/// See [ `get_links` ].
+pub fn get_typed_links<L: LinkType>(base: EntryHash) -> ExternResult<Vec<(Link, L)>> {
+    get_links(base, Some(L::link_tag_prefix()?))?
+        .into_inner()
+        .into_iter()
+        .filter_map(|link| match L::try_from_link_tag(&link.tag) {
+            Ok(Some(payload)) => Some(Ok((link, payload))),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        })
+        .collect()
+}
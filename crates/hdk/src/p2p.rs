@@ -136,3 +136,45 @@ where
         })
     })
 }
+
+/// ## Remote Signal With Receipts
+/// Send a signal to a list of other agents, like [ `remote_signal` ],
+/// but wait for each agent to acknowledge delivery and return a
+/// [ `RemoteSignalReceipt` ] per agent saying whether the signal was
+/// delivered, the agent was unreachable or it timed out.
+///
+/// ### Blocking
+/// Unlike [ `remote_signal` ] this call blocks until every agent has
+/// either acknowledged the signal or run out of attempts.
+/// Each agent is tried once, then up to `options.retries` more times
+/// if that fails, waiting at most `options.timeout` per attempt.
+///
+/// ### Outbox
+/// If `options.outbox_ttl` is set, signals which could not be delivered
+/// are kept in this cell's outbox and retried in the background until
+/// they are delivered or expire. The receipt for such an agent carries
+/// the `outbox_id` of the queued signal, and a
+/// `SystemSignal::RemoteSignalDelivered` with the same id is emitted
+/// over the conductor's interfaces once it arrives (if network system
+/// signals are enabled).
+///
+/// ### Usage
+/// The receiving zome needs to expose `recv_remote_signal` exactly as
+/// described for [ `remote_signal` ].
+pub fn remote_signal_with_receipts<I>(
+    input: I,
+    agents: Vec<AgentPubKey>,
+    options: RemoteSignalOptions,
+) -> ExternResult<Vec<RemoteSignalReceipt>>
+where
+    I: serde::Serialize + std::fmt::Debug,
+{
+    HDK.with(|h| {
+        h.borrow()
+            .remote_signal_with_receipts(RemoteSignalWithReceipts {
+                signal: ExternIO::encode(input)?,
+                agents,
+                options,
+            })
+    })
+}
@@ -18,6 +18,10 @@ pub use crate::entry::delete;
 pub use crate::entry::delete_entry;
 pub use crate::entry::get;
 pub use crate::entry::get_details;
+pub use crate::entry::get_details_many;
+pub use crate::entry::get_many;
+pub use crate::entry::get_merged;
+pub use crate::entry::get_with_quorum;
 pub use crate::entry::hash_entry;
 pub use crate::entry::update;
 pub use crate::entry::update_entry;
@@ -33,25 +37,40 @@ pub use crate::hash_path::anchor::list_anchor_tags;
 pub use crate::hash_path::anchor::list_anchor_type_addresses;
 pub use crate::hash_path::anchor::Anchor;
 pub use crate::hash_path::path::Path;
+pub use crate::hash_path::time_index::TimeIndex;
+pub use crate::hash_path::time_index::TimeIndexGranularity;
+pub use crate::hash_path::time_index::TimeIndexedLink;
 pub use crate::hdk::*;
 pub use crate::info::agent_info;
 pub use crate::info::zome_info;
+pub use crate::link::count_links;
 pub use crate::link::create_link;
+pub use crate::link::create_typed_link;
 pub use crate::link::delete_link;
 pub use crate::link::get_link_details;
 pub use crate::link::get_links;
+pub use crate::link::get_links_filtered;
+pub use crate::link::get_links_with_quorum;
+pub use crate::link::get_typed_links;
+pub use crate::link::LinkType;
+pub use crate::link_type;
 pub use crate::map_extern;
 pub use crate::map_extern::ExternResult;
 pub use crate::p2p::call;
 pub use crate::p2p::call_remote;
 pub use crate::p2p::emit_signal;
 pub use crate::p2p::remote_signal;
+pub use crate::p2p::remote_signal_with_receipts;
 pub use crate::random::*;
 pub use crate::register_entry;
 pub use crate::time::schedule;
 pub use crate::time::sleep;
 pub use crate::time::sys_time;
 pub use crate::time::*;
+pub use crate::validate::validate_create_entry_typed;
+pub use crate::validate::validate_delete_entry_typed;
+pub use crate::validate::validate_update_entry_typed;
+pub use crate::validate::ValidateEntry;
 pub use crate::x_salsa20_poly1305::create_x25519_keypair;
 pub use crate::x_salsa20_poly1305::x_25519_x_salsa20_poly1305_decrypt;
 pub use crate::x_salsa20_poly1305::x_25519_x_salsa20_poly1305_encrypt;
@@ -59,6 +78,7 @@ pub use crate::x_salsa20_poly1305::x_salsa20_poly1305_decrypt;
 pub use crate::x_salsa20_poly1305::x_salsa20_poly1305_encrypt;
 pub use hdk_derive::hdk_entry;
 pub use hdk_derive::hdk_extern;
+pub use hdk_derive::hdk_link_type;
 pub use holo_hash;
 pub use holo_hash::AgentPubKey;
 pub use holo_hash::AnyDhtHash;
@@ -102,8 +122,14 @@ macro_rules! holochain_externs {
             __capability_grants,
             __capability_info,
             __get,
+            __get_with_quorum,
             __get_details,
+            __get_many,
+            __get_merged,
+            __get_details_many,
             __get_links,
+            __count_links,
+            __get_links_with_quorum,
             __get_link_details,
             __get_agent_activity,
             __query,
@@ -112,6 +138,7 @@ macro_rules! holochain_externs {
             __create,
             __emit_signal,
             __remote_signal,
+            __remote_signal_with_receipts,
             __create_link,
             __delete_link,
             __update,
@@ -0,0 +1,167 @@
+use crate::prelude::*;
+
+/// Typed validation for an app entry type.
+///
+/// `#[hdk_entry(id = "post", validate = true)]` generates the
+/// `validate_{{ create|update|delete }}_entry_post` callbacks.
+/// These deserialize the entries into `Post` and call these methods,
+/// so the entry type must implement this trait.
+/// Each method defaults to valid so only the ones that matter need implementing.
+///
+/// An entry that doesn't deserialize into the type is invalid.
+/// Private entries are hidden from everyone but their author,
+/// so the methods are only called where the entries can be seen.
+/// An update is invalid if the original entry has a different entry type.
+///
+/// ```ignore
+/// #[hdk_entry(id = "post", validate = true)]
+/// struct Post(String);
+///
+/// impl ValidateEntry for Post {
+///     fn validate_create(self, _: ValidateData) -> ExternResult<ValidateCallbackResult> {
+///         Ok(if self.0.len() <= 280 {
+///             ValidateCallbackResult::Valid
+///         } else {
+///             ValidateCallbackResult::Invalid("Posts are at most 280 characters".into())
+///         })
+///     }
+/// }
+/// ```
+pub trait ValidateEntry: Sized {
+    /// Validate a create of this entry.
+    fn validate_create(self, _validate_data: ValidateData) -> ExternResult<ValidateCallbackResult> {
+        Ok(ValidateCallbackResult::Valid)
+    }
+
+    /// Validate an update from the original entry to this one.
+    fn validate_update(
+        self,
+        _original: Self,
+        _validate_data: ValidateData,
+    ) -> ExternResult<ValidateCallbackResult> {
+        Ok(ValidateCallbackResult::Valid)
+    }
+
+    /// Validate a delete of this entry.
+    fn validate_delete(
+        _deleted: Self,
+        _validate_data: ValidateData,
+    ) -> ExternResult<ValidateCallbackResult> {
+        Ok(ValidateCallbackResult::Valid)
+    }
+}
+
+/// Deserialize the entry being created and call [ `ValidateEntry::validate_create` ].
+pub fn validate_create_entry_typed<T>(
+    validate_data: ValidateData,
+) -> ExternResult<ValidateCallbackResult>
+where
+    T: ValidateEntry + for<'a> TryFrom<&'a Entry, Error = WasmError>,
+{
+    match deserialize_entry::<T>(validate_data.element.entry()) {
+        Ok(Some(entry)) => entry.validate_create(validate_data),
+        Ok(None) => Ok(ValidateCallbackResult::Valid),
+        Err(invalid) => Ok(invalid),
+    }
+}
+
+/// Deserialize the new and original entries of an update
+/// and call [ `ValidateEntry::validate_update` ].
+pub fn validate_update_entry_typed<T>(
+    validate_data: ValidateData,
+) -> ExternResult<ValidateCallbackResult>
+where
+    T: ValidateEntry + for<'a> TryFrom<&'a Entry, Error = WasmError>,
+{
+    let (original_header_address, entry_type) = match validate_data.element.header() {
+        Header::Update(update) => (
+            update.original_header_address.clone(),
+            update.entry_type.clone(),
+        ),
+        _ => return Ok(ValidateCallbackResult::Invalid("Not an update".to_string())),
+    };
+    let entry = match deserialize_entry::<T>(validate_data.element.entry()) {
+        Ok(Some(entry)) => entry,
+        Ok(None) => return Ok(ValidateCallbackResult::Valid),
+        Err(invalid) => return Ok(invalid),
+    };
+    let original = match get_original(original_header_address)? {
+        Ok(original) => original,
+        Err(not_valid) => return Ok(not_valid),
+    };
+    if original.header().entry_type() != Some(&entry_type) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The original entry is not a {}",
+            std::any::type_name::<T>()
+        )));
+    }
+    match deserialize_entry::<T>(original.entry()) {
+        Ok(Some(original)) => entry.validate_update(original, validate_data),
+        Ok(None) => Ok(ValidateCallbackResult::Valid),
+        Err(invalid) => Ok(invalid),
+    }
+}
+
+/// Deserialize the entry being deleted and call [ `ValidateEntry::validate_delete` ].
+pub fn validate_delete_entry_typed<T>(
+    validate_data: ValidateData,
+) -> ExternResult<ValidateCallbackResult>
+where
+    T: ValidateEntry + for<'a> TryFrom<&'a Entry, Error = WasmError>,
+{
+    let deletes_address = match validate_data.element.header() {
+        Header::Delete(delete) => delete.deletes_address.clone(),
+        _ => return Ok(ValidateCallbackResult::Invalid("Not a delete".to_string())),
+    };
+    let deleted = match get_original(deletes_address)? {
+        Ok(deleted) => deleted,
+        Err(not_valid) => return Ok(not_valid),
+    };
+    if !matches!(deleted.header().entry_type(), Some(EntryType::App(_))) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "The deleted entry is not a {}",
+            std::any::type_name::<T>()
+        )));
+    }
+    match deserialize_entry::<T>(deleted.entry()) {
+        Ok(Some(deleted)) => T::validate_delete(deleted, validate_data),
+        Ok(None) => Ok(ValidateCallbackResult::Valid),
+        Err(invalid) => Ok(invalid),
+    }
+}
+
+/// Get the element of the header being updated or deleted,
+/// or the result to return if it can't be found yet.
+fn get_original(
+    header_address: HeaderHash,
+) -> ExternResult<Result<Element, ValidateCallbackResult>> {
+    match get(header_address.clone(), GetOptions::content())? {
+        Some(element) => Ok(Ok(element)),
+        None => Ok(Err(ValidateCallbackResult::UnresolvedDependencies(vec![
+            header_address.into(),
+        ]))),
+    }
+}
+
+/// The entry as a `T`, or why it's invalid.
+/// Private entries are only seen by their author so everyone else
+/// gets `None`, and there is nothing for them to validate.
+fn deserialize_entry<T>(entry: &ElementEntry) -> Result<Option<T>, ValidateCallbackResult>
+where
+    T: for<'a> TryFrom<&'a Entry, Error = WasmError>,
+{
+    match entry {
+        ElementEntry::Present(entry) => T::try_from(entry).map(Some).map_err(|e| {
+            ValidateCallbackResult::Invalid(format!(
+                "Entry is not a {}: {:?}",
+                std::any::type_name::<T>(),
+                e
+            ))
+        }),
+        ElementEntry::Hidden | ElementEntry::NotStored => Ok(None),
+        ElementEntry::NotApplicable => Err(ValidateCallbackResult::Invalid(format!(
+            "Missing the {} entry",
+            std::any::type_name::<T>()
+        ))),
+    }
+}
@@ -7,12 +7,14 @@ use syn::parse::ParseStream;
 use syn::parse::Result;
 use syn::punctuated::Punctuated;
 
-struct EntryDef(holochain_zome_types::entry_def::EntryDef);
+/// The entry def and whether to generate typed validation callbacks for it.
+struct EntryDef(holochain_zome_types::entry_def::EntryDef, bool);
 struct EntryDefId(holochain_zome_types::entry_def::EntryDefId);
 struct EntryVisibility(holochain_zome_types::entry_def::EntryVisibility);
 struct CrdtType(holochain_zome_types::crdt::CrdtType);
 struct RequiredValidations(holochain_zome_types::entry_def::RequiredValidations);
 struct RequiredValidationType(holochain_zome_types::validate::RequiredValidationType);
+struct LinkTypeId(String);
 
 impl Parse for EntryDef {
     fn parse(input: ParseStream) -> Result<Self> {
@@ -20,9 +22,10 @@ impl Parse for EntryDef {
         let mut required_validations =
             holochain_zome_types::entry_def::RequiredValidations::default();
         let mut visibility = holochain_zome_types::entry_def::EntryVisibility::default();
-        let crdt_type = holochain_zome_types::crdt::CrdtType::default();
+        let mut crdt_type = holochain_zome_types::crdt::CrdtType::default();
         let mut required_validation_type =
             holochain_zome_types::validate::RequiredValidationType::default();
+        let mut validate = false;
 
         let vars = Punctuated::<syn::MetaNameValue, syn::Token![,]>::parse_terminated(input)?;
         for var in vars {
@@ -86,26 +89,75 @@ impl Parse for EntryDef {
                         };
                     }
                     "crdt_type" => {
-                        unimplemented!();
+                        match var.lit {
+                            syn::Lit::Str(s) => {
+                                crdt_type = match s.value().as_str() {
+                                    "no_merge" => holochain_zome_types::crdt::CrdtType::NoMerge,
+                                    "last_writer_wins" => {
+                                        holochain_zome_types::crdt::CrdtType::LastWriterWins
+                                    }
+                                    "grow_only_set" => {
+                                        holochain_zome_types::crdt::CrdtType::GrowOnlySet
+                                    }
+                                    "grow_only_counter" => {
+                                        holochain_zome_types::crdt::CrdtType::GrowOnlyCounter
+                                    }
+                                    _ => unreachable!(
+                                        "Invalid crdt_type
+                                        Options are: no_merge, last_writer_wins, grow_only_set and grow_only_counter"
+                                    ),
+                                }
+                            }
+                            _ => unreachable!(),
+                        };
                     }
+                    "validate" => match var.lit {
+                        syn::Lit::Bool(b) => validate = b.value,
+                        _ => unreachable!(),
+                    },
                     _ => {}
                 }
             }
         }
-        Ok(EntryDef(holochain_zome_types::entry_def::EntryDef {
-            id,
-            visibility,
-            crdt_type,
-            required_validations,
-            required_validation_type,
-        }))
+        // The id is the suffix of the validation callback names.
+        if let holochain_zome_types::entry_def::EntryDefId::App(id) = &id {
+            if validate
+                && (id.is_empty()
+                    || !id
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'))
+            {
+                return Err(input.error(
+                    "Entries with typed validation need an id of lowercase letters, digits or underscores",
+                ));
+            }
+        }
+        Ok(EntryDef(
+            holochain_zome_types::entry_def::EntryDef {
+                id,
+                visibility,
+                crdt_type,
+                required_validations,
+                required_validation_type,
+            },
+            validate,
+        ))
     }
 }
 
 impl quote::ToTokens for CrdtType {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let variant = syn::Ident::new(
+            match self.0 {
+                holochain_zome_types::crdt::CrdtType::NoMerge => "NoMerge",
+                holochain_zome_types::crdt::CrdtType::LastWriterWins => "LastWriterWins",
+                holochain_zome_types::crdt::CrdtType::GrowOnlySet => "GrowOnlySet",
+                holochain_zome_types::crdt::CrdtType::GrowOnlyCounter => "GrowOnlyCounter",
+            },
+            proc_macro2::Span::call_site(),
+        );
         tokens.append_all(quote::quote! {
-            hdk::prelude::CrdtType
+            hdk::prelude::CrdtType::#variant
         });
     }
 }
@@ -184,6 +236,43 @@ impl quote::ToTokens for EntryDef {
     }
 }
 
+impl Parse for LinkTypeId {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut id = None;
+
+        let vars = Punctuated::<syn::MetaNameValue, syn::Token![,]>::parse_terminated(input)?;
+        for var in vars {
+            if let Some(segment) = var.path.segments.first() {
+                if segment.ident == "id" {
+                    match var.lit {
+                        syn::Lit::Str(s) => {
+                            let value = s.value();
+                            // The id is the suffix of the validation callback name.
+                            if value.is_empty()
+                                || value.len() > holochain_zome_types::link::MAX_LINK_TYPE_LEN
+                                || !value.chars().all(|c| {
+                                    c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'
+                                })
+                            {
+                                return Err(syn::Error::new(
+                                    s.span(),
+                                    "Link type ids are 1 to 255 lowercase letters, digits or underscores",
+                                ));
+                            }
+                            id = Some(value);
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+            }
+        }
+        match id {
+            Some(id) => Ok(LinkTypeId(id)),
+            None => Err(input.error("Link types need an id")),
+        }
+    }
+}
+
 #[proc_macro_attribute]
 pub fn hdk_entry(attrs: TokenStream, code: TokenStream) -> TokenStream {
     let item = syn::parse_macro_input!(code as syn::Item);
@@ -195,10 +284,61 @@ pub fn hdk_entry(attrs: TokenStream, code: TokenStream) -> TokenStream {
     };
     let entry_def = syn::parse_macro_input!(attrs as EntryDef);
 
+    // Each validation callback for this entry type deserializes
+    // the entry and calls the matching `ValidateEntry` method.
+    let mut validate_fns = proc_macro2::TokenStream::new();
+    if let (holochain_zome_types::entry_def::EntryDefId::App(id), true) =
+        (&entry_def.0.id, entry_def.1)
+    {
+        for (action, dispatch) in [
+            ("create", "validate_create_entry_typed"),
+            ("update", "validate_update_entry_typed"),
+            ("delete", "validate_delete_entry_typed"),
+        ]
+        .iter()
+        {
+            let fn_ident = quote::format_ident!("validate_{}_entry_{}", action, id);
+            let dispatch_ident = quote::format_ident!("{}", dispatch);
+            validate_fns.append_all(quote::quote! {
+                fn #fn_ident(
+                    validate_data: hdk::prelude::ValidateData,
+                ) -> hdk::prelude::ExternResult<hdk::prelude::ValidateCallbackResult> {
+                    hdk::prelude::#dispatch_ident::<#struct_ident>(validate_data)
+                }
+                hdk::prelude::map_extern!(
+                    #fn_ident,
+                    #fn_ident,
+                    hdk::prelude::ValidateData,
+                    hdk::prelude::ExternResult<hdk::prelude::ValidateCallbackResult>
+                );
+            });
+        }
+    }
+
     (quote::quote! {
         #[derive(serde::Serialize, serde::Deserialize, hdk::prelude::SerializedBytes, std::fmt::Debug)]
         #item
         hdk::prelude::entry_def!(#struct_ident #entry_def);
+        #validate_fns
+    })
+    .into()
+}
+
+#[proc_macro_attribute]
+pub fn hdk_link_type(attrs: TokenStream, code: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(code as syn::Item);
+
+    let struct_ident = match item.clone() {
+        syn::Item::Struct(item_struct) => item_struct.ident,
+        syn::Item::Enum(item_enum) => item_enum.ident,
+        _ => unimplemented!(),
+    };
+    let LinkTypeId(id) = syn::parse_macro_input!(attrs as LinkTypeId);
+
+    (quote::quote! {
+        #[derive(serde::Serialize, serde::Deserialize, hdk::prelude::SerializedBytes, std::fmt::Debug)]
+        #item
+        hdk::prelude::link_type!(#struct_ident #id);
     })
     .into()
 }
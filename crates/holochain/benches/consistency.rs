@@ -157,7 +157,7 @@ async fn setup() -> (Producer, Consumer, Others) {
         ConductorConfig {
             network: Some(network),
             admin_interfaces: Some(vec![AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port: 0 },
+                driver: InterfaceDriver::websocket(0),
             }]),
             ..Default::default()
         }
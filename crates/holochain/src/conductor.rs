@@ -15,6 +15,7 @@
 
 pub mod api;
 mod cell;
+pub mod cell_archive;
 #[allow(clippy::module_inception)]
 #[allow(missing_docs)]
 pub mod conductor;
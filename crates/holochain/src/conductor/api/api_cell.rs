@@ -5,6 +5,7 @@ use std::sync::Arc;
 use super::error::ConductorApiError;
 use super::error::ConductorApiResult;
 use crate::conductor::interface::SignalBroadcaster;
+use crate::conductor::interface::SystemSignalSender;
 use crate::conductor::ConductorHandle;
 use crate::core::workflow::ZomeCallResult;
 use async_trait::async_trait;
@@ -75,6 +76,10 @@ impl CellConductorApiT for CellConductorApi {
         self.conductor_handle.signal_broadcaster().await
     }
 
+    fn system_signals(&self) -> &SystemSignalSender {
+        self.conductor_handle.system_signals()
+    }
+
     async fn get_dna(&self, dna_hash: &DnaHash) -> Option<DnaFile> {
         self.conductor_handle.get_dna(dna_hash).await
     }
@@ -130,6 +135,10 @@ pub trait CellConductorApiT: Clone + Send + Sync + Sized {
     /// attached app interface
     async fn signal_broadcaster(&self) -> SignalBroadcaster;
 
+    /// Access the sender of system signals, which are sent across every
+    /// attached admin and app interface
+    fn system_signals(&self) -> &SystemSignalSender;
+
     /// Get a [Dna] from the [DnaStore]
     async fn get_dna(&self, dna_hash: &DnaHash) -> Option<DnaFile>;
 
@@ -162,6 +171,9 @@ pub trait CellConductorReadHandleT: Send + Sync {
 
     /// Get a zome from this cell's Dna
     async fn get_zome(&self, dna_hash: &DnaHash, zome_name: &ZomeName) -> ConductorApiResult<Zome>;
+
+    /// Get a [EntryDef] from the [EntryDefBuf]
+    async fn get_entry_def(&self, key: &EntryDefBufferKey) -> Option<EntryDef>;
 }
 
 #[async_trait]
@@ -187,4 +199,8 @@ impl CellConductorReadHandleT for CellConductorApi {
     async fn get_zome(&self, dna_hash: &DnaHash, zome_name: &ZomeName) -> ConductorApiResult<Zome> {
         CellConductorApiT::get_zome(self, dna_hash, zome_name).await
     }
+
+    async fn get_entry_def(&self, key: &EntryDefBufferKey) -> Option<EntryDef> {
+        CellConductorApiT::get_entry_def(self, key).await
+    }
 }
@@ -3,6 +3,7 @@ use holochain_serialized_bytes::prelude::*;
 
 mod admin_interface;
 mod app_interface;
+mod zome_call_limiter;
 pub use admin_interface::*;
 pub use app_interface::*;
 
@@ -18,4 +19,10 @@ pub trait InterfaceApi: 'static + Send + Sync + Clone {
         &self,
         request: Result<Self::ApiRequest, SerializedBytesError>,
     ) -> InterfaceResult<Self::ApiResponse>;
+
+    /// Get a copy of this API to serve a new client connection.
+    /// Any state kept per connection should start afresh in the copy.
+    fn for_new_connection(&self) -> Self {
+        self.clone()
+    }
 }
@@ -7,6 +7,8 @@ use crate::conductor::api::error::SerializationError;
 use crate::conductor::error::CreateAppError;
 use crate::conductor::interface::error::InterfaceError;
 use crate::conductor::interface::error::InterfaceResult;
+use crate::conductor::interface::InterfaceDriver;
+use crate::conductor::state::AppInterfaceConfig;
 use crate::conductor::ConductorHandle;
 use holochain_keystore::KeystoreSenderExt;
 use holochain_serialized_bytes::prelude::*;
@@ -218,6 +220,10 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                 let app_ids = self.conductor_handle.list_active_apps().await?;
                 Ok(AdminResponse::ActiveAppsListed(app_ids))
             }
+            ListApps { status_filter } => {
+                let apps = self.conductor_handle.list_apps(status_filter).await?;
+                Ok(AdminResponse::AppsListed(apps))
+            }
             ActivateApp { installed_app_id } => {
                 // Activate app
                 self.conductor_handle
@@ -249,12 +255,28 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                     .await?;
                 Ok(AdminResponse::AppDeactivated)
             }
-            AttachAppInterface { port } => {
-                let port = port.unwrap_or(0);
+            AttachAppInterface {
+                port,
+                require_signed_zome_calls,
+                bind_address,
+                tls,
+                allowed_origins,
+                zome_call_limits,
+            } => {
+                let driver = InterfaceDriver::Websocket {
+                    port: port.unwrap_or(0),
+                    bind_address: bind_address
+                        .unwrap_or_else(|| std::net::Ipv4Addr::LOCALHOST.into()),
+                    tls,
+                    allowed_origins,
+                };
+                let config = AppInterfaceConfig::new(driver)
+                    .require_signed_zome_calls(require_signed_zome_calls)
+                    .zome_call_limits(zome_call_limits);
                 let port = self
                     .conductor_handle
                     .clone()
-                    .add_app_interface(port)
+                    .add_app_interface(config)
                     .await?;
                 Ok(AdminResponse::AppInterfaceAttached { port })
             }
@@ -266,6 +288,25 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                 let state = self.conductor_handle.dump_cell_state(&cell_id).await?;
                 Ok(AdminResponse::StateDumped(state))
             }
+            DumpFullState {
+                cell_id,
+                dht_ops_cursor,
+                dht_ops_limit,
+            } => {
+                let state = self
+                    .conductor_handle
+                    .dump_full_cell_state(&cell_id, dht_ops_cursor, dht_ops_limit)
+                    .await?;
+                Ok(AdminResponse::FullStateDumped(Box::new(state)))
+            }
+            ExportCell { cell_id } => {
+                let archive = self.conductor_handle.export_cell(*cell_id).await?;
+                Ok(AdminResponse::CellExported(Box::new(archive)))
+            }
+            ImportCell { archive } => {
+                self.conductor_handle.import_cell(*archive).await?;
+                Ok(AdminResponse::CellImported)
+            }
             AddAgentInfo { agent_infos } => {
                 self.conductor_handle.add_agent_infos(agent_infos).await?;
                 Ok(AdminResponse::AgentInfoAdded)
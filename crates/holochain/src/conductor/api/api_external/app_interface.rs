@@ -1,3 +1,5 @@
+use super::zome_call_limiter::ZomeCallLimiter;
+use super::zome_call_limiter::ZomeCallPermit;
 use super::InterfaceApi;
 use crate::conductor::api::error::ConductorApiResult;
 use crate::conductor::api::error::ExternalApiWireError;
@@ -10,6 +12,9 @@ use crate::conductor::ConductorHandle;
 use holochain_serialized_bytes::prelude::*;
 
 use holochain_types::prelude::*;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 pub use holochain_conductor_api::*;
 
@@ -41,16 +46,74 @@ pub trait AppInterfaceApi: 'static + Send + Sync + Clone {
 pub struct RealAppInterfaceApi {
     conductor_handle: ConductorHandle,
     interface_id: AppInterfaceId,
+    require_signed_zome_calls: bool,
+    zome_call_limits: ZomeCallLimits,
+    /// Limiter for the connection this api is serving
+    connection_limiter: Arc<ZomeCallLimiter>,
+    /// Limiters for each app, shared by all connections to this interface
+    app_limiters: Arc<Mutex<HashMap<InstalledAppId, Arc<ZomeCallLimiter>>>>,
 }
 
 impl RealAppInterfaceApi {
     /// Create a new instance from a shared Conductor reference
     pub fn new(conductor_handle: ConductorHandle, interface_id: AppInterfaceId) -> Self {
+        let zome_call_limits = ZomeCallLimits::default();
         Self {
             conductor_handle,
             interface_id,
+            require_signed_zome_calls: false,
+            zome_call_limits,
+            connection_limiter: ZomeCallLimiter::new(zome_call_limits.per_connection),
+            app_limiters: Default::default(),
         }
     }
+
+    /// Reject any zome call which is not an [AppRequest::SignedZomeCall]
+    pub fn require_signed_zome_calls(mut self, require: bool) -> Self {
+        self.require_signed_zome_calls = require;
+        self
+    }
+
+    /// Reject zome calls which exceed these limits
+    pub fn zome_call_limits(mut self, limits: ZomeCallLimits) -> Self {
+        self.zome_call_limits = limits;
+        self.connection_limiter = ZomeCallLimiter::new(limits.per_connection);
+        self.app_limiters = Default::default();
+        self
+    }
+
+    /// Check a zome call into this cell against the connection and app limits.
+    /// The call counts as running until the returned permits are dropped.
+    async fn start_zome_call(
+        &self,
+        cell_id: &CellId,
+    ) -> ConductorApiResult<Result<Vec<ZomeCallPermit>, String>> {
+        let mut permits = Vec::new();
+        match self.connection_limiter.try_start() {
+            Ok(permit) => permits.push(permit),
+            Err(reason) => return Ok(Err(format!("This connection made {}", reason))),
+        }
+        let per_app = self.zome_call_limits.per_app;
+        if !per_app.is_unlimited() {
+            let apps = self
+                .conductor_handle
+                .list_active_apps_for_cell_id(cell_id)
+                .await?;
+            for app in apps {
+                let limiter = self
+                    .app_limiters
+                    .lock()
+                    .entry(app.clone())
+                    .or_insert_with(|| ZomeCallLimiter::new(per_app))
+                    .clone();
+                match limiter.try_start() {
+                    Ok(permit) => permits.push(permit),
+                    Err(reason) => return Ok(Err(format!("App {} has {}", app, reason))),
+                }
+            }
+        }
+        Ok(Ok(permits))
+    }
 }
 
 #[async_trait::async_trait]
@@ -81,7 +144,20 @@ impl AppInterfaceApi for RealAppInterfaceApi {
                         }
                     })
             }
+            AppRequest::ZomeCall(_) if self.require_signed_zome_calls => Ok(AppResponse::Error(
+                ExternalApiWireError::ZomeCallUnauthorized(
+                    "This app interface only accepts signed zome calls".to_string(),
+                ),
+            )),
             AppRequest::ZomeCall(call) => {
+                let _permits = match self.start_zome_call(&call.cell_id).await? {
+                    Ok(permits) => permits,
+                    Err(reason) => {
+                        return Ok(AppResponse::Error(
+                            ExternalApiWireError::ZomeCallLimitExceeded(reason),
+                        ))
+                    }
+                };
                 match self.conductor_handle.call_zome(*call.clone()).await? {
                     Ok(ZomeCallResponse::Ok(output)) => Ok(AppResponse::ZomeCall(Box::new(output))),
                     Ok(ZomeCallResponse::Unauthorized(_, _, _, _)) => Ok(AppResponse::Error(
@@ -97,6 +173,31 @@ impl AppInterfaceApi for RealAppInterfaceApi {
                     Err(e) => Ok(AppResponse::Error(e.into())),
                 }
             }
+            AppRequest::SignedZomeCall(call) => {
+                let zc = call.call.clone();
+                let _permits = match self.start_zome_call(&zc.cell_id).await? {
+                    Ok(permits) => permits,
+                    Err(reason) => {
+                        return Ok(AppResponse::Error(
+                            ExternalApiWireError::ZomeCallLimitExceeded(reason),
+                        ))
+                    }
+                };
+                match self.conductor_handle.call_zome_signed(*call).await? {
+                    Ok(ZomeCallResponse::Ok(output)) => Ok(AppResponse::ZomeCall(Box::new(output))),
+                    Ok(ZomeCallResponse::Unauthorized(_, _, _, _)) => Ok(AppResponse::Error(
+                        ExternalApiWireError::ZomeCallUnauthorized(format!(
+                            "Either the call signature is invalid, expired or replayed, or no capabilities grant has been committed that allows the CapSecret {:?} to call the function {} in zome {}",
+                            zc.cap, zc.fn_name, zc.zome_name
+                        )),
+                    )),
+                    Ok(ZomeCallResponse::NetworkError(e)) => unreachable!(
+                        "Interface zome calls should never be routed to the network. This is a bug. Got {}",
+                        e
+                    ),
+                    Err(e) => Ok(AppResponse::Error(e.into())),
+                }
+            }
             AppRequest::SignalSubscription(_) => Ok(AppResponse::Unimplemented(request)),
             AppRequest::Crypto(_) => Ok(AppResponse::Unimplemented(request)),
         }
@@ -123,4 +224,11 @@ impl InterfaceApi for RealAppInterfaceApi {
             Err(e) => Ok(AppResponse::Error(SerializationError::from(e).into())),
         }
     }
+
+    fn for_new_connection(&self) -> Self {
+        Self {
+            connection_limiter: ZomeCallLimiter::new(self.zome_call_limits.per_connection),
+            ..self.clone()
+        }
+    }
 }
@@ -0,0 +1,115 @@
+//! Enforces the [CallLimit]s configured on an app interface.
+
+use holochain_conductor_api::CallLimit;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Tracks the calls made against a single [CallLimit],
+/// using a token bucket for the rate and a counter for concurrency.
+pub(crate) struct ZomeCallLimiter {
+    limit: CallLimit,
+    state: Mutex<LimiterState>,
+}
+
+struct LimiterState {
+    /// Calls which can be started right now without exceeding the rate
+    tokens: f64,
+    /// When the tokens were last topped up
+    last_refill: Instant,
+    /// Calls which have started but not yet finished
+    running: u32,
+}
+
+/// A call which has been allowed to run.
+/// The call stops counting towards the concurrency limit when this is dropped.
+pub(crate) struct ZomeCallPermit(Arc<ZomeCallLimiter>);
+
+impl ZomeCallLimiter {
+    /// Create a limiter with nothing running and a full burst available.
+    /// A limit of 0 would reject every call, but interfaces with one
+    /// are refused before they get a limiter.
+    pub(crate) fn new(limit: CallLimit) -> Arc<Self> {
+        Arc::new(Self {
+            limit,
+            state: Mutex::new(LimiterState {
+                tokens: limit.calls_per_second.unwrap_or(0) as f64,
+                last_refill: Instant::now(),
+                running: 0,
+            }),
+        })
+    }
+
+    /// Start a call if the limit allows it, otherwise
+    /// return the reason it was rejected.
+    pub(crate) fn try_start(self: &Arc<Self>) -> Result<ZomeCallPermit, String> {
+        let mut state = self.state.lock();
+        if let Some(max) = self.limit.max_concurrent_calls {
+            if state.running >= max {
+                return Err(format!("more than {} concurrent zome calls", max));
+            }
+        }
+        if let Some(rate) = self.limit.calls_per_second {
+            let now = Instant::now();
+            let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+            state.tokens = (state.tokens + elapsed * rate as f64).min(rate as f64);
+            state.last_refill = now;
+            if state.tokens < 1.0 {
+                return Err(format!("more than {} zome calls per second", rate));
+            }
+            state.tokens -= 1.0;
+        }
+        state.running += 1;
+        Ok(ZomeCallPermit(self.clone()))
+    }
+}
+
+impl Drop for ZomeCallPermit {
+    fn drop(&mut self) {
+        self.0.state.lock().running -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn limits_concurrent_calls() {
+        let limiter = ZomeCallLimiter::new(CallLimit {
+            calls_per_second: None,
+            max_concurrent_calls: Some(2),
+        });
+        let a = limiter.try_start().unwrap();
+        let _b = limiter.try_start().unwrap();
+        assert!(limiter.try_start().is_err());
+        drop(a);
+        assert!(limiter.try_start().is_ok());
+    }
+
+    #[test]
+    fn limits_call_rate() {
+        let limiter = ZomeCallLimiter::new(CallLimit {
+            calls_per_second: Some(20),
+            max_concurrent_calls: None,
+        });
+        // The whole burst is available straight away
+        for _ in 0..20 {
+            limiter.try_start().unwrap();
+        }
+        assert!(limiter.try_start().is_err());
+        // and refills at the configured rate
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(limiter.try_start().is_ok());
+    }
+
+    #[test]
+    fn unlimited_allows_everything() {
+        let limiter = ZomeCallLimiter::new(CallLimit::default());
+        let permits = (0..1000)
+            .map(|_| limiter.try_start().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(1000, permits.len());
+    }
+}
@@ -4,6 +4,7 @@ use super::CellConductorApiT;
 use super::ZomeCall;
 use crate::conductor::api::error::ConductorApiResult;
 use crate::conductor::interface::SignalBroadcaster;
+use crate::conductor::interface::SystemSignalSender;
 use crate::core::workflow::ZomeCallResult;
 use async_trait::async_trait;
 use holo_hash::DnaHash;
@@ -30,6 +31,7 @@ mock! {
 
         fn mock_keystore(&self) -> &KeystoreSender;
         fn mock_signal_broadcaster(&self) -> SignalBroadcaster;
+        fn mock_system_signals(&self) -> &SystemSignalSender;
         fn sync_get_dna(&self, dna_hash: &DnaHash) -> Option<DnaFile>;
         fn sync_get_this_dna(&self) -> ConductorApiResult<DnaFile>;
         fn sync_get_zome(&self, dna_hash: &DnaHash, zome_name: &ZomeName) -> ConductorApiResult<Zome>;
@@ -68,6 +70,10 @@ impl CellConductorApiT for MockCellConductorApi {
         self.mock_signal_broadcaster()
     }
 
+    fn system_signals(&self) -> &SystemSignalSender {
+        self.mock_system_signals()
+    }
+
     async fn get_dna(&self, dna_hash: &DnaHash) -> Option<DnaFile> {
         self.sync_get_dna(dna_hash)
     }
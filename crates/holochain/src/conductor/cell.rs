@@ -4,6 +4,7 @@
 //! Elements can be added. A constructed Cell is guaranteed to have a valid
 //! SourceChain which has already undergone Genesis.
 
+use super::api::SignedZomeCall;
 use super::api::ZomeCall;
 use super::interface::SignalBroadcaster;
 use super::manager::ManagedTaskAdd;
@@ -22,6 +23,7 @@ use crate::core::workflow::call_zome_workflow;
 use crate::core::workflow::genesis_workflow::genesis_workflow;
 use crate::core::workflow::incoming_dht_ops_workflow::incoming_dht_ops_workflow;
 use crate::core::workflow::initialize_zomes_workflow;
+use crate::core::workflow::verify_signed_zome_call;
 use crate::core::workflow::CallZomeWorkflowArgs;
 use crate::core::workflow::GenesisWorkflowArgs;
 use crate::core::workflow::GenesisWorkspace;
@@ -230,7 +232,7 @@ impl Cell {
                 // store lives.
                 unreachable!()
             }
-            PutMetricDatum { .. } | QueryMetrics { .. } => {
+            PutMetricDatum { .. } | PutReachabilityDatum { .. } | QueryMetrics { .. } => {
                 // Same goes for metrics
                 unreachable!()
             }
@@ -307,6 +309,23 @@ impl Cell {
                 .instrument(debug_span!("cell_handle_get"))
                 .await;
             }
+            GetMany {
+                span_context: _,
+                respond,
+                dht_hashes,
+                options,
+                ..
+            } => {
+                async {
+                    let res = self
+                        .handle_get_many(dht_hashes, options)
+                        .await
+                        .map_err(holochain_p2p::HolochainP2pError::other);
+                    respond.respond(Ok(async move { res }.boxed().into()));
+                }
+                .instrument(debug_span!("cell_handle_get_many"))
+                .await;
+            }
             GetMeta {
                 span_context: _,
                 respond,
@@ -522,6 +541,28 @@ impl Cell {
         r
     }
 
+    #[instrument(skip(self, dht_hashes, options))]
+    /// a remote node is asking us for entries for many hashes at once
+    /// a hash we fail to get is `None` so it doesn't fail the others
+    async fn handle_get_many(
+        &self,
+        dht_hashes: Vec<holo_hash::AnyDhtHash>,
+        options: holochain_p2p::event::GetOptions,
+    ) -> CellResult<Vec<Option<WireOps>>> {
+        debug!("handling get many");
+        let mut out = Vec::with_capacity(dht_hashes.len());
+        for dht_hash in dht_hashes {
+            match self.handle_get(dht_hash.clone(), options.clone()).await {
+                Ok(ops) => out.push(Some(ops)),
+                Err(e) => {
+                    warn!(?dht_hash, ?e, "Failed to get a hash for a get many");
+                    out.push(None);
+                }
+            }
+        }
+        Ok(out)
+    }
+
     #[instrument(skip(self, options))]
     async fn handle_get_entry(
         &self,
@@ -590,6 +631,11 @@ impl Cell {
     #[tracing::instrument(skip(self))]
     async fn handle_validation_receipt(&self, receipt: SerializedBytes) -> CellResult<()> {
         let receipt: SignedValidationReceipt = receipt.try_into()?;
+        let signal = SystemSignal::ValidationReceiptReceived {
+            cell_id: self.id.clone(),
+            op_hash: receipt.receipt.dht_op_hash.clone(),
+            validator: receipt.receipt.validator.clone(),
+        };
 
         self.env
             .async_commit(move |txn| {
@@ -600,6 +646,8 @@ impl Cell {
             })
             .await?;
 
+        self.conductor_api.system_signals().send(signal);
+
         Ok(())
     }
 
@@ -774,6 +822,39 @@ impl Cell {
         &self,
         call: ZomeCall,
         workspace_lock: Option<HostFnWorkspace>,
+    ) -> CellResult<ZomeCallResult> {
+        self.call_zome_inner(call, workspace_lock).await
+    }
+
+    /// Function called by the Conductor for zome calls
+    /// signed by their provenance.
+    /// The signature is checked before anything else,
+    /// including running init, so a rejected call has no effect on the cell.
+    #[instrument(skip(self, call))]
+    pub async fn call_zome_signed(&self, call: SignedZomeCall) -> CellResult<ZomeCallResult> {
+        let verified = verify_signed_zome_call(&self.env, &call)
+            .await
+            .map_err(Box::new)?;
+        if !verified {
+            tracing::warn!(provenance = ?call.call.provenance, "rejected signed zome call");
+            let ZomeCall {
+                cell_id,
+                zome_name,
+                fn_name,
+                provenance,
+                ..
+            } = call.call;
+            return Ok(Ok(ZomeCallResponse::Unauthorized(
+                cell_id, zome_name, fn_name, provenance,
+            )));
+        }
+        self.call_zome_inner(call.call, None).await
+    }
+
+    async fn call_zome_inner(
+        &self,
+        call: ZomeCall,
+        workspace_lock: Option<HostFnWorkspace>,
     ) -> CellResult<ZomeCallResult> {
         // Check if init has run if not run it
         self.check_or_run_zome_init().await?;
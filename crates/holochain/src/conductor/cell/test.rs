@@ -32,6 +32,9 @@ async fn test_cell_handle_publish() {
     mock_handle
         .expect_get_dna()
         .returning(|_| Some(fixt!(DnaFile)));
+    mock_handle
+        .expect_system_signals()
+        .return_const(crate::conductor::interface::SystemSignalSender::default());
 
     let mock_handle: crate::conductor::handle::ConductorHandle = Arc::new(mock_handle);
     let mut mock_ribosome = MockRibosomeT::new();
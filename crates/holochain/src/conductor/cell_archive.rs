@@ -0,0 +1,110 @@
+//! Backing up and restoring a cell's source chain with [CellArchive]s
+
+use super::api::error::ConductorApiResult;
+use super::error::ConductorError;
+use super::error::ConductorResult;
+use holochain_conductor_api::CellArchive;
+use holochain_conductor_api::CELL_ARCHIVE_VERSION;
+use holochain_keystore::AgentPubKeyExt;
+use holochain_keystore::KeystoreSender;
+use holochain_state::source_chain;
+use holochain_types::prelude::*;
+
+/// Read the whole source chain of a cell into an archive signed by its agent
+pub async fn export(
+    keystore: &KeystoreSender,
+    cell_id: CellId,
+    vault: EnvRead,
+) -> ConductorApiResult<CellArchive> {
+    let agent = cell_id.agent_pubkey().clone();
+    let dump = source_chain::dump_state(vault, agent.clone()).await?;
+    if dump.elements.is_empty() {
+        return Err(ConductorError::CellNotInitialized.into());
+    }
+    let elements: Vec<Element> = dump
+        .elements
+        .into_iter()
+        .map(|e| {
+            let header = HeaderHashed::with_pre_hashed(e.header, e.header_address);
+            Element::new(
+                SignedHeaderHashed::with_presigned(header, e.signature),
+                e.entry,
+            )
+        })
+        .collect();
+    let exported_at = timestamp::now();
+    let content =
+        CellArchive::signed_content(CELL_ARCHIVE_VERSION, &cell_id, exported_at, &elements)
+            .map_err(ConductorError::from)?;
+    let signature = agent.sign_raw(keystore, &content).await?;
+    Ok(CellArchive {
+        version: CELL_ARCHIVE_VERSION,
+        cell_id,
+        exported_at,
+        elements,
+        signature,
+    })
+}
+
+/// Check that an archive is intact, was signed by the cell's agent,
+/// holds a well formed chain for that cell, and that this conductor's
+/// keystore can sign for the agent once the chain is restored.
+pub async fn check(keystore: &KeystoreSender, archive: &CellArchive) -> ConductorResult<()> {
+    fn invalid<T>(reason: impl Into<String>) -> ConductorResult<T> {
+        Err(ConductorError::InvalidCellArchive(reason.into()))
+    }
+
+    if archive.version != CELL_ARCHIVE_VERSION {
+        return invalid(format!(
+            "unsupported version {}, expected {}",
+            archive.version, CELL_ARCHIVE_VERSION
+        ));
+    }
+    let agent = archive.cell_id.agent_pubkey();
+    let content = CellArchive::signed_content(
+        archive.version,
+        &archive.cell_id,
+        archive.exported_at,
+        &archive.elements,
+    )?;
+    if !agent
+        .verify_signature_raw(&archive.signature, &content)
+        .await?
+    {
+        return invalid("the archive was not signed by the cell's agent");
+    }
+    if agent.sign_raw(keystore, &content).await.is_err() {
+        return invalid("the agent key is not in this conductor's keystore");
+    }
+
+    match archive.elements.first().map(|e| e.header()) {
+        Some(Header::Dna(dna)) if &dna.hash == archive.cell_id.dna_hash() => (),
+        Some(Header::Dna(_)) => return invalid("the chain belongs to a different dna"),
+        _ => return invalid("the chain does not start with a Dna header"),
+    }
+    let mut prev_header: Option<&HeaderHash> = None;
+    for (seq, element) in archive.elements.iter().enumerate() {
+        let header = element.header();
+        if header.author() != agent {
+            return invalid(format!("element {} has a different author", seq));
+        }
+        if header.header_seq() as usize != seq || header.prev_header() != prev_header {
+            return invalid(format!("element {} is out of order", seq));
+        }
+        if &HeaderHash::with_data_sync(header) != element.header_address() {
+            return invalid(format!("element {} has the wrong header hash", seq));
+        }
+        if !agent.verify_signature(element.signature(), header).await? {
+            return invalid(format!("element {} has an invalid signature", seq));
+        }
+        if let Some((entry_hash, _)) = header.entry_data() {
+            match element.entry().as_option() {
+                Some(entry) if &EntryHash::with_data_sync(entry) == entry_hash => (),
+                Some(_) => return invalid(format!("element {} has the wrong entry", seq)),
+                None => return invalid(format!("element {} is missing its entry", seq)),
+            }
+        }
+        prev_header = Some(element.header_address());
+    }
+    Ok(())
+}
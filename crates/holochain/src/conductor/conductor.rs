@@ -8,6 +8,7 @@
 //! In normal use cases, a single Holochain user runs a single Conductor in a single process.
 //! However, there's no reason we can't have multiple Conductors in a single process, simulating multiple
 //! users in a testing environment.
+use super::cell_archive;
 use super::config::AdminInterfaceConfig;
 use super::config::InterfaceDriver;
 use super::dna_store::RealDnaStore;
@@ -15,12 +16,16 @@ use super::entry_def_store::get_entry_defs;
 use super::error::ConductorError;
 use super::error::CreateAppError;
 use super::handle::ConductorHandleImpl;
+use super::handle::ConductorHandleT;
+use super::interface::error::InterfaceError;
 use super::interface::error::InterfaceResult;
 use super::interface::websocket::spawn_admin_interface_task;
 use super::interface::websocket::spawn_app_interface_task;
+use super::interface::websocket::spawn_unix_socket_listener;
 use super::interface::websocket::spawn_websocket_listener;
 use super::interface::websocket::SIGNAL_BUFFER_SIZE;
 use super::interface::SignalBroadcaster;
+use super::interface::SystemSignalSender;
 use super::manager::keep_alive_task;
 use super::manager::spawn_task_manager;
 use super::manager::ManagedTaskAdd;
@@ -29,6 +34,7 @@ use super::manager::TaskManagerRunHandle;
 use super::p2p_agent_store::all_agent_infos;
 use super::p2p_agent_store::get_single_agent_info;
 use super::p2p_agent_store::inject_agent_infos;
+use super::p2p_metrics::query_metrics;
 use super::paths::EnvironmentRootPath;
 use super::state::AppInterfaceId;
 use super::state::ConductorState;
@@ -38,6 +44,7 @@ use super::{api::CellConductorApiT, interface::AppInterfaceRuntime};
 use super::{api::RealAdminInterfaceApi, manager::TaskManagerClient};
 use super::{api::RealAppInterfaceApi, p2p_agent_store};
 use crate::conductor::cell::Cell;
+use crate::conductor::config::CachePolicyConfig;
 use crate::conductor::config::ConductorConfig;
 use crate::conductor::error::ConductorResult;
 use crate::conductor::handle::ConductorHandle;
@@ -51,21 +58,37 @@ use futures::future;
 use futures::future::TryFutureExt;
 use futures::stream::StreamExt;
 use holo_hash::DnaHash;
+use holochain_conductor_api::AppStatusFilter;
+use holochain_conductor_api::AppStatusInfo;
+use holochain_conductor_api::CellArchive;
+use holochain_conductor_api::CellHealth;
+use holochain_conductor_api::CellStatusInfo;
+use holochain_conductor_api::DhtOpDump;
+use holochain_conductor_api::FullIntegrationStateDump;
+use holochain_conductor_api::FullStateDump;
 use holochain_conductor_api::IntegrationStateDump;
 use holochain_conductor_api::JsonDump;
+use holochain_conductor_api::PeerGossipDump;
 use holochain_keystore::lair_keystore::spawn_lair_keystore;
 use holochain_keystore::test_keystore::spawn_test_keystore;
 use holochain_keystore::KeystoreSender;
 use holochain_keystore::KeystoreSenderExt;
+use holochain_p2p::AgentPubKeyExt;
 use holochain_p2p::DnaHashExt;
 use holochain_sqlite::db::DbKind;
 use holochain_sqlite::prelude::*;
+use holochain_state::cache_gc;
+use holochain_state::cache_gc::CacheLimits;
 use holochain_state::mutations;
 use holochain_state::prelude::from_blob;
 use holochain_state::prelude::StateMutationResult;
+use holochain_state::query::StateQueryError;
+use holochain_state::query::StateQueryResult;
 use holochain_state::source_chain;
 use holochain_types::prelude::*;
 use kitsune_p2p::agent_store::AgentInfoSigned;
+use kitsune_p2p::event::MetricQuery;
+use kitsune_p2p::event::MetricQueryAnswer;
 use kitsune_p2p::KitsuneSpace;
 use rusqlite::OptionalExtension;
 use std::collections::{HashMap, HashSet};
@@ -151,6 +174,9 @@ where
 
     /// Handle to the network actor.
     holochain_p2p: holochain_p2p::HolochainP2pRef,
+
+    /// Emits system signals over all admin and app interfaces
+    system_signals: SystemSignalSender,
 }
 
 impl Conductor {
@@ -245,6 +271,7 @@ where
         DS: DnaStore + 'static,
     {
         let admin_api = RealAdminInterfaceApi::new(handle);
+        let system_signals = self.system_signals.clone();
         let stop_tx = self
             .task_manager
             .as_ref()
@@ -255,19 +282,34 @@ where
         // Closure to process each admin config item
         let spawn_from_config = |AdminInterfaceConfig { driver, .. }| {
             let admin_api = admin_api.clone();
+            let system_signals = system_signals.clone();
             let stop_tx = stop_tx.clone();
             async move {
-                match driver {
-                    InterfaceDriver::Websocket { port } => {
-                        let (listener_handle, listener) = spawn_websocket_listener(port).await?;
-                        let port = listener_handle.local_addr().port().unwrap_or(port);
+                match &driver {
+                    InterfaceDriver::Websocket { port, .. } => {
+                        let (listener_handle, listener) = spawn_websocket_listener(&driver).await?;
+                        let port = listener_handle.local_addr().port().unwrap_or(*port);
+                        let handle: ManagedTaskHandle = spawn_admin_interface_task(
+                            listener_handle,
+                            listener,
+                            admin_api.clone(),
+                            system_signals.clone(),
+                            stop_tx.subscribe(),
+                        )?;
+                        let name = format!("admin interface, port {}", port);
+                        InterfaceResult::Ok((Some(port), name, handle))
+                    }
+                    InterfaceDriver::UnixSocket { path } => {
+                        let (listener_handle, listener) = spawn_unix_socket_listener(path).await?;
                         let handle: ManagedTaskHandle = spawn_admin_interface_task(
                             listener_handle,
                             listener,
                             admin_api.clone(),
+                            system_signals.clone(),
                             stop_tx.subscribe(),
                         )?;
-                        InterfaceResult::Ok((port, handle))
+                        let name = format!("admin interface, socket {}", path.display());
+                        InterfaceResult::Ok((None, name, handle))
                     }
                 }
             }
@@ -295,13 +337,10 @@ where
             .await?;
 
             // Now that tasks are spawned, register them with the TaskManager
-            for (port, handle) in handles {
-                ports.push(port);
-                self.manage_task(ManagedTaskAdd::ignore(
-                    handle,
-                    &format!("admin interface, port {}", port),
-                ))
-                .await?
+            for (port, name, handle) in handles {
+                ports.extend(port);
+                self.manage_task(ManagedTaskAdd::ignore(handle, &name))
+                    .await?
             }
             for p in ports {
                 self.add_admin_port(p);
@@ -312,16 +351,37 @@ where
 
     pub(super) async fn add_app_interface_via_handle(
         &mut self,
-        port: either::Either<u16, AppInterfaceId>,
+        interface: either::Either<AppInterfaceConfig, AppInterfaceId>,
         handle: ConductorHandle,
     ) -> ConductorResult<u16> {
-        let interface_id = match port {
-            either::Either::Left(port) => AppInterfaceId::new(port),
-            either::Either::Right(id) => id,
+        let (interface_id, mut config) = match interface {
+            either::Either::Left(config) => {
+                let port = config.driver.port().ok_or_else(|| {
+                    Box::new(InterfaceError::UnsupportedDriver(config.driver.clone()))
+                })?;
+                if config.zome_call_limits.is_zero() {
+                    return Err(Box::new(InterfaceError::ZeroZomeCallLimit(
+                        config.zome_call_limits,
+                    ))
+                    .into());
+                }
+                (AppInterfaceId::new(port), config)
+            }
+            either::Either::Right(id) => {
+                let config = self
+                    .get_state()
+                    .await?
+                    .interface_by_id(&id)
+                    .unwrap_or_else(|| AppInterfaceConfig::websocket(id.port()));
+                (id, config)
+            }
         };
         let port = interface_id.port();
         tracing::debug!("Attaching interface {}", port);
-        let app_api = RealAppInterfaceApi::new(handle, interface_id.clone());
+        config.driver.set_port(port);
+        let app_api = RealAppInterfaceApi::new(handle, interface_id.clone())
+            .require_signed_zome_calls(config.require_signed_zome_calls)
+            .zome_call_limits(config.zome_call_limits);
         // This receiver is thrown away because we can produce infinite new
         // receivers from the Sender
         let (signal_tx, _r) = tokio::sync::broadcast::channel(SIGNAL_BUFFER_SIZE);
@@ -331,9 +391,15 @@ where
             .expect("Task manager not initialized")
             .task_stop_broadcaster()
             .subscribe();
-        let (port, task) = spawn_app_interface_task(port, app_api, signal_tx.clone(), stop_rx)
-            .await
-            .map_err(Box::new)?;
+        let (port, task) = spawn_app_interface_task(
+            &config.driver,
+            app_api,
+            signal_tx.clone(),
+            self.system_signals.clone(),
+            stop_rx,
+        )
+        .await
+        .map_err(Box::new)?;
         // TODO: RELIABILITY: Handle this task by restarting it if it fails and log the error
         self.manage_task(ManagedTaskAdd::ignore(
             task,
@@ -347,7 +413,7 @@ where
         }
 
         self.app_interfaces.insert(interface_id.clone(), interface);
-        let config = AppInterfaceConfig::websocket(port);
+        config.driver.set_port(port);
         self.update_state(|mut state| {
             state.app_interfaces.insert(interface_id, config);
             Ok(state)
@@ -363,7 +429,7 @@ where
             .await?
             .app_interfaces
             .values()
-            .map(|config| config.driver.port())
+            .filter_map(|config| config.driver.port())
             .collect())
     }
 
@@ -492,6 +558,10 @@ where
 
             Err(ConductorError::GenesisFailed { errors })
         } else {
+            for cell_id in success {
+                self.system_signals
+                    .send(SystemSignal::GenesisComplete { cell_id });
+            }
             // No errors so return the cells
             Ok(())
         }
@@ -964,6 +1034,267 @@ where
         Ok(serde_json::to_string_pretty(&out)?)
     }
 
+    pub(super) async fn dump_full_cell_state(
+        &self,
+        cell_id: &CellId,
+        dht_ops_cursor: Option<u64>,
+        dht_ops_limit: Option<u32>,
+    ) -> ConductorApiResult<FullStateDump> {
+        let cell = self.cell_by_id(cell_id)?;
+        let vault: EnvRead = cell.env().clone().into();
+        let integration_dump = full_integration_dump(&vault, dht_ops_cursor, dht_ops_limit).await?;
+
+        // Only the first page has everything besides the ops
+        if dht_ops_cursor.is_some() {
+            return Ok(FullStateDump {
+                peer_dump: None,
+                source_chain_dump: None,
+                integration_dump,
+                gossip_metrics: None,
+            });
+        }
+
+        let space = cell_id.dna_hash().to_kitsune();
+        let p2p_env: EnvRead = self.p2p_env(space.clone()).into();
+        let peer_dump = p2p_agent_store::dump_state(p2p_env.clone(), Some(cell_id.clone()))?;
+        let source_chain_dump =
+            source_chain::dump_state(vault.clone(), cell_id.agent_pubkey().clone()).await?;
+
+        let this_agent = cell_id.agent_pubkey().to_kitsune();
+        let metrics_env = self.p2p_metrics_env(space);
+        let mut gossip_metrics = Vec::new();
+        for info in all_agent_infos(p2p_env)? {
+            let agent = Arc::new(info.as_agent_ref().clone());
+            if agent == this_agent {
+                continue;
+            }
+            let last_sync = match query_metrics(
+                metrics_env.clone(),
+                MetricQuery::LastSync {
+                    agent: agent.clone(),
+                },
+            )
+            .await?
+            {
+                MetricQueryAnswer::LastSync(Some(time)) => {
+                    Some(Timestamp::from(chrono::DateTime::<chrono::Utc>::from(time)))
+                }
+                _ => None,
+            };
+            gossip_metrics.push(PeerGossipDump {
+                kitsune_agent: (*agent).clone(),
+                last_sync,
+            });
+        }
+
+        Ok(FullStateDump {
+            peer_dump: Some(peer_dump),
+            source_chain_dump: Some(source_chain_dump),
+            integration_dump,
+            gossip_metrics: Some(gossip_metrics),
+        })
+    }
+
+    /// List every installed app, with its status and the health of its cells
+    pub(super) async fn list_apps(
+        &self,
+        status_filter: Option<AppStatusFilter>,
+    ) -> ConductorApiResult<Vec<AppStatusInfo>> {
+        let state = self.get_state().await?;
+        let apps = state
+            .active_apps
+            .into_iter()
+            .map(|(_, app)| InstalledApp::Active(app))
+            .chain(
+                state
+                    .inactive_apps
+                    .into_iter()
+                    .map(|(_, app)| InstalledApp::Inactive(app)),
+            )
+            .filter(|app| {
+                status_filter
+                    .map(|filter| filter.matches(&app.status()))
+                    .unwrap_or(true)
+            });
+        let mut out = Vec::new();
+        for app in apps {
+            let mut cells = Vec::new();
+            for (cell_nick, cell_id) in app.provisioned_cells() {
+                cells.push(CellStatusInfo {
+                    cell_nick: cell_nick.clone(),
+                    cell_id: cell_id.clone(),
+                    health: self.cell_health(cell_id).await?,
+                });
+            }
+            out.push(AppStatusInfo {
+                installed_app_id: app.installed_app_id().clone(),
+                status: app.status(),
+                cells,
+            });
+        }
+        out.sort_by(|a, b| a.installed_app_id.cmp(&b.installed_app_id));
+        Ok(out)
+    }
+
+    /// Summarize the state of a cell, or None if it isn't running
+    async fn cell_health(&self, cell_id: &CellId) -> ConductorApiResult<Option<CellHealth>> {
+        let cell = match self.cell_by_id(cell_id) {
+            Ok(cell) => cell,
+            Err(_) => return Ok(None),
+        };
+        let vault: EnvRead = cell.env().clone().into();
+        let integration = integration_dump(&vault).await?;
+
+        let author = cell_id.agent_pubkey().clone();
+        let chain_length = vault
+            .async_reader(move |txn| {
+                txn.query_row(
+                    "
+                    SELECT COUNT(DISTINCT Header.hash) FROM Header
+                    JOIN DhtOp ON DhtOp.header_hash = Header.hash
+                    WHERE
+                    DhtOp.is_authored = 1
+                    AND
+                    Header.author = :author
+                    ",
+                    named_params! {
+                        ":author": author,
+                    },
+                    |row| row.get(0),
+                )
+                .map_err(StateQueryError::from)
+            })
+            .await?;
+
+        let space = cell_id.dna_hash().to_kitsune();
+        let this_agent = cell_id.agent_pubkey().to_kitsune();
+        let peers = all_agent_infos(self.p2p_env(space.clone()).into())?
+            .into_iter()
+            .map(|info| Arc::new(info.as_agent_ref().clone()))
+            .filter(|agent| *agent != this_agent)
+            .collect::<Vec<_>>();
+        let metrics_env = self.p2p_metrics_env(space);
+        let mut last_gossip = None;
+        for agent in peers.iter().cloned() {
+            if let MetricQueryAnswer::LastSync(Some(time)) =
+                query_metrics(metrics_env.clone(), MetricQuery::LastSync { agent }).await?
+            {
+                last_gossip = last_gossip.max(Some(time));
+            }
+        }
+
+        Ok(Some(CellHealth {
+            chain_length,
+            integrated_ops: integration.integrated,
+            pending_ops: integration.validation_limbo + integration.integration_limbo,
+            peers_seen: peers.len(),
+            last_gossip: last_gossip
+                .map(|time| Timestamp::from(chrono::DateTime::<chrono::Utc>::from(time))),
+        }))
+    }
+
+    /// Evict data from the cache of every dna according to the cache policy,
+    /// returning how many ops were evicted.
+    /// Data in the storage arc of any of our running cells is kept.
+    pub(super) async fn gc_caches(&self, policy: &CachePolicyConfig) -> ConductorResult<usize> {
+        self.check_running()?;
+        let limits = CacheLimits {
+            max_bytes: policy.max_bytes,
+            max_age: policy.max_age_secs.map(std::time::Duration::from_secs),
+        };
+        let caches: Vec<_> = self
+            .caches
+            .lock()
+            .iter()
+            .map(|(dna_hash, env)| (dna_hash.clone(), env.clone()))
+            .collect();
+        let mut evicted = 0;
+        'caches: for (dna_hash, cache) in caches {
+            let space = dna_hash.to_kitsune();
+            let p2p_env: EnvRead = self.p2p_env(space).into();
+            let mut authority_arcs = Vec::new();
+            for cell_id in self.cells.keys().filter(|id| *id.dna_hash() == dna_hash) {
+                let info = get_single_agent_info(
+                    p2p_env.clone(),
+                    dna_hash.clone(),
+                    cell_id.agent_pubkey().clone(),
+                )?;
+                match info {
+                    Some(info) => {
+                        let info = kitsune_p2p::agent_store::AgentInfo::try_from(&info)?;
+                        authority_arcs.push(info.dht_arc()?);
+                    }
+                    // We can't tell what this agent is an authority for
+                    // until it has joined the network, so leave the cache alone.
+                    None => continue 'caches,
+                }
+            }
+            let limits = limits.clone();
+            // Stamp the reads served from this cache since the last run
+            // before deciding what is least recently used.
+            let accessed = cache_gc::take_pending_access(&cache);
+            evicted += cache
+                .async_commit(move |txn| {
+                    let now = timestamp::now();
+                    cache_gc::record_access(txn, &accessed, now)?;
+                    cache_gc::evict(txn, &limits, now, &authority_arcs)
+                })
+                .await?;
+        }
+        Ok(evicted)
+    }
+
+    /// Whether any active or inactive app contains this cell
+    async fn is_cell_installed(&self, cell_id: &CellId) -> ConductorResult<bool> {
+        let state = self.get_state().await?;
+        Ok(state
+            .active_apps
+            .values()
+            .flat_map(|app| app.all_cells())
+            .chain(state.inactive_apps.values().flat_map(|app| app.all_cells()))
+            .any(|id| id == cell_id))
+    }
+
+    /// Back up the source chain of a cell which belongs to an installed app
+    pub(super) async fn export_cell(&self, cell_id: CellId) -> ConductorApiResult<CellArchive> {
+        let vault: EnvRead = match self.cell_by_id(&cell_id) {
+            Ok(cell) => cell.env().clone().into(),
+            Err(_) => {
+                if !self.is_cell_installed(&cell_id).await? {
+                    return Err(ConductorError::CellMissing(cell_id).into());
+                }
+                EnvWrite::open(
+                    &std::path::PathBuf::from(self.root_env_dir.clone()),
+                    DbKind::Cell(cell_id.clone()),
+                    self.keystore.clone(),
+                )?
+                .into()
+            }
+        };
+        cell_archive::export(&self.keystore, cell_id, vault).await
+    }
+
+    /// Restore a source chain from a backup so that a later app install uses it
+    /// instead of running genesis
+    pub(super) async fn import_cell(&self, archive: CellArchive) -> ConductorApiResult<()> {
+        let cell_id = archive.cell_id.clone();
+        if self.is_cell_installed(&cell_id).await? || self.cells.contains_key(&cell_id) {
+            return Err(ConductorError::CellAlreadyInstalled(cell_id).into());
+        }
+        if self.dna_store().get(cell_id.dna_hash()).is_none() {
+            return Err(ConductorApiError::DnaMissing(cell_id.dna_hash().clone()));
+        }
+        cell_archive::check(&self.keystore, &archive).await?;
+
+        let vault = EnvWrite::open(
+            &std::path::PathBuf::from(self.root_env_dir.clone()),
+            DbKind::Cell(cell_id.clone()),
+            self.keystore.clone(),
+        )?;
+        source_chain::restore(vault, cell_id.agent_pubkey().clone(), archive.elements).await?;
+        Ok(())
+    }
+
     pub(super) fn p2p_env(&self, space: Arc<KitsuneSpace>) -> EnvWrite {
         let mut p2p_env = self.p2p_env.lock();
         p2p_env
@@ -1057,6 +1388,85 @@ pub async fn integration_dump(vault: &EnvRead) -> ConductorApiResult<Integration
         .await
 }
 
+/// Every op in the vault, by how far it is through validation and integration.
+/// Ops are returned in the order they were added to the vault, starting after
+/// `dht_ops_cursor` and stopping after `dht_ops_limit` ops.
+/// A limit of 0 is an error, as its cursor would never move.
+pub async fn full_integration_dump(
+    vault: &EnvRead,
+    dht_ops_cursor: Option<u64>,
+    dht_ops_limit: Option<u32>,
+) -> ConductorApiResult<FullIntegrationStateDump> {
+    if dht_ops_limit == Some(0) {
+        return Err(ConductorApiError::other(
+            "dht_ops_limit must be greater than 0",
+        ));
+    }
+    let cursor = dht_ops_cursor.unwrap_or(0) as i64;
+    // A negative limit means no limit to sqlite
+    let limit = dht_ops_limit.map(i64::from).unwrap_or(-1);
+    vault
+        .async_reader(move |txn| {
+            let mut dump = FullIntegrationStateDump {
+                validation_limbo: Vec::new(),
+                integration_limbo: Vec::new(),
+                integrated: Vec::new(),
+                dht_ops_cursor: None,
+            };
+            let mut stmt = txn.prepare(
+                "
+                SELECT rowid, type, hash, basis_hash, header_hash, is_authored,
+                validation_status, validation_stage, num_validation_attempts,
+                when_integrated_ns
+                FROM DhtOp
+                WHERE rowid > :cursor
+                AND NOT (is_authored = 1 AND validation_stage IS NULL AND when_integrated IS NULL)
+                ORDER BY rowid ASC
+                LIMIT :limit
+                ",
+            )?;
+            let mut rows = stmt.query(named_params! {
+                ":cursor": cursor,
+                ":limit": limit,
+            })?;
+            let mut count = 0;
+            let mut last_rowid = cursor;
+            while let Some(row) = rows.next()? {
+                last_rowid = row.get("rowid")?;
+                count += 1;
+                let when_integrated: Option<Vec<u8>> = row.get("when_integrated_ns")?;
+                let when_integrated = match when_integrated {
+                    Some(time) => Some(from_blob::<Timestamp>(time)?),
+                    None => None,
+                };
+                let op = DhtOpDump {
+                    op_type: row.get("type")?,
+                    hash: row.get("hash")?,
+                    basis: row.get("basis_hash")?,
+                    header_hash: row.get("header_hash")?,
+                    is_authored: row.get("is_authored")?,
+                    validation_status: row.get("validation_status")?,
+                    validation_stage: row.get("validation_stage")?,
+                    num_validation_attempts: row.get("num_validation_attempts")?,
+                    when_integrated,
+                };
+                if op.when_integrated.is_some() {
+                    dump.integrated.push(op);
+                } else if op.validation_stage == Some(3) {
+                    dump.integration_limbo.push(op);
+                } else {
+                    dump.validation_limbo.push(op);
+                }
+            }
+            if limit >= 0 && count == limit {
+                dump.dht_ops_cursor = Some(last_rowid as u64);
+            }
+            StateQueryResult::Ok(dump)
+        })
+        .await
+        .map_err(Into::into)
+}
+
 //-----------------------------------------------------------------------------
 // Private methods
 //-----------------------------------------------------------------------------
@@ -1075,6 +1485,7 @@ where
         keystore: KeystoreSender,
         root_env_dir: EnvironmentRootPath,
         holochain_p2p: holochain_p2p::HolochainP2pRef,
+        system_signals: SystemSignalSender,
     ) -> ConductorResult<Self> {
         Ok(Self {
             env,
@@ -1091,6 +1502,7 @@ where
             keystore,
             root_env_dir,
             holochain_p2p,
+            system_signals,
         })
     }
 
@@ -1305,6 +1717,7 @@ mod builder {
                 keystore,
                 env_path,
                 holochain_p2p,
+                SystemSignalSender::new(config.signals.clone()),
             )
             .await?;
 
@@ -1322,12 +1735,14 @@ mod builder {
             // Get data before handle
             let keystore = conductor.keystore.clone();
             let holochain_p2p = conductor.holochain_p2p.clone();
+            let system_signals = conductor.system_signals.clone();
 
             // Create handle
             let handle: ConductorHandle = Arc::new(ConductorHandleImpl {
                 conductor: RwLock::new(conductor),
                 keystore,
                 holochain_p2p,
+                system_signals,
             });
 
             let configs = conductor_config.admin_interfaces.unwrap_or_default();
@@ -1337,6 +1752,10 @@ mod builder {
 
             tokio::task::spawn(p2p_event_task(p2p_evt, handle.clone()));
 
+            if let Some(policy) = conductor_config.cache_policy {
+                tokio::task::spawn(cache_gc_task(Arc::downgrade(&handle), policy));
+            }
+
             let cell_startup_errors = handle.clone().setup_cells().await?;
 
             // TODO: This should probably be emitted over the admin interface
@@ -1403,6 +1822,7 @@ mod builder {
                 keystore,
                 envs.tempdir().path().to_path_buf().into(),
                 holochain_p2p,
+                SystemSignalSender::new(self.config.signals.clone()),
             )
             .await?;
 
@@ -1441,5 +1861,27 @@ async fn p2p_event_task(
     tracing::warn!("p2p_event_task has ended");
 }
 
+/// Garbage collect the DHT caches at the policy's interval,
+/// until the conductor shuts down
+async fn cache_gc_task(handle: std::sync::Weak<dyn ConductorHandleT>, policy: CachePolicyConfig) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        policy.gc_interval_secs.max(1),
+    ));
+    // The first tick completes immediately
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        let handle = match handle.upgrade() {
+            Some(handle) => handle,
+            None => break,
+        };
+        match handle.gc_caches(&policy).await {
+            Ok(evicted) => tracing::debug!(evicted, "garbage collected the DHT caches"),
+            Err(ConductorError::ShuttingDown) => break,
+            Err(e) => tracing::error!(msg = "failed to garbage collect the DHT caches", error = ?e),
+        }
+    }
+}
+
 #[cfg(test)]
 pub mod tests;
@@ -1,16 +1,25 @@
 use super::Conductor;
 use super::ConductorState;
 use super::*;
+use crate::conductor::api::AppInterfaceApi;
+use crate::conductor::api::InterfaceApi;
+use crate::conductor::config::SignalConfig;
 use crate::core::ribosome::guest_callback::validate::ValidateResult;
+use crate::core::workflow::SIGNED_ZOME_CALL_MAX_TTL;
 use crate::sweettest::*;
 use crate::test_utils::fake_valid_dna_file;
 use crate::{
     assert_eq_retry_10s, core::ribosome::guest_callback::genesis_self_check::GenesisSelfCheckResult,
 };
 use ::fixt::prelude::*;
-use holochain_conductor_api::{AdminRequest, AdminResponse, AppRequest, AppResponse, ZomeCall};
+use holochain_conductor_api::{
+    AdminRequest, AdminResponse, AppRequest, AppResponse, AppStatusFilter, CallLimit,
+    ExternalApiWireError, SignedZomeCall, ZomeCall, ZomeCallLimits, ZomeCallNonce,
+};
 use holochain_keystore::crude_mock_keystore::spawn_crude_mock_keystore;
+use holochain_keystore::AgentPubKeyExt;
 use holochain_state::prelude::*;
+use holochain_types::test_utils::fake_agent_pubkey_1;
 use holochain_types::test_utils::fake_cell_id;
 use holochain_wasm_test_utils::TestWasm;
 use holochain_websocket::WebsocketSender;
@@ -33,6 +42,7 @@ async fn can_update_state() {
         keystore,
         envs.tempdir().path().to_path_buf().into(),
         holochain_p2p,
+        SystemSignalSender::default(),
     )
     .await
     .unwrap();
@@ -83,6 +93,7 @@ async fn can_add_clone_cell_to_app() {
         keystore,
         envs.tempdir().path().to_path_buf().into(),
         holochain_p2p,
+        SystemSignalSender::default(),
     )
     .await
     .unwrap();
@@ -143,6 +154,7 @@ async fn app_ids_are_unique() {
         environments.keystore().clone(),
         environments.tempdir().path().to_path_buf().into(),
         holochain_p2p,
+        SystemSignalSender::default(),
     )
     .await
     .unwrap();
@@ -426,6 +438,143 @@ async fn test_setup_cells_idempotency() {
     assert_eq_retry_10s!(conductor.list_active_apps().await.unwrap().len(), 1);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_list_apps() {
+    observability::test_run().ok();
+    let zome = InlineZome::new_unique(Vec::new()).callback("echo", |_api, n: u32| Ok(n));
+    let (dna, _) = mk_dna("listed", zome).await.unwrap();
+
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let (cell,) = conductor
+        .setup_app("app1", &[dna.clone()])
+        .await
+        .unwrap()
+        .into_tuple();
+    conductor.setup_app("app2", &[dna]).await.unwrap();
+    conductor
+        .deactivate_app("app2".to_string(), DeactivationReason::Normal)
+        .await
+        .unwrap();
+    let handle = conductor.inner_handle();
+
+    let apps = handle.list_apps(None).await.unwrap();
+    assert_eq!(
+        apps.iter()
+            .map(|app| app.installed_app_id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["app1", "app2"]
+    );
+    assert_eq!(apps[0].status, InstalledAppStatus::Active);
+    assert_eq!(
+        apps[1].status,
+        InstalledAppStatus::Inactive {
+            reason: DeactivationReason::Normal
+        }
+    );
+
+    // - Running cells report their health
+    assert_eq!(&apps[0].cells[0].cell_id, cell.cell_id());
+    let health = apps[0].cells[0].health.clone().unwrap();
+    // Genesis commits at least the dna, agent validation package and agent key
+    assert!(health.chain_length >= 3);
+    assert!(health.integrated_ops + health.pending_ops > 0);
+
+    // - but cells of inactive apps are not running
+    assert_eq!(apps[1].cells[0].health, None);
+
+    // - Apps can be filtered by their status
+    let active = handle
+        .list_apps(Some(AppStatusFilter::Active))
+        .await
+        .unwrap();
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].installed_app_id, "app1");
+    let inactive = handle
+        .list_apps(Some(AppStatusFilter::Inactive))
+        .await
+        .unwrap();
+    assert_eq!(inactive.len(), 1);
+    assert_eq!(inactive[0].installed_app_id, "app2");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_export_and_import_cell() {
+    observability::test_run().ok();
+    let (dna, _) = mk_dna("archived", simple_create_entry_zome())
+        .await
+        .unwrap();
+    let agent = fake_agent_pubkey_1();
+
+    let mut conductor_a = SweetConductor::from_standard_config().await;
+    let (cell,) = conductor_a
+        .setup_app_for_agent("app", agent.clone(), &[dna.clone()])
+        .await
+        .unwrap()
+        .into_tuple();
+    let cell_id = cell.cell_id().clone();
+    let _: HeaderHash = conductor_a.call(&cell.zome("archived"), "create", ()).await;
+    let archive = conductor_a.export_cell(cell_id.clone()).await.unwrap();
+    assert_eq!(archive.cell_id, cell_id);
+    assert_matches!(archive.elements[0].header(), Header::Dna(_));
+
+    let mut conductor_b = SweetConductor::from_standard_config().await;
+
+    // - The Dna must be registered first
+    assert_matches!(
+        conductor_b.import_cell(archive.clone()).await,
+        Err(ConductorApiError::DnaMissing(_))
+    );
+    conductor_b.register_dna(dna.clone()).await.unwrap();
+
+    // - Tampered archives are rejected
+    let mut tampered = archive.clone();
+    tampered.exported_at = timestamp::now();
+    assert_matches!(
+        conductor_b.import_cell(tampered).await,
+        Err(ConductorApiError::ConductorError(
+            ConductorError::InvalidCellArchive(_)
+        ))
+    );
+    let mut truncated = archive.clone();
+    truncated.elements.remove(1);
+    truncated.signature = agent
+        .sign_raw(
+            &conductor_b.keystore(),
+            &CellArchive::signed_content(
+                truncated.version,
+                &truncated.cell_id,
+                truncated.exported_at,
+                &truncated.elements,
+            )
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_matches!(
+        conductor_b.import_cell(truncated).await,
+        Err(ConductorApiError::ConductorError(
+            ConductorError::InvalidCellArchive(_)
+        ))
+    );
+
+    // - A restored chain is picked up by installing an app, without running genesis again
+    conductor_b.import_cell(archive.clone()).await.unwrap();
+    conductor_b
+        .setup_app_for_agent("app", agent, &[dna])
+        .await
+        .unwrap();
+    let restored = conductor_b.export_cell(cell_id).await.unwrap();
+    assert_eq!(restored.elements, archive.elements);
+
+    // - but a cell in an installed app can't be overwritten
+    assert_matches!(
+        conductor_b.import_cell(archive).await,
+        Err(ConductorApiError::ConductorError(
+            ConductorError::CellAlreadyInstalled(_)
+        ))
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_signing_error_during_genesis() {
     observability::test_run().ok();
@@ -476,6 +625,254 @@ async fn make_signing_call(client: &mut WebsocketSender, cell: &SweetCell) -> Ap
         .unwrap()
 }
 
+async fn sign_zome_call(
+    keystore: &KeystoreSender,
+    signer: &AgentPubKey,
+    call: ZomeCall,
+    expires_at: Timestamp,
+) -> SignedZomeCall {
+    let nonce = ZomeCallNonce(rand::random());
+    let data = SignedZomeCall::data_to_sign(&call, &nonce, &expires_at).unwrap();
+    let signature = signer.sign_raw(keystore, &data).await.unwrap();
+    SignedZomeCall {
+        call,
+        nonce,
+        expires_at,
+        signature,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_signed_zome_calls() {
+    observability::test_run().ok();
+    let zome = InlineZome::new_unique(Vec::new()).callback("echo", |_api, n: u32| Ok(n));
+    let (dna, _) = mk_dna("signed", zome).await.unwrap();
+
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let keystore = conductor.keystore();
+    let (cell,) = conductor
+        .setup_app("app", &[dna])
+        .await
+        .unwrap()
+        .into_tuple();
+    let handle = conductor.inner_handle();
+
+    let call = ZomeCall {
+        cell_id: cell.cell_id().clone(),
+        zome_name: "signed".into(),
+        fn_name: "echo".into(),
+        payload: ExternIO::encode(42u32).unwrap(),
+        cap: None,
+        provenance: cell.agent_pubkey().clone(),
+    };
+    let now = holochain_types::timestamp::now();
+    let expires_at = (now + std::time::Duration::from_secs(60)).unwrap();
+
+    // - A correctly signed call succeeds
+    let signed = sign_zome_call(&keystore, cell.agent_pubkey(), call.clone(), expires_at).await;
+    let result = handle.call_zome_signed(signed.clone()).await.unwrap();
+    assert_matches!(result, Ok(ZomeCallResponse::Ok(_)));
+
+    // - Replaying the same call is rejected
+    let result = handle.call_zome_signed(signed.clone()).await.unwrap();
+    assert_matches!(result, Ok(ZomeCallResponse::Unauthorized(_, _, _, _)));
+
+    // - even after the conductor restarts
+    conductor.shutdown().await;
+    conductor.startup().await;
+    let handle = conductor.inner_handle();
+    let result = handle.call_zome_signed(signed).await.unwrap();
+    assert_matches!(result, Ok(ZomeCallResponse::Unauthorized(_, _, _, _)));
+
+    // - A call signed by someone other than the provenance is rejected
+    let other = SweetAgents::one(keystore.clone()).await;
+    let signed = sign_zome_call(&keystore, &other, call.clone(), expires_at).await;
+    let result = handle.call_zome_signed(signed).await.unwrap();
+    assert_matches!(result, Ok(ZomeCallResponse::Unauthorized(_, _, _, _)));
+
+    // - A tampered call is rejected
+    let mut signed = sign_zome_call(&keystore, cell.agent_pubkey(), call.clone(), expires_at).await;
+    signed.call.payload = ExternIO::encode(43u32).unwrap();
+    let result = handle.call_zome_signed(signed).await.unwrap();
+    assert_matches!(result, Ok(ZomeCallResponse::Unauthorized(_, _, _, _)));
+
+    // - Expired calls, or calls expiring too far in the future, are rejected
+    for expires_at in vec![
+        (now - std::time::Duration::from_secs(1)).unwrap(),
+        (now + (SIGNED_ZOME_CALL_MAX_TTL + std::time::Duration::from_secs(60))).unwrap(),
+    ] {
+        let signed = sign_zome_call(&keystore, cell.agent_pubkey(), call.clone(), expires_at).await;
+        let result = handle.call_zome_signed(signed).await.unwrap();
+        assert_matches!(result, Ok(ZomeCallResponse::Unauthorized(_, _, _, _)));
+    }
+
+    // - An interface requiring signed calls rejects unsigned ones
+    let api = RealAppInterfaceApi::new(handle.clone(), Default::default())
+        .require_signed_zome_calls(true);
+    let response = api
+        .handle_app_request(AppRequest::ZomeCall(Box::new(call.clone())))
+        .await;
+    assert_matches!(
+        response,
+        AppResponse::Error(ExternalApiWireError::ZomeCallUnauthorized(_))
+    );
+
+    // - but accepts signed ones
+    let signed = sign_zome_call(&keystore, cell.agent_pubkey(), call, expires_at).await;
+    let response = api
+        .handle_app_request(AppRequest::SignedZomeCall(Box::new(signed)))
+        .await;
+    assert_matches!(response, AppResponse::ZomeCall(_));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_signed_zome_call_is_verified_before_init() {
+    observability::test_run().ok();
+    let zome = InlineZome::new_unique(Vec::new())
+        .callback("init", |_api, _: ()| {
+            Ok(InitCallbackResult::Fail("init always fails".into()))
+        })
+        .callback("echo", |_api, n: u32| Ok(n));
+    let (dna, _) = mk_dna("signed", zome).await.unwrap();
+
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let keystore = conductor.keystore();
+    let (cell,) = conductor
+        .setup_app("app", &[dna])
+        .await
+        .unwrap()
+        .into_tuple();
+    let handle = conductor.inner_handle();
+
+    let call = ZomeCall {
+        cell_id: cell.cell_id().clone(),
+        zome_name: "signed".into(),
+        fn_name: "echo".into(),
+        payload: ExternIO::encode(42u32).unwrap(),
+        cap: None,
+        provenance: cell.agent_pubkey().clone(),
+    };
+    let expires_at =
+        (holochain_types::timestamp::now() + std::time::Duration::from_secs(60)).unwrap();
+
+    // - A forged call is rejected without running init
+    let other = SweetAgents::one(keystore.clone()).await;
+    let signed = sign_zome_call(&keystore, &other, call.clone(), expires_at).await;
+    let result = handle.call_zome_signed(signed).await.unwrap();
+    assert_matches!(result, Ok(ZomeCallResponse::Unauthorized(_, _, _, _)));
+
+    // - A correctly signed call runs init, which fails
+    let signed = sign_zome_call(&keystore, cell.agent_pubkey(), call, expires_at).await;
+    assert!(handle.call_zome_signed(signed).await.is_err());
+}
+
+/// Make zome calls until one is rejected, returning how many were
+/// accepted and the rejection. A new connection is used for each call
+/// if `new_connections` is set.
+async fn call_until_rejected(
+    api: &RealAppInterfaceApi,
+    call: &ZomeCall,
+    new_connections: bool,
+) -> (usize, AppResponse) {
+    let mut accepted = 0;
+    loop {
+        let api = if new_connections {
+            api.for_new_connection()
+        } else {
+            api.clone()
+        };
+        match api
+            .handle_app_request(AppRequest::ZomeCall(Box::new(call.clone())))
+            .await
+        {
+            AppResponse::ZomeCall(_) => accepted += 1,
+            other => return (accepted, other),
+        }
+        assert!(accepted < 10, "zome calls were never limited");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_zome_call_limits() {
+    observability::test_run().ok();
+    let zome = InlineZome::new_unique(Vec::new()).callback("echo", |_api, n: u32| Ok(n));
+    let (dna, _) = mk_dna("limited", zome).await.unwrap();
+
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let (cell,) = conductor
+        .setup_app("app", &[dna])
+        .await
+        .unwrap()
+        .into_tuple();
+    let handle = conductor.inner_handle();
+
+    let call = ZomeCall {
+        cell_id: cell.cell_id().clone(),
+        zome_name: "limited".into(),
+        fn_name: "echo".into(),
+        payload: ExternIO::encode(42u32).unwrap(),
+        cap: None,
+        provenance: cell.agent_pubkey().clone(),
+    };
+    let limit = CallLimit {
+        calls_per_second: Some(3),
+        max_concurrent_calls: None,
+    };
+
+    // - A connection is limited to its rate
+    let api = RealAppInterfaceApi::new(handle.clone(), Default::default()).zome_call_limits(
+        ZomeCallLimits {
+            per_connection: limit,
+            per_app: CallLimit::default(),
+        },
+    );
+    let (accepted, response) = call_until_rejected(&api, &call, false).await;
+    assert!(accepted >= 3);
+    assert_matches!(
+        response,
+        AppResponse::Error(ExternalApiWireError::ZomeCallLimitExceeded(_))
+    );
+
+    // - but other connections have their own allowance
+    let response = api
+        .for_new_connection()
+        .handle_app_request(AppRequest::ZomeCall(Box::new(call.clone())))
+        .await;
+    assert_matches!(response, AppResponse::ZomeCall(_));
+
+    // - The app limit is shared by every connection
+    let api = RealAppInterfaceApi::new(handle.clone(), Default::default()).zome_call_limits(
+        ZomeCallLimits {
+            per_connection: CallLimit::default(),
+            per_app: limit,
+        },
+    );
+    let (accepted, response) = call_until_rejected(&api, &call, true).await;
+    assert!(accepted >= 3);
+    assert_matches!(
+        response,
+        AppResponse::Error(ExternalApiWireError::ZomeCallLimitExceeded(_))
+    );
+
+    // - An interface whose limits would reject every call can't be attached
+    for zero in &[
+        CallLimit {
+            calls_per_second: Some(0),
+            max_concurrent_calls: None,
+        },
+        CallLimit {
+            calls_per_second: None,
+            max_concurrent_calls: Some(0),
+        },
+    ] {
+        let config = AppInterfaceConfig::websocket(0).zome_call_limits(ZomeCallLimits {
+            per_connection: CallLimit::default(),
+            per_app: *zero,
+        });
+        assert!(handle.clone().add_app_interface(config).await.is_err());
+    }
+}
+
 /// A test which simulates Keystore errors with a test keystore which is designed
 /// to fail.
 ///
@@ -525,7 +922,11 @@ async fn test_signing_error_during_genesis_doesnt_bork_interfaces() {
     let (cell1,) = app1.into_tuple();
     let (cell2,) = app2.into_tuple();
 
-    let app_port = conductor.inner_handle().add_app_interface(0).await.unwrap();
+    let app_port = conductor
+        .inner_handle()
+        .add_app_interface(AppInterfaceConfig::websocket(0))
+        .await
+        .unwrap();
     let (mut app_client, _) = websocket_client_by_port(app_port).await.unwrap();
     let (mut admin_client, _) = conductor.admin_ws_client().await;
 
@@ -577,6 +978,70 @@ pub(crate) fn simple_create_entry_zome() -> InlineZome {
     })
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn system_signals_report_lifecycle_and_authored_ops() {
+    observability::test_run().ok();
+    let mut config = standard_config();
+    config.signals = SignalConfig {
+        lifecycle: true,
+        consistency: true,
+        network: false,
+    };
+    let mut conductor = SweetConductor::from_config(config).await;
+    let mut signals = conductor.inner_handle().system_signals().subscribe();
+
+    let (dna, _) = mk_dna("signals", simple_create_entry_zome()).await.unwrap();
+    let app = conductor.setup_app("app", &[dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+    let cell_id = cell.cell_id().clone();
+    let _: HeaderHash = conductor.call(&cell.zome("signals"), "create", ()).await;
+
+    let mut lifecycle = Vec::new();
+    let (mut published, mut validated, mut integrated) = (false, false, false);
+    while !(published && validated && integrated) {
+        let signal = tokio::time::timeout(std::time::Duration::from_secs(30), signals.recv())
+            .await
+            .expect("timed out waiting for system signals")
+            .unwrap();
+        match signal {
+            Signal::System(SystemSignal::OpPublished { cell_id: id, .. }) => {
+                published |= id == cell_id
+            }
+            Signal::System(SystemSignal::OpValidated {
+                cell_id: id,
+                status,
+                ..
+            }) => {
+                assert_eq!(status, ValidationStatus::Valid);
+                validated |= id == cell_id
+            }
+            Signal::System(SystemSignal::OpIntegrated { cell_id: id, .. }) => {
+                integrated |= id == cell_id
+            }
+            Signal::System(SystemSignal::PeerJoined { .. }) => {
+                panic!("network signals are disabled")
+            }
+            Signal::System(signal) => lifecycle.push(signal),
+            Signal::App(..) => (),
+        }
+    }
+
+    assert_eq!(
+        lifecycle,
+        vec![
+            SystemSignal::GenesisComplete {
+                cell_id: cell_id.clone()
+            },
+            SystemSignal::AppInstalled {
+                installed_app_id: "app".into()
+            },
+            SystemSignal::AppActivated {
+                installed_app_id: "app".into()
+            },
+        ]
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_reactivate_app() {
     observability::test_run().ok();
@@ -94,6 +94,12 @@ pub enum ConductorError {
     #[error("Tried to perform an operation on an app that was not active: {0}")]
     AppNotActive(InstalledAppId),
 
+    #[error("Invalid cell archive: {0}")]
+    InvalidCellArchive(String),
+
+    #[error("Tried to import a cell which is part of an installed app: {0:?}")]
+    CellAlreadyInstalled(CellId),
+
     #[error(transparent)]
     HolochainP2pError(#[from] holochain_p2p::HolochainP2pError),
 
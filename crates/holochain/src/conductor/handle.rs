@@ -40,27 +40,37 @@
 //! code which interacted with the Conductor would also have to be highly generic.
 
 use super::api::error::ConductorApiResult;
+use super::api::SignedZomeCall;
 use super::api::ZomeCall;
 use super::config::AdminInterfaceConfig;
+use super::config::CachePolicyConfig;
 use super::error::ConductorResult;
 use super::error::CreateAppError;
 use super::interface::SignalBroadcaster;
+use super::interface::SystemSignalSender;
 use super::manager::TaskManagerRunHandle;
 use super::p2p_agent_store::get_agent_info_signed;
 use super::p2p_agent_store::put_agent_info_signed;
 use super::p2p_agent_store::query_agent_info_signed;
+use super::state::AppInterfaceConfig;
 use super::Cell;
 use super::Conductor;
 use crate::conductor::p2p_metrics::put_metric_datum;
+use crate::conductor::p2p_metrics::put_reachability_datum;
 use crate::conductor::p2p_metrics::query_metrics;
 use crate::core::workflow::ZomeCallResult;
 use crate::core::{queue_consumer::InitialQueueTriggers, ribosome::real_ribosome::RealRibosome};
 use derive_more::From;
 use futures::future::FutureExt;
 use futures::StreamExt;
+use holochain_conductor_api::AppStatusFilter;
+use holochain_conductor_api::AppStatusInfo;
+use holochain_conductor_api::CellArchive;
+use holochain_conductor_api::FullStateDump;
 use holochain_conductor_api::InstalledAppInfo;
 use holochain_p2p::event::HolochainP2pEvent;
 use holochain_p2p::event::HolochainP2pEvent::*;
+use holochain_p2p::AgentPubKeyExt;
 use holochain_p2p::DnaHashExt;
 use holochain_p2p::HolochainP2pCellT;
 use holochain_state::host_fn_workspace::HostFnWorkspace;
@@ -107,7 +117,8 @@ pub trait ConductorHandleT: Send + Sync {
     ) -> ConductorResult<()>;
 
     /// Add an app interface
-    async fn add_app_interface(self: Arc<Self>, port: u16) -> ConductorResult<u16>;
+    async fn add_app_interface(self: Arc<Self>, config: AppInterfaceConfig)
+        -> ConductorResult<u16>;
 
     /// List the app interfaces currently install.
     async fn list_app_interfaces(&self) -> ConductorResult<Vec<u16>>;
@@ -144,6 +155,12 @@ pub trait ConductorHandleT: Send + Sync {
     /// Invoke a zome function on a Cell
     async fn call_zome(&self, invocation: ZomeCall) -> ConductorApiResult<ZomeCallResult>;
 
+    /// Invoke a zome function on a Cell, on behalf of the provenance
+    /// which signed the call.
+    /// The call is rejected as unauthorized if its signature is invalid,
+    /// it has expired, or its nonce has already been used.
+    async fn call_zome_signed(&self, call: SignedZomeCall) -> ConductorApiResult<ZomeCallResult>;
+
     /// Invoke a zome function on a Cell with a workspace
     async fn call_zome_with_workspace(
         &self,
@@ -170,6 +187,9 @@ pub trait ConductorHandleT: Send + Sync {
     /// Request access to this conductor's networking handle
     fn holochain_p2p(&self) -> &holochain_p2p::HolochainP2pRef;
 
+    /// Request access to this conductor's system signal sender
+    fn system_signals(&self) -> &SystemSignalSender;
+
     /// Create a new Cell in an existing App based on an existing DNA
     async fn create_clone_cell(
         self: Arc<Self>,
@@ -225,6 +245,33 @@ pub trait ConductorHandleT: Send + Sync {
     /// Dump the cells state
     async fn dump_cell_state(&self, cell_id: &CellId) -> ConductorApiResult<String>;
 
+    /// Dump the cells state as structured data,
+    /// optionally only a page of its ops
+    async fn dump_full_cell_state(
+        &self,
+        cell_id: &CellId,
+        dht_ops_cursor: Option<u64>,
+        dht_ops_limit: Option<u32>,
+    ) -> ConductorApiResult<FullStateDump>;
+
+    /// List every installed app with its status and the health of its cells,
+    /// optionally only those with a particular status
+    async fn list_apps(
+        &self,
+        status_filter: Option<AppStatusFilter>,
+    ) -> ConductorApiResult<Vec<AppStatusInfo>>;
+
+    /// Back up the source chain of a cell in an installed app
+    async fn export_cell(&self, cell_id: CellId) -> ConductorApiResult<CellArchive>;
+
+    /// Restore a cell's source chain from a backup, ready for an app
+    /// containing the cell to be installed
+    async fn import_cell(&self, archive: CellArchive) -> ConductorApiResult<()>;
+
+    /// Evict data from the DHT caches beyond the limits of the cache policy,
+    /// returning how many ops were evicted
+    async fn gc_caches(&self, policy: &CachePolicyConfig) -> ConductorResult<usize>;
+
     /// Access the broadcast Sender which will send a Signal across every
     /// attached app interface
     async fn signal_broadcaster(&self) -> SignalBroadcaster;
@@ -288,6 +335,7 @@ pub struct ConductorHandleImpl<DS: DnaStore + 'static> {
     pub(crate) conductor: RwLock<Conductor<DS>>,
     pub(crate) keystore: KeystoreSender,
     pub(crate) holochain_p2p: holochain_p2p::HolochainP2pRef,
+    pub(crate) system_signals: SystemSignalSender,
 }
 
 #[async_trait::async_trait]
@@ -321,9 +369,12 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         Ok(())
     }
 
-    async fn add_app_interface(self: Arc<Self>, port: u16) -> ConductorResult<u16> {
+    async fn add_app_interface(
+        self: Arc<Self>,
+        config: AppInterfaceConfig,
+    ) -> ConductorResult<u16> {
         let mut lock = self.conductor.write().await;
-        lock.add_app_interface_via_handle(either::Left(port), self.clone())
+        lock.add_app_interface_via_handle(either::Left(config), self.clone())
             .await
     }
 
@@ -390,10 +441,23 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
                 ..
             } => {
                 // TODO: This read lock isn't needed to get the p2p_env.
-                let env = { self.conductor.read().await.p2p_env(space) };
+                let env = { self.conductor.read().await.p2p_env(space.clone()) };
+                let agent = Arc::new(agent_info_signed.as_agent_ref().clone());
+                // Only look up whether this peer is new if anyone is listening
+                let is_new_peer = self.system_signals.allows(SystemSignalKind::Network)
+                    && matches!(
+                        get_agent_info_signed(env.clone(), space, agent.clone()),
+                        Ok(None)
+                    );
                 let res = put_agent_info_signed(env, agent_info_signed)
                     .await
                     .map_err(holochain_p2p::HolochainP2pError::other);
+                if res.is_ok() && is_new_peer {
+                    self.system_signals.send(SystemSignal::PeerJoined {
+                        dna_hash: cell_id.dna_hash().clone(),
+                        agent: AgentPubKey::from_kitsune(&agent),
+                    });
+                }
                 respond.respond(Ok(async move { res }.boxed().into()));
             }
             GetAgentInfoSigned {
@@ -430,6 +494,13 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
                     .map_err(holochain_p2p::HolochainP2pError::other);
                 respond.respond(Ok(async move { res }.boxed().into()));
             }
+            PutReachabilityDatum { respond, datum, .. } => {
+                let env = { self.conductor.read().await.p2p_metrics_env(space) };
+                let res = put_reachability_datum(env, datum)
+                    .await
+                    .map_err(holochain_p2p::HolochainP2pError::other);
+                respond.respond(Ok(async move { res }.boxed().into()));
+            }
             QueryMetrics { respond, query, .. } => {
                 let env = { self.conductor.read().await.p2p_metrics_env(space) };
                 let res = query_metrics(env, query)
@@ -449,6 +520,7 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
             | Publish { .. }
             | GetValidationPackage { .. }
             | Get { .. }
+            | GetMany { .. }
             | GetMeta { .. }
             | GetLinks { .. }
             | GetAgentActivity { .. }
@@ -467,6 +539,11 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         Ok(cell.call_zome(call, None).await?)
     }
 
+    async fn call_zome_signed(&self, call: SignedZomeCall) -> ConductorApiResult<ZomeCallResult> {
+        let cell = self.cell_by_id(&call.call.cell_id).await?;
+        Ok(cell.call_zome_signed(call).await?)
+    }
+
     async fn call_zome_with_workspace(
         &self,
         call: ZomeCall,
@@ -500,6 +577,10 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         &self.holochain_p2p
     }
 
+    fn system_signals(&self) -> &SystemSignalSender {
+        &self.system_signals
+    }
+
     async fn create_clone_cell(
         self: Arc<Self>,
         payload: CreateCloneCellPayload,
@@ -550,7 +631,7 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
             .await?;
 
         let cell_data = cell_data.into_iter().map(|(c, _)| c);
-        let app = InstalledAppCommon::new_legacy(installed_app_id, cell_data)?;
+        let app = InstalledAppCommon::new_legacy(installed_app_id.clone(), cell_data)?;
 
         // Update the db
         let _ = self
@@ -560,6 +641,9 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
             .add_inactive_app_to_db(app)
             .await?;
 
+        self.system_signals
+            .send(SystemSignal::AppInstalled { installed_app_id });
+
         Ok(())
     }
 
@@ -615,6 +699,10 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
             .add_inactive_app_to_db(app.clone())
             .await?;
 
+        self.system_signals.send(SystemSignal::AppInstalled {
+            installed_app_id: app.installed_app_id().clone(),
+        });
+
         Ok(app)
     }
 
@@ -646,9 +734,12 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         self.conductor
             .write()
             .await
-            .activate_app_in_db(installed_app_id)
-            .await
+            .activate_app_in_db(installed_app_id.clone())
+            .await?;
         // MD: Should we be doing `Conductor::add_cells()` here? (see below comment)
+        self.system_signals
+            .send(SystemSignal::AppActivated { installed_app_id });
+        Ok(())
     }
 
     async fn deactivate_app(
@@ -656,14 +747,20 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         installed_app_id: InstalledAppId,
         reason: DeactivationReason,
     ) -> ConductorResult<()> {
-        let mut conductor = self.conductor.write().await;
-        let cell_ids_to_remove = conductor
-            .deactivate_app_in_db(installed_app_id, reason)
-            .await?;
-        // MD: I'm not sure about this. We never add the cells back in after re-activating an app,
-        //     so it seems either we shouldn't remove them here, or we should be sure to add them
-        //     back in when re-activating.
-        conductor.remove_cells(cell_ids_to_remove).await;
+        {
+            let mut conductor = self.conductor.write().await;
+            let cell_ids_to_remove = conductor
+                .deactivate_app_in_db(installed_app_id.clone(), reason.clone())
+                .await?;
+            // MD: I'm not sure about this. We never add the cells back in after re-activating an app,
+            //     so it seems either we shouldn't remove them here, or we should be sure to add them
+            //     back in when re-activating.
+            conductor.remove_cells(cell_ids_to_remove).await;
+        }
+        self.system_signals.send(SystemSignal::AppDeactivated {
+            installed_app_id,
+            reason,
+        });
         Ok(())
     }
 
@@ -698,6 +795,39 @@ impl<DS: DnaStore + 'static> ConductorHandleT for ConductorHandleImpl<DS> {
         self.conductor.read().await.dump_cell_state(cell_id).await
     }
 
+    async fn dump_full_cell_state(
+        &self,
+        cell_id: &CellId,
+        dht_ops_cursor: Option<u64>,
+        dht_ops_limit: Option<u32>,
+    ) -> ConductorApiResult<FullStateDump> {
+        self.conductor
+            .read()
+            .await
+            .dump_full_cell_state(cell_id, dht_ops_cursor, dht_ops_limit)
+            .await
+    }
+
+    async fn list_apps(
+        &self,
+        status_filter: Option<AppStatusFilter>,
+    ) -> ConductorApiResult<Vec<AppStatusInfo>> {
+        self.conductor.read().await.list_apps(status_filter).await
+    }
+
+    async fn export_cell(&self, cell_id: CellId) -> ConductorApiResult<CellArchive> {
+        self.conductor.read().await.export_cell(cell_id).await
+    }
+
+    async fn import_cell(&self, archive: CellArchive) -> ConductorApiResult<()> {
+        // Hold the write lock so no app can install this cell mid-restore
+        self.conductor.write().await.import_cell(archive).await
+    }
+
+    async fn gc_caches(&self, policy: &CachePolicyConfig) -> ConductorResult<usize> {
+        self.conductor.read().await.gc_caches(policy).await
+    }
+
     async fn signal_broadcaster(&self) -> SignalBroadcaster {
         self.conductor.read().await.signal_broadcaster()
     }
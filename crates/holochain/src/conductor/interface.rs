@@ -2,14 +2,19 @@
 //! and dispatch them to the appropriate handlers within Holochain.
 //! They also allow emitting responses and one-way Signals.
 //!
-//! Currently the only InterfaceDriver is a Websocket-based one, whose
-//! implementation can be found in the `websocket` module here.
+//! All InterfaceDrivers are Websocket-based, running either over tcp or
+//! over a unix domain socket. The implementation can be found in the
+//! `websocket` module here.
 
 use crate::conductor::api::*;
 use error::InterfaceError;
 use error::InterfaceResult;
+use holochain_conductor_api::config::conductor::SignalConfig;
 use holochain_types::signal::Signal;
+use holochain_types::signal::SystemSignal;
+use holochain_types::signal::SystemSignalKind;
 use std::convert::TryInto;
+use std::sync::Arc;
 use tokio::sync::broadcast;
 
 #[allow(missing_docs)]
@@ -98,4 +103,51 @@ impl SignalBroadcaster {
     }
 }
 
+/// Emits [SystemSignal]s to every connected admin and app interface client,
+/// dropping any signals which are not enabled by the conductor's [SignalConfig]
+#[derive(Clone, Debug)]
+pub struct SystemSignalSender {
+    config: Arc<SignalConfig>,
+    tx: broadcast::Sender<Signal>,
+}
+
+impl SystemSignalSender {
+    /// Constructor
+    pub fn new(config: SignalConfig) -> Self {
+        // This receiver is thrown away because we can produce infinite new
+        // receivers from the Sender
+        let (tx, _) = broadcast::channel(websocket::SIGNAL_BUFFER_SIZE);
+        Self {
+            config: Arc::new(config),
+            tx,
+        }
+    }
+
+    /// Send the signal if it is enabled.
+    /// Signals sent while no clients are connected are dropped.
+    pub fn send(&self, signal: SystemSignal) {
+        if self.config.allows(signal.kind()) {
+            let _ = self.tx.send(signal.into());
+        }
+    }
+
+    /// Are signals of this kind enabled?
+    /// Useful to skip work which only exists to produce signals.
+    pub fn allows(&self, kind: SystemSignalKind) -> bool {
+        self.config.allows(kind)
+    }
+
+    /// Get a receiver of all enabled system signals
+    pub fn subscribe(&self) -> broadcast::Receiver<Signal> {
+        self.tx.subscribe()
+    }
+}
+
+impl Default for SystemSignalSender {
+    /// A sender with all system signals disabled
+    fn default() -> Self {
+        Self::new(SignalConfig::default())
+    }
+}
+
 pub use holochain_conductor_api::config::InterfaceDriver;
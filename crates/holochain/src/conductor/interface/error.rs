@@ -27,6 +27,12 @@ pub enum InterfaceError {
     WebsocketError(#[from] holochain_websocket::WebsocketError),
     #[error("Failed to find free port")]
     PortError,
+    #[error("Failed to read interface tls file {0:?}: {1}")]
+    TlsFile(std::path::PathBuf, std::io::Error),
+    #[error("Interface driver is not supported here: {0:?}")]
+    UnsupportedDriver(crate::conductor::interface::InterfaceDriver),
+    #[error("Zome call limits of 0 would reject every call: {0:?}")]
+    ZeroZomeCallLimit(holochain_conductor_api::ZomeCallLimits),
 }
 
 impl From<String> for InterfaceError {
@@ -16,6 +16,7 @@ use holochain_websocket::WebsocketListener;
 use holochain_websocket::WebsocketMessage;
 use holochain_websocket::WebsocketReceiver;
 use holochain_websocket::WebsocketSender;
+use holochain_websocket::WebsocketTlsConfig;
 use std::convert::TryFrom;
 
 use std::sync::atomic::AtomicIsize;
@@ -25,6 +26,7 @@ use tokio::sync::broadcast;
 use tokio_stream::StreamExt;
 use tracing::*;
 use url2::url2;
+use url2::Url2;
 
 // TODO: This is arbitrary, choose reasonable size.
 /// Number of signals in buffer before applying
@@ -32,29 +34,89 @@ use url2::url2;
 pub(crate) const SIGNAL_BUFFER_SIZE: usize = 50;
 const MAX_CONNECTIONS: isize = 400;
 
+/// The url to bind to and the websocket config for an interface driver
+async fn websocket_binding(driver: &InterfaceDriver) -> InterfaceResult<(Url2, WebsocketConfig)> {
+    match driver {
+        InterfaceDriver::Websocket {
+            port,
+            bind_address,
+            tls,
+            allowed_origins,
+        } => {
+            let mut config = WebsocketConfig::default();
+            if let Some(tls) = tls {
+                let read = |path: &std::path::PathBuf| {
+                    let path = path.clone();
+                    async move {
+                        tokio::fs::read(&path)
+                            .await
+                            .map_err(|e| InterfaceError::TlsFile(path, e))
+                    }
+                };
+                config = config.tls(WebsocketTlsConfig {
+                    cert_pem: read(&tls.cert_path).await?,
+                    key_pem: read(&tls.key_path).await?,
+                });
+            }
+            if let Some(origins) = allowed_origins {
+                config = config.allowed_origins(origins.clone());
+            }
+            let addr = std::net::SocketAddr::new(*bind_address, *port);
+            Ok((url2!("{}://{}", config.scheme, addr), config))
+        }
+        InterfaceDriver::UnixSocket { .. } => {
+            Err(InterfaceError::UnsupportedDriver(driver.clone()))
+        }
+    }
+}
+
 /// Create a WebsocketListener to be used in interfaces
 pub async fn spawn_websocket_listener(
-    port: u16,
+    driver: &InterfaceDriver,
+) -> InterfaceResult<(
+    ListenerHandle,
+    impl futures::stream::Stream<Item = ListenerItem>,
+)> {
+    trace!("Initializing Admin interface");
+    let (url, config) = websocket_binding(driver).await?;
+    let listener = WebsocketListener::bind_with_handle(url, Arc::new(config)).await?;
+    trace!("LISTENING AT: {}", listener.0.local_addr());
+    Ok(listener)
+}
+
+/// Create a WebsocketListener on a unix domain socket to be used in interfaces
+#[cfg(unix)]
+pub async fn spawn_unix_socket_listener(
+    path: &std::path::Path,
 ) -> InterfaceResult<(
     ListenerHandle,
     impl futures::stream::Stream<Item = ListenerItem>,
 )> {
     trace!("Initializing Admin interface");
-    let listener = WebsocketListener::bind_with_handle(
-        url2!("ws://127.0.0.1:{}", port),
-        Arc::new(WebsocketConfig::default()),
-    )
-    .await?;
+    let listener =
+        WebsocketListener::bind_unix_with_handle(path, Arc::new(WebsocketConfig::default()))
+            .await?;
     trace!("LISTENING AT: {}", listener.0.local_addr());
     Ok(listener)
 }
 
+/// Unix domain sockets are not available on this platform
+#[cfg(not(unix))]
+pub async fn spawn_unix_socket_listener(
+    path: &std::path::Path,
+) -> InterfaceResult<(ListenerHandle, futures::stream::Empty<ListenerItem>)> {
+    Err(InterfaceError::UnsupportedDriver(
+        InterfaceDriver::unix_socket(path),
+    ))
+}
+
 /// Create an Admin Interface, which only receives AdminRequest messages
 /// from the external client
 pub fn spawn_admin_interface_task<A: InterfaceApi>(
     handle: ListenerHandle,
     listener: impl futures::stream::Stream<Item = ListenerItem> + Send + 'static,
     api: A,
+    system_signals: SystemSignalSender,
     mut stop_rx: StopReceiver,
 ) -> InterfaceResult<ManagedTaskHandle> {
     Ok(tokio::task::spawn(async move {
@@ -68,12 +130,13 @@ pub fn spawn_admin_interface_task<A: InterfaceApi>(
         // establish a new connection to a client
         while let Some(connection) = listener.next().await {
             match connection {
-                Ok((_, rx_from_iface)) => {
+                Ok((tx_to_iface, rx_from_iface)) => {
                     if num_connections.fetch_add(1, Ordering::Relaxed) > MAX_CONNECTIONS {
                         // Max connections so drop this connection
                         // which will close it.
                         continue;
                     };
+                    spawn_outgoing_signals(signal_stream(system_signals.subscribe()), tx_to_iface);
                     tokio::task::spawn(recv_incoming_admin_msgs(
                         api.clone(),
                         rx_from_iface,
@@ -90,19 +153,17 @@ pub fn spawn_admin_interface_task<A: InterfaceApi>(
 }
 
 /// Create an App Interface, which includes the ability to receive signals
-/// from Cells via a broadcast channel
+/// from Cells via a broadcast channel, along with any system signals
 pub async fn spawn_app_interface_task<A: InterfaceApi>(
-    port: u16,
+    driver: &InterfaceDriver,
     api: A,
     signal_broadcaster: broadcast::Sender<Signal>,
+    system_signals: SystemSignalSender,
     mut stop_rx: StopReceiver,
 ) -> InterfaceResult<(u16, ManagedTaskHandle)> {
     trace!("Initializing App interface");
-    let (handle, mut listener) = WebsocketListener::bind_with_handle(
-        url2!("ws://127.0.0.1:{}", port),
-        Arc::new(WebsocketConfig::default()),
-    )
-    .await?;
+    let (url, config) = websocket_binding(driver).await?;
+    let (handle, mut listener) = WebsocketListener::bind_with_handle(url, Arc::new(config)).await?;
     trace!("LISTENING AT: {}", handle.local_addr());
     let port = handle
         .local_addr()
@@ -118,10 +179,12 @@ pub async fn spawn_app_interface_task<A: InterfaceApi>(
             match connection {
                 Ok((tx_to_iface, rx_from_iface)) => {
                     let rx_from_cell = signal_broadcaster.subscribe();
+                    let rx_from_system = system_signals.subscribe();
                     spawn_recv_incoming_msgs_and_outgoing_signals(
-                        api.clone(),
+                        api.for_new_connection(),
                         rx_from_iface,
                         rx_from_cell,
+                        rx_from_system,
                         tx_to_iface,
                     );
                 }
@@ -161,26 +224,55 @@ async fn recv_incoming_admin_msgs<A: InterfaceApi>(
 
 /// Polls for messages coming in from the external client while simultaneously
 /// polling for signals being broadcast from the Cells associated with this
-/// App interface.
+/// App interface, and from the conductor itself.
 fn spawn_recv_incoming_msgs_and_outgoing_signals<A: InterfaceApi>(
     api: A,
     rx_from_iface: WebsocketReceiver,
     rx_from_cell: broadcast::Receiver<Signal>,
+    rx_from_system: broadcast::Receiver<Signal>,
     tx_to_iface: WebsocketSender,
 ) {
     use futures::stream::StreamExt;
 
     trace!("CONNECTION: {}", rx_from_iface.remote_addr());
 
-    let rx_from_cell = futures::stream::unfold(rx_from_cell, |mut rx_from_cell| async move {
-        if let Ok(item) = rx_from_cell.recv().await {
-            Some((item, rx_from_cell))
+    spawn_outgoing_signals(
+        futures::stream::select(signal_stream(rx_from_cell), signal_stream(rx_from_system)),
+        tx_to_iface,
+    );
+
+    tokio::task::spawn(rx_from_iface.for_each_concurrent(4096, move |msg| {
+        let api = api.clone();
+        async move {
+            if let Err(err) = handle_incoming_message(msg, api).await {
+                error!(?err, "error handling websocket message");
+            }
+        }
+    }));
+}
+
+/// Turn a broadcast receiver into a stream of signals,
+/// which ends when the receiver errors.
+fn signal_stream(
+    rx: broadcast::Receiver<Signal>,
+) -> impl futures::stream::Stream<Item = Signal> + Send + 'static {
+    futures::stream::unfold(rx, |mut rx| async move {
+        if let Ok(item) = rx.recv().await {
+            Some((item, rx))
         } else {
             None
         }
-    });
+    })
+}
+
+/// Forwards signals to the external client.
+fn spawn_outgoing_signals(
+    signals: impl futures::stream::Stream<Item = Signal> + Send + 'static,
+    tx_to_iface: WebsocketSender,
+) {
+    use futures::stream::StreamExt;
 
-    tokio::task::spawn(rx_from_cell.for_each_concurrent(4096, move |signal| {
+    tokio::task::spawn(signals.for_each_concurrent(4096, move |signal| {
         let mut tx_to_iface = tx_to_iface.clone();
         async move {
             trace!(msg = "Sending signal!", ?signal);
@@ -195,15 +287,6 @@ fn spawn_recv_incoming_msgs_and_outgoing_signals<A: InterfaceApi>(
             }
         }
     }));
-
-    tokio::task::spawn(rx_from_iface.for_each_concurrent(4096, move |msg| {
-        let api = api.clone();
-        async move {
-            if let Err(err) = handle_incoming_message(msg, api).await {
-                error!(?err, "error handling websocket message");
-            }
-        }
-    }));
 }
 
 /// Handles messages on all interfaces
@@ -282,6 +365,7 @@ pub mod test {
     use crate::test_utils::conductor_setup::ConductorTestData;
     use ::fixt::prelude::*;
     use futures::future::FutureExt;
+    use holochain_conductor_api::FullStateDump;
     use holochain_p2p::{AgentPubKeyExt, DnaHashExt};
     use holochain_serialized_bytes::prelude::*;
     use holochain_sqlite::prelude::*;
@@ -616,7 +700,14 @@ pub mod test {
         let (_tmpdir, conductor_handle) = setup_admin().await;
         let shutdown = conductor_handle.take_shutdown_handle().await.unwrap();
         let admin_api = RealAdminInterfaceApi::new(conductor_handle.clone());
-        let msg = AdminRequest::AttachAppInterface { port: None };
+        let msg = AdminRequest::AttachAppInterface {
+            port: None,
+            require_signed_zome_calls: false,
+            bind_address: None,
+            tls: None,
+            allowed_origins: None,
+            zome_call_limits: Default::default(),
+        };
         let msg = msg.try_into().unwrap();
         let respond = |bytes: SerializedBytes| {
             let response: AdminResponse = bytes.try_into().unwrap();
@@ -678,6 +769,97 @@ pub mod test {
         shutdown.await.unwrap().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn dump_full_state() {
+        observability::test_run().ok();
+        let uuid = Uuid::new_v4();
+        let dna = fake_dna_zomes(
+            &uuid.to_string(),
+            vec![("zomey".into(), TestWasm::Foo.into())],
+        );
+        let cell_id = CellId::from((dna.dna_hash().clone(), fake_agent_pubkey_1()));
+
+        let mut dna_store = MockDnaStore::new();
+        dna_store.expect_get().returning(move |_| Some(dna.clone()));
+        dna_store
+            .expect_add_dnas::<Vec<_>>()
+            .times(1)
+            .return_const(());
+        dna_store
+            .expect_add_entry_defs::<Vec<_>>()
+            .times(1)
+            .return_const(());
+
+        let (_tmpdir, conductor_handle) =
+            setup_admin_fake_cells(vec![(cell_id.clone(), None)], dna_store).await;
+        let conductor_handle = activate(conductor_handle).await;
+        let shutdown = conductor_handle.take_shutdown_handle().await.unwrap();
+        // Allow agents time to join
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
+        // Get state
+        let expected = conductor_handle
+            .dump_full_cell_state(&cell_id, None, None)
+            .await
+            .unwrap();
+        let num_ops = |dump: &FullStateDump| {
+            let i = &dump.integration_dump;
+            i.validation_limbo.len() + i.integration_limbo.len() + i.integrated.len()
+        };
+        assert!(num_ops(&expected) > 0);
+        assert_eq!(expected.integration_dump.dht_ops_cursor, None);
+        assert!(expected.peer_dump.is_some());
+        assert!(expected.gossip_metrics.is_some());
+
+        // A limit of 0 would never move the cursor
+        assert!(conductor_handle
+            .dump_full_cell_state(&cell_id, None, Some(0))
+            .await
+            .is_err());
+
+        // Paging through one op at a time finds every op,
+        // and only the first page has the source chain
+        let mut cursor = None;
+        let mut paged = 0;
+        loop {
+            let page = conductor_handle
+                .dump_full_cell_state(&cell_id, cursor, Some(1))
+                .await
+                .unwrap();
+            assert_eq!(page.source_chain_dump.is_some(), cursor.is_none());
+            assert_eq!(page.peer_dump.is_some(), cursor.is_none());
+            paged += num_ops(&page);
+            cursor = page.integration_dump.dht_ops_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(paged, num_ops(&expected));
+
+        let admin_api = RealAdminInterfaceApi::new(conductor_handle.clone());
+        let msg = AdminRequest::DumpFullState {
+            cell_id: Box::new(cell_id),
+            dht_ops_cursor: None,
+            dht_ops_limit: None,
+        };
+        let msg = msg.try_into().unwrap();
+        let respond = move |bytes: SerializedBytes| {
+            let response: AdminResponse = bytes.try_into().unwrap();
+            assert_matches!(
+                response,
+                AdminResponse::FullStateDumped(s)
+                    if s.source_chain_dump.as_ref().map(|d| d.elements.len())
+                        == expected.source_chain_dump.as_ref().map(|d| d.elements.len())
+            );
+            async { Ok(()) }.boxed().into()
+        };
+        let respond = Respond::Request(Box::new(respond));
+        let msg = (msg, respond);
+        handle_incoming_message(msg, admin_api).await.unwrap();
+        conductor_handle.shutdown().await;
+        shutdown.await.unwrap().unwrap();
+    }
+
     async fn make_dna(uid: &str, zomes: Vec<TestWasm>) -> DnaFile {
         DnaFile::new(
             DnaDef {
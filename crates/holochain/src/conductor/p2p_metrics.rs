@@ -6,7 +6,7 @@ use super::error::ConductorResult;
 use holochain_p2p::AgentPubKeyExt;
 use holochain_sqlite::prelude::*;
 use holochain_types::prelude::*;
-use kitsune_p2p::event::{MetricKind, MetricQuery, MetricQueryAnswer};
+use kitsune_p2p::event::{MetricKind, MetricQuery, MetricQueryAnswer, ReachabilityDatum};
 use std::time::SystemTime;
 
 /// Record a p2p metric datum
@@ -23,6 +23,16 @@ pub async fn put_metric_datum(
     Ok(())
 }
 
+/// Record the outcome of an attempt to reach a remote agent
+pub async fn put_reachability_datum(
+    env: EnvWrite,
+    datum: ReachabilityDatum,
+) -> ConductorResult<()> {
+    env.async_commit(move |txn| holochain_sqlite::db::put_reachability_datum(txn, datum))
+        .await?;
+    Ok(())
+}
+
 /// Query the p2p_metrics database in a variety of ways
 pub async fn query_metrics(
     env: EnvWrite,
@@ -129,6 +139,88 @@ mod tests {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_query_reachability() {
+        use kitsune_p2p::event::{PeerReachability, ReachabilityOutcome};
+
+        let test_env = test_p2p_metrics_env();
+        let env = test_env.env();
+        let agent1 = fixt!(AgentPubKey).to_kitsune();
+        let agent2 = fixt!(AgentPubKey).to_kitsune();
+        let agent3 = fixt!(AgentPubKey).to_kitsune();
+        let ms: Vec<SystemTime> = moments().take(4).collect();
+
+        let datum =
+            |agent: &Arc<kitsune_p2p::KitsuneAgent>, outcome, timestamp| ReachabilityDatum {
+                agent: agent.clone(),
+                outcome,
+                bytes_out: 10,
+                bytes_in: 20,
+                timestamp,
+            };
+        let success = |rtt_ms| ReachabilityOutcome::Success {
+            rtt: Duration::from_millis(rtt_ms),
+        };
+
+        put_reachability_datum(env.clone(), datum(&agent1, success(100), ms[0]))
+            .await
+            .unwrap();
+        put_reachability_datum(env.clone(), datum(&agent1, success(20), ms[1]))
+            .await
+            .unwrap();
+        put_reachability_datum(
+            env.clone(),
+            datum(&agent2, ReachabilityOutcome::Failure, ms[2]),
+        )
+        .await
+        .unwrap();
+        put_reachability_datum(
+            env.clone(),
+            datum(&agent2, ReachabilityOutcome::Failure, ms[3]),
+        )
+        .await
+        .unwrap();
+
+        let answer = query_metrics(
+            env.clone(),
+            MetricQuery::Reachability {
+                agents: vec![agent1.clone(), agent2.clone(), agent3.clone()],
+            },
+        )
+        .await
+        .unwrap();
+
+        // - agent3 has no data so is omitted
+        let expected = vec![
+            PeerReachability {
+                agent: agent1,
+                rtt_ewma: Some(Duration::from_millis(90)),
+                success_count: 2,
+                failure_count: 0,
+                consecutive_failures: 0,
+                bytes_out: 20,
+                bytes_in: 40,
+                last_failure: None,
+            },
+            PeerReachability {
+                agent: agent2,
+                rtt_ewma: None,
+                success_count: 0,
+                failure_count: 2,
+                consecutive_failures: 2,
+                bytes_out: 20,
+                bytes_in: 40,
+                last_failure: Some(ms[3]),
+            },
+        ];
+        assert_eq!(answer, MetricQueryAnswer::Reachability(expected.clone()));
+
+        // - the healthy, fast agent scores better and the failing one is backing off
+        assert!(expected[0].score() > expected[1].score());
+        assert!(!expected[0].is_backing_off(ms[3]));
+        assert!(expected[1].is_backing_off(ms[3]));
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_query_oldest() {
         use MetricKind::*;
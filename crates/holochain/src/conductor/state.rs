@@ -2,7 +2,7 @@
 //! startups and shutdowns
 
 use holochain_conductor_api::signal_subscription::SignalSubscription;
-use holochain_conductor_api::{config::InterfaceDriver, InstalledAppInfo};
+use holochain_conductor_api::{config::InterfaceDriver, config::ZomeCallLimits, InstalledAppInfo};
 use holochain_types::prelude::*;
 use serde::Deserialize;
 use serde::Serialize;
@@ -96,16 +96,44 @@ pub struct AppInterfaceConfig {
 
     /// The driver for the interface, e.g. Websocket
     pub driver: InterfaceDriver,
+
+    /// Only accept zome calls made as an `AppRequest::SignedZomeCall`
+    /// over this interface.
+    #[serde(default)]
+    pub require_signed_zome_calls: bool,
+
+    /// Per connection and per app limits on zome calls over this interface.
+    #[serde(default)]
+    pub zome_call_limits: ZomeCallLimits,
 }
 
 impl AppInterfaceConfig {
-    /// Create config for a websocket interface
-    pub fn websocket(port: u16) -> Self {
+    /// Create config for an interface with the given driver
+    pub fn new(driver: InterfaceDriver) -> Self {
         Self {
             signal_subscriptions: HashMap::new(),
-            driver: InterfaceDriver::Websocket { port },
+            driver,
+            require_signed_zome_calls: false,
+            zome_call_limits: ZomeCallLimits::default(),
         }
     }
+
+    /// Create config for a websocket interface
+    pub fn websocket(port: u16) -> Self {
+        Self::new(InterfaceDriver::websocket(port))
+    }
+
+    /// Only accept signed zome calls over this interface
+    pub fn require_signed_zome_calls(mut self, require: bool) -> Self {
+        self.require_signed_zome_calls = require;
+        self
+    }
+
+    /// Limit the zome calls made over this interface
+    pub fn zome_call_limits(mut self, limits: ZomeCallLimits) -> Self {
+        self.zome_call_limits = limits;
+        self
+    }
 }
 
 // TODO: Tons of consistency check tests were ripped out in the great legacy code cleanup
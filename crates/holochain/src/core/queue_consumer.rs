@@ -20,6 +20,8 @@
 //! | DhtOpIntegr.   | IntegrationLimbo | IntegratedDhtOps | SysVal + VR    |
 //! | ValReceipt.    | IntegratedDhtOps | IntegratedDhtOps | *n/a           |
 //! | Publish        | AuthoredDhtOps   | *n/a*            | *n/a*          |
+//! |                        **remote signals**                             |
+//! | SignalOutbox   | RemoteSignalOut. | *n/a*            | *n/a*          |
 //!
 //! († Auth'd + IntQ is short for: AuthoredDhtOps + IntegrationLimbo)
 //!
@@ -43,6 +45,8 @@ use app_validation_consumer::*;
 mod publish_dht_ops_consumer;
 use validation_receipt_consumer::*;
 mod validation_receipt_consumer;
+use remote_signal_outbox_consumer::*;
+mod remote_signal_outbox_consumer;
 use crate::conductor::{api::CellConductorApiT, error::ConductorError, manager::ManagedTaskResult};
 use crate::conductor::{manager::ManagedTaskAdd, ConductorHandle};
 use holochain_p2p::HolochainP2pCell;
@@ -98,6 +102,22 @@ pub async fn spawn_queue_consumer_tasks(
         .await
         .expect("Failed to manage workflow handle");
 
+    // Remote signal outbox
+    let (tx_signal_outbox, handle) = spawn_remote_signal_outbox_consumer(
+        env.clone(),
+        conductor_handle.clone(),
+        stop.subscribe(),
+        cell_network.clone(),
+    );
+    task_sender
+        .send(ManagedTaskAdd::cell_critical(
+            handle,
+            cell_id.clone(),
+            "remote_signal_outbox_consumer",
+        ))
+        .await
+        .expect("Failed to manage workflow handle");
+
     let (create_tx_sys, get_tx_sys) = tokio::sync::oneshot::channel();
 
     // Integration
@@ -161,7 +181,14 @@ pub async fn spawn_queue_consumer_tasks(
 
     (
         QueueTriggers::new(tx_sys.clone(), tx_publish.clone()),
-        InitialQueueTriggers::new(tx_sys, tx_publish, tx_app, tx_integration, tx_receipt),
+        InitialQueueTriggers::new(
+            tx_sys,
+            tx_publish,
+            tx_app,
+            tx_integration,
+            tx_receipt,
+            tx_signal_outbox,
+        ),
     )
 }
 
@@ -183,6 +210,7 @@ pub struct InitialQueueTriggers {
     app_validation: TriggerSender,
     integrate_dht_ops: TriggerSender,
     validation_receipt: TriggerSender,
+    remote_signal_outbox: TriggerSender,
 }
 
 impl QueueTriggers {
@@ -202,6 +230,7 @@ impl InitialQueueTriggers {
         app_validation: TriggerSender,
         integrate_dht_ops: TriggerSender,
         validation_receipt: TriggerSender,
+        remote_signal_outbox: TriggerSender,
     ) -> Self {
         Self {
             sys_validation,
@@ -209,6 +238,7 @@ impl InitialQueueTriggers {
             app_validation,
             integrate_dht_ops,
             validation_receipt,
+            remote_signal_outbox,
         }
     }
 
@@ -219,6 +249,7 @@ impl InitialQueueTriggers {
         self.integrate_dht_ops.trigger();
         self.publish_dht_ops.trigger();
         self.validation_receipt.trigger();
+        self.remote_signal_outbox.trigger();
     }
 }
 /// The means of nudging a queue consumer to tell it to look for more work
@@ -30,6 +30,7 @@ pub fn spawn_app_validation_consumer(
     let (tx, mut rx) = TriggerSender::new();
     let mut trigger_self = tx.clone();
     let handle = tokio::spawn(async move {
+        let system_signals = conductor_handle.system_signals().clone();
         loop {
             // Wait for next job
             if let Job::Shutdown = next_job_or_exit(&mut rx, &mut stop).await {
@@ -46,6 +47,7 @@ pub fn spawn_app_validation_consumer(
                 trigger_integration.clone(),
                 conductor_api.clone(),
                 network.clone(),
+                system_signals.clone(),
             )
             .await;
             match result {
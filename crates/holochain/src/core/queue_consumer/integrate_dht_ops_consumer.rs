@@ -20,6 +20,7 @@ pub fn spawn_integrate_dht_ops_consumer(
     let mut trigger_self = tx.clone();
     let handle = tokio::spawn(async move {
         let trigger_sys = trigger_sys.await.expect("failed to get tx sys");
+        let system_signals = conductor_handle.system_signals().clone();
         loop {
             // Wait for next job
             if let Job::Shutdown = next_job_or_exit(&mut rx, &mut stop).await {
@@ -34,6 +35,8 @@ pub fn spawn_integrate_dht_ops_consumer(
                 env.clone(),
                 trigger_sys.clone(),
                 trigger_receipt.clone(),
+                cell_id.clone(),
+                system_signals.clone(),
             )
             .await
             {
@@ -18,6 +18,7 @@ pub fn spawn_publish_dht_ops_consumer(
     let (tx, mut rx) = TriggerSender::new();
     let mut trigger_self = tx.clone();
     let handle = tokio::spawn(async move {
+        let system_signals = conductor_handle.system_signals().clone();
         loop {
             // Wait for next job
             if let Job::Shutdown = next_job_or_exit(&mut rx, &mut stop).await {
@@ -28,7 +29,13 @@ pub fn spawn_publish_dht_ops_consumer(
             }
 
             // Run the workflow
-            match publish_dht_ops_workflow(env.clone(), cell_network.clone()).await {
+            match publish_dht_ops_workflow(
+                env.clone(),
+                cell_network.clone(),
+                system_signals.clone(),
+            )
+            .await
+            {
                 Ok(WorkComplete::Incomplete) => trigger_self.trigger(),
                 Err(err) => {
                     handle_workflow_error(
@@ -0,0 +1,57 @@
+//! The workflow and queue consumer for the remote signal outbox
+
+use super::*;
+use crate::conductor::manager::ManagedTaskResult;
+use crate::core::workflow::remote_signal_outbox_workflow::remote_signal_outbox_workflow;
+use holochain_state::remote_signal_outbox::MIN_RETRY_DELAY;
+use tokio::task::JoinHandle;
+use tracing::*;
+
+/// Spawn the QueueConsumer for remote signal outbox workflow
+#[instrument(skip(env, conductor_handle, stop, cell_network))]
+pub fn spawn_remote_signal_outbox_consumer(
+    env: EnvWrite,
+    conductor_handle: ConductorHandle,
+    mut stop: sync::broadcast::Receiver<()>,
+    mut cell_network: HolochainP2pCell,
+) -> (TriggerSender, JoinHandle<ManagedTaskResult>) {
+    let (tx, mut rx) = TriggerSender::new();
+    let mut trigger_self = tx.clone();
+    let handle = tokio::spawn(async move {
+        loop {
+            // Wait for next job, or look for signals which are due anyway.
+            // Each signal backs off on its own, so this is only how often we check.
+            if let Ok(Job::Shutdown) =
+                tokio::time::timeout(MIN_RETRY_DELAY, next_job_or_exit(&mut rx, &mut stop)).await
+            {
+                tracing::warn!(
+                    "Cell is shutting down: stopping remote_signal_outbox_workflow queue consumer."
+                );
+                break;
+            }
+
+            // Run the workflow
+            match remote_signal_outbox_workflow(
+                env.clone(),
+                &mut cell_network,
+                conductor_handle.system_signals().clone(),
+            )
+            .await
+            {
+                Ok(WorkComplete::Incomplete) => trigger_self.trigger(),
+                Err(err) => {
+                    handle_workflow_error(
+                        conductor_handle.clone(),
+                        cell_network.cell_id(),
+                        err,
+                        "remote_signal_outbox_workflow failure",
+                    )
+                    .await?
+                }
+                _ => (),
+            };
+        }
+        Ok(())
+    });
+    (tx, handle)
+}
@@ -73,6 +73,16 @@ impl Invocation for ValidateInvocation {
                     fns.push(entry_def_id);
                 }
             }
+            // A delete has no entry but the entry def is
+            // found from the header being deleted.
+            None => {
+                if let (Header::Delete(_), Some(EntryDefId::App(entry_def_id))) =
+                    (self.element.header(), self.entry_def_id.clone())
+                {
+                    fns.push("entry".into());
+                    fns.push(entry_def_id);
+                }
+            }
             _ => {}
         }
         fns.into()
@@ -263,6 +273,45 @@ mod test {
         for fn_component in validate_invocation.fn_components() {
             assert_eq!(fn_component, expected.pop().unwrap(),);
         }
+
+        let app_entry = Entry::App(
+            AppEntryBytesFixturator::new(::fixt::Unpredictable)
+                .next()
+                .unwrap()
+                .into(),
+        );
+        let el = fixt!(Element, (app_entry, HeaderType::Create));
+        validate_invocation.element = Arc::new(el);
+        validate_invocation.entry_def_id = Some(EntryDefId::App("post".into()));
+        let mut expected = vec![
+            "validate",
+            "validate_create",
+            "validate_create_entry",
+            "validate_create_entry_post",
+        ];
+        for fn_component in validate_invocation.fn_components() {
+            assert_eq!(fn_component, expected.pop().unwrap(),);
+        }
+
+        // Deletes are dispatched by the entry def of the deleted header
+        let delete = Element::new(
+            SignedHeaderHashed::with_presigned(
+                HeaderHashed::from_content_sync(Header::Delete(fixt!(Delete))),
+                fixt!(Signature),
+            ),
+            None,
+        );
+        validate_invocation.element = Arc::new(delete);
+        let mut expected = vec![
+            "validate",
+            "validate_delete",
+            "validate_delete_entry",
+            "validate_delete_entry_post",
+        ];
+        for fn_component in validate_invocation.fn_components() {
+            assert_eq!(fn_component, expected.pop().unwrap(),);
+        }
+        assert!(expected.is_empty());
     }
 
     #[tokio::test(flavor = "multi_thread")]
@@ -284,10 +333,13 @@ mod test {
 #[cfg(feature = "slow_tests")]
 mod slow_tests {
     use super::ValidateResult;
+    use crate::conductor::api::error::ConductorApiResult;
     use crate::core::ribosome::RibosomeT;
     use crate::core::ribosome::ZomesToInvoke;
     use crate::fixt::curve::Zomes;
     use crate::fixt::*;
+    use crate::sweettest::SweetConductor;
+    use crate::sweettest::SweetDnaFile;
     use ::fixt::prelude::*;
     use holo_hash::fixt::AgentPubKeyFixturator;
     use holochain_state::host_fn_workspace::HostFnWorkspace;
@@ -328,6 +380,90 @@ mod slow_tests {
         assert_eq!(result, ValidateResult::Valid,);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_validate_typed_entry_dispatch() {
+        #[derive(serde::Serialize, Debug)]
+        struct Post(String);
+
+        let ribosome = RealRibosomeFixturator::new(Zomes(vec![TestWasm::ValidateValid]))
+            .next()
+            .unwrap();
+
+        let post =
+            |bytes: Vec<u8>| Entry::app(SerializedBytes::from(UnsafeBytes::from(bytes))).unwrap();
+        for (entry, expected) in vec![
+            (
+                post(holochain_serialized_bytes::encode(&Post("hello".into())).unwrap()),
+                ValidateResult::Valid,
+            ),
+            (
+                post(holochain_serialized_bytes::encode(&Post("a".repeat(281))).unwrap()),
+                ValidateResult::Invalid("Posts are at most 280 characters".into()),
+            ),
+        ] {
+            let mut validate_invocation = ValidateInvocationFixturator::new(::fixt::Empty)
+                .next()
+                .unwrap();
+            validate_invocation.zomes_to_invoke =
+                ZomesToInvoke::One(TestWasm::ValidateValid.into());
+            validate_invocation.element = Arc::new(fixt!(Element, (entry, HeaderType::Create)));
+            validate_invocation.entry_def_id = Some(EntryDefId::App("post".into()));
+
+            let result = ribosome
+                .run_validate(fixt!(ValidateHostAccess), validate_invocation)
+                .unwrap();
+            assert_eq!(result, expected);
+        }
+
+        // An entry that isn't a post is invalid
+        let mut validate_invocation = ValidateInvocationFixturator::new(::fixt::Empty)
+            .next()
+            .unwrap();
+        validate_invocation.zomes_to_invoke = ZomesToInvoke::One(TestWasm::ValidateValid.into());
+        let entry = post(holochain_serialized_bytes::encode(&42_u32).unwrap());
+        validate_invocation.element = Arc::new(fixt!(Element, (entry, HeaderType::Create)));
+        validate_invocation.entry_def_id = Some(EntryDefId::App("post".into()));
+        let result = ribosome
+            .run_validate(fixt!(ValidateHostAccess), validate_invocation)
+            .unwrap();
+        assert!(matches!(result, ValidateResult::Invalid(_)));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_validate_typed_update_and_delete() {
+        let (dna, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::ValidateValid])
+            .await
+            .unwrap();
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", &[dna]).await.unwrap();
+        let (cell,) = app.into_tuple();
+        let zome = cell.zome(TestWasm::ValidateValid);
+
+        // - Updates and deletes are validated against the original post.
+        let draft: HeaderHash = conductor.call(&zome, "create_post", "draft").await;
+        let _: HeaderHash = conductor
+            .call(&zome, "update_post", (draft.clone(), "edited"))
+            .await;
+        let _: HeaderHash = conductor.call(&zome, "delete_post", draft).await;
+
+        let final_post: HeaderHash = conductor.call(&zome, "create_post", "final").await;
+        let result: ConductorApiResult<HeaderHash> = conductor
+            .call_fallible(&zome, "update_post", (final_post.clone(), "edited"))
+            .await;
+        assert!(result.is_err());
+        let result: ConductorApiResult<HeaderHash> = conductor
+            .call_fallible(&zome, "delete_post", final_post)
+            .await;
+        assert!(result.is_err());
+
+        // - Private entries can be updated and deleted.
+        let note: HeaderHash = conductor.call(&zome, "create_note", "draft").await;
+        let _: HeaderHash = conductor
+            .call(&zome, "update_note", (note.clone(), "edited"))
+            .await;
+        let _: HeaderHash = conductor.call(&zome, "delete_note", note).await;
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_validate_implemented_invalid() {
         let ribosome = RealRibosomeFixturator::new(Zomes(vec![TestWasm::ValidateInvalid]))
@@ -117,13 +117,34 @@ impl Invocation for ValidateCreateLinkInvocation {
         ZomesToInvoke::One(self.zome.clone())
     }
     fn fn_components(&self) -> FnComponents {
-        vec!["validate_create_link".into()].into()
+        let mut fns = vec!["validate_create_link".into()];
+        // typed links are also validated by the callback for their type,
+        // as long as the type can be part of a function name
+        if let Some(link_type) = self
+            .link_add
+            .tag
+            .link_type()
+            .filter(|link_type| is_link_type_callback_name(link_type))
+        {
+            fns.push(link_type.to_string());
+        }
+        fns.into()
     }
     fn host_input(self) -> Result<ExternIO, SerializedBytesError> {
         ExternIO::encode(ValidateCreateLinkData::from(self))
     }
 }
 
+/// Link types come from the tag so anyone can create a link with any type.
+/// Only types matching `[a-z0-9_]{1,255}` are dispatched to a callback.
+fn is_link_type_callback_name(link_type: &str) -> bool {
+    !link_type.is_empty()
+        && link_type.len() <= MAX_LINK_TYPE_LEN
+        && link_type
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+}
+
 impl Invocation for ValidateDeleteLinkInvocation {
     fn zomes(&self) -> ZomesToInvoke {
         // links are specific to zomes so only validate in the zome the link is defined in
@@ -183,6 +204,7 @@ mod test {
     use holochain_zome_types::validate_link::ValidateLinkCallbackResult;
     use holochain_zome_types::ExternIO;
     use rand::seq::SliceRandom;
+    use std::sync::Arc;
 
     #[tokio::test(flavor = "multi_thread")]
     async fn validate_link_add_callback_result_fold() {
@@ -255,6 +277,40 @@ mod test {
         }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn validate_typed_link_add_invocation_fn_components() {
+        let mut validate_create_link_invocation =
+            ValidateCreateLinkInvocationFixturator::new(::fixt::Unpredictable)
+                .next()
+                .unwrap();
+        let mut link_add = (*validate_create_link_invocation.link_add).clone();
+        link_add.tag = LinkTag::typed("comment", &[1, 2, 3]).unwrap();
+        validate_create_link_invocation.link_add = Arc::new(link_add);
+
+        let mut expected = vec!["validate_create_link", "validate_create_link_comment"];
+        for fn_component in validate_create_link_invocation.fn_components() {
+            assert_eq!(fn_component, expected.pop().unwrap(),);
+        }
+        assert!(expected.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn validate_typed_link_add_invocation_fn_components_unusable_type() {
+        for link_type in vec!["", "Comment", "comment-text", "comment.text", "评论"] {
+            let mut validate_create_link_invocation =
+                ValidateCreateLinkInvocationFixturator::new(::fixt::Unpredictable)
+                    .next()
+                    .unwrap();
+            let mut link_add = (*validate_create_link_invocation.link_add).clone();
+            link_add.tag = LinkTag::typed(link_type, &[1, 2, 3]).unwrap();
+            validate_create_link_invocation.link_add = Arc::new(link_add);
+
+            let fn_components: Vec<String> =
+                validate_create_link_invocation.fn_components().collect();
+            assert_eq!(fn_components, vec!["validate_create_link".to_string()]);
+        }
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn validate_link_add_invocation_host_input() {
         let validate_create_link_invocation =
@@ -280,6 +336,8 @@ mod test {
 #[cfg(test)]
 #[cfg(feature = "slow_tests")]
 mod slow_tests {
+    use super::ValidateCreateLinkInvocation;
+    use super::ValidateLinkInvocation;
     use super::ValidateLinkResult;
     use crate::core::ribosome::RibosomeT;
     use crate::fixt::curve::Zomes;
@@ -325,6 +383,56 @@ mod slow_tests {
         assert_eq!(result, ValidateLinkResult::Valid,);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_validate_typed_link_dispatch() {
+        #[derive(serde::Serialize, Debug)]
+        struct Rating {
+            stars: u8,
+        }
+
+        let ribosome = RealRibosomeFixturator::new(Zomes(vec![TestWasm::ValidateCreateLinkValid]))
+            .next()
+            .unwrap();
+
+        for (tag, expected) in vec![
+            (
+                LinkTag::typed(
+                    "rating",
+                    &holochain_serialized_bytes::encode(&Rating { stars: 3 }).unwrap(),
+                ),
+                ValidateLinkResult::Valid,
+            ),
+            (
+                LinkTag::typed(
+                    "rating",
+                    &holochain_serialized_bytes::encode(&Rating { stars: 6 }).unwrap(),
+                ),
+                ValidateLinkResult::Invalid("ratings are out of five".into()),
+            ),
+            // Untyped links only go to validate_create_link
+            (Some(LinkTag::new("rating")), ValidateLinkResult::Valid),
+        ] {
+            let mut validate_create_link_invocation =
+                ValidateCreateLinkInvocationFixturator::new(::fixt::Unpredictable)
+                    .next()
+                    .unwrap();
+            validate_create_link_invocation.zome = Zome::from(TestWasm::ValidateCreateLinkValid);
+            let mut link_add = (*validate_create_link_invocation.link_add).clone();
+            link_add.tag = tag.unwrap();
+            validate_create_link_invocation.link_add = std::sync::Arc::new(link_add);
+
+            let result = ribosome
+                .run_validate_link(
+                    fixt!(ValidateLinkHostAccess),
+                    ValidateLinkInvocation::<ValidateCreateLinkInvocation>::new(
+                        validate_create_link_invocation,
+                    ),
+                )
+                .unwrap();
+            assert_eq!(result, expected);
+        }
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_validate_link_add_implemented_invalid() {
         let ribosome =
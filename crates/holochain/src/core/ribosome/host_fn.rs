@@ -115,13 +115,31 @@ host_fn_api_impls! {
     // Attempt to get a live entry from the cascade.
     fn get (zt::entry::GetInput) -> Option<zt::element::Element>;
 
+    // Attempt to get many live entries at once, in the same order as the hashes.
+    fn get_many (zt::entry::GetManyInput) -> Vec<Option<zt::element::Element>>;
+
+    // Get the latest versions of an entry merged according to its crdt type.
+    fn get_merged (zt::entry::GetInput) -> Option<zt::crdt::MergedEntry>;
+
+    // Get a live entry, reporting how many authorities answered and agreed.
+    fn get_with_quorum (zt::entry::GetInput) -> zt::quorum::QuorumOutput<Option<zt::element::Element>>;
+
     fn get_agent_activity (zt::agent_activity::GetAgentActivityInput) -> zt::query::AgentActivity;
 
     fn get_details (zt::entry::GetInput) -> Option<zt::metadata::Details>;
 
+    // Get the details of many hashes at once, in the same order as the hashes.
+    fn get_details_many (zt::entry::GetManyInput) -> Vec<Option<zt::metadata::Details>>;
+
     // Get links by entry hash from the cascade.
     fn get_links (zt::link::GetLinksInput) -> zt::link::Links;
 
+    // Count the live links matching the input, without fetching them.
+    fn count_links (zt::link::GetLinksInput) -> u32;
+
+    // Get links, reporting how many authorities answered and agreed.
+    fn get_links_with_quorum (zt::link::GetLinksInput) -> zt::quorum::QuorumOutput<zt::link::Links>;
+
     fn get_link_details (zt::link::GetLinksInput) -> zt::link::LinkDetails;
 
     // Hash an entry on the host.
@@ -136,6 +154,9 @@ host_fn_api_impls! {
     // Remotely signal many agents without waiting for responses
     fn remote_signal (zt::signal::RemoteSignal) -> ();
 
+    // Remotely signal many agents, returning each agent's delivery outcome
+    fn remote_signal_with_receipts (zt::signal::RemoteSignalWithReceipts) -> Vec<zt::signal::RemoteSignalReceipt>;
+
     // // @todo
     // fn send (()) -> ();
 
@@ -1,5 +1,10 @@
+use crate::core::ribosome::guest_callback::entry_defs::EntryDefsInvocation;
+use crate::core::ribosome::guest_callback::entry_defs::EntryDefsResult;
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostAccess;
 use crate::core::ribosome::RibosomeT;
+use crate::core::ribosome::ZomeCallHostAccess;
+use holo_hash::hash_type::AnyDht;
 use holochain_cascade::Cascade;
 use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::WasmError;
@@ -7,7 +12,7 @@ use std::sync::Arc;
 
 #[allow(clippy::extra_unused_lifetimes)]
 pub fn get<'a>(
-    _ribosome: Arc<impl RibosomeT>,
+    ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
     input: GetInput,
 ) -> Result<Option<Element>, WasmError> {
@@ -18,18 +23,75 @@ pub fn get<'a>(
 
     // Get the network from the context
     let network = call_context.host_access.network().clone();
+    let workspace = call_context.host_access.workspace();
+    let mut cascade = Cascade::from_workspace_network(workspace, network);
+    let is_entry = matches!(*any_dht_hash.hash_type(), AnyDht::Entry);
 
     // timeouts must be handled by the network
-    tokio_helper::block_forever_on(async move {
-        let workspace = call_context.host_access.workspace();
-        let mut cascade = Cascade::from_workspace_network(workspace, network);
-        let maybe_element = cascade
-            .dht_get(any_dht_hash, get_options)
-            .await
+    let maybe_element =
+        tokio_helper::block_forever_on(cascade.dht_get(any_dht_hash, get_options.clone()))
             .map_err(|cascade_error| WasmError::Host(cascade_error.to_string()))?;
 
-        Ok(maybe_element)
-    })
+    // Only a get by entry hash follows updates.
+    // The newest version is returned as it is,
+    // the merged entry is only returned by `get_merged`.
+    match maybe_element {
+        Some(element) if is_entry && element.entry().as_option().is_some() => {
+            let crdt_type = crdt_type_of(ribosome, call_context, &element)?;
+            if !crdt_type.merges() {
+                return Ok(Some(element));
+            }
+            let merged = tokio_helper::block_forever_on(cascade.merge_updates(
+                element,
+                crdt_type,
+                get_options,
+            ))
+            .map_err(|cascade_error| WasmError::Host(cascade_error.to_string()))?;
+            Ok(merged.and_then(MergedEntry::into_newest))
+        }
+        maybe_element => Ok(maybe_element),
+    }
+}
+
+/// The [CrdtType] from the entry def of this element's app entry.
+/// Elements without an app entry don't merge.
+/// The def is read from the conductor's entry def store when this is a zome call,
+/// and the entry defs callback is only run if the def isn't there.
+pub fn crdt_type_of(
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    element: &Element,
+) -> Result<CrdtType, WasmError> {
+    let (zome_id, entry_def_index) = match element.header().entry_type() {
+        Some(EntryType::App(app_entry_type)) => (app_entry_type.zome_id(), app_entry_type.id()),
+        _ => return Ok(CrdtType::NoMerge),
+    };
+    let (zome_name, zome) = match ribosome.dna_def().zomes.get(zome_id.index()) {
+        Some((zome_name, zome)) => (zome_name.clone(), zome.clone()),
+        None => return Ok(CrdtType::NoMerge),
+    };
+    if let HostAccess::ZomeCall(ZomeCallHostAccess {
+        call_zome_handle, ..
+    }) = &call_context.host_access
+    {
+        let key = EntryDefBufferKey::new(zome, entry_def_index);
+        if let Some(entry_def) =
+            tokio_helper::block_forever_on(call_zome_handle.get_entry_def(&key))
+        {
+            return Ok(entry_def.crdt_type);
+        }
+    }
+    match ribosome
+        .run_entry_defs((&call_context.host_access).into(), EntryDefsInvocation)
+        .map_err(|ribosome_error| WasmError::Host(ribosome_error.to_string()))?
+    {
+        EntryDefsResult::Defs(mut defs) => Ok(defs
+            .remove(&zome_name)
+            .and_then(|entry_defs| entry_defs.into_iter().nth(entry_def_index.index()))
+            .map(|entry_def| entry_def.crdt_type)
+            .unwrap_or_default()),
+        _ => Ok(CrdtType::NoMerge),
+    }
 }
 
 // we are relying on the create tests to show the commit/get round trip
@@ -15,8 +15,18 @@ pub fn get_link_details<'a>(
     let GetLinksInput {
         base_address,
         tag_prefix,
+        quorum,
+        // Link details are neither filtered nor paged.
+        filter: _,
     } = input;
 
+    // Link details don't report agreement, so a quorum can't be honoured.
+    if quorum.is_some() {
+        return Err(WasmError::Host(
+            "get_link_details does not support a quorum".to_string(),
+        ));
+    }
+
     // Get zome id
     let zome_id = ribosome
         .zome_to_id(&call_context.zome)
@@ -55,8 +65,8 @@ pub mod slow_tests {
     use holochain_state::host_fn_workspace::HostFnWorkspace;
     use holochain_wasm_test_utils::TestWasm;
     use holochain_zome_types::element::SignedHeaderHashed;
-    use holochain_zome_types::Header;
     use holochain_zome_types::fake_agent_pubkey_1;
+    use holochain_zome_types::Header;
 
     #[tokio::test(flavor = "multi_thread")]
     async fn ribosome_entry_hash_path_children_details() {
@@ -64,10 +74,10 @@ pub mod slow_tests {
         let test_cache = holochain_state::test_utils::test_cache_env();
         let env = test_env.env();
         let author = fake_agent_pubkey_1();
-        crate::test_utils::fake_genesis(env.clone())
+        crate::test_utils::fake_genesis(env.clone()).await.unwrap();
+        let workspace = HostFnWorkspace::new(env.clone(), test_cache.env(), author)
             .await
             .unwrap();
-        let workspace = HostFnWorkspace::new(env.clone(), test_cache.env(), author).await.unwrap();
         let mut host_access = fixt!(ZomeCallHostAccess);
         host_access.workspace = workspace;
 
@@ -15,6 +15,8 @@ pub fn get_links<'a>(
     let GetLinksInput {
         base_address,
         tag_prefix,
+        quorum,
+        filter,
     } = input;
 
     // Get zome id
@@ -36,10 +38,23 @@ pub fn get_links<'a>(
         let mut cascade = Cascade::from_workspace_network(workspace, network);
 
         // Get the links from the dht
-        let links = cascade
-            .dht_get_links(key, GetLinksOptions::default())
-            .await
-            .map_err(|cascade_error| WasmError::Host(cascade_error.to_string()))?;
+        let links = match quorum {
+            Some(quorum) => cascade
+                .dht_get_links_with_quorum(key, filter, &quorum)
+                .await
+                .and_then(|QuorumOutput { output, report }| {
+                    holochain_cascade::quorum::require(report, &quorum)?;
+                    Ok(output)
+                }),
+            None => {
+                let options = GetLinksOptions {
+                    filter,
+                    ..Default::default()
+                };
+                cascade.dht_get_links(key, options).await
+            }
+        }
+        .map_err(|cascade_error| WasmError::Host(cascade_error.to_string()))?;
 
         Ok(links.into())
     })
@@ -68,7 +83,9 @@ pub mod slow_tests {
         let env = test_env.env();
         let author = fake_agent_pubkey_1();
         crate::test_utils::fake_genesis(env.clone()).await.unwrap();
-        let workspace = HostFnWorkspace::new(env.clone(), test_cache.env(), author).await.unwrap();
+        let workspace = HostFnWorkspace::new(env.clone(), test_cache.env(), author)
+            .await
+            .unwrap();
         let mut host_access = fixt!(ZomeCallHostAccess);
         host_access.workspace = workspace;
 
@@ -137,7 +154,9 @@ pub mod slow_tests {
         let env = test_env.env();
         let author = fake_agent_pubkey_1();
         crate::test_utils::fake_genesis(env.clone()).await.unwrap();
-        let workspace = HostFnWorkspace::new(env.clone(), test_cache.env(), author).await.unwrap();
+        let workspace = HostFnWorkspace::new(env.clone(), test_cache.env(), author)
+            .await
+            .unwrap();
         let mut host_access = fixt!(ZomeCallHostAccess);
         host_access.workspace = workspace;
 
@@ -288,4 +307,35 @@ pub mod slow_tests {
         assert_eq!(links.into_inner().len(), 1);
         conductor_test.shutdown_conductor().await;
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn typed_links_round_trip() {
+        // test workspace boilerplate
+        let test_env = holochain_state::test_utils::test_cell_env();
+        let test_cache = holochain_state::test_utils::test_cache_env();
+        let env = test_env.env();
+
+        let author = fake_agent_pubkey_1();
+        crate::test_utils::fake_genesis(env.clone()).await.unwrap();
+
+        let workspace = HostFnWorkspace::new(env.clone(), test_cache.env(), author)
+            .await
+            .unwrap();
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace;
+
+        let _: HeaderHash = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::ValidateCreateLinkValid,
+            "add_rating",
+            4_u8
+        );
+        let ratings: Vec<u8> = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::ValidateCreateLinkValid,
+            "get_ratings",
+            ()
+        );
+        assert_eq!(ratings, vec![4]);
+    }
 }
@@ -0,0 +1,50 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::RibosomeT;
+use holochain_cascade::Cascade;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
+
+#[allow(clippy::extra_unused_lifetimes)]
+pub fn get_links_with_quorum<'a>(
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: GetLinksInput,
+) -> Result<QuorumOutput<Links>, WasmError> {
+    let GetLinksInput {
+        base_address,
+        tag_prefix,
+        quorum,
+        filter,
+    } = input;
+    let quorum = quorum.unwrap_or_default();
+
+    // Get zome id
+    let zome_id = ribosome
+        .zome_to_id(&call_context.zome)
+        .expect("Failed to get ID for current zome.");
+
+    // Get the network from the context
+    let network = call_context.host_access.network().clone();
+
+    tokio_helper::block_forever_on(async move {
+        // Create the key
+        let key = WireLinkKey {
+            base: base_address,
+            zome_id,
+            tag: tag_prefix,
+        };
+        let workspace = call_context.host_access.workspace();
+        let mut cascade = Cascade::from_workspace_network(workspace, network);
+
+        let QuorumOutput { output, report } = cascade
+            .dht_get_links_with_quorum(key, filter, &quorum)
+            .await
+            .map_err(|cascade_error| WasmError::Host(cascade_error.to_string()))?;
+
+        Ok(QuorumOutput {
+            output: output.into(),
+            report,
+        })
+    })
+}
@@ -0,0 +1,70 @@
+use super::get::crdt_type_of;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::RibosomeT;
+use holo_hash::hash_type::AnyDht;
+use holochain_cascade::Cascade;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::WasmError;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[allow(clippy::extra_unused_lifetimes)]
+pub fn get_many<'a>(
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: GetManyInput,
+) -> Result<Vec<Option<Element>>, WasmError> {
+    let GetManyInput {
+        any_dht_hashes,
+        get_options,
+    } = input;
+
+    // Get the network from the context
+    let network = call_context.host_access.network().clone();
+    let workspace = call_context.host_access.workspace();
+    let mut cascade = Cascade::from_workspace_network(workspace, network);
+    let is_entry: Vec<bool> = any_dht_hashes
+        .iter()
+        .map(|hash| matches!(*hash.hash_type(), AnyDht::Entry))
+        .collect();
+
+    // timeouts must be handled by the network
+    let maybe_elements =
+        tokio_helper::block_forever_on(cascade.dht_get_many(any_dht_hashes, get_options.clone()))
+            .map_err(|cascade_error| WasmError::Host(cascade_error.to_string()))?;
+
+    // Only a get by entry hash follows updates.
+    // The entry defs are only looked up once per entry type.
+    let mut crdt_types = HashMap::new();
+    let mut out = Vec::with_capacity(maybe_elements.len());
+    for (maybe_element, is_entry) in maybe_elements.into_iter().zip(is_entry) {
+        let element = match maybe_element {
+            Some(element) if is_entry && element.entry().as_option().is_some() => element,
+            maybe_element => {
+                out.push(maybe_element);
+                continue;
+            }
+        };
+        let key = element.header().entry_type().cloned();
+        let crdt_type = match crdt_types.get(&key) {
+            Some(crdt_type) => *crdt_type,
+            None => {
+                let crdt_type = crdt_type_of(ribosome.clone(), call_context.clone(), &element)?;
+                crdt_types.insert(key, crdt_type);
+                crdt_type
+            }
+        };
+        if !crdt_type.merges() {
+            out.push(Some(element));
+            continue;
+        }
+        let merged = tokio_helper::block_forever_on(cascade.merge_updates(
+            element,
+            crdt_type,
+            get_options.clone(),
+        ))
+        .map_err(|cascade_error| WasmError::Host(cascade_error.to_string()))?;
+        out.push(merged.and_then(MergedEntry::into_newest));
+    }
+    Ok(out)
+}
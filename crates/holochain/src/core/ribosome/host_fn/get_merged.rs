@@ -0,0 +1,105 @@
+use super::get::crdt_type_of;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::RibosomeT;
+use holo_hash::hash_type::AnyDht;
+use holochain_cascade::Cascade;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
+
+#[allow(clippy::extra_unused_lifetimes)]
+pub fn get_merged<'a>(
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: GetInput,
+) -> Result<Option<MergedEntry>, WasmError> {
+    let GetInput {
+        any_dht_hash,
+        get_options,
+    } = input;
+
+    // Get the network from the context
+    let network = call_context.host_access.network().clone();
+    let workspace = call_context.host_access.workspace();
+    let mut cascade = Cascade::from_workspace_network(workspace, network);
+    let is_entry = matches!(*any_dht_hash.hash_type(), AnyDht::Entry);
+
+    // timeouts must be handled by the network
+    let element =
+        match tokio_helper::block_forever_on(cascade.dht_get(any_dht_hash, get_options.clone()))
+            .map_err(|cascade_error| WasmError::Host(cascade_error.to_string()))?
+        {
+            Some(element) => element,
+            None => return Ok(None),
+        };
+
+    // Only a get by entry hash follows updates.
+    let crdt_type = if is_entry && element.entry().as_option().is_some() {
+        crdt_type_of(ribosome, call_context, &element)?
+    } else {
+        CrdtType::NoMerge
+    };
+    tokio_helper::block_forever_on(cascade.merge_updates(element, crdt_type, get_options))
+        .map_err(|cascade_error| WasmError::Host(cascade_error.to_string()))
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+    use holo_hash::EntryHash;
+    use holochain_state::host_fn_workspace::HostFnWorkspace;
+    use holochain_types::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+    use holochain_zome_types::fake_agent_pubkey_1;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_get_merged_update_heads() {
+        let test_env = holochain_state::test_utils::test_cell_env();
+        let test_cache = holochain_state::test_utils::test_cache_env();
+        let env = test_env.env();
+        let author = fake_agent_pubkey_1();
+        crate::test_utils::fake_genesis(env.clone()).await.unwrap();
+        let workspace = HostFnWorkspace::new(env.clone(), test_cache.env(), author)
+            .await
+            .unwrap();
+        let mut host_access = fixt!(ZomeCallHostAccess);
+        host_access.workspace = workspace;
+
+        let entry_hash: EntryHash =
+            crate::call_test_ribosome!(host_access, TestWasm::Update, "create_concurrent_tags", ());
+
+        let merged: Option<MergedEntry> = crate::call_test_ribosome!(
+            host_access,
+            TestWasm::Update,
+            "get_merged_tags",
+            entry_hash.clone()
+        );
+        let merged = merged.unwrap();
+
+        // - Both updates are heads and the merged set is their union.
+        assert_eq!(merged.heads.len(), 2);
+        let set = match &merged.entry {
+            Entry::App(bytes) => GrowOnlySet::try_from(bytes.as_ref().clone()).unwrap(),
+            _ => unreachable!(),
+        };
+        let mut items: Vec<String> = set.items().unwrap();
+        items.sort();
+        assert_eq!(items, vec!["a", "b", "c"]);
+
+        // - The heads are untouched so each entry still matches its header.
+        for head in &merged.heads {
+            let entry = head.entry().as_option().unwrap();
+            assert_eq!(
+                head.header().entry_hash(),
+                Some(&EntryHash::with_data_sync(entry))
+            );
+        }
+
+        // - A get returns the newest head as it is.
+        let element: Option<Element> =
+            crate::call_test_ribosome!(host_access, TestWasm::Update, "get_tags", entry_hash);
+        assert_eq!(element.as_ref(), merged.newest());
+    }
+}
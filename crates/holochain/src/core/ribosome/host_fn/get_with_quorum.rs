@@ -0,0 +1,34 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::RibosomeT;
+use holochain_cascade::Cascade;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::WasmError;
+use std::sync::Arc;
+
+#[allow(clippy::extra_unused_lifetimes)]
+pub fn get_with_quorum<'a>(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: GetInput,
+) -> Result<QuorumOutput<Option<Element>>, WasmError> {
+    let GetInput {
+        any_dht_hash,
+        get_options,
+    } = input;
+    let quorum = get_options.quorum.unwrap_or_default();
+
+    // Get the network from the context
+    let network = call_context.host_access.network().clone();
+
+    // timeouts must be handled by the network
+    tokio_helper::block_forever_on(async move {
+        let workspace = call_context.host_access.workspace();
+        let mut cascade = Cascade::from_workspace_network(workspace, network);
+        let output = cascade
+            .dht_get_with_quorum(any_dht_hash, &quorum)
+            .await
+            .map_err(|cascade_error| WasmError::Host(cascade_error.to_string()))?;
+
+        Ok(output)
+    })
+}
@@ -0,0 +1,234 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::RibosomeT;
+use crate::core::workflow::remote_signal_outbox_workflow::deliver_remote_signal;
+use holochain_state::remote_signal_outbox::OutboxSignal;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::WasmError;
+use holochain_zome_types::signal::RemoteSignalDelivery;
+use holochain_zome_types::signal::RemoteSignalReceipt;
+use holochain_zome_types::signal::RemoteSignalWithReceipts;
+use std::sync::Arc;
+
+#[tracing::instrument(skip(_ribosome, call_context, input))]
+pub fn remote_signal_with_receipts(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: RemoteSignalWithReceipts,
+) -> Result<Vec<RemoteSignalReceipt>, WasmError> {
+    let network = call_context.host_access().network().clone();
+    let scratch = call_context
+        .host_access()
+        .workspace()
+        .source_chain()
+        .scratch();
+    let RemoteSignalWithReceipts {
+        agents,
+        signal,
+        options,
+    } = input;
+    let zome_name: ZomeName = call_context.zome().into();
+    tokio_helper::block_forever_on(async move {
+        // Every agent is signalled concurrently so one slow agent
+        // doesn't eat into everyone else's timeout.
+        let deliveries = futures::future::join_all(agents.into_iter().map(|agent| {
+            let mut network = network.clone();
+            let zome_name = zome_name.clone();
+            let signal = signal.clone();
+            async move {
+                let mut attempts = 0;
+                let delivery = loop {
+                    attempts += 1;
+                    let delivery = deliver_remote_signal(
+                        &mut network,
+                        agent.clone(),
+                        zome_name.clone(),
+                        signal.clone(),
+                        options.timeout,
+                    )
+                    .await;
+                    if let RemoteSignalDelivery::Delivered = delivery {
+                        break delivery;
+                    }
+                    if attempts > options.retries as u32 {
+                        break delivery;
+                    }
+                };
+                (agent, delivery, attempts)
+            }
+        }))
+        .await;
+
+        let mut receipts = Vec::with_capacity(deliveries.len());
+        for (agent, delivery, attempts) in deliveries {
+            let outbox_id = match (delivery, options.outbox_ttl) {
+                (RemoteSignalDelivery::Delivered, _) | (_, None) => None,
+                (_, Some(ttl)) => {
+                    let expires_at =
+                        (timestamp::now() + ttl).map_err(|e| WasmError::Host(e.to_string()))?;
+                    // The signal is written to the outbox when the source chain is flushed,
+                    // so it needs its id before it reaches the database.
+                    let id = rand::random::<u64>();
+                    scratch
+                        .apply(|scratch| {
+                            scratch.add_remote_signal(OutboxSignal {
+                                id,
+                                agent: agent.clone(),
+                                zome_name: zome_name.clone(),
+                                signal: signal.clone(),
+                                timeout: options.timeout,
+                                expires_at,
+                                attempts,
+                            })
+                        })
+                        .map_err(|e| WasmError::Host(e.to_string()))?;
+                    Some(id)
+                }
+            };
+            receipts.push(RemoteSignalReceipt {
+                agent,
+                delivery,
+                outbox_id,
+            });
+        }
+        Ok(receipts)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweettest::SweetDnaFile;
+    use crate::sweettest::{SweetAgents, SweetConductor, SweetConductorBatch};
+    use ::fixt::prelude::*;
+    use futures::future;
+    use hdk::prelude::*;
+    use holo_hash::fixt::AgentPubKeyFixturator;
+    use holochain_sqlite::db::ReadManager;
+    use holochain_state::remote_signal_outbox;
+    use holochain_types::timestamp;
+
+    fn zome(agents: Vec<AgentPubKey>) -> InlineZome {
+        let entry_def = EntryDef::default_with_id("entrydef");
+
+        InlineZome::new_unique(vec![entry_def.clone()])
+            .callback("signal_others", move |api, ()| {
+                let signal = ExternIO::encode("Hey").unwrap();
+                let signal = RemoteSignalWithReceipts {
+                    agents: agents.clone(),
+                    signal,
+                    options: RemoteSignalOptions {
+                        retries: 2,
+                        ..Default::default()
+                    },
+                };
+                Ok(api.remote_signal_with_receipts(signal)?)
+            })
+            .callback("recv_remote_signal", move |api, signal: ExternIO| {
+                api.emit_signal(AppSignal::new(signal)).map_err(Into::into)
+            })
+            .callback("init", move |api, ()| {
+                let mut functions: GrantedFunctions = BTreeSet::new();
+                functions.insert((
+                    api.zome_info(()).unwrap().zome_name,
+                    "recv_remote_signal".into(),
+                ));
+                let cap_grant_entry = CapGrantEntry {
+                    tag: "".into(),
+                    // empty access converts to unrestricted
+                    access: ().into(),
+                    functions,
+                };
+                api.create(EntryWithDefId::new(
+                    EntryDefId::CapGrant,
+                    Entry::CapGrant(cap_grant_entry),
+                ))
+                .unwrap();
+
+                Ok(InitCallbackResult::Pass)
+            })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(feature = "test_utils")]
+    async fn remote_signal_with_receipts_test() -> anyhow::Result<()> {
+        observability::test_run().ok();
+        const NUM_CONDUCTORS: usize = 3;
+
+        let mut conductors = SweetConductorBatch::from_standard_config(NUM_CONDUCTORS).await;
+        let agents =
+            future::join_all(conductors.iter().map(|c| SweetAgents::one(c.keystore()))).await;
+
+        let (dna_file, _) = SweetDnaFile::unique_from_inline_zome("zome1", zome(agents.clone()))
+            .await
+            .unwrap();
+
+        let apps = conductors
+            .setup_app_for_zipped_agents("app", &agents, &[dna_file.clone().into()])
+            .await
+            .unwrap();
+
+        conductors.exchange_peer_info().await;
+
+        let cells: Vec<_> = apps.cells_flattened();
+
+        let receipts: Vec<RemoteSignalReceipt> = conductors[0]
+            .call(&cells[0].zome("zome1"), "signal_others", ())
+            .await;
+
+        assert_eq!(receipts.len(), NUM_CONDUCTORS);
+        for (receipt, agent) in receipts.iter().zip(agents.iter()) {
+            assert_eq!(&receipt.agent, agent);
+            assert_eq!(receipt.delivery, RemoteSignalDelivery::Delivered);
+            assert_eq!(receipt.outbox_id, None);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(feature = "test_utils")]
+    async fn undelivered_remote_signal_goes_to_outbox() -> anyhow::Result<()> {
+        observability::test_run().ok();
+        let missing_agent = fixt!(AgentPubKey);
+        let zome = InlineZome::new_unique(vec![]).callback("signal_missing", move |api, ()| {
+            let signal = RemoteSignalWithReceipts {
+                agents: vec![missing_agent.clone()],
+                signal: ExternIO::encode("Hey").unwrap(),
+                options: RemoteSignalOptions {
+                    timeout: Some(std::time::Duration::from_millis(500)),
+                    retries: 0,
+                    outbox_ttl: Some(std::time::Duration::from_secs(60)),
+                },
+            };
+            Ok(api.remote_signal_with_receipts(signal)?)
+        });
+
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let (dna_file, _) = SweetDnaFile::unique_from_inline_zome("zome1", zome).await?;
+        let app = conductor.setup_app("app", &[dna_file]).await?;
+        let (cell,) = app.into_tuple();
+
+        let receipts: Vec<RemoteSignalReceipt> = conductor
+            .call(&cell.zome("zome1"), "signal_missing", ())
+            .await;
+
+        assert_eq!(receipts.len(), 1);
+        assert_ne!(receipts[0].delivery, RemoteSignalDelivery::Delivered);
+        let outbox_id = receipts[0]
+            .outbox_id
+            .expect("signal should be in the outbox");
+
+        // The signal was written with the rest of the zome call
+        // and waits for its backoff before being retried.
+        let later = (timestamp::now() + remote_signal_outbox::MAX_RETRY_DELAY)?;
+        let pending = cell
+            .env()
+            .conn()?
+            .with_reader_test(|reader| remote_signal_outbox::list_pending(&reader, later).unwrap());
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, outbox_id);
+        assert_eq!(pending[0].agent, receipts[0].agent);
+        assert_eq!(pending[0].attempts, 1);
+
+        Ok(())
+    }
+}
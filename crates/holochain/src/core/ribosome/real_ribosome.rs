@@ -37,6 +37,7 @@ use crate::core::ribosome::host_fn::call_remote::call_remote;
 use crate::core::ribosome::host_fn::capability_claims::capability_claims;
 use crate::core::ribosome::host_fn::capability_grants::capability_grants;
 use crate::core::ribosome::host_fn::capability_info::capability_info;
+use crate::core::ribosome::host_fn::count_links::count_links;
 use crate::core::ribosome::host_fn::create::create;
 use crate::core::ribosome::host_fn::create_link::create_link;
 use crate::core::ribosome::host_fn::create_x25519_keypair::create_x25519_keypair;
@@ -46,12 +47,18 @@ use crate::core::ribosome::host_fn::dna_info::dna_info;
 use crate::core::ribosome::host_fn::emit_signal::emit_signal;
 use crate::core::ribosome::host_fn::get::get;
 use crate::core::ribosome::host_fn::get_details::get_details;
+use crate::core::ribosome::host_fn::get_details_many::get_details_many;
 use crate::core::ribosome::host_fn::get_link_details::get_link_details;
 use crate::core::ribosome::host_fn::get_links::get_links;
+use crate::core::ribosome::host_fn::get_links_with_quorum::get_links_with_quorum;
+use crate::core::ribosome::host_fn::get_many::get_many;
+use crate::core::ribosome::host_fn::get_merged::get_merged;
+use crate::core::ribosome::host_fn::get_with_quorum::get_with_quorum;
 use crate::core::ribosome::host_fn::hash_entry::hash_entry;
 use crate::core::ribosome::host_fn::query::query;
 use crate::core::ribosome::host_fn::random_bytes::random_bytes;
 use crate::core::ribosome::host_fn::remote_signal::remote_signal;
+use crate::core::ribosome::host_fn::remote_signal_with_receipts::remote_signal_with_receipts;
 use crate::core::ribosome::host_fn::schedule::schedule;
 use crate::core::ribosome::host_fn::sign::sign;
 use crate::core::ribosome::host_fn::sign_ephemeral::sign_ephemeral;
@@ -333,16 +340,28 @@ impl RealRibosome {
         {
             host_fn_builder
                 .with_host_function(&mut ns, "__get", get)
+                .with_host_function(&mut ns, "__get_many", get_many)
+                .with_host_function(&mut ns, "__get_merged", get_merged)
+                .with_host_function(&mut ns, "__get_with_quorum", get_with_quorum)
                 .with_host_function(&mut ns, "__get_details", get_details)
+                .with_host_function(&mut ns, "__get_details_many", get_details_many)
                 .with_host_function(&mut ns, "__get_links", get_links)
+                .with_host_function(&mut ns, "__count_links", count_links)
+                .with_host_function(&mut ns, "__get_links_with_quorum", get_links_with_quorum)
                 .with_host_function(&mut ns, "__get_link_details", get_link_details)
                 .with_host_function(&mut ns, "__get_agent_activity", get_agent_activity)
                 .with_host_function(&mut ns, "__query", query);
         } else {
             host_fn_builder
                 .with_host_function(&mut ns, "__get", unreachable)
+                .with_host_function(&mut ns, "__get_many", unreachable)
+                .with_host_function(&mut ns, "__get_merged", unreachable)
+                .with_host_function(&mut ns, "__get_with_quorum", unreachable)
                 .with_host_function(&mut ns, "__get_details", unreachable)
+                .with_host_function(&mut ns, "__get_details_many", unreachable)
                 .with_host_function(&mut ns, "__get_links", unreachable)
+                .with_host_function(&mut ns, "__count_links", unreachable)
+                .with_host_function(&mut ns, "__get_links_with_quorum", unreachable)
                 .with_host_function(&mut ns, "__get_link_details", unreachable)
                 .with_host_function(&mut ns, "__get_agent_activity", unreachable)
                 .with_host_function(&mut ns, "__query", unreachable);
@@ -355,11 +374,17 @@ impl RealRibosome {
         {
             host_fn_builder
                 .with_host_function(&mut ns, "__call_remote", call_remote)
-                .with_host_function(&mut ns, "__remote_signal", remote_signal);
+                .with_host_function(&mut ns, "__remote_signal", remote_signal)
+                .with_host_function(
+                    &mut ns,
+                    "__remote_signal_with_receipts",
+                    remote_signal_with_receipts,
+                );
         } else {
             host_fn_builder
                 .with_host_function(&mut ns, "__call_remote", unreachable)
-                .with_host_function(&mut ns, "__remote_signal", unreachable);
+                .with_host_function(&mut ns, "__remote_signal", unreachable)
+                .with_host_function(&mut ns, "__remote_signal_with_receipts", unreachable);
         }
 
         if let HostFnAccess {
@@ -30,6 +30,7 @@ pub mod incoming_dht_ops_workflow;
 pub mod initialize_zomes_workflow;
 pub mod integrate_dht_ops_workflow;
 pub mod publish_dht_ops_workflow;
+pub mod remote_signal_outbox_workflow;
 pub mod sys_validation_workflow;
 pub mod validation_receipt_workflow;
 
@@ -11,6 +11,7 @@ use super::error::WorkflowResult;
 use super::sys_validation_workflow::validation_query;
 use crate::conductor::api::CellConductorApiT;
 use crate::conductor::entry_def_store::get_entry_def;
+use crate::conductor::interface::SystemSignalSender;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
 use crate::core::ribosome::guest_callback::validate::ValidateHostAccess;
@@ -50,14 +51,17 @@ mod error;
 mod types;
 pub mod validation_package;
 
-#[instrument(skip(workspace, trigger_integration, conductor_api, network))]
+#[instrument(skip(workspace, trigger_integration, conductor_api, network, system_signals))]
 pub async fn app_validation_workflow(
     mut workspace: AppValidationWorkspace,
     mut trigger_integration: TriggerSender,
     conductor_api: impl CellConductorApiT,
     network: HolochainP2pCell,
+    system_signals: SystemSignalSender,
 ) -> WorkflowResult<WorkComplete> {
-    let complete = app_validation_workflow_inner(&mut workspace, conductor_api, &network).await?;
+    let complete =
+        app_validation_workflow_inner(&mut workspace, conductor_api, &network, &system_signals)
+            .await?;
     // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
 
     // trigger other workflows
@@ -70,6 +74,7 @@ async fn app_validation_workflow_inner(
     workspace: &mut AppValidationWorkspace,
     conductor_api: impl CellConductorApiT,
     network: &HolochainP2pCell,
+    system_signals: &SystemSignalSender,
 ) -> WorkflowResult<WorkComplete> {
     let env = workspace.vault.clone().into();
     let sorted_ops = validation_query::get_ops_to_app_validate(&env).await?;
@@ -92,25 +97,38 @@ async fn app_validation_workflow_inner(
             );
         }
 
+        let is_authored = *op.header().author() == network.from_agent();
+        let emit_validated = |op_hash: DhtOpHash, status: ValidationStatus| {
+            if is_authored {
+                system_signals.send(SystemSignal::OpValidated {
+                    cell_id: network.cell_id(),
+                    op_hash,
+                    status,
+                });
+            }
+        };
+
         match outcome {
             Outcome::Accepted => {
                 workspace
-                    .put_integration_limbo(op_hash, ValidationStatus::Valid)
+                    .put_integration_limbo(op_hash.clone(), ValidationStatus::Valid)
                     .await?;
+                emit_validated(op_hash, ValidationStatus::Valid);
             }
             Outcome::AwaitingDeps(deps) => {
                 let status = ValidationLimboStatus::AwaitingAppDeps(deps);
                 workspace.put_validation_limbo(op_hash, status).await?;
             }
             Outcome::Rejected(_) => {
-                if *op.header().author() == network.from_agent() {
+                if is_authored {
                     tracing::warn!("Authored invalid op! If you didn't hack your node, this is a bug in Holochain.\nOp: {:?}", op.to_light());
                 } else {
                     tracing::warn!("Received invalid op! Warrants aren't implemented yet, so we can't do anything about this right now, but be warned that somebody on the network has maliciously hacked their node.\nOp: {:?}", op.to_light());
                 }
                 workspace
-                    .put_integration_limbo(op_hash, ValidationStatus::Rejected)
+                    .put_integration_limbo(op_hash.clone(), ValidationStatus::Rejected)
                     .await?;
+                emit_validated(op_hash, ValidationStatus::Rejected);
             }
         }
     }
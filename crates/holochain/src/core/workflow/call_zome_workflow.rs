@@ -17,8 +17,11 @@ use holochain_keystore::KeystoreSender;
 use holochain_p2p::HolochainP2pCell;
 use holochain_state::host_fn_workspace::HostFnWorkspace;
 use holochain_state::source_chain::SourceChainError;
+use holochain_state::zome_call_nonce;
 use holochain_zome_types::element::Element;
 
+use crate::conductor::api::SignedZomeCall;
+use holochain_keystore::AgentPubKeyExt;
 use holochain_types::prelude::*;
 use std::sync::Arc;
 use tracing::instrument;
@@ -29,6 +32,11 @@ mod validation_test;
 /// Placeholder for the return value of a zome invocation
 pub type ZomeCallResult = RibosomeResult<ZomeCallResponse>;
 
+/// How far into the future a signed zome call may set its expiry.
+/// Nonces are only remembered until their call expires,
+/// so this bounds how many need to be kept.
+pub const SIGNED_ZOME_CALL_MAX_TTL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
 #[derive(Debug)]
 pub struct CallZomeWorkflowArgs<Ribosome, C>
 where
@@ -116,6 +124,48 @@ where
     Ok(result)
 }
 
+/// Check the signature of a zome call was made by its provenance over this
+/// exact call, that the call has not expired and that its nonce is fresh.
+/// The nonce is recorded in the database of the cell being called, `env`.
+pub async fn verify_signed_zome_call(
+    env: &EnvWrite,
+    signed: &SignedZomeCall,
+) -> WorkflowResult<bool> {
+    let SignedZomeCall {
+        call,
+        nonce,
+        expires_at,
+        signature,
+    } = signed;
+    let expires_at = *expires_at;
+
+    let now = holochain_types::timestamp::now();
+    if expires_at <= now {
+        return Ok(false);
+    }
+    match now + SIGNED_ZOME_CALL_MAX_TTL {
+        Ok(max_expires_at) if expires_at <= max_expires_at => (),
+        _ => return Ok(false),
+    }
+
+    let data = SignedZomeCall::data_to_sign(call, nonce, &expires_at)?;
+    if !call
+        .provenance
+        .verify_signature_raw(signature, &data)
+        .await?
+    {
+        return Ok(false);
+    }
+
+    // Only record the nonce once the signature is known to be good,
+    // so forged calls cannot use up the nonces of real ones.
+    let nonce = nonce.0;
+    let fresh = env
+        .async_commit(move |txn| zome_call_nonce::check_and_insert(txn, &nonce, expires_at, now))
+        .await?;
+    Ok(fresh)
+}
+
 /// Run validation inline and wait for the result.
 pub async fn inline_validation<C, Ribosome>(
     workspace: HostFnWorkspace,
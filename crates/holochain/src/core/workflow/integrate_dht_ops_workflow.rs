@@ -1,6 +1,7 @@
 //! The workflow and queue consumer for DhtOp integration
 
 use super::*;
+use crate::conductor::interface::SystemSignalSender;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
 use error::WorkflowResult;
@@ -14,14 +15,17 @@ mod query_tests;
 #[cfg(feature = "test_utils")]
 mod tests;
 
-#[instrument(skip(vault, trigger_sys, trigger_receipt))]
+#[instrument(skip(vault, trigger_sys, trigger_receipt, system_signals))]
 pub async fn integrate_dht_ops_workflow(
     vault: EnvWrite,
     mut trigger_sys: TriggerSender,
     mut trigger_receipt: TriggerSender,
+    cell_id: CellId,
+    system_signals: SystemSignalSender,
 ) -> WorkflowResult<WorkComplete> {
     let time = holochain_types::timestamp::now();
-    let changed = vault
+    let emit_signals = system_signals.allows(SystemSignalKind::Consistency);
+    let (changed, integrated_authored) = vault
         .async_commit(move |txn| {
             let changed = txn
                 .prepare_cached(holochain_sqlite::sql::sql_cell::UPDATE_INTEGRATE_OPS)?
@@ -39,10 +43,32 @@ pub async fn integrate_dht_ops_workflow(
                     ":delete_link": DhtOpType::RegisterRemoveLink,
 
                 })?;
-            WorkflowResult::Ok(changed)
+            // Find our own authored ops which were integrated in this batch
+            let integrated_authored = if changed > 0 && emit_signals {
+                txn.prepare_cached(
+                    "SELECT hash FROM DhtOp
+                    WHERE is_authored = 1 AND when_integrated_ns = :when_integrated_ns",
+                )?
+                .query_map(
+                    named_params! {
+                        ":when_integrated_ns": to_blob(time)?,
+                    },
+                    |row| row.get::<_, DhtOpHash>("hash"),
+                )?
+                .collect::<Result<Vec<_>, _>>()?
+            } else {
+                Vec::new()
+            };
+            WorkflowResult::Ok((changed, integrated_authored))
         })
         .await?;
     tracing::debug!(?changed);
+    for op_hash in integrated_authored {
+        system_signals.send(SystemSignal::OpIntegrated {
+            cell_id: cell_id.clone(),
+            op_hash,
+        });
+    }
     if changed > 0 {
         trigger_sys.trigger();
         trigger_receipt.trigger();
@@ -41,9 +41,15 @@ async fn integrate_query() {
     let (qt, _rx) = TriggerSender::new();
     let (qt2, _rx) = TriggerSender::new();
     // dump_tmp(&env.env());
-    integrate_dht_ops_workflow(env.env().into(), qt, qt2)
-        .await
-        .unwrap();
+    integrate_dht_ops_workflow(
+        env.env().into(),
+        qt,
+        qt2,
+        holochain_types::test_utils::fake_cell_id(1),
+        SystemSignalSender::default(),
+    )
+    .await
+    .unwrap();
     let hashes = env
         .conn()
         .unwrap()
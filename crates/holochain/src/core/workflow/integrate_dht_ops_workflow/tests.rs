@@ -467,9 +467,15 @@ impl Db {
 async fn call_workflow<'env>(env: EnvWrite) {
     let (qt, _rx) = TriggerSender::new();
     let (qt2, _rx) = TriggerSender::new();
-    integrate_dht_ops_workflow(env.clone(), qt, qt2)
-        .await
-        .unwrap();
+    integrate_dht_ops_workflow(
+        env.clone(),
+        qt,
+        qt2,
+        holochain_types::test_utils::fake_cell_id(1),
+        SystemSignalSender::default(),
+    )
+    .await
+    .unwrap();
 }
 
 // Need to clear the data from the previous test
@@ -11,6 +11,7 @@
 //!
 
 use super::error::WorkflowResult;
+use crate::conductor::interface::SystemSignalSender;
 use crate::core::queue_consumer::WorkComplete;
 use holo_hash::*;
 use holochain_p2p::HolochainP2pCell;
@@ -34,10 +35,11 @@ pub const DEFAULT_RECEIPT_BUNDLE_SIZE: u32 = 5;
 /// flooding the network with spurious publishes.
 pub const MIN_PUBLISH_INTERVAL: time::Duration = time::Duration::from_secs(5);
 
-#[instrument(skip(env, network))]
+#[instrument(skip(env, network, system_signals))]
 pub async fn publish_dht_ops_workflow(
     env: EnvWrite,
     mut network: HolochainP2pCell,
+    system_signals: SystemSignalSender,
 ) -> WorkflowResult<WorkComplete> {
     let (to_publish, hashes) =
         publish_dht_ops_workflow_inner(env.clone().into(), network.from_agent()).await?;
@@ -45,8 +47,17 @@ pub async fn publish_dht_ops_workflow(
     // Commit to the network
     tracing::info!("sending {} ops", to_publish.len());
     for (basis, ops) in to_publish {
-        if let Err(e) = network.publish(true, basis, ops, None).await {
-            tracing::info!(failed_to_send_publish = ?e);
+        let op_hashes: Vec<_> = ops.iter().map(|(op_hash, _)| op_hash.clone()).collect();
+        match network.publish(true, basis, ops, None).await {
+            Ok(()) => {
+                for op_hash in op_hashes {
+                    system_signals.send(SystemSignal::OpPublished {
+                        cell_id: network.cell_id(),
+                        op_hash,
+                    });
+                }
+            }
+            Err(e) => tracing::info!(failed_to_send_publish = ?e),
         }
     }
     tracing::info!("sent {} ops", hashes.len());
@@ -211,9 +222,13 @@ mod tests {
 
     /// Call the workflow
     async fn call_workflow(env: EnvWrite, cell_network: HolochainP2pCell) {
-        publish_dht_ops_workflow(env.clone().into(), cell_network)
-            .await
-            .unwrap();
+        publish_dht_ops_workflow(
+            env.clone().into(),
+            cell_network,
+            SystemSignalSender::default(),
+        )
+        .await
+        .unwrap();
     }
 
     /// There is a test that shows that network messages would be sent to all agents via broadcast.
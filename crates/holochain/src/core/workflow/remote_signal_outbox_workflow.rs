@@ -0,0 +1,259 @@
+//! Retry delivery of remote signals which are waiting in the outbox.
+
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use holochain_p2p::HolochainP2pCellT;
+use holochain_p2p::HolochainP2pError;
+use holochain_state::remote_signal_outbox;
+use holochain_types::prelude::*;
+use holochain_types::signal::SystemSignal;
+use holochain_zome_types::signal::RemoteSignalDelivery;
+use kitsune_p2p::KitsuneP2pError;
+use kitsune_p2p_types::KitsuneErrorKind;
+use tracing::*;
+
+use crate::conductor::interface::SystemSignalSender;
+use crate::core::queue_consumer::WorkComplete;
+
+use super::error::WorkflowResult;
+
+/// The zome function a remote signal is delivered to.
+pub const RECV_REMOTE_SIGNAL: &str = "recv_remote_signal";
+
+/// How long to wait for an agent to acknowledge a remote signal
+/// if the sender didn't ask for a specific timeout.
+pub const DEFAULT_REMOTE_SIGNAL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Make a single attempt to deliver a remote signal to an agent,
+/// waiting at most `timeout` for them to acknowledge it.
+pub async fn deliver_remote_signal<N: HolochainP2pCellT>(
+    network: &mut N,
+    agent: AgentPubKey,
+    zome_name: ZomeName,
+    signal: ExternIO,
+    timeout: Option<Duration>,
+) -> RemoteSignalDelivery {
+    let timeout = timeout.unwrap_or(DEFAULT_REMOTE_SIGNAL_TIMEOUT);
+    let result = tokio::time::timeout(
+        timeout,
+        network.call_remote(
+            agent.clone(),
+            zome_name,
+            RECV_REMOTE_SIGNAL.into(),
+            None,
+            signal,
+        ),
+    )
+    .await;
+    match result {
+        Ok(Ok(response)) => match ZomeCallResponse::try_from(response) {
+            Ok(ZomeCallResponse::Ok(_)) => RemoteSignalDelivery::Delivered,
+            other => {
+                debug!(?agent, ?other, "remote signal was not accepted");
+                RemoteSignalDelivery::Unreachable
+            }
+        },
+        Ok(Err(HolochainP2pError::OtherKitsuneP2pError(KitsuneP2pError::KitsuneError(e))))
+            if matches!(e.kind(), KitsuneErrorKind::TimedOut) =>
+        {
+            RemoteSignalDelivery::Timeout
+        }
+        Ok(Err(e)) => {
+            debug!(?agent, ?e, "failed to send remote signal");
+            RemoteSignalDelivery::Unreachable
+        }
+        Err(_) => RemoteSignalDelivery::Timeout,
+    }
+}
+
+#[instrument(skip(vault, network, system_signals))]
+/// Drop expired signals from the outbox and make another delivery
+/// attempt for each of the rest which are due to be retried.
+pub async fn remote_signal_outbox_workflow<N: HolochainP2pCellT>(
+    vault: EnvWrite,
+    network: &mut N,
+    system_signals: SystemSignalSender,
+) -> WorkflowResult<WorkComplete> {
+    let now = timestamp::now();
+    vault
+        .async_commit(move |txn| remote_signal_outbox::delete_expired(txn, now))
+        .await?;
+    let pending = vault
+        .async_reader(move |txn| remote_signal_outbox::list_pending(&txn, now))
+        .await?;
+
+    for outbox_signal in pending {
+        let id = outbox_signal.id;
+        let delivery = deliver_remote_signal(
+            network,
+            outbox_signal.agent.clone(),
+            outbox_signal.zome_name,
+            outbox_signal.signal,
+            outbox_signal.timeout,
+        )
+        .await;
+        if let RemoteSignalDelivery::Delivered = delivery {
+            vault
+                .async_commit(move |txn| remote_signal_outbox::remove(txn, id))
+                .await?;
+            system_signals.send(SystemSignal::RemoteSignalDelivered {
+                cell_id: network.cell_id(),
+                agent: outbox_signal.agent,
+                outbox_id: id,
+            });
+        } else {
+            let attempts = outbox_signal.attempts + 1;
+            vault
+                .async_commit(move |txn| {
+                    remote_signal_outbox::record_attempt(txn, id, attempts, timestamp::now())
+                })
+                .await?;
+        }
+    }
+
+    Ok(WorkComplete::Complete)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conductor::config::SignalConfig;
+    use fixt::prelude::*;
+    use holochain_p2p::actor::HolochainP2pResult;
+    use holochain_p2p::MockHolochainP2pCellT;
+    use holochain_sqlite::db::ReadManager;
+    use holochain_sqlite::db::WriteManager;
+    use holochain_state::remote_signal_outbox::retry_delay;
+    use holochain_state::remote_signal_outbox::OutboxSignal;
+    use holochain_types::signal::Signal;
+    use holochain_types::test_utils::fake_cell_id;
+    use holochain_zome_types::fixt::*;
+    use std::convert::TryInto;
+
+    fn delivered() -> HolochainP2pResult<SerializedBytes> {
+        Ok(ZomeCallResponse::Ok(ExternIO::encode(()).unwrap())
+            .try_into()
+            .unwrap())
+    }
+
+    fn timed_out() -> HolochainP2pResult<SerializedBytes> {
+        Err(HolochainP2pError::OtherKitsuneP2pError(
+            KitsuneP2pError::KitsuneError(KitsuneErrorKind::TimedOut.into()),
+        ))
+    }
+
+    fn unreachable() -> HolochainP2pResult<SerializedBytes> {
+        Err(HolochainP2pError::RoutingAgentError(fixt!(AgentPubKey)))
+    }
+
+    fn network(response: fn() -> HolochainP2pResult<SerializedBytes>) -> MockHolochainP2pCellT {
+        let mut network = MockHolochainP2pCellT::new();
+        let cell_id = fake_cell_id(1);
+        network
+            .expect_call_remote()
+            .returning(move |_, _, _, _, _| response());
+        network.expect_cell_id().returning(move || cell_id.clone());
+        network
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn delivery_outcomes() {
+        let attempt = |mut network: MockHolochainP2pCellT| async move {
+            deliver_remote_signal(
+                &mut network,
+                fixt!(AgentPubKey),
+                "zome".into(),
+                ExternIO::encode("hi").unwrap(),
+                None,
+            )
+            .await
+        };
+        assert_eq!(
+            attempt(network(delivered)).await,
+            RemoteSignalDelivery::Delivered
+        );
+        assert_eq!(
+            attempt(network(timed_out)).await,
+            RemoteSignalDelivery::Timeout
+        );
+        assert_eq!(
+            attempt(network(unreachable)).await,
+            RemoteSignalDelivery::Unreachable
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn outbox_retries_and_redelivers() {
+        observability::test_run().ok();
+        let test_env = holochain_state::test_utils::test_cell_env();
+        let env = test_env.env();
+        let system_signals = SystemSignalSender::new(SignalConfig {
+            network: true,
+            ..Default::default()
+        });
+        let mut signals = system_signals.subscribe();
+
+        let agent = fixt!(AgentPubKey);
+        // The time at which a signal with this many attempts is due now.
+        let due_at = |attempts| (timestamp::now() - retry_delay(attempts)).unwrap();
+        let outbox_signal = OutboxSignal {
+            id: 7,
+            agent: agent.clone(),
+            zome_name: "zome".into(),
+            signal: ExternIO::encode("hi").unwrap(),
+            timeout: None,
+            expires_at: (timestamp::now() + Duration::from_secs(60)).unwrap(),
+            attempts: 1,
+        };
+        env.conn()
+            .unwrap()
+            .with_commit_sync(|txn| remote_signal_outbox::insert(txn, &outbox_signal, due_at(1)))
+            .unwrap();
+        let pending = |env: &EnvWrite, at: Timestamp| {
+            env.conn()
+                .unwrap()
+                .with_reader_test(|reader| remote_signal_outbox::list_pending(&reader, at).unwrap())
+        };
+
+        // - A failed retry is recorded and backs off
+        remote_signal_outbox_workflow(
+            env.clone(),
+            &mut network(unreachable),
+            system_signals.clone(),
+        )
+        .await
+        .unwrap();
+        assert!(pending(&env, timestamp::now()).is_empty());
+        let later = (timestamp::now() + retry_delay(2)).unwrap();
+        assert_eq!(pending(&env, later)[0].attempts, 2);
+        assert!(signals.try_recv().is_err());
+
+        // - Once it is due again a successful retry empties the outbox
+        //   and tells the sender it was delivered
+        env.conn()
+            .unwrap()
+            .with_commit_sync(|txn| {
+                remote_signal_outbox::record_attempt(txn, outbox_signal.id, 2, due_at(2))
+            })
+            .unwrap();
+        let mut network = network(delivered);
+        let cell_id = network.cell_id();
+        remote_signal_outbox_workflow(env.clone(), &mut network, system_signals.clone())
+            .await
+            .unwrap();
+        assert!(pending(&env, later).is_empty());
+        match signals.try_recv().unwrap() {
+            Signal::System(SystemSignal::RemoteSignalDelivered {
+                cell_id: id,
+                agent: to,
+                outbox_id,
+            }) => {
+                assert_eq!(id, cell_id);
+                assert_eq!(to, agent);
+                assert_eq!(outbox_id, outbox_signal.id);
+            }
+            other => panic!("unexpected signal {:?}", other),
+        }
+    }
+}
@@ -287,7 +287,10 @@ fixturator!(
 );
 
 fn make_call_zome_handle(cell_id: CellId) -> CellConductorReadHandle {
-    let handle = Arc::new(MockConductorHandleT::new());
+    let mut handle = MockConductorHandleT::new();
+    // No entry defs are stored so they come from the entry defs callback.
+    handle.expect_get_entry_def().returning(|_| None);
+    let handle = Arc::new(handle);
     let cell_conductor_api = CellConductorApi::new(handle, cell_id);
     Arc::new(cell_conductor_api)
 }
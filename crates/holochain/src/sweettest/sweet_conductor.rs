@@ -43,7 +43,7 @@ pub fn standard_config() -> ConductorConfig {
         override_port: None,
     }];
     let admin_interface = AdminInterfaceConfig {
-        driver: InterfaceDriver::Websocket { port: 0 },
+        driver: InterfaceDriver::websocket(0),
     };
     ConductorConfig {
         network: Some(network),
@@ -193,7 +193,14 @@ async fn speed_test(n: Option<usize>) -> TestEnvs {
 
     // Setup websocket handle and app interface
     let (mut client, _) = websocket_client(&handle).await.unwrap();
-    let request = AdminRequest::AttachAppInterface { port: None };
+    let request = AdminRequest::AttachAppInterface {
+        port: None,
+        require_signed_zome_calls: false,
+        bind_address: None,
+        tls: None,
+        allowed_origins: None,
+        zome_call_limits: Default::default(),
+    };
     let response = client.request(request);
     let response = response.await.unwrap();
     let app_port = match response {
@@ -316,7 +323,7 @@ pub async fn setup_app(
     let conductor_handle = ConductorBuilder::with_mock_dna_store(dna_store)
         .config(ConductorConfig {
             admin_interfaces: Some(vec![AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket { port: 0 },
+                driver: InterfaceDriver::websocket(0),
             }]),
             ..Default::default()
         })
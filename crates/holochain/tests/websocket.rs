@@ -73,7 +73,7 @@ pub async fn check_started(holochain: &mut Child) {
 fn create_config(port: u16, environment_path: PathBuf) -> ConductorConfig {
     ConductorConfig {
         admin_interfaces: Some(vec![AdminInterfaceConfig {
-            driver: InterfaceDriver::Websocket { port },
+            driver: InterfaceDriver::websocket(port),
         }]),
         environment_path: environment_path.into(),
         network: None,
@@ -83,6 +83,8 @@ fn create_config(port: u16, environment_path: PathBuf) -> ConductorConfig {
         }),
         keystore_path: None,
         use_dangerous_test_keystore: true,
+        signals: Default::default(),
+        cache_policy: None,
     }
 }
 
@@ -256,7 +258,14 @@ pub async fn attach_app_interface(
     holochain: &mut Child,
     port: Option<u16>,
 ) -> u16 {
-    let request = AdminRequest::AttachAppInterface { port };
+    let request = AdminRequest::AttachAppInterface {
+        port,
+        require_signed_zome_calls: false,
+        bind_address: None,
+        tls: None,
+        allowed_origins: None,
+        zome_call_limits: Default::default(),
+    };
     let response = client.request(request);
     let response = check_timeout(holochain, response, 3000).await;
     match response {
@@ -600,6 +609,32 @@ async fn conductor_admin_interface_runs_from_config() -> Result<()> {
     Ok(())
 }
 
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread")]
+async fn conductor_admin_interface_runs_on_unix_socket() -> Result<()> {
+    observability::test_run().ok();
+    let tmp_dir = TempDir::new("conductor_cfg").unwrap();
+    let environment_path = tmp_dir.path().to_path_buf();
+    let socket_path = tmp_dir.path().join("admin.sock");
+    let mut config = create_config(0, environment_path);
+    config.admin_interfaces = Some(vec![AdminInterfaceConfig {
+        driver: InterfaceDriver::unix_socket(socket_path.clone()),
+    }]);
+    let conductor_handle = Conductor::builder().config(config).build().await?;
+    assert_eq!(
+        None,
+        conductor_handle.get_arbitrary_admin_websocket_port().await
+    );
+
+    let (mut client, _) = connect_unix(&socket_path, Arc::new(WebsocketConfig::default())).await?;
+    let response: AdminResponse = client.request(AdminRequest::ListDnas).await?;
+    assert_matches!(response, AdminResponse::DnasListed(_));
+
+    conductor_handle.shutdown().await;
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn conductor_admin_interface_ends_with_shutdown() -> Result<()> {
     if let Err(e) = conductor_admin_interface_ends_with_shutdown_inner().await {
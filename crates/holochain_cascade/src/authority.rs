@@ -83,11 +83,21 @@ pub async fn handle_get_agent_activity_deterministic(
 pub async fn handle_get_links(
     env: EnvRead,
     link_key: WireLinkKey,
-    _options: holochain_p2p::event::GetLinksOptions,
+    options: holochain_p2p::event::GetLinksOptions,
 ) -> CascadeResult<WireLinkOps> {
-    let query = GetLinksOpsQuery::new(link_key);
+    let count_only = options.count_only;
+    let query = GetLinksOpsQuery::new(link_key, options);
     let results = env
-        .async_reader(move |txn| query.run(Txn::from(&txn)))
+        .async_reader(move |txn| {
+            if count_only {
+                Ok(WireLinkOps {
+                    count: Some(query.count(&txn)?),
+                    ..Default::default()
+                })
+            } else {
+                query.run(Txn::from(&txn))
+            }
+        })
         .await?;
     Ok(results)
 }
@@ -1,6 +1,11 @@
+use holo_hash::AgentPubKey;
 use holo_hash::EntryHash;
+use holo_hash::HeaderHash;
+use holochain_p2p::event::GetLinksOptions;
 use holochain_sqlite::rusqlite::named_params;
 use holochain_sqlite::rusqlite::Row;
+use holochain_sqlite::rusqlite::ToSql;
+use holochain_sqlite::rusqlite::Transaction;
 use holochain_state::query::prelude::*;
 use holochain_state::query::StateQueryError;
 use holochain_types::dht_op::DhtOpType;
@@ -12,6 +17,7 @@ use holochain_zome_types::Header;
 use holochain_zome_types::Judged;
 use holochain_zome_types::LinkTag;
 use holochain_zome_types::SignedHeader;
+use holochain_zome_types::ValidationStatus;
 use holochain_zome_types::ZomeId;
 
 use super::WireLinkKey;
@@ -21,16 +27,152 @@ pub struct GetLinksOpsQuery {
     base: Arc<EntryHash>,
     zome_id: ZomeId,
     tag: Option<Arc<LinkTag>>,
+    after_ms: Option<i64>,
+    before_ms: Option<i64>,
+    authors: Vec<(String, AgentPubKey)>,
+    cursor_ms: Option<i64>,
+    cursor_hash: Option<HeaderHash>,
+    limit: Option<i64>,
+    /// Only return valid creates that haven't been deleted.
+    live_only: bool,
 }
 
 impl GetLinksOpsQuery {
-    pub fn new(key: WireLinkKey) -> Self {
+    pub fn new(key: WireLinkKey, options: GetLinksOptions) -> Self {
+        let GetLinksOptions { filter, count_only } = options;
+        let live_only = count_only || filter.is_paged();
         Self {
             base: Arc::new(key.base),
             zome_id: key.zome_id,
             tag: key.tag.map(Arc::new),
+            after_ms: filter.after.map(|t| t.to_sql_ms_lossy()),
+            before_ms: filter.before.map(|t| t.to_sql_ms_lossy()),
+            authors: filter
+                .authors
+                .into_iter()
+                .enumerate()
+                .map(|(i, author)| (format!(":author{}", i), author))
+                .collect(),
+            cursor_ms: filter
+                .cursor
+                .as_ref()
+                .map(|c| c.timestamp.to_sql_ms_lossy()),
+            cursor_hash: filter.cursor.map(|c| c.create_link_hash),
+            limit: filter.limit.map(i64::from),
+            live_only,
         }
     }
+
+    /// Count the live links matching this query.
+    pub fn count(&self, txn: &Transaction) -> StateQueryResult<u32> {
+        let count: i64 = txn.query_row_named(
+            &format!("SELECT COUNT(*) FROM ({})", self.create_query()),
+            &self.params(),
+            |row| row.get(0),
+        )?;
+        Ok(count as u32)
+    }
+
+    /// The conditions every create must match,
+    /// and every delete's create must match.
+    fn common_query(&self) -> String {
+        let mut common = "
+            JOIN Header On DhtOp.header_hash = Header.hash
+            WHERE DhtOp.type = :create
+            AND
+            Header.base_hash = :base_hash
+            AND
+            Header.zome_id = :zome_id
+            AND
+            DhtOp.when_integrated IS NOT NULL
+        "
+        .to_string();
+        // TODO: This should not be = but should be a partial match.
+        if self.tag.is_some() {
+            common.push_str(
+                "
+            AND
+            Header.tag = :tag
+            ",
+            );
+        }
+        if self.after_ms.is_some() {
+            common.push_str(
+                "
+            AND
+            DhtOp.authored_timestamp_ms > :after_ms
+            ",
+            );
+        }
+        if self.before_ms.is_some() {
+            common.push_str(
+                "
+            AND
+            DhtOp.authored_timestamp_ms < :before_ms
+            ",
+            );
+        }
+        if !self.authors.is_empty() {
+            let authors: Vec<&str> = self.authors.iter().map(|(p, _)| p.as_str()).collect();
+            common.push_str(&format!(
+                "
+            AND
+            Header.author IN ({})
+            ",
+                authors.join(", ")
+            ));
+        }
+        common
+    }
+
+    /// The creates, narrowed down to a page of live links if needed.
+    fn create_query(&self) -> String {
+        let mut create = format!(
+            "
+            SELECT Header.blob AS header_blob, DhtOp.type AS dht_type,
+            DhtOp.validation_status AS status
+            FROM DhtOp
+            {}
+            ",
+            self.common_query()
+        );
+        if self.live_only {
+            create.push_str(
+                "
+            AND
+            DhtOp.validation_status = :valid
+            AND NOT EXISTS (
+                SELECT 1 FROM DhtOp AS DeleteOp
+                JOIN Header AS DeleteLink ON DeleteOp.header_hash = DeleteLink.hash
+                WHERE DeleteLink.create_link_hash = Header.hash
+                AND DeleteOp.type = :delete
+                AND DeleteOp.validation_status = :valid
+                AND DeleteOp.when_integrated IS NOT NULL
+            )
+            ",
+            );
+        }
+        if self.cursor_ms.is_some() {
+            create.push_str(
+                "
+            AND (
+                DhtOp.authored_timestamp_ms > :cursor_ms
+                OR
+                (DhtOp.authored_timestamp_ms = :cursor_ms AND Header.hash > :cursor_hash)
+            )
+            ",
+            );
+        }
+        if self.limit.is_some() {
+            create.push_str(
+                "
+            ORDER BY DhtOp.authored_timestamp_ms, Header.hash
+            LIMIT :limit
+            ",
+            );
+        }
+        create
+    }
 }
 
 pub struct Item {
@@ -44,36 +186,20 @@ impl Query for GetLinksOpsQuery {
     type Output = Self::State;
 
     fn query(&self) -> String {
-        let create = "
-            SELECT Header.blob AS header_blob, DhtOp.type AS dht_type,
-            DhtOp.validation_status AS status
-            FROM DhtOp
-        ";
-        let sub_create = "
+        // The page is limited inside a sub query so the limit
+        // doesn't apply to the deletes.
+        let create_query = format!("SELECT * FROM ({})", self.create_query());
+        // Only live creates are returned so their deletes aren't needed.
+        if self.live_only {
+            return create_query;
+        }
+        let sub_create_query = format!(
+            "
             SELECT Header.hash FROM DhtOp
-        ";
-        let common = "
-            JOIN Header On DhtOp.header_hash = Header.hash
-            WHERE DhtOp.type = :create
-            AND
-            Header.base_hash = :base_hash
-            AND
-            Header.zome_id = :zome_id
-            AND
-            DhtOp.when_integrated IS NOT NULL
-        ";
-        // TODO: This should not be = but should be a partial match.
-        let tag = "
-            AND
-            Header.tag = :tag
-        ";
-        let common_query = if self.tag.is_some() {
-            format!("{}{}", common, tag)
-        } else {
-            common.into()
-        };
-        let create_query = format!("{}{}", create, common_query);
-        let sub_create_query = format!("{}{}", sub_create, common_query);
+            {}
+            ",
+            self.common_query()
+        );
         let delete_query = format!(
             "
             SELECT Header.blob AS header_blob, DhtOp.type AS dht_type,
@@ -104,6 +230,35 @@ impl Query for GetLinksOpsQuery {
                 ":tag": self.tag,
             });
         }
+        if self.after_ms.is_some() {
+            params.extend(named_params! {
+                ":after_ms": self.after_ms,
+            });
+        }
+        if self.before_ms.is_some() {
+            params.extend(named_params! {
+                ":before_ms": self.before_ms,
+            });
+        }
+        for (param, author) in &self.authors {
+            params.push((param.as_str(), author as &dyn ToSql));
+        }
+        if self.live_only {
+            params.extend(named_params! {
+                ":valid": ValidationStatus::Valid,
+            });
+        }
+        if self.cursor_ms.is_some() {
+            params.extend(named_params! {
+                ":cursor_ms": self.cursor_ms,
+                ":cursor_hash": self.cursor_hash,
+            });
+        }
+        if self.limit.is_some() {
+            params.extend(named_params! {
+                ":limit": self.limit,
+            });
+        }
         params
     }
 
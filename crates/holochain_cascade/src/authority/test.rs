@@ -1,11 +1,13 @@
 use super::*;
 use crate::authority::handle_get_agent_activity;
 use crate::test_utils::*;
+use ::fixt::prelude::*;
 use ghost_actor::dependencies::observability;
 use holochain_p2p::actor;
 use holochain_p2p::event::GetRequest;
 use holochain_state::prelude::test_cell_env;
 use holochain_types::activity::ChainItems;
+use holochain_zome_types::fixt::*;
 
 fn options() -> holochain_p2p::event::GetOptions {
     holochain_p2p::event::GetOptions {
@@ -171,6 +173,7 @@ async fn get_links() {
     let expected = WireLinkOps {
         creates: vec![td.wire_create_link.clone()],
         deletes: vec![],
+        count: None,
     };
     assert_eq!(result, expected);
 
@@ -182,10 +185,127 @@ async fn get_links() {
     let expected = WireLinkOps {
         creates: vec![td.wire_create_link_base.clone()],
         deletes: vec![td.wire_delete_link.clone()],
+        count: None,
     };
     assert_eq!(result, expected);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn get_links_filtered() {
+    observability::test_run().ok();
+    let env = test_cell_env();
+
+    let td = EntryTestData::create();
+    let create_link = match td.create_link_op.as_content() {
+        DhtOp::RegisterAddLink(_, create_link) => create_link.clone(),
+        _ => unreachable!(),
+    };
+    let author = fixt!(AgentPubKey);
+    let other_author = fixt!(AgentPubKey);
+
+    // - Three links a second apart, the last by another author.
+    let links: Vec<_> = (0..3)
+        .map(|i| {
+            let link = CreateLink {
+                timestamp: Timestamp(1_000 + i, 0),
+                author: if i == 2 {
+                    other_author.clone()
+                } else {
+                    author.clone()
+                },
+                ..create_link.clone()
+            };
+            let hash = HeaderHash::with_data_sync(&Header::CreateLink(link.clone()));
+            let signature = fixt!(Signature);
+            fill_db(
+                &env.env(),
+                DhtOpHashed::from_content_sync(DhtOp::RegisterAddLink(
+                    signature.clone(),
+                    link.clone(),
+                )),
+            );
+            let wire = WireCreateLink::condense(link.clone(), signature, ValidationStatus::Valid);
+            (link, hash, wire)
+        })
+        .collect();
+
+    // - The first link is deleted.
+    let delete_link = DeleteLink {
+        link_add_address: links[0].1.clone(),
+        base_address: create_link.base_address.clone(),
+        ..fixt!(DeleteLink)
+    };
+    let signature = fixt!(Signature);
+    fill_db(
+        &env.env(),
+        DhtOpHashed::from_content_sync(DhtOp::RegisterRemoveLink(
+            signature.clone(),
+            delete_link.clone(),
+        )),
+    );
+    let wire_delete = WireDeleteLink::condense(delete_link, signature, ValidationStatus::Valid);
+
+    let get = |filter: LinkFilter, count_only: bool| {
+        handle_get_links(
+            env.env().into(),
+            td.link_key.clone(),
+            holochain_p2p::event::GetLinksOptions { filter, count_only },
+        )
+    };
+
+    // - Without paging every create is returned along with the deletes.
+    let result = get(LinkFilter::default(), false).await.unwrap();
+    assert_eq!(result.creates.len(), 3);
+    assert_eq!(result.deletes, vec![wire_delete]);
+
+    // - A page skips deleted links and leaves out the deletes.
+    let result = get(LinkFilter::default().page(1, None), false)
+        .await
+        .unwrap();
+    assert_eq!(result.creates, vec![links[1].2.clone()]);
+    assert!(result.deletes.is_empty());
+
+    // - The next page starts after the cursor.
+    let cursor = LinkCursor {
+        timestamp: links[1].0.timestamp,
+        create_link_hash: links[1].1.clone(),
+    };
+    let result = get(LinkFilter::default().page(10, Some(cursor)), false)
+        .await
+        .unwrap();
+    assert_eq!(result.creates, vec![links[2].2.clone()]);
+
+    // - Time bounds and authors.
+    let result = get(
+        LinkFilter::default()
+            .after(links[0].0.timestamp)
+            .before(links[2].0.timestamp),
+        false,
+    )
+    .await
+    .unwrap();
+    assert_eq!(result.creates, vec![links[1].2.clone()]);
+    let result = get(LinkFilter::default().author(other_author), false)
+        .await
+        .unwrap();
+    assert_eq!(result.creates, vec![links[2].2.clone()]);
+    assert!(result.deletes.is_empty());
+
+    // - Only the number of live links is sent when counting.
+    let result = get(LinkFilter::default(), true).await.unwrap();
+    assert_eq!(
+        result,
+        WireLinkOps {
+            count: Some(2),
+            ..Default::default()
+        }
+    );
+    let result = get(LinkFilter::default().author(author), true)
+        .await
+        .unwrap();
+    assert_eq!(result.count, Some(1));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn get_agent_activity() {
     observability::test_run().ok();
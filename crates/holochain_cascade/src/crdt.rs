@@ -0,0 +1,140 @@
+//! Merging the latest versions of an entry according to its [CrdtType]
+
+use super::*;
+use crate::error::CascadeError;
+
+/// The most versions of an entry that are visited when following its updates.
+/// Each visit gets the entry's details, one after another, which may
+/// be a network round trip, so this also bounds how long a merge takes.
+/// An entry with a longer update history is merged from the
+/// latest versions found before this limit was reached.
+pub const MAX_MERGED_VERSIONS: usize = 100;
+
+/// Merge the latest versions of an entry into a [MergedEntry].
+///
+/// The versions are sorted by timestamp and then header hash.
+/// For [CrdtType::NoMerge] and [CrdtType::LastWriterWins] the merged entry
+/// is the entry of the newest version.
+/// For [CrdtType::GrowOnlySet] and [CrdtType::GrowOnlyCounter]
+/// it is the merge of every version's entry, which doesn't match
+/// the entry hash of any of their headers when there is more than one.
+pub fn merge(crdt_type: CrdtType, mut heads: Vec<Element>) -> CascadeResult<Option<MergedEntry>> {
+    heads.sort_by(|a, b| {
+        (a.header().timestamp(), a.header_address())
+            .cmp(&(b.header().timestamp(), b.header_address()))
+    });
+    let entry = match (crdt_type, heads.len()) {
+        (_, 0) => return Ok(None),
+        (CrdtType::NoMerge, _) | (CrdtType::LastWriterWins, _) | (_, 1) => {
+            match heads.last().and_then(|newest| newest.entry().as_option()) {
+                Some(entry) => entry.clone(),
+                None => return Ok(None),
+            }
+        }
+        (CrdtType::GrowOnlySet, _) => {
+            let mut merged = GrowOnlySet::new();
+            for head in &heads {
+                merged.merge(GrowOnlySet::try_from(app_bytes(head)?)?);
+            }
+            Entry::app(SerializedBytes::try_from(merged)?)?
+        }
+        (CrdtType::GrowOnlyCounter, _) => {
+            let mut merged = GrowOnlyCounter::new();
+            for head in &heads {
+                merged.merge(GrowOnlyCounter::try_from(app_bytes(head)?)?);
+            }
+            Entry::app(SerializedBytes::try_from(merged)?)?
+        }
+    };
+    Ok(Some(MergedEntry { entry, heads }))
+}
+
+/// The bytes of the app entry in this element.
+fn app_bytes(element: &Element) -> CascadeResult<SerializedBytes> {
+    match element.entry().as_option() {
+        Some(Entry::App(bytes)) => Ok(bytes.as_ref().clone()),
+        _ => Err(CascadeError::EntryMissing(element.header_address().clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use holochain_zome_types::fixt::*;
+
+    fn version(seconds: i64, entry: SerializedBytes) -> Element {
+        let mut update = fixt!(Update);
+        update.timestamp = Timestamp(seconds, 0);
+        let entry = Entry::app(entry).unwrap();
+        update.entry_hash = EntryHash::with_data_sync(&entry);
+        let header = HeaderHashed::from_content_sync(Header::Update(update));
+        let signed_header = SignedHeaderHashed::with_presigned(header, fixt!(Signature));
+        Element::new(signed_header, Some(entry))
+    }
+
+    fn entry_bytes(entry: &Entry) -> SerializedBytes {
+        match entry {
+            Entry::App(bytes) => bytes.as_ref().clone(),
+            _ => unreachable!(),
+        }
+    }
+
+    fn set_of(items: &[&str]) -> GrowOnlySet {
+        let mut set = GrowOnlySet::new();
+        for item in items {
+            set.insert(&item.to_string()).unwrap();
+        }
+        set
+    }
+
+    #[test]
+    fn last_writer_wins_picks_newest() {
+        let old = version(1, SerializedBytes::try_from(set_of(&["a"])).unwrap());
+        let new = version(2, SerializedBytes::try_from(set_of(&["b"])).unwrap());
+        let merged = merge(CrdtType::LastWriterWins, vec![new.clone(), old.clone()])
+            .unwrap()
+            .unwrap();
+        assert_eq!(merged.heads, vec![old, new.clone()]);
+        assert_eq!(Some(&merged.entry), new.entry().as_option());
+    }
+
+    #[test]
+    fn grow_only_set_takes_union() {
+        let a = version(1, SerializedBytes::try_from(set_of(&["a", "b"])).unwrap());
+        let b = version(2, SerializedBytes::try_from(set_of(&["c"])).unwrap());
+        let merged = merge(CrdtType::GrowOnlySet, vec![b.clone(), a.clone()])
+            .unwrap()
+            .unwrap();
+        // - The versions are left as they are.
+        assert_eq!(merged.heads, vec![a, b.clone()]);
+        assert_eq!(merged.newest(), Some(&b));
+        let set = GrowOnlySet::try_from(entry_bytes(&merged.entry)).unwrap();
+        assert_eq!(set, set_of(&["a", "b", "c"]));
+    }
+
+    #[test]
+    fn grow_only_counter_keeps_highest_counts() {
+        let alice = fixt!(AgentPubKey);
+        let bob = fixt!(AgentPubKey);
+        let mut a = GrowOnlyCounter::new();
+        a.increment(alice.clone(), 3);
+        let mut b = GrowOnlyCounter::new();
+        b.increment(alice.clone(), 1);
+        b.increment(bob.clone(), 4);
+        let merged = merge(
+            CrdtType::GrowOnlyCounter,
+            vec![
+                version(1, SerializedBytes::try_from(a).unwrap()),
+                version(2, SerializedBytes::try_from(b).unwrap()),
+            ],
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(merged.heads.len(), 2);
+        let counter = GrowOnlyCounter::try_from(entry_bytes(&merged.entry)).unwrap();
+        assert_eq!(counter.count_of(&alice), 3);
+        assert_eq!(counter.count_of(&bob), 4);
+        assert_eq!(counter.value(), 7);
+    }
+}
@@ -30,6 +30,19 @@ pub enum CascadeError {
     #[error("Got an invalid response from an authority for the request hash: {0:?}")]
     InvalidResponse(AnyDhtHash),
 
+    #[error("Only {agreeing} of the {responded} authorities that responded agreed but {required} were required")]
+    QuorumNotReached {
+        required: u8,
+        agreeing: u32,
+        responded: u32,
+    },
+
+    #[error("A quorum needs at least one agreeing authority")]
+    ZeroQuorum,
+
+    #[error("A quorum of {required} authorities can't be reached, at most {max} is supported")]
+    QuorumUnsupported { required: u8, max: u8 },
+
     #[error(transparent)]
     JoinError(#[from] JoinError),
 
@@ -42,6 +55,9 @@ pub enum CascadeError {
     #[error(transparent)]
     SerializedBytesError(#[from] SerializedBytesError),
 
+    #[error(transparent)]
+    EntryError(#[from] EntryError),
+
     #[error(transparent)]
     WrongHeaderError(#[from] WrongHeaderError),
 
@@ -16,8 +16,10 @@ use holochain_p2p::actor::GetOptions as NetworkGetOptions;
 use holochain_p2p::HolochainP2pCell;
 use holochain_p2p::HolochainP2pCellT;
 use holochain_sqlite::rusqlite::Transaction;
+use holochain_state::cache_gc;
 use holochain_state::host_fn_workspace::HostFnStores;
 use holochain_state::host_fn_workspace::HostFnWorkspace;
+use holochain_state::mutations::set_last_access_time;
 use holochain_state::mutations::set_validation_status;
 use holochain_state::prelude::*;
 use holochain_state::query::element_details::GetElementDetailsQuery;
@@ -33,11 +35,15 @@ use holochain_types::prelude::*;
 use mutations::insert_entry;
 use mutations::insert_header;
 use mutations::insert_op_lite;
+use std::collections::HashSet;
 use tracing::*;
 
 pub mod authority;
 pub mod error;
 
+pub mod crdt;
+pub mod quorum;
+
 mod agent_activity;
 
 #[cfg(any(test, feature = "test_utils"))]
@@ -178,8 +184,10 @@ where
             set_validation_status(txn, op_hash.clone(), status)?;
         }
         // We set the integrated to for the cache so it can match the
-        // same query as the vault. This can also be used for garbage collection.
-        set_when_integrated(txn, op_hash, timestamp::now())?;
+        // same query as the vault.
+        set_when_integrated(txn, op_hash.clone(), timestamp::now())?;
+        // Fetching counts as using the op, so it isn't garbage collected too soon.
+        set_last_access_time(txn, op_hash, timestamp::now())?;
         Ok(())
     }
 
@@ -194,12 +202,11 @@ where
         Ok(())
     }
 
-    async fn merge_ops_into_cache(&mut self, responses: Vec<WireOps>) -> CascadeResult<()> {
+    async fn merge_ops_into_cache(&mut self, responses: Vec<RenderedOps>) -> CascadeResult<()> {
         let cache = ok_or_return!(self.cache.as_mut());
         cache
             .async_commit(|txn| {
-                for response in responses {
-                    let ops = response.render()?;
+                for ops in responses {
                     Self::insert_rendered_ops(txn, ops)?;
                 }
                 CascadeResult::Ok(())
@@ -208,22 +215,33 @@ where
         Ok(())
     }
 
-    async fn merge_link_ops_into_cache(
-        &mut self,
-        responses: Vec<WireLinkOps>,
-        key: WireLinkKey,
-    ) -> CascadeResult<()> {
-        let cache = ok_or_return!(self.cache.as_mut());
-        cache
+    /// Mark the cached ops for this basis as recently used,
+    /// so the cache garbage collector keeps them for longer.
+    /// Only call this for reads served from the cache, not for
+    /// data we are authoring or are an authority for.
+    /// The reads are kept in memory and written by the garbage collector,
+    /// or here once too many are waiting.
+    /// Failing to write them is only logged.
+    async fn record_cache_access(&mut self, basis: AnyDhtHash) {
+        let cache = match self.cache.as_mut() {
+            Some(cache) => cache,
+            None => return,
+        };
+        if !cache_gc::note_access(cache, basis) {
+            return;
+        }
+        let bases = cache_gc::take_pending_access(cache);
+        let result = cache
             .async_commit(move |txn| {
-                for response in responses {
-                    let ops = response.render(&key)?;
-                    Self::insert_rendered_ops(txn, ops)?;
-                }
+                cache_gc::record_access(txn, &bases, timestamp::now())?;
                 CascadeResult::Ok(())
             })
-            .await?;
-        Ok(())
+            .await;
+        // The data was already read so failing to stamp
+        // the access time shouldn't fail the get.
+        if let Err(e) = result {
+            warn!(?e, "Failed to record access to the cache");
+        }
     }
 
     #[instrument(skip(self, options))]
@@ -231,15 +249,98 @@ where
         &mut self,
         hash: AnyDhtHash,
         options: NetworkGetOptions,
-    ) -> CascadeResult<()> {
-        let network = ok_or_return!(self.network.as_mut());
+    ) -> CascadeResult<QuorumReport> {
+        let results = self.fetch_element_answers(hash, options).await?;
+        let report = quorum::report(&results);
+
+        self.merge_ops_into_cache(results).await?;
+        Ok(report)
+    }
+
+    /// Ask the authorities for an element without caching their answers.
+    async fn fetch_element_answers(
+        &mut self,
+        hash: AnyDhtHash,
+        options: NetworkGetOptions,
+    ) -> CascadeResult<Vec<RenderedOps>> {
+        let network = ok_or_return!(self.network.as_mut(), Vec::with_capacity(0));
         let results = network
             .get(hash, options.clone())
             .instrument(debug_span!("fetch_element::network_get"))
             .await?;
+        let results = results
+            .into_iter()
+            .map(WireOps::render)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(results)
+    }
 
-        self.merge_ops_into_cache(results).await?;
-        Ok(())
+    #[instrument(skip(self, options))]
+    async fn fetch_many(
+        &mut self,
+        hashes: Vec<AnyDhtHash>,
+        options: NetworkGetOptions,
+    ) -> CascadeResult<()> {
+        if hashes.is_empty() {
+            return Ok(());
+        }
+        let network = ok_or_return!(self.network.as_mut());
+        let results = network
+            .get_many(hashes, options)
+            .instrument(debug_span!("fetch_many::network_get_many"))
+            .await?;
+        let results = results
+            .into_iter()
+            .flatten()
+            .map(WireOps::render)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.merge_ops_into_cache(results).await
+    }
+
+    /// Fetch every hash that a single get would have gone to
+    /// the network for, in one batch, and return those hashes.
+    /// `details` chooses whether a local hit is checked against
+    /// the details or the live query when using [`GetStrategy::Content`].
+    async fn fetch_many_missing(
+        &mut self,
+        hashes: &[AnyDhtHash],
+        options: &GetOptions,
+        details: bool,
+    ) -> CascadeResult<HashSet<AnyDhtHash>> {
+        let mut missing = Vec::new();
+        for hash in hashes {
+            let authoring = self.am_i_authoring(hash)?;
+            let authority = self.am_i_an_authority(hash.clone()).await?;
+            if let GetStrategy::Content = options.strategy {
+                let found = match (*hash.hash_type(), details) {
+                    (AnyDht::Entry, false) => self
+                        .cascading(GetLiveEntryQuery::new(hash.clone().into()))?
+                        .is_some(),
+                    (AnyDht::Header, false) => self
+                        .cascading(GetLiveElementQuery::new(hash.clone().into()))?
+                        .is_some(),
+                    (AnyDht::Entry, true) => self
+                        .cascading(GetEntryDetailsQuery::new(hash.clone().into()))?
+                        .is_some(),
+                    (AnyDht::Header, true) => self
+                        .cascading(GetElementDetailsQuery::new(hash.clone().into()))?
+                        .is_some(),
+                };
+                if found {
+                    if !authoring && !authority {
+                        self.record_cache_access(hash.clone()).await;
+                    }
+                    continue;
+                }
+            }
+            if !authoring && !authority {
+                missing.push(hash.clone());
+            }
+        }
+        self.fetch_many(missing.clone(), options.clone().into())
+            .await?;
+        Ok(missing.into_iter().collect())
     }
 
     #[instrument(skip(self, options))]
@@ -247,13 +348,27 @@ where
         &mut self,
         link_key: WireLinkKey,
         options: GetLinksOptions,
-    ) -> CascadeResult<()> {
-        let network = ok_or_return!(self.network.as_mut());
-        let results = network.get_links(link_key.clone(), options).await?;
+    ) -> CascadeResult<QuorumReport> {
+        let results = self.fetch_links_answers(link_key, options).await?;
+        let report = quorum::report(&results);
 
-        self.merge_link_ops_into_cache(results, link_key.clone())
-            .await?;
-        Ok(())
+        self.merge_ops_into_cache(results).await?;
+        Ok(report)
+    }
+
+    /// Ask the authorities for links without caching their answers.
+    async fn fetch_links_answers(
+        &mut self,
+        link_key: WireLinkKey,
+        options: GetLinksOptions,
+    ) -> CascadeResult<Vec<RenderedOps>> {
+        let network = ok_or_return!(self.network.as_mut(), Vec::with_capacity(0));
+        let results = network.get_links(link_key.clone(), options).await?;
+        let results = results
+            .into_iter()
+            .map(|response| response.render(&link_key))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(results)
     }
 
     #[instrument(skip(self, options))]
@@ -402,12 +517,26 @@ where
         let authority = self.am_i_an_authority(entry_hash.clone().into()).await?;
         let query = GetEntryDetailsQuery::new(entry_hash.clone());
 
+        // A quorum always asks the authorities, even if we have the data
+        // or are an authority, because several of them need to agree.
+        if let Some(required) = &options.quorum {
+            quorum::check(required)?;
+            let report = self
+                .fetch_element(entry_hash.into(), required.into())
+                .await?;
+            quorum::require(report, required)?;
+            return Ok(self.cascading(query)?);
+        }
+
         // We don't need metadata and only need the content
         // so if we have it locally then we can avoid the network.
         if let GetStrategy::Content = options.strategy {
             let results = self.cascading(query.clone())?;
             // We got a result so can short circuit.
             if results.is_some() {
+                if !authoring && !authority {
+                    self.record_cache_access(entry_hash.into()).await;
+                }
                 return Ok(results);
             // We didn't get a result so if we are either authoring
             // or the authority there's nothing left to do.
@@ -438,6 +567,17 @@ where
         let authority = self.am_i_an_authority(header_hash.clone().into()).await?;
         let query = GetElementDetailsQuery::new(header_hash.clone());
 
+        // A quorum always asks the authorities, even if we have the data
+        // or are an authority, because several of them need to agree.
+        if let Some(required) = &options.quorum {
+            quorum::check(required)?;
+            let report = self
+                .fetch_element(header_hash.into(), required.into())
+                .await?;
+            quorum::require(report, required)?;
+            return Ok(self.cascading(query)?);
+        }
+
         // TODO: we can short circuit if we have any local deletes on a header.
         // Is this bad because we will not go back to the network until our
         // cache is cleared. Could someone create an attack based on this fact?
@@ -448,6 +588,9 @@ where
             let results = self.cascading(query.clone())?;
             // We got a result so can short circuit.
             if results.is_some() {
+                if !authoring && !authority {
+                    self.record_cache_access(header_hash.into()).await;
+                }
                 return Ok(results);
             // We didn't get a result so if we are either authoring
             // or the authority there's nothing left to do.
@@ -482,6 +625,17 @@ where
         let authority = self.am_i_an_authority(header_hash.clone().into()).await?;
         let query = GetLiveElementQuery::new(header_hash.clone());
 
+        // A quorum always asks the authorities, even if we have the data
+        // or are an authority, because several of them need to agree.
+        if let Some(required) = &options.quorum {
+            quorum::check(required)?;
+            let report = self
+                .fetch_element(header_hash.into(), required.into())
+                .await?;
+            quorum::require(report, required)?;
+            return Ok(self.cascading(query)?);
+        }
+
         // TODO: we can short circuit if we have any local deletes on a header.
         // Is this bad because we will not go back to the network until our
         // cache is cleared. Could someone create an attack based on this fact?
@@ -492,6 +646,9 @@ where
             let results = self.cascading(query.clone())?;
             // We got a result so can short circuit.
             if results.is_some() {
+                if !authoring && !authority {
+                    self.record_cache_access(header_hash.into()).await;
+                }
                 return Ok(results);
             // We didn't get a result so if we are either authoring
             // or the authority there's nothing left to do.
@@ -524,12 +681,26 @@ where
         let authority = self.am_i_an_authority(entry_hash.clone().into()).await?;
         let query = GetLiveEntryQuery::new(entry_hash.clone());
 
+        // A quorum always asks the authorities, even if we have the data
+        // or are an authority, because several of them need to agree.
+        if let Some(required) = &options.quorum {
+            quorum::check(required)?;
+            let report = self
+                .fetch_element(entry_hash.into(), required.into())
+                .await?;
+            quorum::require(report, required)?;
+            return Ok(self.cascading(query)?);
+        }
+
         // We don't need metadata and only need the content
         // so if we have it locally then we can avoid the network.
         if let GetStrategy::Content = options.strategy {
             let results = self.cascading(query.clone())?;
             // We got a result so can short circuit.
             if results.is_some() {
+                if !authoring && !authority {
+                    self.record_cache_access(entry_hash.into()).await;
+                }
                 return Ok(results);
             // We didn't get a result so if we are either authoring
             // or the authority there's nothing left to do.
@@ -550,6 +721,8 @@ where
         Ok(results)
     }
 
+    /// Get many hashes at once, running up to ten gets at a time.
+    /// The results are in the same order as the hashes.
     pub async fn get_concurrent<I: IntoIterator<Item = AnyDhtHash>>(
         &mut self,
         hashes: I,
@@ -565,7 +738,7 @@ where
             }
         });
         Ok(futures::stream::iter(iter)
-            .buffer_unordered(10)
+            .buffered(10)
             .try_collect()
             .await?)
     }
@@ -604,6 +777,157 @@ where
         }
     }
 
+    #[instrument(skip(self, options))]
+    /// Returns the live [Element] for each hash, in the same order as the hashes.
+    /// This is the same as calling [`Self::dht_get`] for each hash
+    /// except the hashes missing locally are fetched in one batch,
+    /// which only takes a request per group of authorities.
+    /// A hash the batch didn't find is fetched on its own
+    /// in case the authorities asked don't hold it.
+    pub async fn dht_get_many(
+        &mut self,
+        hashes: Vec<AnyDhtHash>,
+        options: GetOptions,
+    ) -> CascadeResult<Vec<Option<Element>>> {
+        // Each hash needs its own quorum.
+        if options.quorum.is_some() {
+            return self.get_concurrent(hashes, options).await;
+        }
+        let fetched = self.fetch_many_missing(&hashes, &options, false).await?;
+        let mut out = Vec::with_capacity(hashes.len());
+        for hash in hashes {
+            let element = match *hash.hash_type() {
+                AnyDht::Entry => self.cascading(GetLiveEntryQuery::new(hash.clone().into()))?,
+                AnyDht::Header => self.cascading(GetLiveElementQuery::new(hash.clone().into()))?,
+            };
+            let element = match element {
+                None if fetched.contains(&hash) => self.dht_get(hash, options.clone()).await?,
+                element => element,
+            };
+            out.push(element);
+        }
+        Ok(out)
+    }
+
+    #[instrument(skip(self, options))]
+    /// Returns the [Details] for each hash, in the same order as the hashes.
+    /// This is the same as calling [`Self::get_details`] for each hash
+    /// except the hashes missing locally are fetched in one batch.
+    /// A hash the batch didn't find is fetched on its own.
+    pub async fn get_details_many(
+        &mut self,
+        hashes: Vec<AnyDhtHash>,
+        options: GetOptions,
+    ) -> CascadeResult<Vec<Option<Details>>> {
+        // Each hash needs its own quorum.
+        if options.quorum.is_some() {
+            let mut out = Vec::with_capacity(hashes.len());
+            for hash in hashes {
+                out.push(self.get_details(hash, options.clone()).await?);
+            }
+            return Ok(out);
+        }
+        let fetched = self.fetch_many_missing(&hashes, &options, true).await?;
+        let mut out = Vec::with_capacity(hashes.len());
+        for hash in hashes {
+            let details = match *hash.hash_type() {
+                AnyDht::Entry => self
+                    .cascading(GetEntryDetailsQuery::new(hash.clone().into()))?
+                    .map(Details::Entry),
+                AnyDht::Header => self
+                    .cascading(GetElementDetailsQuery::new(hash.clone().into()))?
+                    .map(Details::Element),
+            };
+            let details = match details {
+                None if fetched.contains(&hash) => self.get_details(hash, options.clone()).await?,
+                details => details,
+            };
+            out.push(details);
+        }
+        Ok(out)
+    }
+
+    #[instrument(skip(self, required))]
+    /// Returns the live [Element] for this hash from the merged answers
+    /// of the authorities, along with how many of them answered and agreed.
+    /// Local data isn't used, so the output is only what the network said.
+    /// This doesn't fail if the quorum isn't reached.
+    pub async fn dht_get_with_quorum(
+        &mut self,
+        hash: AnyDhtHash,
+        required: &Quorum,
+    ) -> CascadeResult<QuorumOutput<Option<Element>>> {
+        quorum::check(required)?;
+        let results = self
+            .fetch_element_answers(hash.clone(), required.into())
+            .await?;
+        let report = quorum::report(&results);
+        let answers = quorum::merge(&results);
+        self.merge_ops_into_cache(results).await?;
+        let output = match *hash.hash_type() {
+            AnyDht::Entry => GetLiveEntryQuery::new(hash.into()).run(answers)?,
+            AnyDht::Header => GetLiveElementQuery::new(hash.into()).run(answers)?,
+        };
+        Ok(QuorumOutput { output, report })
+    }
+
+    #[instrument(skip(self, element, options))]
+    /// Follow the updates to the entry of this [Element] to its latest versions
+    /// and merge them according to the [CrdtType] of the entry.
+    /// Deleted versions are not merged but updates to them are still followed.
+    /// If the entry has no updates, or the type doesn't merge,
+    /// the element is the only version.
+    /// Returns `None` if the element has no entry.
+    ///
+    /// Every version visited is a separate, sequential [Cascade::get_entry_details],
+    /// so a long update history can take up to [crdt::MAX_MERGED_VERSIONS]
+    /// network round trips when the versions aren't held locally.
+    pub async fn merge_updates(
+        &mut self,
+        element: Element,
+        crdt_type: CrdtType,
+        options: GetOptions,
+    ) -> CascadeResult<Option<MergedEntry>> {
+        let entry_hash = match element.header().entry_hash() {
+            Some(entry_hash) if crdt_type.merges() => entry_hash.clone(),
+            _ => return Ok(MergedEntry::from_element(element)),
+        };
+        let mut seen = std::collections::HashSet::new();
+        seen.insert(entry_hash.clone());
+        // Each version is the update header that created it and its entry hash.
+        let mut to_visit = std::collections::VecDeque::new();
+        to_visit.push_back((None, entry_hash));
+        let mut heads = Vec::new();
+        while let Some((update, entry_hash)) = to_visit.pop_front() {
+            if seen.len() > crdt::MAX_MERGED_VERSIONS {
+                break;
+            }
+            let details = match self.get_entry_details(entry_hash, options.clone()).await? {
+                Some(details) => details,
+                None => continue,
+            };
+            if details.updates.is_empty() {
+                // The original entry is never a head
+                // because then there is nothing to merge.
+                if let (Some(update), EntryDhtStatus::Live) = (update, details.entry_dht_status) {
+                    heads.push(Element::new(update, Some(details.entry)));
+                }
+                continue;
+            }
+            for update in details.updates {
+                if let Some(next) = update.header().entry_hash() {
+                    if seen.insert(next.clone()) {
+                        to_visit.push_back((Some(update.clone()), next.clone()));
+                    }
+                }
+            }
+        }
+        if heads.is_empty() {
+            return Ok(MergedEntry::from_element(element));
+        }
+        crdt::merge(crdt_type, heads)
+    }
+
     #[instrument(skip(self, options))]
     /// Gets an links from the cas or cache depending on it's metadata
     // The default behavior is to skip deleted or replaced entries.
@@ -612,15 +936,71 @@ where
         key: WireLinkKey,
         options: GetLinksOptions,
     ) -> CascadeResult<Vec<Link>> {
+        let filter = options.filter.clone();
         let authority = self.am_i_an_authority(key.base.clone().into()).await?;
         if !authority {
             self.fetch_links(key.clone(), options).await?;
         }
-        let query = GetLinksQuery::new(key.base, key.zome_id, key.tag);
+        let query = GetLinksQuery::new(key.base, key.zome_id, key.tag).with_filter(filter);
         let results = self.cascading(query)?;
         Ok(results)
     }
 
+    #[instrument(skip(self, key, options))]
+    /// Count the live links matching the filter without fetching them.
+    /// If we aren't an authority for the base the largest count
+    /// given by the authorities or found locally is returned.
+    pub async fn dht_count_links(
+        &mut self,
+        key: WireLinkKey,
+        options: GetLinksOptions,
+    ) -> CascadeResult<u32> {
+        let authority = self.am_i_an_authority(key.base.clone().into()).await?;
+        let mut count = 0;
+        if !authority {
+            if let Some(network) = self.network.as_mut() {
+                let options = GetLinksOptions {
+                    count_only: true,
+                    ..options.clone()
+                };
+                let results = network.get_links(key.clone(), options).await?;
+                count = results
+                    .into_iter()
+                    .filter_map(|response| response.count)
+                    .max()
+                    .unwrap_or(0);
+            }
+        }
+        let query = GetLinksQuery::new(key.base, key.zome_id, key.tag).with_filter(options.filter);
+        let local = self.cascading(query)?.len() as u32;
+        Ok(count.max(local))
+    }
+
+    #[instrument(skip(self, required))]
+    /// Returns the links from the merged answers of the authorities,
+    /// along with how many of them answered and agreed.
+    /// Local data isn't used, so the output is only what the network said.
+    /// This doesn't fail if the quorum isn't reached.
+    pub async fn dht_get_links_with_quorum(
+        &mut self,
+        key: WireLinkKey,
+        filter: LinkFilter,
+        required: &Quorum,
+    ) -> CascadeResult<QuorumOutput<Vec<Link>>> {
+        quorum::check(required)?;
+        let options = GetLinksOptions {
+            filter: filter.clone(),
+            ..required.into()
+        };
+        let results = self.fetch_links_answers(key.clone(), options).await?;
+        let report = quorum::report(&results);
+        let answers = quorum::merge(&results);
+        self.merge_ops_into_cache(results).await?;
+        let query = GetLinksQuery::new(key.base, key.zome_id, key.tag).with_filter(filter);
+        let output = query.run(answers)?;
+        Ok(QuorumOutput { output, report })
+    }
+
     #[instrument(skip(self, key, options))]
     /// Return all CreateLink headers
     /// and DeleteLink headers ordered by time.
@@ -647,7 +1027,7 @@ where
     /// - include_full_headers will fetch the valid headers in parallel (requires include_valid_activity)
     /// Query:
     /// - include_entries will also fetch the entries in parallel (requires include_full_headers)
-    /// - sequence_range will get all the activity in the exclusive range
+    /// - sequence_range will get all the activity in the range
     /// - header_type and entry_type will filter the activity (requires include_full_headers)
     pub async fn get_agent_activity(
         &mut self,
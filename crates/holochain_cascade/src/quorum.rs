@@ -0,0 +1,82 @@
+//! Checking how many authorities agree on an answer
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use super::*;
+use crate::error::CascadeError;
+use holochain_state::scratch::Scratch;
+
+/// Count how many authorities answered and the size of the largest
+/// group that gave exactly the same answer.
+/// Answers are the same if they hold the same ops with the same validation status.
+pub(crate) fn report(responses: &[RenderedOps]) -> QuorumReport {
+    let mut groups: HashMap<BTreeSet<(DhtOpHash, Option<ValidationStatus>)>, u32> = HashMap::new();
+    for response in responses {
+        let answer = response
+            .ops
+            .iter()
+            .map(|op| (op.op_hash.clone(), op.validation_status))
+            .collect();
+        *groups.entry(answer).or_default() += 1;
+    }
+    QuorumReport {
+        responded: responses.len() as u32,
+        agreeing: groups.values().max().copied().unwrap_or(0),
+    }
+}
+
+/// Fail unless enough authorities agreed.
+pub fn require(report: QuorumReport, quorum: &Quorum) -> CascadeResult<()> {
+    if report.is_reached(quorum) {
+        Ok(())
+    } else {
+        Err(CascadeError::QuorumNotReached {
+            required: quorum.min_agreeing,
+            agreeing: report.agreeing,
+            responded: report.responded,
+        })
+    }
+}
+
+/// The largest quorum that can be required.
+/// Reads are only answered by the agents joined to this conductor
+/// until they are fanned out to remote authorities,
+/// so more than one agreeing answer wouldn't be independent.
+pub const MAX_MIN_AGREEING: u8 = 1;
+
+/// Fail if the quorum would be reached without any authority answering,
+/// or needs more independent authorities than a read can reach.
+pub fn check(quorum: &Quorum) -> CascadeResult<()> {
+    if quorum.min_agreeing == 0 {
+        Err(CascadeError::ZeroQuorum)
+    } else if quorum.min_agreeing > MAX_MIN_AGREEING {
+        Err(CascadeError::QuorumUnsupported {
+            required: quorum.min_agreeing,
+            max: MAX_MIN_AGREEING,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Merge the answers of all the authorities into a scratch space
+/// so a query only sees what they returned.
+/// Headers no authority has found valid are left out.
+pub(crate) fn merge(responses: &[RenderedOps]) -> Scratch {
+    let mut scratch = Scratch::new();
+    let mut seen = HashSet::new();
+    for response in responses {
+        if let Some(entry) = &response.entry {
+            scratch.add_entry(entry.clone());
+        }
+        for op in &response.ops {
+            if op.validation_status == Some(ValidationStatus::Valid)
+                && seen.insert(op.header.header_address().clone())
+            {
+                scratch.add_header(op.header.clone());
+            }
+        }
+    }
+    scratch
+}
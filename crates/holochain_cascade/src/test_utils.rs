@@ -112,6 +112,17 @@ impl HolochainP2pCellT for PassThroughNetwork {
         }
         Ok(out)
     }
+    async fn get_many(
+        &mut self,
+        dht_hashes: Vec<holo_hash::AnyDhtHash>,
+        options: actor::GetOptions,
+    ) -> actor::HolochainP2pResult<Vec<Vec<WireOps>>> {
+        let mut out = Vec::with_capacity(dht_hashes.len());
+        for dht_hash in dht_hashes {
+            out.push(self.get(dht_hash, options.clone()).await?);
+        }
+        Ok(out)
+    }
     async fn get_meta(
         &mut self,
         _dht_hash: holo_hash::AnyDhtHash,
@@ -277,6 +288,14 @@ impl HolochainP2pCellT for MockNetwork {
         self.0.lock().await.get(dht_hash, options).await
     }
 
+    async fn get_many(
+        &mut self,
+        dht_hashes: Vec<holo_hash::AnyDhtHash>,
+        options: actor::GetOptions,
+    ) -> actor::HolochainP2pResult<Vec<Vec<WireOps>>> {
+        self.0.lock().await.get_many(dht_hashes, options).await
+    }
+
     async fn get_meta(
         &mut self,
         dht_hash: holo_hash::AnyDhtHash,
@@ -35,6 +35,8 @@ pub struct EntryTestData {
     pub update_content_op: DhtOpHashed,
     pub wire_update: Judged<WireUpdateRelationship>,
     pub update_hash: HeaderHash,
+    pub store_update_entry_op: DhtOpHashed,
+    pub update_entry_hash: EntryHash,
     pub hash: EntryHash,
     pub entry: EntryData,
     // Links
@@ -71,7 +73,7 @@ impl EntryTestData {
 
         create.entry_hash = entry_hash.clone();
         create.entry_type = entry_type_fixt.next().unwrap();
-        update.entry_hash = update_entry_hash;
+        update.entry_hash = update_entry_hash.clone();
         update.entry_type = entry_type_fixt.next().unwrap();
 
         let create_header = Header::Create(create.clone());
@@ -114,8 +116,13 @@ impl EntryTestData {
         let signature = fixt!(Signature);
         let update_content_op = DhtOpHashed::from_content_sync(DhtOp::RegisterUpdatedContent(
             signature.clone(),
-            update,
-            Some(Box::new(update_entry)),
+            update.clone(),
+            Some(Box::new(update_entry.clone())),
+        ));
+        let store_update_entry_op = DhtOpHashed::from_content_sync(DhtOp::StoreEntry(
+            signature.clone(),
+            NewEntryHeader::Update(update),
+            Box::new(update_entry),
         ));
         let wire_update = Judged::valid(SignedHeader(update_header, signature).try_into().unwrap());
 
@@ -190,6 +197,8 @@ impl EntryTestData {
             create_hash,
             delete_hash,
             update_hash,
+            store_update_entry_op,
+            update_entry_hash,
             create_link_op,
             delete_link_op,
             wire_create_link,
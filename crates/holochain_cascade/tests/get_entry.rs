@@ -7,11 +7,13 @@ use holochain_p2p::MockHolochainP2pCellT;
 use holochain_state::mutations::insert_op_scratch;
 use holochain_state::prelude::test_cell_env;
 use holochain_state::scratch::Scratch;
+use holochain_zome_types::CrdtType;
 use holochain_zome_types::Details;
 use holochain_zome_types::ElementDetails;
 use holochain_zome_types::EntryDetails;
 use holochain_zome_types::EntryDhtStatus;
 use holochain_zome_types::GetOptions;
+use holochain_zome_types::Quorum;
 use holochain_zome_types::ValidationStatus;
 
 async fn assert_can_get<N: HolochainP2pCellT + Clone + Send + 'static>(
@@ -246,6 +248,248 @@ async fn entry_not_authority_or_authoring() {
     assert_can_get(&td_entry, &td_element, &mut cascade, GetOptions::latest()).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn entry_quorum() {
+    observability::test_run().ok();
+
+    // Environments
+    let cache = test_cell_env();
+    let authority = test_cell_env();
+
+    // Data
+    let td_entry = EntryTestData::create();
+    let td_element = ElementTestData::create();
+    fill_db(&authority.env(), td_entry.store_entry_op.clone());
+    fill_db(&authority.env(), td_element.any_store_element_op.clone());
+
+    // Network
+    let network = PassThroughNetwork::authority_for_nothing(vec![authority.env().clone().into()]);
+
+    // Cascade
+    let mut cascade = Cascade::empty().with_network(network, cache.env());
+
+    // - A single authority is enough for a quorum of one
+    assert_can_get(
+        &td_entry,
+        &td_element,
+        &mut cascade,
+        GetOptions::latest().with_quorum(Quorum::new(1)),
+    )
+    .await;
+
+    // - A quorum of two can't be reached by independent authorities yet
+    let r = cascade
+        .dht_get(
+            td_entry.hash.clone().into(),
+            GetOptions::content().with_quorum(Quorum::new(2)),
+        )
+        .await;
+    assert!(matches!(
+        r,
+        Err(holochain_cascade::error::CascadeError::QuorumUnsupported {
+            required: 2,
+            max: 1,
+        })
+    ));
+
+    // - No authority answering isn't a quorum, even though the data is now cached
+    let network = PassThroughNetwork::authority_for_nothing(vec![]);
+    let mut cascade = Cascade::empty().with_network(network, cache.env());
+    let r = cascade
+        .dht_get(
+            td_entry.hash.clone().into(),
+            GetOptions::content().with_quorum(Quorum::new(1)),
+        )
+        .await;
+    assert!(matches!(
+        r,
+        Err(holochain_cascade::error::CascadeError::QuorumNotReached {
+            required: 1,
+            agreeing: 0,
+            responded: 0,
+        })
+    ));
+
+    // - A quorum of zero is rejected
+    let r = cascade
+        .dht_get(
+            td_entry.hash.clone().into(),
+            GetOptions::content().with_quorum(Quorum::new(0)),
+        )
+        .await;
+    assert!(matches!(
+        r,
+        Err(holochain_cascade::error::CascadeError::ZeroQuorum)
+    ));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn get_many_in_order() {
+    observability::test_run().ok();
+
+    // Environments
+    let cache = test_cell_env();
+    let authority = test_cell_env();
+
+    // Data
+    let td_entry = EntryTestData::create();
+    let td_element = ElementTestData::create();
+    let td_missing = ElementTestData::create();
+    fill_db(&authority.env(), td_entry.store_entry_op.clone());
+    fill_db(&authority.env(), td_element.any_store_element_op.clone());
+
+    // Network
+    let network = PassThroughNetwork::authority_for_nothing(vec![authority.env().clone().into()]);
+
+    // Cascade
+    let mut cascade = Cascade::empty().with_network(network, cache.env());
+
+    let hashes = vec![
+        td_element.any_header_hash.clone().into(),
+        td_missing.any_header_hash.clone().into(),
+        td_entry.hash.clone().into(),
+    ];
+
+    // - Results line up with the hashes, including the missing one
+    let r = cascade
+        .dht_get_many(hashes.clone(), GetOptions::latest())
+        .await
+        .unwrap();
+    assert_eq!(r.len(), 3);
+    assert_eq!(
+        *r[0].as_ref().unwrap().header_address(),
+        td_element.any_header_hash
+    );
+    assert!(r[1].is_none());
+    assert_eq!(
+        *r[2].as_ref().unwrap().header_address(),
+        td_entry.create_hash
+    );
+
+    // - Details line up too
+    let r = cascade
+        .get_details_many(hashes, GetOptions::latest())
+        .await
+        .unwrap();
+    assert!(matches!(r[0], Some(Details::Element(_))));
+    assert!(r[1].is_none());
+    assert!(matches!(r[2], Some(Details::Entry(_))));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn merge_updates_follows_to_latest() {
+    observability::test_run().ok();
+
+    // Environments
+    let cache = test_cell_env();
+    let authority = test_cell_env();
+
+    // Data
+    let td_entry = EntryTestData::create();
+    fill_db(&authority.env(), td_entry.store_entry_op.clone());
+    fill_db(&authority.env(), td_entry.update_content_op.clone());
+    fill_db(&authority.env(), td_entry.store_update_entry_op.clone());
+
+    // Network
+    let network = PassThroughNetwork::authority_for_nothing(vec![authority.env().clone().into()]);
+
+    // Cascade
+    let mut cascade = Cascade::empty().with_network(network, cache.env());
+
+    let element = cascade
+        .dht_get(td_entry.hash.clone().into(), GetOptions::latest())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(*element.header_address(), td_entry.create_hash);
+
+    // - An entry that doesn't merge is returned as it is
+    let r = cascade
+        .merge_updates(element.clone(), CrdtType::NoMerge, GetOptions::latest())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(r.heads, vec![element.clone()]);
+
+    // - Otherwise the update is followed
+    let r = cascade
+        .merge_updates(element, CrdtType::LastWriterWins, GetOptions::latest())
+        .await
+        .unwrap()
+        .unwrap();
+    let newest = r.newest().unwrap();
+    assert_eq!(*newest.header_address(), td_entry.update_hash);
+    assert_eq!(
+        newest.header().entry_hash(),
+        Some(&td_entry.update_entry_hash)
+    );
+    assert_eq!(Some(&r.entry), newest.entry().as_option());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn get_many_falls_back_to_single_gets() {
+    observability::test_run().ok();
+
+    // Environments
+    let cache = test_cell_env();
+    let authority = test_cell_env();
+
+    // Data
+    let td_entry = EntryTestData::create();
+    let td_element = ElementTestData::create();
+    fill_db(&authority.env(), td_entry.store_entry_op.clone());
+    fill_db(&authority.env(), td_element.any_store_element_op.clone());
+
+    let hashes: Vec<holo_hash::AnyDhtHash> = vec![
+        td_element.any_header_hash.clone().into(),
+        td_entry.hash.clone().into(),
+    ];
+
+    // What the authorities for each hash answer when asked on their own.
+    let mut pass_through =
+        PassThroughNetwork::authority_for_nothing(vec![authority.env().clone().into()]);
+    let mut answers = std::collections::HashMap::new();
+    for hash in &hashes {
+        let ops = pass_through
+            .get(hash.clone(), Default::default())
+            .await
+            .unwrap();
+        answers.insert(hash.clone(), ops);
+    }
+
+    // Network
+    // The batch goes to peers with narrow arcs
+    // that don't hold any of the hashes.
+    let mut mock = MockHolochainP2pCellT::new();
+    mock.expect_authority_for_hash().returning(|_| Ok(false));
+    mock.expect_get_many()
+        .times(1)
+        .returning(|hashes, _| Ok(vec![vec![]; hashes.len()]));
+    mock.expect_get()
+        .times(2)
+        .returning(move |hash, _| Ok(answers.get(&hash).cloned().unwrap_or_default()));
+    let mock = MockNetwork::new(mock);
+
+    // Cascade
+    let mut cascade = Cascade::empty().with_network(mock, cache.env());
+
+    let r = cascade
+        .dht_get_many(hashes, GetOptions::latest())
+        .await
+        .unwrap();
+    assert_eq!(
+        *r[0]
+            .as_ref()
+            .expect("Failed to get element")
+            .header_address(),
+        td_element.any_header_hash
+    );
+    assert_eq!(
+        *r[1].as_ref().expect("Failed to get entry").header_address(),
+        td_entry.create_hash
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn entry_authoring() {
     observability::test_run().ok();
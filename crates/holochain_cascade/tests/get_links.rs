@@ -1,11 +1,22 @@
 use ghost_actor::dependencies::observability;
+use holo_hash::HeaderHash;
 use holochain_cascade::test_utils::*;
 use holochain_cascade::Cascade;
 use holochain_p2p::MockHolochainP2pCellT;
 use holochain_state::mutations::insert_op_scratch;
 use holochain_state::prelude::test_cell_env;
 use holochain_state::scratch::Scratch;
+use holochain_types::dht_op::DhtOp;
+use holochain_types::dht_op::DhtOpHashed;
 use holochain_types::link::WireLinkOps;
+use holochain_zome_types::CreateLink;
+use holochain_zome_types::DeleteLink;
+use holochain_zome_types::Header;
+use holochain_zome_types::LinkCursor;
+use holochain_zome_types::LinkFilter;
+use holochain_zome_types::Quorum;
+use holochain_zome_types::QuorumReport;
+use holochain_zome_types::Timestamp;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn links_not_authority() {
@@ -125,6 +136,7 @@ async fn links_authoring() {
         Ok(vec![WireLinkOps {
             creates: vec![],
             deletes: vec![],
+            count: None,
         }])
     });
     let mock = MockNetwork::new(mock);
@@ -155,8 +167,201 @@ async fn links_authoring() {
     assert!(r.is_empty());
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn links_paged_not_authority() {
+    observability::test_run().ok();
+
+    // Environments
+    let cache = test_cell_env();
+    let authority = test_cell_env();
+
+    // Data
+    let td = EntryTestData::create();
+    let (signature, create_link) = match td.create_link_op.as_content() {
+        DhtOp::RegisterAddLink(signature, create_link) => (signature.clone(), create_link.clone()),
+        _ => unreachable!(),
+    };
+    fill_db(&authority.env(), td.store_entry_op.clone());
+
+    // - Three links a second apart.
+    let links: Vec<_> = (0..3)
+        .map(|i| {
+            let link = CreateLink {
+                timestamp: Timestamp(1_000 + i, 0),
+                ..create_link.clone()
+            };
+            fill_db(
+                &authority.env(),
+                DhtOpHashed::from_content_sync(DhtOp::RegisterAddLink(
+                    signature.clone(),
+                    link.clone(),
+                )),
+            );
+            (
+                link.timestamp,
+                HeaderHash::with_data_sync(&Header::CreateLink(link)),
+            )
+        })
+        .collect();
+
+    // - The first link is deleted.
+    let (signature, delete_link) = match td.delete_link_op.as_content() {
+        DhtOp::RegisterRemoveLink(signature, delete_link) => {
+            (signature.clone(), delete_link.clone())
+        }
+        _ => unreachable!(),
+    };
+    fill_db(
+        &authority.env(),
+        DhtOpHashed::from_content_sync(DhtOp::RegisterRemoveLink(
+            signature,
+            DeleteLink {
+                link_add_address: links[0].1.clone(),
+                ..delete_link
+            },
+        )),
+    );
+
+    // Network
+    let network = PassThroughNetwork::authority_for_nothing(vec![authority.env().clone().into()]);
+
+    // Cascade
+    let mut cascade = Cascade::empty().with_network(network, cache.env());
+
+    let page = |limit, cursor| holochain_p2p::actor::GetLinksOptions {
+        filter: LinkFilter::default().page(limit, cursor),
+        ..Default::default()
+    };
+
+    // - The first page skips the deleted link.
+    let r = cascade
+        .dht_get_links(td.link_key.clone(), page(1, None))
+        .await
+        .unwrap();
+    assert_eq!(r.len(), 1);
+    assert_eq!(r[0].create_link_hash, links[1].1);
+
+    // - The next page starts after the last link of the first page.
+    let cursor = LinkCursor {
+        timestamp: r[0].timestamp,
+        create_link_hash: r[0].create_link_hash.clone(),
+    };
+    let r = cascade
+        .dht_get_links(td.link_key.clone(), page(1, Some(cursor)))
+        .await
+        .unwrap();
+    assert_eq!(r.len(), 1);
+    assert_eq!(r[0].create_link_hash, links[2].1);
+    assert_eq!(r[0].timestamp, links[2].0);
+
+    // - Only the live links are counted.
+    let count = cascade
+        .dht_count_links(td.link_key.clone(), Default::default())
+        .await
+        .unwrap();
+    assert_eq!(count, 2);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 #[ignore = "todo"]
 async fn test_links_can_match_a_partial_tag() {
     todo!()
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn links_quorum() {
+    observability::test_run().ok();
+
+    // Environments
+    let cache = test_cell_env();
+    let authority_1 = test_cell_env();
+    let authority_2 = test_cell_env();
+
+    // Data
+    let td = EntryTestData::create();
+    for authority in &[&authority_1, &authority_2] {
+        fill_db(&authority.env(), td.store_entry_op.clone());
+        fill_db(&authority.env(), td.create_link_op.clone());
+    }
+
+    // Network
+    let network = PassThroughNetwork::authority_for_nothing(vec![
+        authority_1.env().clone().into(),
+        authority_2.env().clone().into(),
+    ]);
+
+    // Cascade
+    let mut cascade = Cascade::empty().with_network(network, cache.env());
+
+    // - Both authorities agree
+    let r = cascade
+        .dht_get_links_with_quorum(td.link_key.clone(), Default::default(), &Quorum::new(1))
+        .await
+        .unwrap();
+
+    assert_eq!(r.output, td.links);
+    assert_eq!(
+        r.report,
+        QuorumReport {
+            responded: 2,
+            agreeing: 2
+        }
+    );
+    assert!(r.report.is_reached(&Quorum::new(2)));
+
+    // - Only one authority has seen the delete
+    fill_db(&authority_1.env(), td.delete_link_op.clone());
+
+    let r = cascade
+        .dht_get_links_with_quorum(td.link_key.clone(), Default::default(), &Quorum::new(1))
+        .await
+        .unwrap();
+
+    // The answers are merged so the freshest one wins.
+    assert!(r.output.is_empty());
+    assert_eq!(
+        r.report,
+        QuorumReport {
+            responded: 2,
+            agreeing: 1
+        }
+    );
+    assert!(!r.report.is_reached(&Quorum::new(2)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn links_quorum_only_uses_the_authorities_answers() {
+    observability::test_run().ok();
+
+    // Environments
+    let cache = test_cell_env();
+    let authority = test_cell_env();
+
+    // Data
+    let td = EntryTestData::create();
+    fill_db(&authority.env(), td.store_entry_op.clone());
+    fill_db(&authority.env(), td.create_link_op.clone());
+    // The delete is only known locally.
+    fill_db(&cache.env(), td.delete_link_op.clone());
+
+    // Network
+    let network = PassThroughNetwork::authority_for_nothing(vec![authority.env().clone().into()]);
+
+    // Cascade
+    let mut cascade = Cascade::empty().with_network(network, cache.env());
+
+    let r = cascade
+        .dht_get_links_with_quorum(td.link_key.clone(), Default::default(), &Quorum::new(1))
+        .await
+        .unwrap();
+    assert_eq!(r.output, td.links);
+
+    // - A quorum of zero is rejected
+    let r = cascade
+        .dht_get_links_with_quorum(td.link_key.clone(), Default::default(), &Quorum::new(0))
+        .await;
+    assert!(matches!(
+        r,
+        Err(holochain_cascade::error::CascadeError::ZeroQuorum)
+    ));
+}
@@ -3,7 +3,13 @@ use holochain_types::prelude::*;
 use holochain_zome_types::cell::CellId;
 use kitsune_p2p::agent_store::AgentInfoSigned;
 
+use crate::AppStatusFilter;
+use crate::AppStatusInfo;
+use crate::CellArchive;
+use crate::FullStateDump;
 use crate::InstalledAppInfo;
+use crate::InterfaceTlsConfig;
+use crate::ZomeCallLimits;
 
 /// Represents the available conductor functions to call over an Admin interface
 /// and will result in a corresponding [`AdminResponse`] message being sent back over the
@@ -130,6 +136,21 @@ pub enum AdminRequest {
     /// [`AdminResponse::ActiveAppsListed`]: enum.AdminResponse.html#variant.ActiveAppsListed
     /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
     ListActiveApps,
+    /// List every installed App along with its status and the health of each
+    /// of its Cells: chain length, integrated and pending op counts, how many
+    /// peers have been seen and when we last gossiped with one of them.
+    /// Optionally only lists Apps with the status given by `status_filter`.
+    ///
+    /// Will be responded to with an [`AdminResponse::AppsListed`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::AppsListed`]: enum.AdminResponse.html#variant.AppsListed
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    ListApps {
+        /// Only list apps with this status
+        #[serde(default)]
+        status_filter: Option<AppStatusFilter>,
+    },
     /// Changes the `App` specified by argument `installed_app_id` from an inactive state to an active state in the conductor,
     /// meaning that Zome calls can now be made and the `App` will be loaded on a reboot of the conductor.
     /// It is likely to want to call this after calling [`AdminRequest::InstallApp`], since a freshly
@@ -174,6 +195,30 @@ pub enum AdminRequest {
         /// Optional port, use None to let the
         /// OS choose a free port
         port: Option<u16>,
+        /// Only accept zome calls over this interface made as an
+        /// [`AppRequest::SignedZomeCall`], so that callers cannot
+        /// impersonate other agents.
+        /// Defaults to `false`.
+        ///
+        /// [`AppRequest::SignedZomeCall`]: enum.AppRequest.html#variant.SignedZomeCall
+        #[serde(default)]
+        require_signed_zome_calls: bool,
+        /// Optional address to bind the interface to,
+        /// defaults to the loopback address.
+        #[serde(default)]
+        bind_address: Option<std::net::IpAddr>,
+        /// Optionally serve the interface over `wss://`
+        /// with the given certificate and key files.
+        #[serde(default)]
+        tls: Option<InterfaceTlsConfig>,
+        /// Optionally only accept browser connections from these origins.
+        #[serde(default)]
+        allowed_origins: Option<Vec<String>>,
+        /// Optional per connection and per app limits on zome calls
+        /// made over this interface. Defaults to unlimited.
+        /// Limits of 0 are rejected.
+        #[serde(default)]
+        zome_call_limits: ZomeCallLimits,
     },
     /// List all the app interfaces currently attached with [`AttachAppInterface`].
     ListAppInterfaces,
@@ -189,6 +234,62 @@ pub enum AdminRequest {
         /// The `CellId` for which to dump state
         cell_id: Box<CellId>,
     },
+    /// Dump the full state of the `Cell` specified by argument `cell_id`
+    /// as structured data: its source chain, every op it holds and how far
+    /// through validation and integration each one is, its peer store
+    /// and its gossip metrics.
+    ///
+    /// Ops can be fetched in pages by setting `dht_ops_limit` and then passing
+    /// the `dht_ops_cursor` from each response into the next request.
+    /// Only the first page, requested without a cursor, has the source chain,
+    /// peer store and gossip metrics. A `dht_ops_limit` of 0 is rejected.
+    ///
+    /// Will be responded to with an [`AdminResponse::FullStateDumped`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::FullStateDumped`]: enum.AdminResponse.html#variant.FullStateDumped
+    DumpFullState {
+        /// The `CellId` for which to dump state
+        cell_id: Box<CellId>,
+        /// Only return ops after this cursor
+        #[serde(default)]
+        dht_ops_cursor: Option<u64>,
+        /// Return at most this many ops, which must be more than 0
+        #[serde(default)]
+        dht_ops_limit: Option<u32>,
+    },
+    /// Export the source chain of the `Cell` specified by argument `cell_id`
+    /// as a [`CellArchive`] signed by the cell's agent, for backing it up
+    /// or moving it to another device.
+    ///
+    /// Will be responded to with an [`AdminResponse::CellExported`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::CellExported`]: enum.AdminResponse.html#variant.CellExported
+    ExportCell {
+        /// The `CellId` whose chain to export
+        cell_id: Box<CellId>,
+    },
+    /// Restore a source chain from a [`CellArchive`] made by [`AdminRequest::ExportCell`].
+    ///
+    /// The archive's signatures and chain structure are checked, the Dna must
+    /// already be registered and the keystore must hold the agent's key.
+    /// The cell must not already be part of an installed app.
+    /// Once imported, installing an app with this agent and Dna
+    /// uses the restored chain instead of running genesis.
+    ///
+    /// Will be responded to with an [`AdminResponse::CellImported`]
+    /// or an [`AdminResponse::Error`]
+    ///
+    /// [`AdminResponse::Error`]: enum.AppResponse.html#variant.Error
+    /// [`AdminResponse::CellImported`]: enum.AdminResponse.html#variant.CellImported
+    /// [`AdminRequest::ExportCell`]: enum.AdminRequest.html#variant.ExportCell
+    ImportCell {
+        /// The archive to restore
+        archive: Box<CellArchive>,
+    },
     /// Add a list [AgentInfoSigned] to this conductor's peer store.
     /// This is another way of finding peers on a dht.
     ///
@@ -310,6 +411,14 @@ pub enum AdminResponse {
     /// [`AdminRequest::ListActiveApps`]: enum.AdminRequest.html#variant.ListActiveApps
     ActiveAppsListed(Vec<InstalledAppId>),
 
+    /// The succesful response to an [`AdminRequest::ListApps`].
+    ///
+    /// Contains the status and cell health of every installed `App`
+    /// matching the filter
+    ///
+    /// [`AdminRequest::ListApps`]: enum.AdminRequest.html#variant.ListApps
+    AppsListed(Vec<AppStatusInfo>),
+
     /// The succesful response to an [`AdminRequest::AttachAppInterface`].
     ///
     /// `AppInterfaceApi` successfully attached.
@@ -347,6 +456,23 @@ pub enum AdminResponse {
     /// [`AdminRequest::DumpState`]: enum.AdminRequest.html#variant.DumpState
     StateDumped(String),
 
+    /// The succesful response to an [`AdminRequest::DumpFullState`].
+    ///
+    /// [`AdminRequest::DumpFullState`]: enum.AdminRequest.html#variant.DumpFullState
+    FullStateDumped(Box<FullStateDump>),
+
+    /// The succesful response to an [`AdminRequest::ExportCell`].
+    ///
+    /// [`AdminRequest::ExportCell`]: enum.AdminRequest.html#variant.ExportCell
+    CellExported(Box<CellArchive>),
+
+    /// The succesful response to an [`AdminRequest::ImportCell`].
+    ///
+    /// The chain has been restored and can be used by installing an app.
+    ///
+    /// [`AdminRequest::ImportCell`]: enum.AdminRequest.html#variant.ImportCell
+    CellImported,
+
     /// The succesful response to an [`AdminRequest::AddAgentInfo`].
     ///
     /// This means the agent info was successfully added to the peer store.
@@ -383,6 +509,9 @@ pub enum ExternalApiWireError {
     ActivateApp(String),
     /// The zome call is unauthorized
     ZomeCallUnauthorized(String),
+    /// The zome call was rejected because the interface's
+    /// rate or concurrency limits were exceeded
+    ZomeCallLimitExceeded(String),
 }
 
 impl ExternalApiWireError {
@@ -39,6 +39,19 @@ pub enum AppRequest {
     /// DEPRECATED. Use `ZomeCall`.
     ZomeCallInvocation(Box<ZomeCall>),
 
+    /// Call a zome function with a call signed by its provenance.
+    /// App interfaces attached with `require_signed_zome_calls` only
+    /// accept zome calls made this way. See the inner [`SignedZomeCall`]
+    /// struct to understand the data that must be provided.
+    ///
+    /// Will be responded to with an [`AppResponse::ZomeCall`]
+    /// or an [`AppResponse::Error`]
+    ///
+    /// [`SignedZomeCall`]: struct.SignedZomeCall.html
+    /// [`AppResponse::ZomeCall`]: enum.AppResponse.html#variant.ZomeCall
+    /// [`AppResponse::Error`]: enum.AppResponse.html#variant.Error
+    SignedZomeCall(Box<SignedZomeCall>),
+
     /// Update signal subscriptions.
     ///
     /// Is currently unimplemented and will return
@@ -103,12 +116,62 @@ pub struct ZomeCall {
     pub cap: Option<CapSecret>,
     /// The provenance (source) of the call.
     ///
-    /// NB: **This will go away** as soon as Holochain has a way of determining who
-    /// is making this ZomeCall over this interface. Until we do, the caller simply
-    /// provides this data and Holochain trusts them.
+    /// NB: Unless the call is made as a [`SignedZomeCall`], Holochain cannot
+    /// determine who is making this ZomeCall over this interface, so the
+    /// caller simply provides this data and Holochain trusts them.
+    ///
+    /// [`SignedZomeCall`]: struct.SignedZomeCall.html
     pub provenance: AgentPubKey,
 }
 
+/// The number of bytes in a [`ZomeCallNonce`].
+///
+/// [`ZomeCallNonce`]: struct.ZomeCallNonce.html
+pub const ZOME_CALL_NONCE_BYTES: usize = 32;
+
+/// Random bytes making each signed zome call unique, so it cannot be replayed.
+/// Callers must generate a fresh nonce for every call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ZomeCallNonce(pub [u8; ZOME_CALL_NONCE_BYTES]);
+
+holochain_zome_types::fixed_array_serialization!(ZomeCallNonce, ZOME_CALL_NONCE_BYTES);
+
+/// A [`ZomeCall`] signed by the agent key of its provenance.
+///
+/// The signature covers the call together with a nonce and an expiry time,
+/// as returned by [`SignedZomeCall::data_to_sign`]. Holochain rejects the call
+/// if the signature does not verify against the provenance, if the call has
+/// expired or expires too far in the future, or if the nonce has already been
+/// used for a call to the same cell.
+///
+/// [`ZomeCall`]: struct.ZomeCall.html
+/// [`SignedZomeCall::data_to_sign`]: struct.SignedZomeCall.html#method.data_to_sign
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct SignedZomeCall {
+    /// The zome call being made
+    pub call: ZomeCall,
+    /// A fresh random nonce for this call
+    pub nonce: ZomeCallNonce,
+    /// The time after which this call will be rejected
+    pub expires_at: Timestamp,
+    /// The provenance's signature of [`SignedZomeCall::data_to_sign`]
+    ///
+    /// [`SignedZomeCall::data_to_sign`]: struct.SignedZomeCall.html#method.data_to_sign
+    pub signature: Signature,
+}
+
+impl SignedZomeCall {
+    /// The bytes the provenance must sign:
+    /// the msgpack encoding of the tuple `(call, nonce, expires_at)`.
+    pub fn data_to_sign(
+        call: &ZomeCall,
+        nonce: &ZomeCallNonce,
+        expires_at: &Timestamp,
+    ) -> Result<Vec<u8>, SerializedBytesError> {
+        holochain_serialized_bytes::encode(&(call, nonce, expires_at))
+    }
+}
+
 #[allow(missing_docs)]
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case", tag = "type", content = "data")]
@@ -0,0 +1,68 @@
+use holochain_types::prelude::*;
+
+/// Which apps to return from [`AdminRequest::ListApps`]
+///
+/// [`AdminRequest::ListApps`]: enum.AdminRequest.html#variant.ListApps
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppStatusFilter {
+    /// Only apps which are active
+    Active,
+    /// Only apps which are inactive, for any reason
+    Inactive,
+}
+
+impl AppStatusFilter {
+    /// Does an app with this status pass the filter?
+    pub fn matches(&self, status: &InstalledAppStatus) -> bool {
+        matches!(
+            (self, status),
+            (AppStatusFilter::Active, InstalledAppStatus::Active)
+                | (
+                    AppStatusFilter::Inactive,
+                    InstalledAppStatus::Inactive { .. }
+                )
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+/// The status of an installed app and the health of each of its cells,
+/// returned as part of [`AdminResponse::AppsListed`]
+///
+/// [`AdminResponse::AppsListed`]: enum.AdminResponse.html#variant.AppsListed
+pub struct AppStatusInfo {
+    /// The unique identifier for an installed app in this conductor
+    pub installed_app_id: InstalledAppId,
+    /// Whether the app is active, or why it is inactive
+    pub status: InstalledAppStatus,
+    /// Each cell provisioned by this app
+    pub cells: Vec<CellStatusInfo>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+/// A cell belonging to an app in an [`AppStatusInfo`]
+pub struct CellStatusInfo {
+    /// The nick this cell was installed with
+    pub cell_nick: CellNick,
+    /// The id of this cell
+    pub cell_id: CellId,
+    /// The health of this cell, or `None` if it is not running,
+    /// e.g. because the app is inactive.
+    pub health: Option<CellHealth>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+/// A summary of the state of a running cell
+pub struct CellHealth {
+    /// Number of elements on this cell's source chain
+    pub chain_length: usize,
+    /// Ops held by this cell which are integrated, including rejected ones
+    pub integrated_ops: usize,
+    /// Ops held by this cell which are still awaiting validation or integration
+    pub pending_ops: usize,
+    /// Number of other agents in this cell's peer store
+    pub peers_seen: usize,
+    /// The most recent time we gossiped with any of those peers
+    pub last_gossip: Option<Timestamp>,
+}
@@ -0,0 +1,41 @@
+use holochain_serialized_bytes::SerializedBytesError;
+use holochain_types::prelude::*;
+
+/// The version of [`CellArchive`] this conductor produces and accepts
+pub const CELL_ARCHIVE_VERSION: u32 = 1;
+
+/// A backup of an agent's source chain for a single cell,
+/// produced by [`AdminRequest::ExportCell`] and restored with [`AdminRequest::ImportCell`].
+///
+/// The whole archive is signed by the agent so it can't be altered or
+/// attributed to another agent, and each element keeps its original header signature.
+///
+/// [`AdminRequest::ExportCell`]: enum.AdminRequest.html#variant.ExportCell
+/// [`AdminRequest::ImportCell`]: enum.AdminRequest.html#variant.ImportCell
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct CellArchive {
+    /// The format of this archive, see [`CELL_ARCHIVE_VERSION`]
+    pub version: u32,
+    /// The cell whose chain this is
+    pub cell_id: CellId,
+    /// When the archive was made
+    pub exported_at: Timestamp,
+    /// Every element of the chain in order, starting with the `Dna` header.
+    /// Private entries such as cap grants are included.
+    pub elements: Vec<Element>,
+    /// The agent's signature of [`CellArchive::signed_content`]
+    pub signature: Signature,
+}
+
+impl CellArchive {
+    /// Everything in the archive apart from the signature,
+    /// encoded as the bytes the agent signs.
+    pub fn signed_content(
+        version: u32,
+        cell_id: &CellId,
+        exported_at: Timestamp,
+        elements: &[Element],
+    ) -> Result<Vec<u8>, SerializedBytesError> {
+        holochain_serialized_bytes::encode(&(version, cell_id, exported_at, elements))
+    }
+}
@@ -6,21 +6,23 @@ use serde::Deserialize;
 use serde::Serialize;
 
 mod admin_interface_config;
+mod cache_policy_config;
 mod dpki_config;
 #[allow(missing_docs)]
 mod error;
 mod passphrase_service_config;
 pub mod paths;
 //mod logger_config;
-//mod signal_config;
+mod signal_config;
 pub use paths::EnvironmentRootPath;
 
 pub use super::*;
+pub use cache_policy_config::CachePolicyConfig;
 pub use dpki_config::DpkiConfig;
 //pub use logger_config::LoggerConfig;
 pub use error::*;
 pub use passphrase_service_config::PassphraseServiceConfig;
-//pub use signal_config::SignalConfig;
+pub use signal_config::SignalConfig;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -57,11 +59,16 @@ pub struct ConductorConfig {
 
     /// Config options for the network module. Optional.
     pub network: Option<holochain_p2p::kitsune_p2p::KitsuneP2pConfig>,
-    //
-    //
-    // /// Which signals to emit
-    // TODO: it's an open question whether signal config is stateful or not, i.e. whether it belongs here.
-    // pub signals: SignalConfig,
+
+    /// Which system signals to emit over admin and app interfaces.
+    /// All system signals are disabled by default.
+    #[serde(default)]
+    pub signals: SignalConfig,
+
+    /// Limits on the DHT cache of each dna, enforced by a background task.
+    /// If omitted the cache is never garbage collected.
+    #[serde(default)]
+    pub cache_policy: Option<CachePolicyConfig>,
 }
 
 /// helper fnction function to load a `Config` from a yaml string.
@@ -129,6 +136,8 @@ pub mod tests {
                 keystore_path: None,
                 admin_interfaces: None,
                 use_dangerous_test_keystore: false,
+                signals: SignalConfig::default(),
+                cache_policy: None,
             }
         );
     }
@@ -156,6 +165,14 @@ pub mod tests {
           type: websocket
           port: 1234
 
+    signals:
+      lifecycle: true
+      consistency: true
+
+    cache_policy:
+      max_bytes: 1000000
+      max_age_secs: 3600
+
     network:
       bootstrap_service: https://bootstrap-staging.holo.host
       transport_pool:
@@ -218,9 +235,19 @@ pub mod tests {
                 passphrase_service: Some(PassphraseServiceConfig::Cmd),
                 keystore_path: None,
                 admin_interfaces: Some(vec![AdminInterfaceConfig {
-                    driver: InterfaceDriver::Websocket { port: 1234 }
+                    driver: InterfaceDriver::websocket(1234)
                 }]),
                 network: Some(network_config),
+                signals: SignalConfig {
+                    lifecycle: true,
+                    consistency: true,
+                    network: false,
+                },
+                cache_policy: Some(CachePolicyConfig {
+                    max_bytes: Some(1000000),
+                    max_age_secs: Some(3600),
+                    gc_interval_secs: 600,
+                }),
             }
         );
     }
@@ -249,7 +276,61 @@ pub mod tests {
                 keystore_path: Some(PathBuf::from("/path/to/keystore").into()),
                 admin_interfaces: None,
                 use_dangerous_test_keystore: true,
+                signals: SignalConfig::default(),
+                cache_policy: None,
             }
         );
     }
+
+    #[test]
+    fn test_config_websocket_tls() {
+        let yaml = r#"---
+    environment_path: /path/to/env
+
+    admin_interfaces:
+      - driver:
+          type: websocket
+          port: 1234
+          bind_address: 0.0.0.0
+          tls:
+            cert_path: /path/to/cert.pem
+            key_path: /path/to/key.pem
+          allowed_origins:
+            - https://ui.example
+    "#;
+        let result: ConductorConfig = config_from_yaml(yaml).unwrap();
+        assert_eq!(
+            result.admin_interfaces,
+            Some(vec![AdminInterfaceConfig {
+                driver: InterfaceDriver::Websocket {
+                    port: 1234,
+                    bind_address: std::net::Ipv4Addr::UNSPECIFIED.into(),
+                    tls: Some(InterfaceTlsConfig {
+                        cert_path: PathBuf::from("/path/to/cert.pem"),
+                        key_path: PathBuf::from("/path/to/key.pem"),
+                    }),
+                    allowed_origins: Some(vec!["https://ui.example".to_string()]),
+                }
+            }])
+        );
+    }
+
+    #[test]
+    fn test_config_unix_socket() {
+        let yaml = r#"---
+    environment_path: /path/to/env
+
+    admin_interfaces:
+      - driver:
+          type: unix_socket
+          path: /path/to/admin.sock
+    "#;
+        let result: ConductorConfig = config_from_yaml(yaml).unwrap();
+        assert_eq!(
+            result.admin_interfaces,
+            Some(vec![AdminInterfaceConfig {
+                driver: InterfaceDriver::unix_socket("/path/to/admin.sock"),
+            }])
+        );
+    }
 }
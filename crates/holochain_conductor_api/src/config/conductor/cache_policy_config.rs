@@ -0,0 +1,33 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Limits on the DHT cache, which holds data fetched from the network.
+/// When set, the conductor periodically evicts cached data beyond these limits,
+/// starting with the least recently used.
+/// Data this conductor's agents are an authority for is never evicted.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+pub struct CachePolicyConfig {
+    /// The most bytes the cache for each dna may hold.
+    #[serde(default)]
+    pub max_bytes: Option<u64>,
+    /// Evict data which hasn't been fetched or read for this many seconds.
+    #[serde(default)]
+    pub max_age_secs: Option<u64>,
+    /// How often to collect garbage, in seconds.
+    #[serde(default = "default_gc_interval_secs")]
+    pub gc_interval_secs: u64,
+}
+
+fn default_gc_interval_secs() -> u64 {
+    60 * 10
+}
+
+impl Default for CachePolicyConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: None,
+            max_age_secs: None,
+            gc_interval_secs: default_gc_interval_secs(),
+        }
+    }
+}
@@ -1,8 +1,32 @@
-use serde::{self, Deserialize, Serialize};
+use holochain_types::signal::SystemSignalKind;
+use serde::Deserialize;
+use serde::Serialize;
 
-/// Configure which signals to emit, to reduce unwanted signal volume
-#[derive(Deserialize, Serialize, Default, Debug, PartialEq)]
+/// Configure which system signals to emit, to reduce unwanted signal volume.
+/// All categories are disabled by default.
+#[derive(Clone, Deserialize, Serialize, Default, Debug, PartialEq)]
 pub struct SignalConfig {
-    pub trace: bool,
+    /// Emit signals when apps are installed, activated or deactivated,
+    /// and when cells complete genesis.
+    #[serde(default)]
+    pub lifecycle: bool,
+    /// Emit signals as our own authored ops are published, validated,
+    /// integrated and receive validation receipts.
+    #[serde(default)]
     pub consistency: bool,
+    /// Emit signals when new peers join our networks.
+    #[serde(default)]
+    pub network: bool,
+}
+
+impl SignalConfig {
+    /// Should signals of this kind be emitted under this config?
+    pub fn allows(&self, kind: SystemSignalKind) -> bool {
+        match kind {
+            SystemSignalKind::Lifecycle => self.lifecycle,
+            SystemSignalKind::Consistency => self.consistency,
+            SystemSignalKind::Network => self.network,
+            SystemSignalKind::Test => true,
+        }
+    }
 }
@@ -1,11 +1,15 @@
 use serde::Deserialize;
 use serde::Serialize;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
 
 /// Information neeeded to spawn an Admin interface
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
 pub struct AdminInterfaceConfig {
     /// By what means will the interface be exposed?
-    /// Current only option is a local websocket running on a configurable port.
+    /// Either a websocket on a configurable port, or a websocket over
+    /// a unix domain socket.
     pub driver: InterfaceDriver,
     // /// How long will this interface be accessible between authentications?
     // /// TODO: implement once we have authentication
@@ -24,14 +28,120 @@ pub enum InterfaceDriver {
     Websocket {
         /// The port on which to establish the WebsocketListener
         port: u16,
+        /// The address on which to establish the WebsocketListener.
+        /// Defaults to the loopback address, so that the interface is only
+        /// reachable from this machine.
+        #[serde(default = "default_bind_address")]
+        bind_address: IpAddr,
+        /// Serve the interface over `wss://` with this certificate.
+        #[serde(default)]
+        tls: Option<InterfaceTlsConfig>,
+        /// Only accept browser connections from these origins.
+        /// "*" allows any origin, as does leaving this unset.
+        #[serde(default)]
+        allowed_origins: Option<Vec<String>>,
     },
+    /// An interface implemented via Websockets over a unix domain socket.
+    /// The socket file is only accessible by the user running the conductor,
+    /// so unlike a port it can't be reached by every local process.
+    /// Only supported for admin interfaces, and only on unix.
+    UnixSocket {
+        /// The path at which to create the socket file
+        path: PathBuf,
+    },
+}
+
+/// Certificate and key files to serve an interface over tls
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+pub struct InterfaceTlsConfig {
+    /// Path to the PEM encoded certificate (chain)
+    pub cert_path: PathBuf,
+    /// Path to the PEM encoded PKCS #8 private key
+    pub key_path: PathBuf,
+}
+
+/// Limits on the zome calls which can be made over an app interface,
+/// to protect a conductor shared between many clients from overload.
+/// Calls over a limit are rejected with
+/// `ExternalApiWireError::ZomeCallLimitExceeded` rather than queued.
+#[derive(Clone, Copy, Default, Deserialize, Serialize, Debug, PartialEq)]
+pub struct ZomeCallLimits {
+    /// Limits applied to each client connection separately
+    #[serde(default)]
+    pub per_connection: CallLimit,
+    /// Limits applied to all calls into an app,
+    /// shared by every connection to the interface
+    #[serde(default)]
+    pub per_app: CallLimit,
+}
+
+impl ZomeCallLimits {
+    /// Whether either limit would reject every call
+    pub fn is_zero(&self) -> bool {
+        self.per_connection.is_zero() || self.per_app.is_zero()
+    }
+}
+
+/// A limit on the rate and concurrency of zome calls.
+/// Unset fields are unlimited. A limit of 0 would reject every call,
+/// so an app interface with one can't be attached.
+#[derive(Clone, Copy, Default, Deserialize, Serialize, Debug, PartialEq)]
+pub struct CallLimit {
+    /// The sustained number of calls allowed per second.
+    /// Up to this many calls can also be made in a single burst.
+    #[serde(default)]
+    pub calls_per_second: Option<u32>,
+    /// The number of calls which can be running at once.
+    #[serde(default)]
+    pub max_concurrent_calls: Option<u32>,
+}
+
+impl CallLimit {
+    /// Whether this limit allows everything
+    pub fn is_unlimited(&self) -> bool {
+        self.calls_per_second.is_none() && self.max_concurrent_calls.is_none()
+    }
+
+    /// Whether this limit would reject every call
+    pub fn is_zero(&self) -> bool {
+        self.calls_per_second == Some(0) || self.max_concurrent_calls == Some(0)
+    }
+}
+
+fn default_bind_address() -> IpAddr {
+    Ipv4Addr::LOCALHOST.into()
 }
 
 impl InterfaceDriver {
-    /// Get the port for this driver.
-    pub fn port(&self) -> u16 {
+    /// A plain websocket driver on the loopback address
+    pub fn websocket(port: u16) -> Self {
+        InterfaceDriver::Websocket {
+            port,
+            bind_address: default_bind_address(),
+            tls: None,
+            allowed_origins: None,
+        }
+    }
+
+    /// A websocket driver over a unix domain socket
+    pub fn unix_socket(path: impl Into<PathBuf>) -> Self {
+        InterfaceDriver::UnixSocket { path: path.into() }
+    }
+
+    /// Get the port for this driver, if it listens on one.
+    pub fn port(&self) -> Option<u16> {
+        match self {
+            InterfaceDriver::Websocket { port, .. } => Some(*port),
+            InterfaceDriver::UnixSocket { .. } => None,
+        }
+    }
+
+    /// Set the port for this driver, e.g. once the actual port is known
+    /// after binding to port 0. Does nothing for drivers without a port.
+    pub fn set_port(&mut self, new_port: u16) {
         match self {
-            InterfaceDriver::Websocket { port } => *port,
+            InterfaceDriver::Websocket { port, .. } => *port = new_port,
+            InterfaceDriver::UnixSocket { .. } => (),
         }
     }
 }
@@ -2,11 +2,15 @@
 
 mod admin_interface;
 mod app_interface;
+mod app_status;
+mod cell_archive;
 pub mod config;
 pub mod signal_subscription;
 pub mod state_dump;
 
 pub use admin_interface::*;
 pub use app_interface::*;
+pub use app_status::*;
+pub use cell_archive::*;
 pub use config::*;
 pub use state_dump::*;
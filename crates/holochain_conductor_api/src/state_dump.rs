@@ -1,6 +1,12 @@
 use holo_hash::AgentPubKey;
+use holo_hash::AnyDhtHash;
+use holo_hash::DhtOpHash;
 use holo_hash::DnaHash;
+use holo_hash::HeaderHash;
 use holochain_state::source_chain::SourceChainJsonDump;
+use holochain_types::dht_op::DhtOpType;
+use holochain_zome_types::Timestamp;
+use holochain_zome_types::ValidationStatus;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -11,6 +17,75 @@ pub struct JsonDump {
     pub integration_dump: IntegrationStateDump,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+/// The full state of a cell as structured data,
+/// returned by [`AdminRequest::DumpFullState`].
+/// When ops are paged, everything but the ops is
+/// only returned with the first page.
+///
+/// [`AdminRequest::DumpFullState`]: enum.AdminRequest.html#variant.DumpFullState
+pub struct FullStateDump {
+    /// This cell's agent and every other agent in the peer store
+    pub peer_dump: Option<P2pAgentsDump>,
+    /// The elements of this cell's source chain
+    pub source_chain_dump: Option<SourceChainJsonDump>,
+    /// The ops held by this cell and where they are currently
+    pub integration_dump: FullIntegrationStateDump,
+    /// When we last gossiped with each peer in the peer store
+    pub gossip_metrics: Option<Vec<PeerGossipDump>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Like [`IntegrationStateDump`] but with every op
+/// rather than just how many there are.
+pub struct FullIntegrationStateDump {
+    /// Ops in validation limbo awaiting sys
+    /// or app validation.
+    pub validation_limbo: Vec<DhtOpDump>,
+    /// Ops waiting to be integrated.
+    pub integration_limbo: Vec<DhtOpDump>,
+    /// Ops that are integrated.
+    /// This includes rejected.
+    pub integrated: Vec<DhtOpDump>,
+    /// If there may be more ops than were returned,
+    /// pass this back as the `dht_ops_cursor` to get the next page.
+    pub dht_ops_cursor: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+/// A single op held by a cell and its progress
+/// through validation and integration.
+pub struct DhtOpDump {
+    /// The type of op
+    pub op_type: DhtOpType,
+    /// The hash of the op
+    pub hash: DhtOpHash,
+    /// The hash this op is stored at on the dht
+    pub basis: AnyDhtHash,
+    /// The header this op was produced from
+    pub header_hash: HeaderHash,
+    /// Whether this op was authored by this cell
+    pub is_authored: bool,
+    /// The outcome of validation, if it has finished
+    pub validation_status: Option<ValidationStatus>,
+    /// How far through validation this op is, if it has started.
+    /// See `ValidationLimboStatus`.
+    pub validation_stage: Option<u8>,
+    /// How many times we have tried to validate this op
+    pub num_validation_attempts: Option<u32>,
+    /// When this op was integrated
+    pub when_integrated: Option<Timestamp>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Gossip metrics for a single peer
+pub struct PeerGossipDump {
+    /// The peer
+    pub kitsune_agent: kitsune_p2p::KitsuneAgent,
+    /// The last time we completed a gossip round with this peer
+    pub last_sync: Option<Timestamp>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 /// A collection of many cells dumps for easy viewing.
 /// Use display to see a nice printout.
@@ -74,6 +149,35 @@ impl std::fmt::Display for JsonDump {
     }
 }
 
+impl std::fmt::Display for FullStateDump {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let i = &self.integration_dump;
+        writeln!(f, "--- Cell State Dump Summary ---")?;
+        if let Some(peer_dump) = &self.peer_dump {
+            writeln!(
+                f,
+                "Number of other peers in p2p store: {},",
+                peer_dump.peers.len()
+            )?;
+        }
+        if let Some(s) = &self.source_chain_dump {
+            writeln!(
+                f,
+                "Elements authored: {}, Ops published: {}",
+                s.elements.len(),
+                s.published_ops_count
+            )?;
+        }
+        writeln!(
+            f,
+            "Ops in validation limbo: {}, integration limbo: {}, integrated: {}",
+            i.validation_limbo.len(),
+            i.integration_limbo.len(),
+            i.integrated.len()
+        )
+    }
+}
+
 impl std::fmt::Display for IntegrationStateDumps {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "[")?;
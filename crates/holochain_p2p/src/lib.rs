@@ -77,6 +77,14 @@ pub trait HolochainP2pCellT {
         options: actor::GetOptions,
     ) -> actor::HolochainP2pResult<Vec<WireOps>>;
 
+    /// Get entries for many hashes from the DHT at once.
+    /// The result is in the same order as the hashes.
+    async fn get_many(
+        &mut self,
+        dht_hashes: Vec<holo_hash::AnyDhtHash>,
+        options: actor::GetOptions,
+    ) -> actor::HolochainP2pResult<Vec<Vec<WireOps>>>;
+
     /// Get metadata from the DHT.
     async fn get_meta(
         &mut self,
@@ -223,6 +231,23 @@ impl HolochainP2pCellT for HolochainP2pCell {
             .await
     }
 
+    /// Get entries for many hashes from the DHT at once.
+    async fn get_many(
+        &mut self,
+        dht_hashes: Vec<holo_hash::AnyDhtHash>,
+        options: actor::GetOptions,
+    ) -> actor::HolochainP2pResult<Vec<Vec<WireOps>>> {
+        self.sender
+            .get_many(
+                (*self.dna_hash).clone(),
+                (*self.from_agent).clone(),
+                dht_hashes,
+                options,
+            )
+            .instrument(tracing::debug_span!("HolochainP2p::get_many"))
+            .await
+    }
+
     /// Get metadata from the DHT.
     async fn get_meta(
         &mut self,
@@ -8,6 +8,7 @@ use kitsune_p2p::event::MetricDatum;
 use kitsune_p2p::event::MetricKind;
 use kitsune_p2p::event::MetricQuery;
 use kitsune_p2p::event::MetricQueryAnswer;
+use kitsune_p2p::event::ReachabilityDatum;
 
 use crate::types::AgentPubKeyExt;
 
@@ -20,6 +21,14 @@ use kitsune_p2p::agent_store::AgentInfoSigned;
 use std::future::Future;
 use std::time::SystemTime;
 
+/// A batched get sends one request for all the hashes whose basis
+/// falls within the same slice of the DHT of this width,
+/// because those hashes are likely to be held by the same authorities.
+/// The request goes to the authorities for the first hash in the slice,
+/// so narrow arcs may not hold the rest. Callers should get any hash
+/// that comes back without an answer on its own.
+const GET_MANY_SLICE_WIDTH: u32 = u32::MAX / 16 + 1;
+
 macro_rules! timing_trace {
     ($code:block $($rest:tt)*) => {{
         let __start = std::time::Instant::now();
@@ -105,6 +114,18 @@ impl WrapEvtSender {
         )
     }
 
+    fn put_reachability_datum(
+        &self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        datum: ReachabilityDatum,
+    ) -> impl Future<Output = HolochainP2pResult<()>> + 'static + Send {
+        timing_trace!(
+            { self.0.put_reachability_datum(dna_hash, to_agent, datum) },
+            "(hp2p:handle) put_reachability_datum",
+        )
+    }
+
     fn query_metrics(
         &self,
         dna_hash: DnaHash,
@@ -180,6 +201,19 @@ impl WrapEvtSender {
         )
     }
 
+    fn get_many(
+        &self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        dht_hashes: Vec<holo_hash::AnyDhtHash>,
+        options: event::GetOptions,
+    ) -> impl Future<Output = HolochainP2pResult<Vec<WireOps>>> + 'static + Send {
+        timing_trace!(
+            { self.0.get_many(dna_hash, to_agent, dht_hashes, options) },
+            "(hp2p:handle) get_many",
+        )
+    }
+
     fn get_meta(
         &self,
         dna_hash: DnaHash,
@@ -374,6 +408,28 @@ impl HolochainP2pActor {
         .into())
     }
 
+    /// receiving an incoming batched get request from a remote node
+    #[tracing::instrument(skip(self, dna_hash, to_agent, dht_hashes, options), level = "trace")]
+    fn handle_incoming_get_many(
+        &mut self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        dht_hashes: Vec<holo_hash::AnyDhtHash>,
+        options: event::GetOptions,
+    ) -> kitsune_p2p::actor::KitsuneP2pHandlerResult<Vec<u8>> {
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            let res = evt_sender
+                .get_many(dna_hash, to_agent, dht_hashes, options)
+                .await;
+            res.and_then(|r| Ok(holochain_serialized_bytes::encode(&r)?))
+                .map_err(kitsune_p2p::KitsuneP2pError::from)
+        }
+        .instrument(tracing::debug_span!("incoming_get_many_task"))
+        .boxed()
+        .into())
+    }
+
     /// receiving an incoming get_meta request from a remote node
     fn handle_incoming_get_meta(
         &mut self,
@@ -595,6 +651,24 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
         .into())
     }
 
+    fn handle_put_reachability_datum(
+        &mut self,
+        datum: ReachabilityDatum,
+    ) -> kitsune_p2p::event::KitsuneP2pEventHandlerResult<()> {
+        let evt_sender = self.evt_sender.clone();
+        // These dummy values are not used
+        let dna_hash = DnaHash::from_raw_32([0; 32].to_vec());
+        let to_agent = AgentPubKey::from_raw_32([0; 32].to_vec());
+
+        Ok(async move {
+            Ok(evt_sender
+                .put_reachability_datum(dna_hash, to_agent, datum)
+                .await?)
+        }
+        .boxed()
+        .into())
+    }
+
     fn handle_query_metrics(
         &mut self,
         query: kitsune_p2p::event::MetricQuery,
@@ -639,6 +713,10 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
             crate::wire::WireMessage::Get { dht_hash, options } => {
                 self.handle_incoming_get(space, to_agent, dht_hash, options)
             }
+            crate::wire::WireMessage::GetMany {
+                dht_hashes,
+                options,
+            } => self.handle_incoming_get_many(space, to_agent, dht_hashes, options),
             crate::wire::WireMessage::GetMeta { dht_hash, options } => {
                 self.handle_incoming_get_meta(space, to_agent, dht_hash, options)
             }
@@ -994,6 +1072,95 @@ impl HolochainP2pHandler for HolochainP2pActor {
         .into())
     }
 
+    #[tracing::instrument(skip(self), level = "trace")]
+    fn handle_get_many(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: AgentPubKey,
+        dht_hashes: Vec<holo_hash::AnyDhtHash>,
+        options: actor::GetOptions,
+    ) -> HolochainP2pHandlerResult<Vec<Vec<WireOps>>> {
+        let space = dna_hash.into_kitsune();
+        let from_agent = from_agent.into_kitsune();
+        let r_options: event::GetOptions = (&options).into();
+        let total = dht_hashes.len();
+
+        // Group the hashes by the slice of the DHT their basis is in,
+        // remembering where each one goes in the output.
+        let mut groups: std::collections::BTreeMap<u32, Vec<(usize, holo_hash::AnyDhtHash)>> =
+            std::collections::BTreeMap::new();
+        for (i, dht_hash) in dht_hashes.into_iter().enumerate() {
+            groups
+                .entry(dht_hash.get_loc() / GET_MANY_SLICE_WIDTH)
+                .or_default()
+                .push((i, dht_hash));
+        }
+
+        let mut requests = Vec::with_capacity(groups.len());
+        for (_, group) in groups {
+            let (indices, dht_hashes): (Vec<usize>, Vec<holo_hash::AnyDhtHash>) =
+                group.into_iter().unzip();
+            let basis = dht_hashes[0].to_kitsune();
+            let payload =
+                crate::wire::WireMessage::get_many(dht_hashes, r_options.clone()).encode()?;
+            let kitsune_p2p = self.kitsune_p2p.clone();
+            let space = space.clone();
+            let from_agent = from_agent.clone();
+            let options = options.clone();
+            requests.push(async move {
+                let result = kitsune_p2p
+                    .rpc_multi(kitsune_p2p::actor::RpcMulti {
+                        space,
+                        from_agent,
+                        basis,
+                        remote_agent_count: options.remote_agent_count,
+                        timeout_ms: options.timeout_ms,
+                        as_race: options.as_race,
+                        race_timeout_ms: options.race_timeout_ms,
+                        payload,
+                    })
+                    .instrument(tracing::debug_span!("rpc_multi"))
+                    .await?;
+
+                let mut responses = Vec::new();
+                for item in result {
+                    let kitsune_p2p::actor::RpcMultiResponse { agent, response } = item;
+                    let ops: Vec<Option<WireOps>> = holochain_serialized_bytes::decode(&response)?;
+                    // An answer that doesn't line up with
+                    // the request can't be trusted.
+                    if ops.len() != indices.len() {
+                        tracing::warn!(
+                            ?agent,
+                            expected = indices.len(),
+                            got = ops.len(),
+                            "Ignoring get_many response that doesn't match the request"
+                        );
+                        continue;
+                    }
+                    responses.push(ops);
+                }
+                HolochainP2pResult::Ok((indices, responses))
+            });
+        }
+
+        Ok(async move {
+            let mut out = vec![Vec::new(); total];
+            for result in futures::future::join_all(requests).await {
+                let (indices, responses) = result?;
+                for ops in responses {
+                    for (i, op) in indices.iter().zip(ops) {
+                        if let Some(op) = op {
+                            out[*i].push(op);
+                        }
+                    }
+                }
+            }
+            Ok(out)
+        }
+        .boxed()
+        .into())
+    }
+
     #[tracing::instrument(skip(self), level = "trace")]
     fn handle_get_meta(
         &mut self,
@@ -1053,17 +1220,16 @@ impl HolochainP2pHandler for HolochainP2pActor {
 
         let kitsune_p2p = self.kitsune_p2p.clone();
         Ok(async move {
-            // TODO - We're just targeting a single remote node for now
-            //        without doing any pagination / etc...
-            //        Setting up RpcMulti to act like RpcSingle
+            // TODO - Unless asked for more we're just targeting a single
+            //        remote node for now without doing any pagination / etc...
             let result = kitsune_p2p
                 .rpc_multi(kitsune_p2p::actor::RpcMulti {
                     space,
                     from_agent,
                     basis,
-                    remote_agent_count: Some(1),
+                    remote_agent_count: options.remote_agent_count.or(Some(1)),
                     timeout_ms: options.timeout_ms,
-                    as_race: false,
+                    as_race: options.as_race,
                     race_timeout_ms: options.timeout_ms,
                     payload,
                 })
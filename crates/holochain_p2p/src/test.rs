@@ -424,6 +424,103 @@ mod tests {
         tracing::info!("test - end of test - final done.");
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_get_many_workflow() {
+        observability::test_run().ok();
+
+        let (dna, a1, a2, _a3) = test_setup();
+
+        let (p2p, mut evt) = spawn_holochain_p2p(
+            KitsuneP2pConfig::default(),
+            TlsConfig::new_ephemeral().await.unwrap(),
+        )
+        .await
+        .unwrap();
+
+        // Hashes at opposite ends of the DHT are sent as separate requests.
+        let hashes: Vec<holo_hash::AnyDhtHash> = vec![b'e', b'0', b'f']
+            .into_iter()
+            .map(|c| {
+                holo_hash::AnyDhtHash::from_raw_36_and_type(
+                    vec![c; 36],
+                    holo_hash::hash_type::AnyDht::Header,
+                )
+            })
+            .collect();
+        // The authorities don't hold the last hash.
+        let answers: std::collections::HashMap<_, _> = hashes[..2]
+            .iter()
+            .map(|hash| {
+                let ops = WireOps::Element(WireElementOps {
+                    header: Some(Judged::valid(SignedHeader(fixt!(Header), fixt!(Signature)))),
+                    deletes: vec![],
+                    updates: vec![],
+                    entry: None,
+                });
+                (hash.clone(), ops)
+            })
+            .collect();
+
+        let responder = answers.clone();
+        let r_task = tokio::task::spawn(async move {
+            use tokio_stream::StreamExt;
+            while let Some(evt) = evt.next().await {
+                use crate::types::event::HolochainP2pEvent::*;
+                match evt {
+                    GetMany {
+                        respond,
+                        dht_hashes,
+                        ..
+                    } => {
+                        let resp: Vec<_> = dht_hashes
+                            .iter()
+                            .map(|hash| responder.get(hash).cloned())
+                            .collect();
+                        respond.r(Ok(async move { Ok(resp) }.boxed().into()));
+                    }
+                    SignNetworkData { respond, .. } => {
+                        respond.r(Ok(async move { Ok([0; 64].into()) }.boxed().into()));
+                    }
+                    PutAgentInfoSigned { respond, .. } => {
+                        respond.r(Ok(async move { Ok(()) }.boxed().into()));
+                    }
+                    QueryAgentInfoSigned { respond, .. } => {
+                        respond.r(Ok(async move { Ok(vec![]) }.boxed().into()));
+                    }
+                    FetchOpHashesForConstraints { respond, .. } => {
+                        respond.r(Ok(async move { Ok(vec![]) }.boxed().into()));
+                    }
+                    evt => println!("unhandled: {:?}", evt),
+                }
+            }
+        });
+
+        p2p.join(dna.clone(), a1.clone()).await.unwrap();
+        p2p.join(dna.clone(), a2.clone()).await.unwrap();
+
+        let res = p2p
+            .get_many(dna, a1, hashes.clone(), actor::GetOptions::default())
+            .await
+            .unwrap();
+
+        // Every agent answered for every hash they hold, in the order asked.
+        assert_eq!(hashes.len(), res.len());
+        for (hash, ops) in hashes.iter().zip(res) {
+            match answers.get(hash) {
+                Some(answer) => {
+                    assert_eq!(2, ops.len());
+                    for op in ops {
+                        assert_eq!(&op, answer);
+                    }
+                }
+                None => assert!(ops.is_empty()),
+            }
+        }
+
+        p2p.ghost_actor_shutdown().await.unwrap();
+        r_task.await.unwrap();
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_get_links_workflow() {
         let (dna, a1, a2, _) = test_setup();
@@ -446,6 +543,7 @@ mod tests {
                 fixt!(Signature),
                 ValidationStatus::Valid,
             )],
+            count: None,
         };
 
         let test_1_clone = test_1.clone();
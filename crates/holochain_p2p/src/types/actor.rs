@@ -78,8 +78,26 @@ impl Default for GetOptions {
 }
 
 impl From<holochain_zome_types::entry::GetOptions> for GetOptions {
-    fn from(_: holochain_zome_types::entry::GetOptions) -> Self {
-        Self::default()
+    fn from(options: holochain_zome_types::entry::GetOptions) -> Self {
+        match options.quorum {
+            Some(quorum) => (&quorum).into(),
+            None => Self::default(),
+        }
+    }
+}
+
+/// How many more authorities than a quorum needs are asked,
+/// so one slow or offline authority doesn't sink the read.
+pub const QUORUM_EXTRA_AUTHORITIES: u8 = 2;
+
+impl From<&Quorum> for GetOptions {
+    fn from(quorum: &Quorum) -> Self {
+        Self {
+            remote_agent_count: Some(quorum.min_agreeing.saturating_add(QUORUM_EXTRA_AUTHORITIES)),
+            timeout_ms: quorum.timeout_ms,
+            as_race: quorum.merge == MergePolicy::FirstAnswer,
+            ..Default::default()
+        }
     }
 }
 
@@ -136,17 +154,52 @@ impl Default for GetMetaOptions {
 /// Fields tagged with `[Remote]` are controls that will be forwarded to the
 /// remote agent processing this `GetLinks` request.
 pub struct GetLinksOptions {
+    /// [Network]
+    /// How many remote nodes should we make requests of / aggregate.
+    /// Set to `None` to ask a single node.
+    pub remote_agent_count: Option<u8>,
+
     /// [Network]
     /// Timeout to await responses for aggregation.
     /// Set to `None` for a default "best-effort".
     /// Note - if all requests time-out you will receive an empty result,
     /// not a timeout error.
     pub timeout_ms: Option<u64>,
+
+    /// [Network]
+    /// If `true` return the results received before the timeout
+    /// instead of waiting for all the remote nodes.
+    pub as_race: bool,
+
+    /// [Remote]
+    /// Only return the links matching this filter.
+    pub filter: LinkFilter,
+
+    /// [Remote]
+    /// Only return the number of live links instead of the link ops.
+    pub count_only: bool,
 }
 
 impl Default for GetLinksOptions {
     fn default() -> Self {
-        Self { timeout_ms: None }
+        Self {
+            remote_agent_count: None,
+            timeout_ms: None,
+            as_race: false,
+            filter: LinkFilter::default(),
+            count_only: false,
+        }
+    }
+}
+
+impl From<&Quorum> for GetLinksOptions {
+    fn from(quorum: &Quorum) -> Self {
+        Self {
+            remote_agent_count: Some(quorum.min_agreeing.saturating_add(QUORUM_EXTRA_AUTHORITIES)),
+            timeout_ms: quorum.timeout_ms,
+            as_race: quorum.merge == MergePolicy::FirstAnswer,
+            ..Default::default()
+        }
     }
 }
 
@@ -235,6 +288,19 @@ ghost_actor::ghost_chan! {
             options: GetOptions,
         ) -> Vec<WireOps>;
 
+        /// Get entries for many hashes from the DHT in as few requests as possible.
+        /// Hashes which are likely to share authorities are sent together.
+        /// The outer vec is in the same order as the hashes and holds
+        /// the answer of each authority that responded for that hash.
+        /// An authority that was asked may not hold every hash in its group,
+        /// so a hash with no answers should be got on its own.
+        fn get_many(
+            dna_hash: DnaHash,
+            from_agent: AgentPubKey,
+            dht_hashes: Vec<holo_hash::AnyDhtHash>,
+            options: GetOptions,
+        ) -> Vec<Vec<WireOps>>;
+
         /// Get metadata from the DHT.
         fn get_meta(
             dna_hash: DnaHash,
@@ -7,7 +7,7 @@ use crate::*;
 use holochain_zome_types::signature::Signature;
 use kitsune_p2p::{
     agent_store::AgentInfoSigned,
-    event::{MetricKind, MetricQuery, MetricQueryAnswer},
+    event::{MetricKind, MetricQuery, MetricQueryAnswer, ReachabilityDatum},
 };
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
@@ -64,12 +64,22 @@ impl From<&actor::GetMetaOptions> for GetMetaOptions {
 }
 
 /// GetLinks options help control how the get is processed at various levels.
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
-pub struct GetLinksOptions {}
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct GetLinksOptions {
+    /// Only return the links matching this filter.
+    #[serde(default)]
+    pub filter: LinkFilter,
+    /// Only return the number of live links instead of the link ops.
+    #[serde(default)]
+    pub count_only: bool,
+}
 
 impl From<&actor::GetLinksOptions> for GetLinksOptions {
-    fn from(_a: &actor::GetLinksOptions) -> Self {
-        Self {}
+    fn from(a: &actor::GetLinksOptions) -> Self {
+        Self {
+            filter: a.filter.clone(),
+            count_only: a.count_only,
+        }
     }
 }
 
@@ -121,6 +131,9 @@ ghost_actor::ghost_chan! {
         /// We need to store some metric data on behalf of kitsune.
         fn put_metric_datum(dna_hash: DnaHash, to_agent: AgentPubKey, agent: AgentPubKey, metric: MetricKind, timestamp: SystemTime) -> ();
 
+        /// We need to store the outcome of an attempt to reach a remote agent.
+        fn put_reachability_datum(dna_hash: DnaHash, to_agent: AgentPubKey, datum: ReachabilityDatum) -> ();
+
         /// We need to provide some metric data to kitsune.
         fn query_metrics(dna_hash: DnaHash, to_agent: AgentPubKey, query: MetricQuery) -> MetricQueryAnswer;
 
@@ -162,6 +175,16 @@ ghost_actor::ghost_chan! {
             options: GetOptions,
         ) -> WireOps;
 
+        /// A remote node is requesting entry data for many hashes from us.
+        /// The ops are returned in the same order as the hashes,
+        /// with `None` for any hash we couldn't get.
+        fn get_many(
+            dna_hash: DnaHash,
+            to_agent: AgentPubKey,
+            dht_hashes: Vec<holo_hash::AnyDhtHash>,
+            options: GetOptions,
+        ) -> Vec<Option<WireOps>>;
+
         /// A remote node is requesting metadata from us.
         fn get_meta(
             dna_hash: DnaHash,
@@ -230,6 +253,7 @@ macro_rules! match_p2p_evt {
             HolochainP2pEvent::Publish { $i, .. } => { $($t)* }
             HolochainP2pEvent::GetValidationPackage { $i, .. } => { $($t)* }
             HolochainP2pEvent::Get { $i, .. } => { $($t)* }
+            HolochainP2pEvent::GetMany { $i, .. } => { $($t)* }
             HolochainP2pEvent::GetMeta { $i, .. } => { $($t)* }
             HolochainP2pEvent::GetLinks { $i, .. } => { $($t)* }
             HolochainP2pEvent::GetAgentActivity { $i, .. } => { $($t)* }
@@ -242,6 +266,7 @@ macro_rules! match_p2p_evt {
             HolochainP2pEvent::QueryAgentInfoSigned { $i, .. } => { $($t)* }
 
             HolochainP2pEvent::PutMetricDatum { $i, .. } => { $($t)* }
+            HolochainP2pEvent::PutReachabilityDatum { $i, .. } => { $($t)* }
             HolochainP2pEvent::QueryMetrics { $i, .. } => { $($t)* }
         }
     };
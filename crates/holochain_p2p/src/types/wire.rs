@@ -42,6 +42,10 @@ pub(crate) enum WireMessage {
         dht_hash: holo_hash::AnyDhtHash,
         options: event::GetOptions,
     },
+    GetMany {
+        dht_hashes: Vec<holo_hash::AnyDhtHash>,
+        options: event::GetOptions,
+    },
     GetMeta {
         dht_hash: holo_hash::AnyDhtHash,
         options: event::GetMetaOptions,
@@ -105,6 +109,16 @@ impl WireMessage {
         Self::Get { dht_hash, options }
     }
 
+    pub fn get_many(
+        dht_hashes: Vec<holo_hash::AnyDhtHash>,
+        options: event::GetOptions,
+    ) -> WireMessage {
+        Self::GetMany {
+            dht_hashes,
+            options,
+        }
+    }
+
     pub fn get_meta(
         dht_hash: holo_hash::AnyDhtHash,
         options: event::GetMetaOptions,
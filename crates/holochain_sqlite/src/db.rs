@@ -6,6 +6,7 @@ use crate::{
 };
 use derive_more::Into;
 use futures::Future;
+use holo_hash::AnyDhtHash;
 use holo_hash::DnaHash;
 use holochain_zome_types::cell::CellId;
 use kitsune_p2p::KitsuneSpace;
@@ -14,7 +15,10 @@ use rusqlite::*;
 use shrinkwraprs::Shrinkwrap;
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
 use tokio::{
     sync::{OwnedSemaphorePermit, Semaphore},
     task,
@@ -35,6 +39,7 @@ pub struct DbRead {
     connection_pool: ConnectionPool,
     write_semaphore: Arc<Semaphore>,
     read_semaphore: Arc<Semaphore>,
+    pending_access: Arc<Mutex<HashSet<AnyDhtHash>>>,
 }
 
 impl DbRead {
@@ -57,6 +62,12 @@ impl DbRead {
         &self.path
     }
 
+    /// Bases read from this database which haven't been
+    /// stamped as accessed yet. Only used by the cache.
+    pub fn pending_access(&self) -> &Mutex<HashSet<AnyDhtHash>> {
+        &self.pending_access
+    }
+
     /// Get a connection from the pool.
     /// TODO: We should eventually swap this for an async solution.
     fn connection_pooled(&self) -> DatabaseResult<PConn> {
@@ -110,6 +121,7 @@ impl DbWrite {
             kind,
             path,
             connection_pool: pool,
+            pending_access: Default::default(),
         }))
     }
 
@@ -3,7 +3,9 @@ use crate::{
     sql::sql_p2p_metrics,
 };
 use holochain_zome_types::prelude::*;
-use kitsune_p2p::event::{MetricKind, MetricQuery, MetricQueryAnswer};
+use kitsune_p2p::event::{
+    MetricKind, MetricQuery, MetricQueryAnswer, PeerReachability, ReachabilityDatum,
+};
 use kitsune_p2p::*;
 use rusqlite::*;
 use std::{
@@ -50,6 +52,89 @@ pub fn put_metric_datum(
     Ok(())
 }
 
+/// Fold a reachability datum into the stored aggregate for its agent
+pub fn put_reachability_datum(
+    txn: &mut Transaction,
+    datum: ReachabilityDatum,
+) -> DatabaseResult<()> {
+    let mut reachability = query_reachability(txn, datum.agent.clone())?
+        .unwrap_or_else(|| PeerReachability::new(datum.agent.clone()));
+    reachability.apply(&datum);
+    let agent_bytes: &[u8] = datum.agent.as_ref();
+    let rtt_ewma_us = reachability.rtt_ewma.map(|rtt| rtt.as_micros() as i64);
+    let last_failure = match reachability.last_failure {
+        Some(t) => Some(time_to_micros(t)?),
+        None => None,
+    };
+    txn.execute(
+        sql_p2p_metrics::INSERT_REACHABILITY,
+        named_params! {
+            ":agent": agent_bytes,
+            ":rtt_ewma_us": rtt_ewma_us,
+            ":success_count": reachability.success_count as i64,
+            ":failure_count": reachability.failure_count as i64,
+            ":consecutive_failures": reachability.consecutive_failures,
+            ":bytes_out": reachability.bytes_out as i64,
+            ":bytes_in": reachability.bytes_in as i64,
+            ":last_failure": last_failure,
+        },
+    )?;
+    Ok(())
+}
+
+/// Get the aggregated reachability of a single agent, if we have any data
+fn query_reachability(
+    txn: &Transaction,
+    agent: Arc<KitsuneAgent>,
+) -> DatabaseResult<Option<PeerReachability>> {
+    let agent_bytes: &[u8] = agent.as_ref();
+    let row = txn
+        .query_row(
+            sql_p2p_metrics::QUERY_REACHABILITY,
+            named_params! {
+                ":agent": agent_bytes,
+            },
+            |row| {
+                Ok((
+                    row.get::<_, Option<i64>>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, u32>(3)?,
+                    row.get::<_, i64>(4)?,
+                    row.get::<_, i64>(5)?,
+                    row.get::<_, Option<i64>>(6)?,
+                ))
+            },
+        )
+        .optional()?;
+    let (
+        rtt_ewma_us,
+        success_count,
+        failure_count,
+        consecutive_failures,
+        bytes_out,
+        bytes_in,
+        last_failure,
+    ) = match row {
+        Some(row) => row,
+        None => return Ok(None),
+    };
+    let last_failure = match last_failure {
+        Some(t) => Some(time_from_micros(t)?),
+        None => None,
+    };
+    Ok(Some(PeerReachability {
+        agent,
+        rtt_ewma: rtt_ewma_us.map(|us| Duration::from_micros(us as u64)),
+        success_count: success_count as u64,
+        failure_count: failure_count as u64,
+        consecutive_failures,
+        bytes_out: bytes_out as u64,
+        bytes_in: bytes_in as u64,
+        last_failure,
+    }))
+}
+
 /// Query the p2p_metrics database in a variety of ways
 pub fn query_metrics(
     txn: &mut Transaction,
@@ -88,5 +173,14 @@ pub fn query_metrics(
                 .optional()?;
             MetricQueryAnswer::Oldest(agent_bytes.map(KitsuneAgent::new).map(Arc::new))
         }
+        MetricQuery::Reachability { agents } => {
+            let mut out = Vec::with_capacity(agents.len());
+            for agent in agents {
+                if let Some(reachability) = query_reachability(txn, agent)? {
+                    out.push(reachability);
+                }
+            }
+            MetricQueryAnswer::Reachability(out)
+        }
     })
 }
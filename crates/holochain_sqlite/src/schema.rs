@@ -6,10 +6,13 @@ use crate::sql::*;
 
 pub static SCHEMA_CELL: Lazy<Schema> = Lazy::new(|| {
     let migration_0 = Migration::initial(sql_cell::SCHEMA);
+    let migration_1 = Migration::forward(sql_cell::MIGRATION_1);
+    let migration_2 = Migration::forward(sql_cell::MIGRATION_2);
+    let migration_3 = Migration::forward(sql_cell::MIGRATION_3);
 
     Schema {
-        current_index: 0,
-        migrations: vec![migration_0],
+        current_index: 3,
+        migrations: vec![migration_0, migration_1, migration_2, migration_3],
     }
 });
 
@@ -70,8 +73,16 @@ impl Schema {
             .unwrap_or_else(|| "<no name>".to_string());
 
         if user_version == 0 {
-            // database just needs to be created / initialized
-            self.migrations[self.current_index].initialize(conn)?;
+            // database just needs to be created / initialized.
+            // Databases from before they were versioned are also at 0
+            // and hold the initial schema, which is safe to run again,
+            // so both are brought up to date by the same migrations.
+            self.migrations[0].initialize(conn)?;
+            for migration in &self.migrations[1..self.current_index + 1] {
+                migration.run(conn)?;
+            }
+            let new_user_version = (self.current_index + 1) as u16;
+            conn.pragma_update(None, "user_version", &new_user_version)?;
             tracing::info!("database initialized: {}", db_kind);
             return Ok(());
         } else {
@@ -79,7 +90,7 @@ impl Schema {
             match current_index.cmp(&self.current_index) {
                 std::cmp::Ordering::Less => {
                     // run forward migrations
-                    for v in current_index..self.current_index + 1 {
+                    for v in current_index + 1..self.current_index + 1 {
                         self.migrations[v].run(conn)?;
                     }
                     // set the DB user_version so that next time we don't run
@@ -111,7 +122,7 @@ impl Schema {
 
 pub struct Migration {
     schema: Sql,
-    _forward: Sql,
+    forward: Sql,
     _backward: Option<Sql>,
 }
 
@@ -119,7 +130,17 @@ impl Migration {
     pub fn initial(schema: &str) -> Self {
         Self {
             schema: schema.into(),
-            _forward: "".into(),
+            forward: "".into(),
+            _backward: None,
+        }
+    }
+
+    /// A migration from the previous version, which is run on
+    /// top of the initial schema and every migration before it.
+    pub fn forward(forward: &str) -> Self {
+        Self {
+            schema: "".into(),
+            forward: forward.into(),
             _backward: None,
         }
     }
@@ -129,9 +150,72 @@ impl Migration {
         Ok(())
     }
 
-    pub fn run(&self, _conn: &mut Connection) -> rusqlite::Result<()> {
-        unimplemented!("actual migrations not yet implemented")
+    pub fn run(&self, conn: &mut Connection) -> rusqlite::Result<()> {
+        conn.execute_batch(&self.forward)?;
+        Ok(())
     }
 }
 
 type Sql = String;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn has_last_access_ms(conn: &Connection) -> bool {
+        let mut stmt = conn.prepare("PRAGMA table_info(DhtOp)").unwrap();
+        let columns: Vec<String> = stmt
+            .query_map(rusqlite::NO_PARAMS, |row| row.get(1))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        columns.iter().any(|c| c == "last_access_ms")
+    }
+
+    #[test]
+    fn cell_schema_migrates_unversioned_databases() {
+        // - A database created before databases were versioned
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(sql_cell::SCHEMA).unwrap();
+        assert!(!has_last_access_ms(&conn));
+
+        SCHEMA_CELL.initialize(&mut conn, None).unwrap();
+        assert!(has_last_access_ms(&conn));
+        let user_version: u16 = conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(user_version, 4);
+
+        // - Opening it again doesn't run the migrations twice
+        SCHEMA_CELL.initialize(&mut conn, None).unwrap();
+    }
+
+    #[test]
+    fn cell_schema_recomputes_authored_timestamps() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(sql_cell::SCHEMA).unwrap();
+        // - An op authored at 2.345678999s, stored with the nanoseconds read as milliseconds
+        conn.execute(
+            "
+            INSERT INTO DhtOp
+            (hash, type, basis_hash, header_hash, is_authored, require_receipt,
+            storage_center_loc, authored_timestamp_ms, op_order, blob)
+            VALUES
+            (x'00', 'StoreElement', x'01', x'02', 1, 0,
+            0, 345680999, '700000000000000000020345678999', x'03')
+            ",
+            rusqlite::NO_PARAMS,
+        )
+        .unwrap();
+
+        SCHEMA_CELL.initialize(&mut conn, None).unwrap();
+        let authored_timestamp_ms: i64 = conn
+            .query_row(
+                "SELECT authored_timestamp_ms FROM DhtOp",
+                rusqlite::NO_PARAMS,
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(authored_timestamp_ms, 2_345);
+    }
+}
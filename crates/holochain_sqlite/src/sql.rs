@@ -1,5 +1,8 @@
 pub mod sql_cell {
     pub(crate) const SCHEMA: &str = include_str!("sql/cell/schema.sql");
+    pub(crate) const MIGRATION_1: &str = include_str!("sql/cell/migration_1.sql");
+    pub(crate) const MIGRATION_2: &str = include_str!("sql/cell/migration_2.sql");
+    pub(crate) const MIGRATION_3: &str = include_str!("sql/cell/migration_3.sql");
     pub const UPDATE_INTEGRATE_OPS: &str = include_str!("sql/cell/update_integrate_ops.sql");
     pub const FETCH_OP_HASHES_FULL: &str = include_str!("sql/cell/fetch_op_hashes_full.sql");
     pub const FETCH_OP_HASHES_SINGLE: &str = include_str!("sql/cell/fetch_op_hashes_single.sql");
@@ -28,4 +31,8 @@ pub(crate) mod sql_p2p_metrics {
     pub(crate) const INSERT: &str = include_str!("sql/p2p_metrics/insert.sql");
     pub(crate) const QUERY_LAST_SYNC: &str = include_str!("sql/p2p_metrics/query_last_sync.sql");
     pub(crate) const QUERY_OLDEST: &str = include_str!("sql/p2p_metrics/query_oldest.sql");
+    pub(crate) const INSERT_REACHABILITY: &str =
+        include_str!("sql/p2p_metrics/insert_reachability.sql");
+    pub(crate) const QUERY_REACHABILITY: &str =
+        include_str!("sql/p2p_metrics/query_reachability.sql");
 }
@@ -0,0 +1,4 @@
+-- Only used in the cache: when each op was last fetched or read,
+-- so the least recently used data can be garbage collected.
+ALTER TABLE DhtOp ADD COLUMN last_access_ms INTEGER NULL; -- UNIX TIMESTAMP MILLISECONDS
+CREATE INDEX IF NOT EXISTS DhtOp_last_access_ms_idx ON DhtOp ( last_access_ms );
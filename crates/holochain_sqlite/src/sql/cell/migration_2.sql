@@ -0,0 +1,7 @@
+-- authored_timestamp_ms used to be computed with the nanoseconds of the
+-- header timestamp read as milliseconds. Recompute it from the op_order,
+-- which holds the same timestamp as zero padded seconds and nanoseconds
+-- after the one digit op type. See OpOrder.
+UPDATE DhtOp SET authored_timestamp_ms =
+    MAX(CAST(substr(op_order, 2, 19) AS INTEGER), 0) * 1000
+    + CAST(substr(op_order, 21, 10) AS INTEGER) / 1000000;
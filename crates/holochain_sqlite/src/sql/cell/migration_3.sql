@@ -0,0 +1,7 @@
+-- Nonces of the signed zome calls made to this cell which haven't expired,
+-- so a call can't be replayed, even after the conductor restarts.
+CREATE TABLE IF NOT EXISTS ZomeCallNonce (
+    nonce            BLOB           PRIMARY KEY,
+    expires_at_ms    INTEGER        NOT NULL  -- UNIX TIMESTAMP MILLISECONDS
+);
+CREATE INDEX IF NOT EXISTS ZomeCallNonce_expires_at_ms_idx ON ZomeCallNonce ( expires_at_ms );
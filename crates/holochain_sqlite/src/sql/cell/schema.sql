@@ -142,4 +142,18 @@ CREATE TABLE IF NOT EXISTS ValidationReceipt (
     op_hash         BLOB           NOT NULL,
     blob            BLOB           NOT NULL,
     FOREIGN KEY(op_hash) REFERENCES DhtOp(hash)
-);
\ No newline at end of file
+);
+-- Remote signals which could not be delivered and are
+-- waiting to be retried in the background.
+CREATE TABLE IF NOT EXISTS RemoteSignalOutbox (
+    id              INTEGER        PRIMARY KEY,
+    agent           BLOB           NOT NULL,
+    zome_name       TEXT           NOT NULL,
+    signal          BLOB           NOT NULL,
+    timeout_ms      INTEGER        NULL,
+    expires_at_ms   INTEGER        NOT NULL,
+    attempts        INTEGER        NOT NULL,
+    next_attempt_at_ms INTEGER     NOT NULL
+);
+CREATE INDEX IF NOT EXISTS RemoteSignalOutbox_expires_at_ms_idx ON RemoteSignalOutbox ( expires_at_ms );
+CREATE INDEX IF NOT EXISTS RemoteSignalOutbox_next_attempt_at_ms_idx ON RemoteSignalOutbox ( next_attempt_at_ms );
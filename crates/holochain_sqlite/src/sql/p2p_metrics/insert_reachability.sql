@@ -0,0 +1,8 @@
+INSERT INTO p2p_reachability (
+  agent, rtt_ewma_us, success_count, failure_count,
+  consecutive_failures, bytes_out, bytes_in, last_failure
+) VALUES (
+  :agent, :rtt_ewma_us, :success_count, :failure_count,
+  :consecutive_failures, :bytes_out, :bytes_in, :last_failure
+)
+;
@@ -0,0 +1,4 @@
+SELECT rtt_ewma_us, success_count, failure_count,
+  consecutive_failures, bytes_out, bytes_in, last_failure
+FROM p2p_reachability
+WHERE agent = :agent
@@ -5,3 +5,15 @@ CREATE TABLE IF NOT EXISTS p2p_metrics (
   moment         INTEGER NOT NULL,
   PRIMARY KEY (agent, kind, moment)
 );
+
+-- aggregated reachability of remote peers
+CREATE TABLE IF NOT EXISTS p2p_reachability (
+  agent                 BLOB      PRIMARY KEY ON CONFLICT REPLACE,
+  rtt_ewma_us           INTEGER   NULL,
+  success_count         INTEGER   NOT NULL,
+  failure_count         INTEGER   NOT NULL,
+  consecutive_failures  INTEGER   NOT NULL,
+  bytes_out             INTEGER   NOT NULL,
+  bytes_in              INTEGER   NOT NULL,
+  last_failure          INTEGER   NULL
+);
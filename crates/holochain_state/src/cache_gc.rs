@@ -0,0 +1,351 @@
+//! Garbage collection for the DHT cache database.
+//!
+//! Ops fetched from the network are kept in a cache which is shared by
+//! every cell of a dna. Each time the cascade fetches an op its
+//! `last_access_ms` is stamped, and [`evict`] removes the least recently
+//! used ops once the cache grows too big or they haven't been used for too long.
+//! Reads served from the cache are only noted in memory with [`note_access`]
+//! and stamped in one batch when the garbage collector next runs.
+//! Ops whose basis is in one of our own storage arcs are never evicted,
+//! because we are an authority for them.
+
+use std::time::Duration;
+
+use holo_hash::AnyDhtHash;
+use holo_hash::DhtOpHash;
+use holochain_p2p::dht_arc::DhtArc;
+use holochain_sqlite::db::DbRead;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::Transaction;
+use holochain_sqlite::rusqlite::NO_PARAMS;
+use holochain_types::Timestamp;
+
+use crate::mutations::StateMutationResult;
+
+/// How much data a cache may hold.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CacheLimits {
+    /// Evict the least recently used ops while the cache holds more than this many bytes.
+    pub max_bytes: Option<u64>,
+    /// Evict ops which haven't been fetched or read for this long.
+    pub max_age: Option<Duration>,
+}
+
+/// How many bases may be waiting to be stamped for one cache
+/// before they are written without waiting for the garbage collector.
+pub const MAX_PENDING_ACCESS: usize = 1000;
+
+/// Note that this basis was read from `cache`.
+/// Returns true once [`MAX_PENDING_ACCESS`] bases are waiting,
+/// in which case the caller should [`take_pending_access`] and stamp them.
+pub fn note_access(cache: &DbRead, basis: AnyDhtHash) -> bool {
+    let mut pending = cache.pending_access().lock();
+    pending.insert(basis);
+    pending.len() >= MAX_PENDING_ACCESS
+}
+
+/// Take every basis noted for `cache` since the last call.
+pub fn take_pending_access(cache: &DbRead) -> Vec<AnyDhtHash> {
+    cache.pending_access().lock().drain().collect()
+}
+
+/// Stamp every cached op for these bases as accessed at `now`.
+pub fn record_access(
+    txn: &mut Transaction,
+    bases: &[AnyDhtHash],
+    now: Timestamp,
+) -> StateMutationResult<()> {
+    let mut stmt =
+        txn.prepare_cached("UPDATE DhtOp SET last_access_ms = :now WHERE basis_hash = :basis")?;
+    for basis in bases {
+        stmt.execute(named_params! {
+            ":now": now.to_sql_ms_lossy(),
+            ":basis": basis,
+        })?;
+    }
+    Ok(())
+}
+
+/// The number of bytes of op, header and entry data in the cache.
+pub fn cache_size(txn: &Transaction) -> StateMutationResult<u64> {
+    let size: i64 = txn.query_row(
+        "
+        SELECT
+        (SELECT COALESCE(SUM(LENGTH(blob)), 0) FROM DhtOp)
+        + (SELECT COALESCE(SUM(LENGTH(blob)), 0) FROM Header)
+        + (SELECT COALESCE(SUM(LENGTH(blob)), 0) FROM Entry)
+        ",
+        NO_PARAMS,
+        |row| row.get(0),
+    )?;
+    Ok(size as u64)
+}
+
+/// Evict ops from the cache until it is within its limits,
+/// keeping any op whose basis is within one of the `authority_arcs`.
+/// Returns how many ops were evicted.
+pub fn evict(
+    txn: &mut Transaction,
+    limits: &CacheLimits,
+    now: Timestamp,
+    authority_arcs: &[DhtArc],
+) -> StateMutationResult<usize> {
+    let mut candidates = evictable_ops(txn, authority_arcs)?.into_iter().peekable();
+    let mut evicted = 0;
+
+    if let Some(max_age) = limits.max_age {
+        let cutoff = now.to_sql_ms_lossy() - max_age.as_millis() as i64;
+        while let Some(op) = candidates.peek() {
+            if op.last_access_ms >= cutoff {
+                break;
+            }
+            delete_op(txn, &op.hash)?;
+            evicted += 1;
+            candidates.next();
+        }
+        delete_orphans(txn)?;
+    }
+
+    if let Some(max_bytes) = limits.max_bytes {
+        let mut size = cache_size(txn)?;
+        while size > max_bytes && candidates.peek().is_some() {
+            // The size of an op includes its header and entry, which may
+            // be shared with other ops, so check the real size after each batch.
+            let mut to_free = (size - max_bytes) as i64;
+            while to_free > 0 {
+                match candidates.next() {
+                    Some(op) => {
+                        delete_op(txn, &op.hash)?;
+                        evicted += 1;
+                        to_free -= op.size;
+                    }
+                    None => break,
+                }
+            }
+            delete_orphans(txn)?;
+            size = cache_size(txn)?;
+        }
+    }
+    Ok(evicted)
+}
+
+struct EvictableOp {
+    hash: DhtOpHash,
+    last_access_ms: i64,
+    size: i64,
+}
+
+/// All ops we aren't an authority for, least recently used first.
+fn evictable_ops(
+    txn: &Transaction,
+    authority_arcs: &[DhtArc],
+) -> StateMutationResult<Vec<EvictableOp>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT
+        DhtOp.hash, DhtOp.storage_center_loc,
+        COALESCE(DhtOp.last_access_ms, DhtOp.when_integrated * 1000, 0) AS last_access_ms,
+        LENGTH(DhtOp.blob) + LENGTH(Header.blob) + COALESCE(LENGTH(Entry.blob), 0) AS size
+        FROM DhtOp
+        JOIN Header ON DhtOp.header_hash = Header.hash
+        LEFT JOIN Entry ON Header.entry_hash = Entry.hash
+        ORDER BY last_access_ms ASC
+        ",
+    )?;
+    let ops = stmt
+        .query_map(NO_PARAMS, |row| {
+            let loc: u32 = row.get("storage_center_loc")?;
+            Ok((
+                loc,
+                EvictableOp {
+                    hash: row.get("hash")?,
+                    last_access_ms: row.get("last_access_ms")?,
+                    size: row.get("size")?,
+                },
+            ))
+        })?
+        .filter_map(|r| match r {
+            Ok((loc, op)) => {
+                if authority_arcs.iter().any(|arc| arc.contains(loc)) {
+                    None
+                } else {
+                    Some(Ok(op))
+                }
+            }
+            Err(e) => Some(Err(e)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ops)
+}
+
+fn delete_op(txn: &mut Transaction, hash: &DhtOpHash) -> StateMutationResult<()> {
+    txn.execute(
+        "DELETE FROM ValidationReceipt WHERE op_hash = :hash",
+        named_params! {
+            ":hash": hash,
+        },
+    )?;
+    txn.execute(
+        "DELETE FROM DhtOp WHERE hash = :hash",
+        named_params! {
+            ":hash": hash,
+        },
+    )?;
+    Ok(())
+}
+
+/// Remove headers which no op refers to anymore,
+/// then entries which no header refers to.
+fn delete_orphans(txn: &mut Transaction) -> StateMutationResult<()> {
+    txn.execute(
+        "DELETE FROM Header WHERE hash NOT IN (SELECT header_hash FROM DhtOp)",
+        NO_PARAMS,
+    )?;
+    txn.execute(
+        "
+        DELETE FROM Entry WHERE hash NOT IN
+        (SELECT entry_hash FROM Header WHERE entry_hash IS NOT NULL)
+        ",
+        NO_PARAMS,
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutations::insert_op;
+    use crate::mutations::set_last_access_time;
+    use fixt::prelude::*;
+    use holo_hash::HasHash;
+    use holochain_sqlite::db::ReadManager;
+    use holochain_sqlite::db::WriteManager;
+    use holochain_types::dht_op::DhtOp;
+    use holochain_types::dht_op::DhtOpHashed;
+    use holochain_types::timestamp;
+    use holochain_zome_types::fixt::*;
+
+    fn op_count(txn: &Transaction) -> usize {
+        txn.query_row("SELECT COUNT(*) FROM DhtOp", NO_PARAMS, |row| {
+            row.get::<_, i64>(0)
+        })
+        .unwrap() as usize
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cache_eviction() -> StateMutationResult<()> {
+        observability::test_run().ok();
+
+        let test_env = crate::test_utils::test_cache_env();
+        let env = test_env.env();
+        let now = timestamp::now();
+        let hour_ago: Timestamp = (now - Duration::from_secs(60 * 60)).unwrap();
+
+        let ops: Vec<DhtOpHashed> = (0..4)
+            .map(|_| {
+                DhtOpHashed::from_content_sync(DhtOp::RegisterAgentActivity(
+                    fixt!(Signature),
+                    fixt!(Header),
+                ))
+            })
+            .collect();
+        let stale = ops[0].as_hash().clone();
+        let protected = ops[1].clone();
+        let protected_arc = DhtArc::new(protected.dht_basis().get_loc(), 1);
+
+        env.conn().unwrap().with_commit_sync(|txn| {
+            for (i, op) in ops.iter().enumerate() {
+                let hash = op.as_hash().clone();
+                insert_op(txn, op.clone(), false)?;
+                let accessed = if i < 2 {
+                    hour_ago
+                } else {
+                    (now + Duration::from_secs(i as u64)).unwrap()
+                };
+                set_last_access_time(txn, hash, accessed)?;
+            }
+            StateMutationResult::Ok(())
+        })?;
+
+        // - Ops older than the max age are evicted, unless we are an authority for them
+        let limits = CacheLimits {
+            max_bytes: None,
+            max_age: Some(Duration::from_secs(60)),
+        };
+        let evicted = env
+            .conn()
+            .unwrap()
+            .with_commit_sync(|txn| evict(txn, &limits, now, &[protected_arc]))?;
+        assert_eq!(evicted, 1);
+        let mut g = env.conn().unwrap();
+        g.with_reader_test(|reader| {
+            assert_eq!(op_count(&reader), 3);
+            let stale_left: i64 = reader
+                .query_row(
+                    "SELECT COUNT(*) FROM DhtOp WHERE hash = :hash",
+                    named_params! { ":hash": stale },
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(stale_left, 0);
+            // The evicted op's header went with it
+            let headers: i64 = reader
+                .query_row("SELECT COUNT(*) FROM Header", NO_PARAMS, |row| row.get(0))
+                .unwrap();
+            assert_eq!(headers, 3);
+        });
+
+        // - Reading a basis refreshes its ops
+        env.conn().unwrap().with_commit_sync(|txn| {
+            record_access(
+                txn,
+                &[ops[2].dht_basis()],
+                (now + Duration::from_secs(60)).unwrap(),
+            )
+        })?;
+
+        // - Over the size limit the least recently used ops go first
+        let limits = CacheLimits {
+            max_bytes: Some(1),
+            max_age: None,
+        };
+        let evicted = env
+            .conn()
+            .unwrap()
+            .with_commit_sync(|txn| evict(txn, &limits, now, &[protected_arc]))?;
+        assert_eq!(evicted, 2);
+        g.with_reader_test(|reader| {
+            assert_eq!(op_count(&reader), 1);
+            let left: DhtOpHash = reader
+                .query_row("SELECT hash FROM DhtOp", NO_PARAMS, |row| row.get(0))
+                .unwrap();
+            assert_eq!(&left, protected.as_hash());
+        });
+        Ok(())
+    }
+
+    #[test]
+    fn pending_access_is_taken_once() {
+        let test_env = crate::test_utils::test_cache_env();
+        let env = test_env.env();
+        let cache = &env;
+        let a: AnyDhtHash = fixt!(EntryHash).into();
+        let b: AnyDhtHash = fixt!(EntryHash).into();
+        assert!(!note_access(cache, a.clone()));
+        assert!(!note_access(cache, b.clone()));
+        assert!(!note_access(cache, a.clone()));
+
+        let mut taken = take_pending_access(cache);
+        taken.sort();
+        let mut expected = vec![a, b];
+        expected.sort();
+        assert_eq!(taken, expected);
+        assert!(take_pending_access(cache).is_empty());
+
+        for _ in 0..MAX_PENDING_ACCESS - 1 {
+            assert!(!note_access(cache, fixt!(EntryHash).into()));
+        }
+        assert!(note_access(cache, fixt!(EntryHash).into()));
+        assert_eq!(take_pending_access(cache).len(), MAX_PENDING_ACCESS);
+    }
+}
@@ -34,6 +34,7 @@
 
 #![allow(deprecated)]
 
+pub mod cache_gc;
 #[allow(missing_docs)]
 pub mod dna_def;
 pub mod entry_def;
@@ -42,6 +43,7 @@ pub mod mutations;
 #[allow(missing_docs)]
 pub mod prelude;
 pub mod query;
+pub mod remote_signal_outbox;
 pub mod scratch;
 #[allow(missing_docs)]
 pub mod source_chain;
@@ -50,6 +52,7 @@ pub mod validation_receipts;
 #[allow(missing_docs)]
 pub mod wasm;
 pub mod workspace;
+pub mod zome_call_nonce;
 
 #[allow(missing_docs)]
 #[cfg(any(test, feature = "test_utils"))]
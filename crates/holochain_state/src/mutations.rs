@@ -280,6 +280,18 @@ pub fn set_last_publish_time(
     Ok(())
 }
 
+/// Set when a cached [`DhtOp`] was last fetched or read.
+pub fn set_last_access_time(
+    txn: &mut Transaction,
+    hash: DhtOpHash,
+    time: Timestamp,
+) -> StateMutationResult<()> {
+    dht_op_update!(txn, hash, {
+        "last_access_ms": time.to_sql_ms_lossy(),
+    })?;
+    Ok(())
+}
+
 /// Set the receipt count for a [`DhtOp`].
 pub fn set_receipt_count(
     txn: &mut Transaction,
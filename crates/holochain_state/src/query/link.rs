@@ -9,6 +9,7 @@ use super::*;
 #[derive(Debug, Clone)]
 pub struct GetLinksQuery {
     query: LinksQuery,
+    filter: LinkFilter,
 }
 
 #[derive(Debug, Clone)]
@@ -136,20 +137,29 @@ impl GetLinksQuery {
     pub fn new(base: EntryHash, zome_id: ZomeId, tag: Option<LinkTag>) -> Self {
         Self {
             query: LinksQuery::new(base, zome_id, tag),
+            filter: LinkFilter::default(),
         }
     }
 
     pub fn base(base: EntryHash, zome_id: ZomeId) -> Self {
         Self {
             query: LinksQuery::base(base, zome_id),
+            filter: LinkFilter::default(),
         }
     }
 
     pub fn tag(base: EntryHash, zome_id: ZomeId, tag: LinkTag) -> Self {
         Self {
             query: LinksQuery::tag(base, zome_id, tag),
+            filter: LinkFilter::default(),
         }
     }
+
+    /// Only return the links matching this filter.
+    pub fn with_filter(mut self, filter: LinkFilter) -> Self {
+        self.filter = filter;
+        self
+    }
 }
 
 impl Query for GetLinksQuery {
@@ -205,7 +215,11 @@ impl Query for GetLinksQuery {
         let (header, hash) = header.into_inner();
         match header {
             Header::CreateLink(create_link) => {
-                if !state.deletes.contains(&hash) {
+                if !state.deletes.contains(&hash)
+                    && self
+                        .filter
+                        .matches(&create_link.author, create_link.timestamp, &hash)
+                {
                     state
                         .creates
                         .insert(hash, link_from_header(Header::CreateLink(create_link))?);
@@ -225,7 +239,14 @@ impl Query for GetLinksQuery {
         S: Store,
     {
         let mut links: Self::Output = state.creates.into_iter().map(|(_, v)| v).collect();
-        links.sort_by_key(|l| l.timestamp);
+        if self.filter.is_paged() {
+            links.sort_by(|a, b| a.page_key().cmp(&b.page_key()));
+            if let Some(limit) = self.filter.limit {
+                links.truncate(limit as usize);
+            }
+        } else {
+            links.sort_by_key(|l| l.timestamp);
+        }
         Ok(links)
     }
 }
@@ -0,0 +1,265 @@
+//! Module for remote signals which are waiting to be redelivered.
+//!
+//! When a remote signal sent with receipts can't be delivered and
+//! an outbox ttl was requested, it is kept here until it is either
+//! delivered in the background or it expires.
+//! Signals are staged in the scratch and written when the source chain is
+//! flushed, so a zome call which fails leaves nothing in the outbox.
+//! Failed attempts are retried with an exponential backoff.
+
+use std::time::Duration;
+
+use holo_hash::AgentPubKey;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::Transaction;
+use holochain_types::Timestamp;
+use holochain_zome_types::zome::ZomeName;
+use holochain_zome_types::zome_io::ExternIO;
+
+use crate::mutations::StateMutationResult;
+use crate::prelude::from_blob;
+use crate::prelude::to_blob;
+use crate::prelude::StateQueryResult;
+
+/// The delay before the first retry of a signal.
+pub const MIN_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// The longest delay between two retries of a signal.
+pub const MAX_RETRY_DELAY: Duration = Duration::from_secs(10 * 60);
+
+/// A remote signal waiting in the outbox.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutboxSignal {
+    /// Id of this signal in the outbox.
+    pub id: u64,
+    /// The agent this signal is for.
+    pub agent: AgentPubKey,
+    /// The zome which sent the signal and will receive it remotely.
+    pub zome_name: ZomeName,
+    /// The signal payload.
+    pub signal: ExternIO,
+    /// The timeout for each delivery attempt.
+    pub timeout: Option<Duration>,
+    /// When this signal is dropped from the outbox.
+    pub expires_at: Timestamp,
+    /// How many times delivery has been attempted.
+    pub attempts: u32,
+}
+
+/// How long to wait before retrying a signal which
+/// has failed `attempts` times.
+/// The delay doubles with each attempt, from [`MIN_RETRY_DELAY`]
+/// up to [`MAX_RETRY_DELAY`].
+pub fn retry_delay(attempts: u32) -> Duration {
+    let doublings = attempts.saturating_sub(1).min(16);
+    (MIN_RETRY_DELAY * 2u32.pow(doublings)).min(MAX_RETRY_DELAY)
+}
+
+fn next_attempt_at_ms(attempts: u32, now: Timestamp) -> i64 {
+    now.to_sql_ms_lossy()
+        .saturating_add(retry_delay(attempts).as_millis() as i64)
+}
+
+/// Add a signal to the outbox, to be retried once the
+/// backoff for its attempts so far has passed.
+pub fn insert(
+    txn: &mut Transaction,
+    signal: &OutboxSignal,
+    now: Timestamp,
+) -> StateMutationResult<()> {
+    txn.execute(
+        "
+        INSERT INTO RemoteSignalOutbox
+        (id, agent, zome_name, signal, timeout_ms, expires_at_ms, attempts, next_attempt_at_ms)
+        VALUES
+        (:id, :agent, :zome_name, :signal, :timeout_ms, :expires_at_ms, :attempts, :next_attempt_at_ms)
+        ",
+        named_params! {
+            ":id": signal.id as i64,
+            ":agent": signal.agent,
+            ":zome_name": signal.zome_name.0,
+            ":signal": to_blob(&signal.signal)?,
+            ":timeout_ms": signal.timeout.map(|t| t.as_millis() as i64),
+            ":expires_at_ms": signal.expires_at.to_sql_ms_lossy(),
+            ":attempts": signal.attempts,
+            ":next_attempt_at_ms": next_attempt_at_ms(signal.attempts, now),
+        },
+    )?;
+    Ok(())
+}
+
+/// List the signals in the outbox which haven't expired
+/// and are due to be retried by `now`.
+pub fn list_pending(txn: &Transaction, now: Timestamp) -> StateQueryResult<Vec<OutboxSignal>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT id, agent, zome_name, signal, timeout_ms, expires_at_ms, attempts
+        FROM RemoteSignalOutbox
+        WHERE expires_at_ms > :now
+        AND next_attempt_at_ms <= :now
+        ORDER BY next_attempt_at_ms
+        ",
+    )?;
+    let iter = stmt.query_and_then(
+        named_params! {
+            ":now": now.to_sql_ms_lossy(),
+        },
+        |row| {
+            let timeout_ms: Option<i64> = row.get("timeout_ms")?;
+            let expires_at_ms: i64 = row.get("expires_at_ms")?;
+            let id: i64 = row.get("id")?;
+            Ok(OutboxSignal {
+                id: id as u64,
+                agent: row.get("agent")?,
+                zome_name: ZomeName(row.get("zome_name")?),
+                signal: from_blob(row.get("signal")?)?,
+                timeout: timeout_ms.map(|t| Duration::from_millis(t as u64)),
+                expires_at: Timestamp(
+                    expires_at_ms / 1000,
+                    (expires_at_ms % 1000 * 1_000_000) as u32,
+                ),
+                attempts: row.get("attempts")?,
+            })
+        },
+    )?;
+    iter.collect()
+}
+
+/// Record another failed delivery attempt for a signal
+/// and push its next attempt back.
+pub fn record_attempt(
+    txn: &mut Transaction,
+    id: u64,
+    attempts: u32,
+    now: Timestamp,
+) -> StateMutationResult<()> {
+    txn.execute(
+        "
+        UPDATE RemoteSignalOutbox
+        SET attempts = :attempts, next_attempt_at_ms = :next_attempt_at_ms
+        WHERE id = :id
+        ",
+        named_params! {
+            ":id": id as i64,
+            ":attempts": attempts,
+            ":next_attempt_at_ms": next_attempt_at_ms(attempts, now),
+        },
+    )?;
+    Ok(())
+}
+
+/// Remove a signal from the outbox, usually because it was delivered.
+pub fn remove(txn: &mut Transaction, id: u64) -> StateMutationResult<()> {
+    txn.execute(
+        "DELETE FROM RemoteSignalOutbox WHERE id = :id",
+        named_params! {
+            ":id": id as i64,
+        },
+    )?;
+    Ok(())
+}
+
+/// Remove all signals which expired by `now`,
+/// returning how many were removed.
+pub fn delete_expired(txn: &mut Transaction, now: Timestamp) -> StateMutationResult<usize> {
+    Ok(txn.execute(
+        "DELETE FROM RemoteSignalOutbox WHERE expires_at_ms <= :now",
+        named_params! {
+            ":now": now.to_sql_ms_lossy(),
+        },
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fixt::prelude::*;
+    use holo_hash::fixt::AgentPubKeyFixturator;
+    use holochain_sqlite::db::ReadManager;
+    use holochain_sqlite::db::WriteManager;
+    use holochain_types::timestamp;
+
+    #[test]
+    fn retry_delay_backs_off() {
+        assert_eq!(retry_delay(1), MIN_RETRY_DELAY);
+        assert_eq!(retry_delay(2), MIN_RETRY_DELAY * 2);
+        assert_eq!(retry_delay(3), MIN_RETRY_DELAY * 4);
+        assert_eq!(retry_delay(100), MAX_RETRY_DELAY);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_remote_signal_outbox_insert_list_expire() -> StateMutationResult<()> {
+        observability::test_run().ok();
+
+        let test_env = crate::test_utils::test_cell_env();
+        let env = test_env.env();
+
+        let now = timestamp::now();
+        // Whole seconds so the expiry survives the round trip through the database.
+        let later = Timestamp(now.0 + 60, 0);
+        let at = |delay: Duration| (now + delay).unwrap();
+        let kept = OutboxSignal {
+            id: 1,
+            agent: fixt!(AgentPubKey),
+            zome_name: "zome1".into(),
+            signal: ExternIO::encode("hi").unwrap(),
+            timeout: Some(Duration::from_millis(500)),
+            expires_at: later,
+            attempts: 1,
+        };
+        let expired = OutboxSignal {
+            id: 2,
+            timeout: None,
+            expires_at: now,
+            ..kept.clone()
+        };
+
+        env.conn().unwrap().with_commit_sync(|txn| {
+            insert(txn, &kept, now)?;
+            insert(txn, &expired, now)
+        })?;
+
+        let removed = env
+            .conn()
+            .unwrap()
+            .with_commit_sync(|txn| delete_expired(txn, now))?;
+        assert_eq!(1, removed);
+
+        let mut g = env.conn().unwrap();
+        g.with_reader_test(|reader| {
+            // - Nothing is retried until the backoff has passed
+            assert!(list_pending(&reader, now).unwrap().is_empty());
+            assert_eq!(
+                list_pending(&reader, at(MIN_RETRY_DELAY)).unwrap(),
+                vec![kept.clone()]
+            );
+        });
+
+        // - Another failed attempt doubles the wait
+        env.conn()
+            .unwrap()
+            .with_commit_sync(|txn| record_attempt(txn, kept.id, 2, now))?;
+        g.with_reader_test(|reader| {
+            assert!(list_pending(&reader, at(MIN_RETRY_DELAY))
+                .unwrap()
+                .is_empty());
+            assert_eq!(
+                list_pending(&reader, at(MIN_RETRY_DELAY * 2)).unwrap(),
+                vec![OutboxSignal {
+                    attempts: 2,
+                    ..kept.clone()
+                }]
+            );
+        });
+
+        env.conn()
+            .unwrap()
+            .with_commit_sync(|txn| remove(txn, kept.id))?;
+        g.with_reader_test(|reader| {
+            assert!(list_pending(&reader, at(MAX_RETRY_DELAY))
+                .unwrap()
+                .is_empty());
+        });
+        Ok(())
+    }
+}
@@ -19,6 +19,7 @@ use crate::prelude::StoresIter;
 use crate::query::StateQueryResult;
 use crate::query::StmtIter;
 use crate::query::Store;
+use crate::remote_signal_outbox::OutboxSignal;
 
 /// The "scratch" is an in-memory space to stage Headers to be committed at the
 /// end of the CallZome workflow.
@@ -32,6 +33,7 @@ use crate::query::Store;
 pub struct Scratch {
     headers: Vec<SignedHeaderHashed>,
     entries: HashMap<EntryHash, Arc<Entry>>,
+    remote_signals: Vec<OutboxSignal>,
 }
 
 #[derive(Debug, Clone)]
@@ -56,6 +58,11 @@ impl Scratch {
         self.entries.insert(hash, Arc::new(entry));
     }
 
+    /// Stage a remote signal to be written to the outbox with the headers.
+    pub fn add_remote_signal(&mut self, signal: OutboxSignal) {
+        self.remote_signals.push(signal);
+    }
+
     pub fn as_filter(&self, f: impl Fn(&SignedHeaderHashed) -> bool) -> FilteredScratch {
         let headers = self.headers.iter().filter(|&t| f(t)).cloned().collect();
         FilteredScratch { headers }
@@ -73,6 +80,11 @@ impl Scratch {
         self.headers.is_empty()
     }
 
+    /// Whether there is nothing at all to write, including remote signals.
+    pub fn has_nothing_to_flush(&self) -> bool {
+        self.headers.is_empty() && self.remote_signals.is_empty()
+    }
+
     pub fn headers(&self) -> impl Iterator<Item = &SignedHeaderHashed> {
         self.headers.iter()
     }
@@ -129,6 +141,11 @@ impl Scratch {
         self.headers.drain(..)
     }
 
+    /// Drain out all the remote signals.
+    pub fn drain_remote_signals(&mut self) -> impl Iterator<Item = OutboxSignal> + '_ {
+        self.remote_signals.drain(..)
+    }
+
     /// Drain out all the entries.
     pub fn drain_entries(&mut self) -> impl Iterator<Item = EntryHashed> + '_ {
         self.entries.drain().map(|(hash, entry)| {
@@ -34,6 +34,7 @@ use holochain_zome_types::SignedHeaderHashed;
 
 use crate::prelude::*;
 use crate::query::chain_head::ChainHeadQuery;
+use crate::remote_signal_outbox;
 use crate::scratch::Scratch;
 use crate::scratch::SyncScratch;
 use holochain_serialized_bytes::prelude::*;
@@ -53,13 +54,13 @@ pub struct SourceChain {
 
 // TODO fix this.  We shouldn't really have nil values but this would
 // show if the database is corrupted and doesn't have an element
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SourceChainJsonDump {
     pub elements: Vec<SourceChainJsonElement>,
     pub published_ops_count: usize,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SourceChainJsonElement {
     pub signature: Signature,
     pub header_address: HeaderHash,
@@ -278,13 +279,10 @@ impl SourceChain {
 
     /// Query Headers in the source chain.
     /// This returns a Vec rather than an iterator because it is intended to be
-    /// used by the `query` host function, which crosses the wasm boundary
-    // FIXME: This query needs to be tested.
+    /// used by the `query` host function, which crosses the wasm boundary.
+    /// The filter, ordering and limit are applied in the database,
+    /// so asking for the last few elements of a long chain is cheap.
     pub async fn query(&self, query: QueryFilter) -> SourceChainResult<Vec<Element>> {
-        let (range_min, range_max) = match query.sequence_range.clone() {
-            Some(range) => (Some(range.start), Some(range.end)),
-            None => (None, None),
-        };
         let author = self.author.clone();
         let mut elements = self
             .vault
@@ -293,7 +291,7 @@ impl SourceChain {
                 move |txn| {
                     let mut sql = "
                 SELECT DISTINCT
-                Header.hash AS header_hash, Header.blob AS header_blob
+                Header.hash AS header_hash, Header.blob AS header_blob, Header.seq AS header_seq
             "
                     .to_string();
                     if query.include_entries {
@@ -325,41 +323,82 @@ impl SourceChain {
                 AND
                 (:range_min IS NULL OR Header.seq >= :range_min)
                 AND
-                (:range_max IS NULL OR Header.seq < :range_max)
-                AND
-                (:entry_type IS NULL OR Header.entry_type = :entry_type)
-                AND
-                (:header_type IS NULL OR Header.type = :header_type)
+                (:range_max IS NULL OR Header.seq <= :range_max)
                 ",
                     );
+
+                    let author: &AgentPubKey = author.as_ref();
+                    let range_min = query.sequence_range.min();
+                    let range_max = query.sequence_range.max();
+                    let limit = query.limit;
+                    let entry_type_params = in_list_params("entry_type", &query.entry_type);
+                    let header_type_params = in_list_params("header_type", &query.header_type);
+                    let entry_hash_params = in_list_params("entry_hash", &query.entry_hashes);
+                    let mut params: Vec<Params> = named_params! {
+                        ":author": author,
+                        ":range_min": range_min,
+                        ":range_max": range_max,
+                    }
+                    .to_vec();
+
+                    for (column, list) in &[
+                        ("Header.entry_type", &entry_type_params),
+                        ("Header.type", &header_type_params),
+                        ("Header.entry_hash", &entry_hash_params),
+                    ] {
+                        if let Some(list) = list {
+                            let names: Vec<&str> = list.iter().map(|(p, _)| p.as_str()).collect();
+                            sql.push_str(&format!(
+                                "
+                AND
+                {} IN ({})
+                ",
+                                column,
+                                names.join(", ")
+                            ));
+                            params.extend(list.iter().map(|(p, v)| (p.as_str(), *v)));
+                        }
+                    }
+
+                    sql.push_str(if query.order_descending {
+                        "
+                ORDER BY Header.seq DESC
+                "
+                    } else {
+                        "
+                ORDER BY Header.seq ASC
+                "
+                    });
+                    if limit.is_some() {
+                        sql.push_str(
+                            "
+                LIMIT :limit
+                ",
+                        );
+                        params.extend(named_params! {
+                            ":limit": limit,
+                        });
+                    }
+
                     let mut stmt = txn.prepare(&sql)?;
                     let elements = stmt
-                        .query_and_then(
-                            named_params! {
-                                ":author": author.as_ref(),
-                                ":range_min": range_min,
-                                ":range_max": range_max,
-                                ":entry_type": query.entry_type,
-                                ":header_type": query.header_type,
-                            },
-                            |row| {
-                                let header = from_blob::<SignedHeader>(row.get("header_blob")?)?;
-                                let SignedHeader(header, signature) = header;
-                                let hash: HeaderHash = row.get("header_hash")?;
-                                let header = HeaderHashed::with_pre_hashed(header, hash);
-                                let shh = SignedHeaderHashed::with_presigned(header, signature);
-                                let entry = if query.include_entries {
-                                    let entry: Option<Vec<u8>> = row.get("entry_blob")?;
-                                    match entry {
-                                        Some(entry) => Some(from_blob::<Entry>(entry)?),
-                                        None => None,
-                                    }
-                                } else {
-                                    None
-                                };
-                                StateQueryResult::Ok(Element::new(shh, entry))
-                            },
-                        )?
+                        .query_and_then(&params[..], |row| {
+                            let header = from_blob::<SignedHeader>(row.get("header_blob")?)?;
+                            let SignedHeader(header, signature) = header;
+                            let hash: HeaderHash = row.get("header_hash")?;
+                            let header = HeaderHashed::with_pre_hashed(header, hash);
+                            let shh = SignedHeaderHashed::with_presigned(header, signature);
+                            let entry = if query.include_entries {
+                                let entry: Option<Vec<u8>> = row.get("entry_blob")?;
+                                match entry {
+                                    Some(entry) => Some(from_blob::<Entry>(entry)?),
+                                    None => None,
+                                }
+                            } else {
+                                None
+                            };
+                            StateQueryResult::Ok(Element::new(shh, entry))
+                        })?
                         .collect::<StateQueryResult<Vec<_>>>();
                     elements
                 }
@@ -378,15 +417,17 @@ impl SourceChain {
                 });
             elements.extend(scratch_iter);
         })?;
+        // The scratch is newer than the database, so order and limit them together.
+        query.order_and_limit(&mut elements, |el| el.header().header_seq());
         Ok(elements)
     }
 
     pub async fn flush(&self) -> SourceChainResult<()> {
         // Nothing to write
-        if self.scratch.apply(|s| s.is_empty())? {
+        if self.scratch.apply(|s| s.has_nothing_to_flush())? {
             return Ok(());
         }
-        let (headers, ops, entries) = self.scratch.apply_and_then(|scratch| {
+        let (headers, ops, entries, remote_signals) = self.scratch.apply_and_then(|scratch| {
             let length = scratch.num_headers();
 
             // The op related data ends up here.
@@ -435,18 +476,25 @@ impl SourceChain {
 
             // Drain out any entries.
             let entries = scratch.drain_entries().collect::<Vec<_>>();
-            SourceChainResult::Ok((headers, ops, entries))
+            // Drain out any remote signals for the outbox.
+            let remote_signals = scratch.drain_remote_signals().collect::<Vec<_>>();
+            SourceChainResult::Ok((headers, ops, entries, remote_signals))
         })?;
 
-        // Write the entries, headers and ops to the database in one transaction.
+        // Write the entries, headers, ops and remote signals
+        // to the database in one transaction.
         let author = self.author.clone();
         let persisted_head = self.persisted_head.clone();
         self.vault
             .async_commit(move |txn| {
                 // As at check.
                 let (new_persisted_head, _) = chain_head_db(&txn, author)?;
+                let now = timestamp::now();
+                for signal in remote_signals {
+                    remote_signal_outbox::insert(txn, &signal, now)?;
+                }
                 if headers.last().is_none() {
-                    // Nothing to write
+                    // Nothing else to write
                     return Ok(());
                 }
                 if persisted_head != new_persisted_head {
@@ -552,6 +600,37 @@ pub async fn genesis(
         .await
 }
 
+/// Write a chain restored from a backup into a vault which has
+/// no chain for this author yet.
+/// Like the genesis elements, the ops are stored as authored but not yet
+/// validated so the cell's workflows will validate, integrate and publish them.
+pub async fn restore(
+    vault: EnvWrite,
+    author: AgentPubKey,
+    elements: Vec<Element>,
+) -> SourceChainResult<()> {
+    let mut to_put = Vec::with_capacity(elements.len());
+    for element in elements {
+        let ops = produce_op_lights_from_elements(vec![&element])?;
+        let (shh, entry) = element.into_inner();
+        to_put.push((shh, ops, entry.into_option()));
+    }
+    let author = Arc::new(author);
+    vault
+        .async_commit(move |txn| {
+            match chain_head_db(txn, author) {
+                Err(SourceChainError::ChainEmpty) => (),
+                Ok((head, _)) => return Err(SourceChainError::ChainExists(head)),
+                Err(e) => return Err(e),
+            }
+            for (shh, ops, entry) in to_put {
+                put_raw(txn, shh, ops, entry)?;
+            }
+            SourceChainResult::Ok(())
+        })
+        .await
+}
+
 pub fn put_raw(
     txn: &mut Transaction,
     shh: SignedHeaderHashed,
@@ -609,6 +688,26 @@ fn chain_head_scratch(scratch: &Scratch, author: &AgentPubKey) -> Option<(Header
         .max_by_key(|h| h.1)
 }
 
+/// Named params for an `IN (...)` list, one per value,
+/// or `None` if there is no list to filter by.
+fn in_list_params<'a, T: holochain_sqlite::rusqlite::ToSql>(
+    name: &str,
+    values: &'a Option<Vec<T>>,
+) -> Option<Vec<(String, &'a dyn holochain_sqlite::rusqlite::ToSql)>> {
+    values.as_ref().map(|values| {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                (
+                    format!(":{}_{}", name, i),
+                    v as &dyn holochain_sqlite::rusqlite::ToSql,
+                )
+            })
+            .collect()
+    })
+}
+
 #[cfg(test)]
 async fn _put_db<H: HeaderInner, B: HeaderBuilder<H>>(
     vault: holochain_types::env::EnvWrite,
@@ -988,6 +1087,98 @@ pub mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn source_chain_query_filters() -> SourceChainResult<()> {
+        observability::test_run().ok();
+        let test_env = test_cell_env();
+        let vault = test_env.env();
+        let author = test_env.cell_id().unwrap().agent_pubkey().clone();
+
+        genesis(vault.clone().into(), fixt!(DnaHash), author.clone(), None)
+            .await
+            .unwrap();
+        let source_chain = SourceChain::new(vault.clone().into(), author.clone())
+            .await
+            .unwrap();
+
+        let type_a = EntryType::App(fixt!(AppEntryType));
+        let type_b = EntryType::App(fixt!(AppEntryType));
+        let mut hashes = Vec::new();
+        let mut entry_hashes = Vec::new();
+        for entry_type in &[&type_a, &type_b, &type_a, &type_a] {
+            let entry = Entry::App(fixt!(AppEntryBytes));
+            let entry_hash = EntryHash::with_data_sync(&entry);
+            let create = builder::Create {
+                entry_type: (*entry_type).clone(),
+                entry_hash: entry_hash.clone(),
+            };
+            hashes.push(source_chain.put(create, Some(entry)).await.unwrap());
+            entry_hashes.push(entry_hash);
+            // Leave the last one in the scratch.
+            if hashes.len() == 3 {
+                source_chain.flush().await.unwrap();
+            }
+        }
+        let addresses = |elements: Vec<Element>| {
+            elements
+                .into_iter()
+                .map(|el| el.header_address().clone())
+                .collect::<Vec<_>>()
+        };
+
+        // - The newest first, across the database and the scratch
+        let res = source_chain
+            .query(QueryFilter::new().descending().limit(2))
+            .await?;
+        assert_eq!(addresses(res), vec![hashes[3].clone(), hashes[2].clone()]);
+
+        // - An open range
+        let res = source_chain
+            .query(QueryFilter::new().sequence_range(4..))
+            .await?;
+        assert_eq!(addresses(res), hashes[1..].to_vec());
+
+        // - The last two of an entry type
+        let res = source_chain
+            .query(
+                QueryFilter::new()
+                    .entry_type(type_a.clone())
+                    .descending()
+                    .limit(2),
+            )
+            .await?;
+        assert_eq!(addresses(res), vec![hashes[3].clone(), hashes[2].clone()]);
+
+        // - Several entry types and entry hashes
+        let res = source_chain
+            .query(QueryFilter::new().entry_types(vec![type_a, type_b]))
+            .await?;
+        assert_eq!(addresses(res), hashes);
+        let res = source_chain
+            .query(
+                QueryFilter::new()
+                    .entry_hash(entry_hashes[1].clone())
+                    .entry_hash(entry_hashes[3].clone())
+                    .include_entries(true),
+            )
+            .await?;
+        assert_eq!(res.len(), 2);
+        assert!(res.iter().all(|el| el.entry().as_option().is_some()));
+        assert_eq!(addresses(res), vec![hashes[1].clone(), hashes[3].clone()]);
+
+        // - Several header types
+        let res = source_chain
+            .query(
+                QueryFilter::new()
+                    .header_type(HeaderType::Dna)
+                    .header_type(HeaderType::AgentValidationPkg),
+            )
+            .await?;
+        assert_eq!(res.len(), 2);
+
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn source_chain_buffer_dump_entries_json() -> SourceChainResult<()> {
         let test_env = test_cell_env();
@@ -20,6 +20,9 @@ pub enum SourceChainError {
     )]
     HeadMoved(Option<HeaderHash>, Option<HeaderHash>),
 
+    #[error("Attempted to restore a source chain, but there is already a chain with head {0:?}")]
+    ChainExists(HeaderHash),
+
     #[error(
         "The source chain's structure is invalid. This error is not recoverable. Detail:\n{0}"
     )]
@@ -0,0 +1,69 @@
+//! Module for the nonces of signed zome calls.
+//!
+//! Each signed zome call carries a nonce which may only be used once.
+//! Nonces are kept in the cell database until their call expires,
+//! so a call can't be replayed after the conductor restarts.
+
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::Transaction;
+use holochain_types::Timestamp;
+
+use crate::mutations::StateMutationResult;
+
+/// Record the nonce of a call which expires at `expires_at`,
+/// first dropping the nonces of calls which expired by `now`.
+/// Returns false if the nonce was already used by a call which hasn't expired.
+pub fn check_and_insert(
+    txn: &mut Transaction,
+    nonce: &[u8],
+    expires_at: Timestamp,
+    now: Timestamp,
+) -> StateMutationResult<bool> {
+    txn.execute(
+        "DELETE FROM ZomeCallNonce WHERE expires_at_ms <= :now",
+        named_params! {
+            ":now": now.to_sql_ms_lossy(),
+        },
+    )?;
+    let inserted = txn.execute(
+        "
+        INSERT OR IGNORE INTO ZomeCallNonce (nonce, expires_at_ms)
+        VALUES (:nonce, :expires_at_ms)
+        ",
+        named_params! {
+            ":nonce": nonce,
+            ":expires_at_ms": expires_at.to_sql_ms_lossy(),
+        },
+    )?;
+    Ok(inserted == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_sqlite::db::WriteManager;
+    use holochain_types::timestamp;
+    use std::time::Duration;
+
+    #[test]
+    fn nonces_are_used_once_until_they_expire() {
+        let test_env = crate::test_utils::test_cell_env();
+        let env = test_env.env();
+        let now = timestamp::now();
+        let expires_at = (now + Duration::from_secs(60)).unwrap();
+        let later = (now + Duration::from_secs(120)).unwrap();
+
+        env.conn()
+            .unwrap()
+            .with_commit_sync(|txn| {
+                assert!(check_and_insert(txn, &[1; 32], expires_at, now)?);
+                assert!(!check_and_insert(txn, &[1; 32], expires_at, now)?);
+                assert!(check_and_insert(txn, &[2; 32], expires_at, now)?);
+
+                // - Once the call has expired its nonce is forgotten
+                assert!(check_and_insert(txn, &[1; 32], later, later)?);
+                StateMutationResult::Ok(())
+            })
+            .unwrap();
+    }
+}
@@ -54,6 +54,10 @@ pub struct WireLinkOps {
     pub creates: Vec<WireCreateLink>,
     /// delete links that match this query.
     pub deletes: Vec<WireDeleteLink>,
+    /// The number of live links that match this query
+    /// if only the count was asked for.
+    #[serde(default)]
+    pub count: Option<u32>,
 }
 
 impl WireLinkOps {
@@ -63,7 +67,9 @@ impl WireLinkOps {
     }
     /// Render these ops to their full types.
     pub fn render(self, key: &WireLinkKey) -> DhtOpResult<RenderedOps> {
-        let Self { creates, deletes } = self;
+        let Self {
+            creates, deletes, ..
+        } = self;
         let mut ops = Vec::with_capacity(creates.len() + deletes.len());
         // We silently ignore ops that fail to render as they come from the network.
         ops.extend(creates.into_iter().filter_map(|op| op.render(key).ok()));
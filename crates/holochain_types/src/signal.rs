@@ -3,7 +3,12 @@
 //! - App-defined signals are produced via the `emit_signal` host function.
 //! - System-defined signals are produced in various places in the system
 
+use crate::app::DeactivationReason;
+use crate::app::InstalledAppId;
 use crate::impl_from;
+use holo_hash::AgentPubKey;
+use holo_hash::DhtOpHash;
+use holo_hash::DnaHash;
 use holochain_serialized_bytes::prelude::*;
 use holochain_zome_types::prelude::*;
 
@@ -20,15 +25,118 @@ pub enum Signal {
 /// A Signal which originates from within the Holochain system, as opposed to
 /// from within a Cell
 ///
-/// TODO, decide what these will be. For instance, maybe there is a
-/// DataAvailable signal for doing async network requests
+/// These describe the lifecycle of apps and cells, and the progress of
+/// this conductor's own authored data through the DHT, so that UIs
+/// can show sync status without polling.
 #[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq, Eq)]
 pub enum SystemSignal {
-    /// Since we have no real system signals, we use a test signal for testing
-    /// TODO: replace instances of this with something real
+    /// An app was installed
+    AppInstalled {
+        /// The id of the installed app
+        installed_app_id: InstalledAppId,
+    },
+    /// An app was activated
+    AppActivated {
+        /// The id of the activated app
+        installed_app_id: InstalledAppId,
+    },
+    /// An app was deactivated
+    AppDeactivated {
+        /// The id of the deactivated app
+        installed_app_id: InstalledAppId,
+        /// Why the app was deactivated
+        reason: DeactivationReason,
+    },
+    /// Genesis has completed for a cell
+    GenesisComplete {
+        /// The cell which completed genesis
+        cell_id: CellId,
+    },
+    /// One of our authored ops was published to the network
+    OpPublished {
+        /// The authoring cell
+        cell_id: CellId,
+        /// The published op
+        op_hash: DhtOpHash,
+    },
+    /// One of our authored ops was app validated
+    OpValidated {
+        /// The authoring cell
+        cell_id: CellId,
+        /// The validated op
+        op_hash: DhtOpHash,
+        /// The outcome of validation
+        status: ValidationStatus,
+    },
+    /// One of our authored ops was integrated into our DHT shard
+    OpIntegrated {
+        /// The authoring cell
+        cell_id: CellId,
+        /// The integrated op
+        op_hash: DhtOpHash,
+    },
+    /// A validation receipt was received for one of our authored ops
+    ValidationReceiptReceived {
+        /// The authoring cell
+        cell_id: CellId,
+        /// The op which was validated
+        op_hash: DhtOpHash,
+        /// The agent who validated the op
+        validator: AgentPubKey,
+    },
+    /// A peer we have not seen before joined one of our DNA's networks
+    PeerJoined {
+        /// The DNA whose network was joined
+        dna_hash: DnaHash,
+        /// The peer who joined
+        agent: AgentPubKey,
+    },
+    /// A remote signal waiting in a cell's outbox was finally delivered
+    RemoteSignalDelivered {
+        /// The cell which sent the signal
+        cell_id: CellId,
+        /// The agent the signal was delivered to
+        agent: AgentPubKey,
+        /// The outbox id given in the original delivery receipt
+        outbox_id: u64,
+    },
+    /// A test signal, for testing interface plumbing
     Test(String),
 }
 
+impl SystemSignal {
+    /// The category this signal belongs to, used for filtering
+    pub fn kind(&self) -> SystemSignalKind {
+        match self {
+            SystemSignal::AppInstalled { .. }
+            | SystemSignal::AppActivated { .. }
+            | SystemSignal::AppDeactivated { .. }
+            | SystemSignal::GenesisComplete { .. } => SystemSignalKind::Lifecycle,
+            SystemSignal::OpPublished { .. }
+            | SystemSignal::OpValidated { .. }
+            | SystemSignal::OpIntegrated { .. }
+            | SystemSignal::ValidationReceiptReceived { .. } => SystemSignalKind::Consistency,
+            SystemSignal::PeerJoined { .. } | SystemSignal::RemoteSignalDelivered { .. } => {
+                SystemSignalKind::Network
+            }
+            SystemSignal::Test(_) => SystemSignalKind::Test,
+        }
+    }
+}
+
+/// The categories of [`SystemSignal`], which can be enabled independently
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SystemSignalKind {
+    /// App install / activation and cell genesis
+    Lifecycle,
+    /// Publishing, validation and integration of our own authored ops
+    Consistency,
+    /// Peers joining our networks
+    Network,
+    /// Test signals, which are always emitted
+    Test,
+}
+
 /// Create a test signal
 pub fn test_signal(s: &str) -> Signal {
     SystemSignal::Test(s.to_string()).into()
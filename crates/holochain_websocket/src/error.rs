@@ -17,6 +17,8 @@ pub enum WebsocketError {
     FailedToSendResp,
     #[error("Response to request timed out")]
     RespTimeout,
+    #[error("The requester stopped waiting for the response")]
+    RespCanceled,
     #[error("The websocket connection has shutdown")]
     Shutdown,
 }
@@ -17,6 +17,15 @@
 //! You can use [`ListenerHandle::close`] to close immediately or
 //! [`ListenerHandle::close_on`] to close on a future completing.
 //!
+//! On unix, [`WebsocketListener::bind_unix`] and [`connect_unix`] run the
+//! same protocol over a unix domain socket instead of tcp.
+//!
+//! Responses larger than [`WebsocketConfig::response_chunk_size`] are
+//! streamed to requesters which accept chunks in chunks, no more than
+//! [`WebsocketConfig::response_chunk_window`] of which are unacknowledged
+//! at once. If the requester stops waiting the rest of the stream is canceled.
+//! This is transparent to [`WebsocketSender::request`] and [`Respond::respond`].
+//!
 //! # Example
 //!
 //! ```
@@ -84,7 +93,11 @@ use holochain_serialized_bytes::prelude::*;
 use stream_cancel::Valve;
 use tracing::instrument;
 use url2::Url2;
+use util::addr_to_url;
+use util::tls_connector;
+use util::tls_err;
 use util::url_to_addr;
+use util::RawSocket;
 use websocket::Websocket;
 
 mod websocket_config;
@@ -115,10 +128,46 @@ pub async fn connect(
 ) -> WebsocketResult<(WebsocketSender, WebsocketReceiver)> {
     let addr = url_to_addr(&url, config.scheme).await?;
     let socket = tokio::net::TcpStream::connect(addr).await?;
+    let peer_addr = addr_to_url(socket.peer_addr()?, config.scheme);
     // TODO: find equivalent of this in new tokio
     // socket.set_keepalive(Some(std::time::Duration::from_secs(
     //     config.tcp_keepalive_s as u64,
     // )))?;
+    let socket: Box<dyn RawSocket> = if url.scheme() == "wss" {
+        let domain = url.host_str().unwrap_or_default();
+        Box::new(
+            tls_connector(config.tls.as_ref())?
+                .connect(domain, socket)
+                .await
+                .map_err(tls_err)?,
+        )
+    } else {
+        Box::new(socket)
+    };
+    client_handshake(url, socket, peer_addr, config).await
+}
+
+#[cfg(unix)]
+#[instrument(skip(config))]
+/// Create a new websocket connection over the unix domain socket at `path`,
+/// e.g. one bound with [`WebsocketListener::bind_unix`].
+pub async fn connect_unix(
+    path: impl AsRef<std::path::Path> + std::fmt::Debug,
+    config: Arc<WebsocketConfig>,
+) -> WebsocketResult<(WebsocketSender, WebsocketReceiver)> {
+    let socket = tokio::net::UnixStream::connect(path.as_ref()).await?;
+    let peer_addr = util::unix_path_to_url(path.as_ref());
+    // The handshake needs a url, the host is irrelevant for unix sockets.
+    let url = url2::url2!("{}://localhost", config.scheme);
+    client_handshake(url, Box::new(socket), peer_addr, config).await
+}
+
+async fn client_handshake(
+    url: Url2,
+    socket: Box<dyn RawSocket>,
+    peer_addr: Url2,
+    config: Arc<WebsocketConfig>,
+) -> WebsocketResult<(WebsocketSender, WebsocketReceiver)> {
     let (socket, _) = tokio_tungstenite::client_async_with_config(
         url.as_str(),
         socket,
@@ -132,7 +181,7 @@ pub async fn connect(
     // ends when creating a client
     let (exit, valve) = Valve::new();
     exit.disable();
-    Websocket::create_ends(config, socket, valve)
+    Websocket::create_ends(config, socket, peer_addr, valve)
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
@@ -155,6 +204,10 @@ pub enum WireMessage {
         #[serde(with = "serde_bytes")]
         /// Actual bytes of the message serialized as [message pack](https://msgpack.org/).
         data: Vec<u8>,
+        #[serde(default)]
+        /// The requester can join a response sent as [`WireMessage::ResponseChunk`]s.
+        /// Requesters that don't set this always get a single [`WireMessage::Response`].
+        accepts_chunks: bool,
     },
     /// The response to a request.
     Response {
@@ -164,4 +217,29 @@ pub enum WireMessage {
         /// Actual bytes of the message serialized as [message pack](https://msgpack.org/).
         data: Option<Vec<u8>>,
     },
+    /// Part of a response which was too large to send as one message.
+    /// The chunks are sent in order and joined back together by the requester.
+    ResponseChunk {
+        /// The id of the request that this response is for.
+        id: u64,
+        #[serde(with = "serde_bytes")]
+        /// The next bytes of the response.
+        data: Vec<u8>,
+        /// Is this the final chunk of the response.
+        last: bool,
+    },
+    /// The requester has received this many chunks of a response
+    /// so the responder can send more.
+    ResponseChunkAck {
+        /// The id of the request that the response is for.
+        id: u64,
+        /// The number of chunks received so far.
+        received: u64,
+    },
+    /// The requester is no longer waiting for this response
+    /// so the responder should stop sending chunks of it.
+    CancelResponse {
+        /// The id of the request that the response is for.
+        id: u64,
+    },
 }
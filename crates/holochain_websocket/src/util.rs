@@ -6,7 +6,19 @@ use url2::{url2, Url2};
 
 use std::io::{Error, ErrorKind, Result};
 
-pub(crate) type ToFromSocket = tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>;
+/// Any byte stream a websocket can run over,
+/// e.g. tcp, tls over tcp or a unix domain socket.
+pub(crate) trait RawSocket:
+    tokio::io::AsyncRead + tokio::io::AsyncWrite + Send + Unpin + 'static
+{
+}
+
+impl<T> RawSocket for T where
+    T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Send + Unpin + 'static
+{
+}
+
+pub(crate) type ToFromSocket = tokio_tungstenite::WebSocketStream<Box<dyn RawSocket>>;
 
 /// Amount of time to spend waiting for channels to empty before forcing them to close.
 pub(crate) const CLOSE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
@@ -16,6 +28,12 @@ pub(crate) fn addr_to_url(a: SocketAddr, scheme: &str) -> Url2 {
     url2!("{}://{}", scheme, a)
 }
 
+/// internal helper to convert unix socket paths to urls
+#[cfg(unix)]
+pub(crate) fn unix_path_to_url(path: &std::path::Path) -> Url2 {
+    url2!("unix://{}", path.display())
+}
+
 /// internal helper convert urls to socket addrs for binding / connection
 pub(crate) async fn url_to_addr(url: &Url2, scheme: &str) -> Result<SocketAddr> {
     if url.scheme() != scheme || url.host_str().is_none() || url.port().is_none() {
@@ -52,3 +70,43 @@ pub(crate) async fn url_to_addr(url: &Url2, scheme: &str) -> Result<SocketAddr>
         format!("could not parse '{}', as 'host:port'", rendered),
     ))
 }
+
+/// internal helper to convert tls errors into io errors
+pub(crate) fn tls_err(e: native_tls::Error) -> Error {
+    Error::new(ErrorKind::Other, e)
+}
+
+/// internal helper to build a tls acceptor for listeners
+pub(crate) fn tls_acceptor(
+    tls: &crate::WebsocketTlsConfig,
+) -> Result<tokio_native_tls::TlsAcceptor> {
+    let identity =
+        native_tls::Identity::from_pkcs8(&tls.cert_pem, &tls.key_pem).map_err(tls_err)?;
+    let acceptor = native_tls::TlsAcceptor::new(identity).map_err(tls_err)?;
+    Ok(acceptor.into())
+}
+
+/// internal helper to build a tls connector for clients.
+/// If the config carries a certificate it is trusted
+/// in addition to the system roots.
+pub(crate) fn tls_connector(
+    tls: Option<&crate::WebsocketTlsConfig>,
+) -> Result<tokio_native_tls::TlsConnector> {
+    let mut builder = native_tls::TlsConnector::builder();
+    if let Some(tls) = tls {
+        builder.add_root_certificate(
+            native_tls::Certificate::from_pem(&tls.cert_pem).map_err(tls_err)?,
+        );
+    }
+    Ok(builder.build().map_err(tls_err)?.into())
+}
+
+/// internal helper to check a connecting client's `Origin` header.
+/// Only browsers are required to send an origin, and any other client
+/// could forge one, so requests without the header are let through.
+pub(crate) fn origin_allowed(allowed: Option<&[String]>, origin: Option<&str>) -> bool {
+    match (allowed, origin) {
+        (Some(allowed), Some(origin)) => allowed.iter().any(|a| a == "*" || a == origin),
+        _ => true,
+    }
+}
@@ -15,8 +15,8 @@ use tracing::Instrument;
 use ghost_actor::*;
 use tungstenite::protocol::frame::coding::CloseCode;
 use tungstenite::protocol::CloseFrame;
+use url2::Url2;
 
-use crate::util::addr_to_url;
 use crate::util::ToFromSocket;
 use crate::util::CLOSE_TIMEOUT;
 use crate::CancelResponse;
@@ -37,19 +37,58 @@ type GhostResult<T> = std::result::Result<T, GhostError>;
 
 #[derive(Debug, Clone)]
 /// Actor that tracks responses.
-pub struct Websocket(GhostActor<WebsocketInner>);
+pub struct Websocket(GhostActor<WebsocketInner>, StreamConfig);
+
+#[derive(Debug, Clone, Copy)]
+/// How we stream responses that are too large for one message.
+struct StreamConfig {
+    /// Responses larger than this are split into chunks of this size.
+    chunk_size: usize,
+    /// Chunks which can be sent before they are acknowledged.
+    window: u64,
+    /// Streamed responses to us larger than this are canceled.
+    max_response_size: usize,
+}
 
 #[derive(Debug)]
 struct ResponseTracker {
     /// Map of registered responses.
     responses: HashMap<u64, RegisterResponse>,
+    /// Responses which are part way through being streamed
+    /// with the bytes and number of chunks received so far.
+    partial: HashMap<u64, (Vec<u8>, u64)>,
     /// The next key to use.
     index: u64,
 }
 
+/// What happened when a chunk of a streamed response arrived.
+enum ChunkReceived {
+    /// More chunks are expected, this many have arrived so far.
+    Partial(u64),
+    /// This was the last chunk so here's the whole response.
+    Complete(RegisterResponse, Vec<u8>),
+    /// The response grew larger than we accept so it was dropped.
+    TooLarge(RegisterResponse),
+    /// Nobody is waiting for this response.
+    Unknown,
+}
+
+/// Messages from the requester to a task streaming it a response.
+#[derive(Debug)]
+enum StreamControl {
+    /// The requester has received this many chunks.
+    Ack(u64),
+    /// The requester is no longer waiting.
+    Cancel,
+}
+
+type TxStreamControl = tokio::sync::mpsc::Sender<StreamControl>;
+
 /// Inner GhostActor data.
 struct WebsocketInner {
     responses: ResponseTracker,
+    /// Responses we are currently streaming to the other side.
+    outgoing_streams: HashMap<u64, TxStreamControl>,
 }
 
 // Channel from the application to the websocket and out to the external socket.
@@ -124,13 +163,10 @@ impl Websocket {
     pub fn create_ends(
         config: Arc<WebsocketConfig>,
         socket: ToFromSocket,
+        peer_addr: Url2,
         listener_shutdown: Valve,
     ) -> WebsocketResult<(WebsocketSender, WebsocketReceiver)> {
-        let remote_addr = url2::url2!(
-            "{}#{}",
-            addr_to_url(socket.get_ref().peer_addr()?, config.scheme),
-            nanoid::nanoid!(),
-        );
+        let remote_addr = url2::url2!("{}#{}", peer_addr, nanoid::nanoid!());
 
         // Channel to the websocket from the application
         let (tx_to_websocket, rx_to_websocket) = tokio::sync::mpsc::channel(config.max_send_queue);
@@ -157,8 +193,15 @@ impl Websocket {
         // TODO: Should this shutdown immediately or gracefully. Currently it is immediately.
         let rx_from_websocket = listener_shutdown.wrap(rx_from_websocket_stream);
 
+        let stream_config = StreamConfig {
+            chunk_size: config.response_chunk_size.max(1),
+            window: config.response_chunk_window.max(1) as u64,
+            max_response_size: config.max_response_size,
+        };
+
         // Run the to and from external socket tasks.
         Websocket::run(
+            stream_config,
             socket,
             tx_to_websocket.clone(),
             rx_to_websocket_stream,
@@ -185,6 +228,7 @@ impl Websocket {
         pair_shutdown
     ))]
     fn run(
+        stream_config: StreamConfig,
         socket: ToFromSocket,
         tx_to_websocket: TxToWebsocket,
         rx_to_websocket: RxToWebsocket,
@@ -194,9 +238,10 @@ impl Websocket {
         // Spawn the actor and run the socket tasks
         let (actor, driver) = GhostActor::new(WebsocketInner {
             responses: ResponseTracker::new(),
+            outgoing_streams: HashMap::new(),
         });
         tokio::task::spawn(driver);
-        let actor = Self(actor);
+        let actor = Self(actor, stream_config);
         actor.run_socket(
             socket,
             tx_to_websocket,
@@ -318,8 +363,20 @@ impl Websocket {
                         id,
                         data: msg.map(|m| UnsafeBytes::from(m).into()),
                     },
+                    OutgoingMessage::ResponseChunk(data, id, last) => {
+                        WireMessage::ResponseChunk { id, data, last }
+                    }
+                    OutgoingMessage::ResponseChunkAck(id, received) => {
+                        WireMessage::ResponseChunkAck { id, received }
+                    }
+                    OutgoingMessage::CancelResponse(id) => WireMessage::CancelResponse { id },
                     OutgoingMessage::StaleRequest(id) => {
-                        return self.handle_stale_request(id).await;
+                        if !self.handle_stale_request(id).await? {
+                            return Task::cont();
+                        }
+                        // The response had started streaming so let
+                        // the other side know it can stop.
+                        WireMessage::CancelResponse { id }
                     }
                     OutgoingMessage::Pong(data) => {
                         // No need to deserialize, just send the data back
@@ -427,10 +484,15 @@ impl Websocket {
                             WireMessage::Signal { data } => {
                                 (Self::deserialize_bytes(data)?, Respond::Signal)
                             }
-                            WireMessage::Request { data, id } => Self::handle_incoming_request(
+                            WireMessage::Request {
+                                data,
+                                id,
+                                accepts_chunks,
+                            } => self.handle_incoming_request(
                                 send_response,
                                 Self::deserialize_bytes(data)?,
                                 id,
+                                accepts_chunks,
                             ),
                             WireMessage::Response {
                                 data: Some(data),
@@ -451,6 +513,20 @@ impl Websocket {
                                 // This means the other sides receiver has shutdown.
                                 return self.handle_incoming_response(None, id).await;
                             }
+                            WireMessage::ResponseChunk { id, data, last } => {
+                                return self
+                                    .handle_incoming_chunk(send_response, id, data, last)
+                                    .await;
+                            }
+                            WireMessage::ResponseChunkAck { id, received } => {
+                                return self
+                                    .handle_stream_control(id, StreamControl::Ack(received))
+                                    .await;
+                            }
+                            WireMessage::CancelResponse { id } => {
+                                tracing::trace!(stream_canceled = ?id);
+                                return self.handle_stream_control(id, StreamControl::Cancel).await;
+                            }
                         };
 
                         // Forward the incoming message to the WebsocketReceiver.
@@ -515,9 +591,11 @@ impl Websocket {
     /// Handling a request coming in from the network
     /// and reply with a response.
     fn handle_incoming_request(
+        &self,
         send_response: &mut TxToWebsocket,
         msg: SerializedBytes,
         id: u64,
+        accepts_chunks: bool,
     ) -> (SerializedBytes, Respond) {
         let resp = {
             // Get the sender to the "to socket" task so we can reply.
@@ -525,17 +603,34 @@ impl Websocket {
             // If the reply closure is never run and only dropped we want
             // to send a canceled response to the other sides WebsocketSender.
             let cancel_response = CancelResponse::new(send_response.clone(), id);
+            // Needed to track acknowledgements if the response is streamed.
+            let actor = self.clone();
 
             // Callback to respond to the request
-            move |msg| {
+            move |msg: SerializedBytes| {
                 async move {
-                    let msg = OutgoingMessage::Response(Some(msg), id);
+                    // Only stream to requesters that can join the chunks
+                    if accepts_chunks && msg.bytes().len() > actor.1.chunk_size {
+                        match actor
+                            .stream_response(&send_response, UnsafeBytes::from(msg).into(), id)
+                            .await
+                        {
+                            // Nobody is waiting for a cancel either.
+                            Err(WebsocketError::RespCanceled) => {
+                                cancel_response.response_sent();
+                                return Err(WebsocketError::RespCanceled);
+                            }
+                            r => r?,
+                        }
+                    } else {
+                        let msg = OutgoingMessage::Response(Some(msg), id);
 
-                    // Send the response to the to_socket task
-                    send_response
-                        .send(msg)
-                        .await
-                        .map_err(|_| WebsocketError::FailedToSendResp)?;
+                        // Send the response to the to_socket task
+                        send_response
+                            .send(msg)
+                            .await
+                            .map_err(|_| WebsocketError::FailedToSendResp)?;
+                    }
                     // Response sent, don't send cancel.
                     cancel_response.response_sent();
                     tracing::trace!("Sent response");
@@ -550,6 +645,64 @@ impl Websocket {
         (msg, resp)
     }
 
+    /// Send a response that is too large for one message in chunks,
+    /// waiting for the requester to acknowledge them so that
+    /// no more than the window of chunks is in flight at once.
+    async fn stream_response(
+        &self,
+        send_response: &TxToWebsocket,
+        bytes: Vec<u8>,
+        id: u64,
+    ) -> WebsocketResult<()> {
+        let StreamConfig {
+            chunk_size, window, ..
+        } = self.1;
+        // Acks never outnumber the chunks in flight
+        // so this channel will never fill up.
+        let (tx_control, mut rx_control) = tokio::sync::mpsc::channel(window as usize + 1);
+        self.0
+            .invoke(move |state| GhostResult::Ok(state.outgoing_streams.insert(id, tx_control)))
+            .await?;
+
+        let num_chunks = (bytes.len() + chunk_size - 1) / chunk_size;
+        let result = async {
+            let mut acknowledged = 0;
+            for (sent, chunk) in bytes.chunks(chunk_size).enumerate() {
+                let sent = sent as u64;
+                // Pick up any acks or a cancel without waiting,
+                // then wait until there's room in the window.
+                loop {
+                    let control = match rx_control.recv().now_or_never() {
+                        Some(control) => control,
+                        None if sent - acknowledged < window => break,
+                        None => rx_control.recv().await,
+                    };
+                    match control {
+                        Some(StreamControl::Ack(received)) => {
+                            acknowledged = acknowledged.max(received)
+                        }
+                        Some(StreamControl::Cancel) | None => {
+                            return Err(WebsocketError::RespCanceled)
+                        }
+                    }
+                }
+                let last = sent + 1 == num_chunks as u64;
+                send_response
+                    .send(OutgoingMessage::ResponseChunk(chunk.to_vec(), id, last))
+                    .await
+                    .map_err(|_| WebsocketError::FailedToSendResp)?;
+            }
+            Ok(())
+        }
+        .await;
+
+        self.0
+            .invoke(move |state| GhostResult::Ok(state.outgoing_streams.remove(&id)))
+            .await
+            .ok();
+        result
+    }
+
     /// Handle a request going out to the network.
     async fn handle_outgoing_request(
         &self,
@@ -580,17 +733,20 @@ impl Websocket {
         if let Err(id) = tx_stale_request.send(id) {
             // If we fail to send the id that means the requester
             // has dropped so we should clean up the stale request.
-            match self.handle_stale_request(id).await {
-                Ok(_) => unreachable!("handle_stale_request always continues or exits the loop"),
-                Err(task) => return Err(task),
-            }
+            self.handle_stale_request(id).await?;
+            return Task::cont();
         }
         let data = UnsafeBytes::from(msg).into();
-        Ok(WireMessage::Request { data, id })
+        Ok(WireMessage::Request {
+            data,
+            id,
+            accepts_chunks: true,
+        })
     }
 
     /// Handle a request that has gone stale.
-    async fn handle_stale_request(&self, id: u64) -> Loop<()> {
+    /// Returns true if the response had started streaming.
+    async fn handle_stale_request(&self, id: u64) -> Loop<bool> {
         // If the actor has closed we can't clean up this response.
         if !self.0.is_active() {
             tracing::error!("Actor is closed");
@@ -599,10 +755,10 @@ impl Websocket {
         tracing::trace!(here = line!());
         match self
             .0
-            .invoke(move |state| GhostResult::Ok(state.responses.pop(id)))
+            .invoke(move |state| GhostResult::Ok(state.responses.pop_stale(id)))
             .await
         {
-            Ok(_) => Task::cont(),
+            Ok(streaming) => Ok(streaming),
             Err(e) => {
                 // Failed to clean up request so something is
                 // wrong with the actor and we should shutdown.
@@ -675,6 +831,118 @@ impl Websocket {
         }
     }
 
+    /// Handle a chunk of a streamed response coming in from the network.
+    async fn handle_incoming_chunk(
+        &self,
+        send_response: &mut TxToWebsocket,
+        id: u64,
+        data: Vec<u8>,
+        last: bool,
+    ) -> Loop<()> {
+        // If the actor has closed we can't find the registered response.
+        if !self.0.is_active() {
+            tracing::error!("Actor is closed");
+            return Task::exit();
+        }
+        let max_size = self.1.max_response_size;
+        let received = self
+            .0
+            .invoke(move |state| {
+                GhostResult::Ok(state.responses.receive_chunk(id, data, last, max_size))
+            })
+            .await;
+        match received {
+            Ok(ChunkReceived::Partial(received)) => {
+                // Let the responder know it can send more.
+                send_response
+                    .send(OutgoingMessage::ResponseChunkAck(id, received))
+                    .await
+                    .ok();
+                Task::cont()
+            }
+            Ok(ChunkReceived::Complete(response, bytes)) => {
+                let msg = Self::deserialize_bytes(bytes)?;
+                match response.respond(Some(msg)) {
+                    Ok(_) => Task::cont(),
+                    Err(e) => {
+                        // Failed to handle the response so we need to
+                        // shutdown.
+                        tracing::error!(handle_response_error = ?e);
+                        Task::exit()
+                    }
+                }
+            }
+            Ok(ChunkReceived::TooLarge(response)) => {
+                tracing::warn!(
+                    max_response_size = max_size,
+                    "Websocket: Canceling streamed response that is too large"
+                );
+                if !last {
+                    send_response
+                        .send(OutgoingMessage::CancelResponse(id))
+                        .await
+                        .ok();
+                }
+                // Fail the request waiting on this response.
+                response.respond(None).ok();
+                Task::cont()
+            }
+            Ok(ChunkReceived::Unknown) => {
+                tracing::warn!("Websocket: Received response chunk for request that doesn't exist or has gone stale");
+                if !last {
+                    send_response
+                        .send(OutgoingMessage::CancelResponse(id))
+                        .await
+                        .ok();
+                }
+                Task::cont()
+            }
+            Err(e) => {
+                // Failed to handle the chunk so something is
+                // wrong with the actor and we should shutdown.
+                tracing::error!(?e);
+                Task::exit()
+            }
+        }
+    }
+
+    /// Pass an ack or cancel from the requester
+    /// on to the task streaming it a response.
+    async fn handle_stream_control(&self, id: u64, control: StreamControl) -> Loop<()> {
+        // If the actor has closed we can't find the stream.
+        if !self.0.is_active() {
+            tracing::error!("Actor is closed");
+            return Task::exit();
+        }
+        let stream = self
+            .0
+            .invoke(move |state| GhostResult::Ok(state.outgoing_streams.get(&id).cloned()))
+            .await;
+        match stream {
+            Ok(Some(tx_control)) => {
+                if tx_control.send(control).await.is_err() {
+                    // The response was dropped part way through streaming.
+                    self.0
+                        .invoke(move |state| GhostResult::Ok(state.outgoing_streams.remove(&id)))
+                        .await
+                        .ok();
+                }
+                Task::cont()
+            }
+            Ok(None) => {
+                // The stream may have just finished.
+                tracing::trace!(
+                    "Websocket: Received ack or cancel for a response that isn't streaming"
+                );
+                Task::cont()
+            }
+            Err(e) => {
+                tracing::error!(?e);
+                Task::exit()
+            }
+        }
+    }
+
     /// Try to serialize the wire message and continue to next
     /// message if failure.
     fn serialize_msg(msg: WireMessage) -> Loop<tungstenite::Message> {
@@ -729,6 +997,7 @@ impl ResponseTracker {
     fn new() -> Self {
         Self {
             responses: HashMap::new(),
+            partial: HashMap::new(),
             index: 0,
         }
     }
@@ -745,9 +1014,45 @@ impl ResponseTracker {
 
     /// Retrieve the response at this id.
     fn pop(&mut self, id: u64) -> Option<RegisterResponse> {
+        self.partial.remove(&id);
         self.responses.remove(&id)
     }
 
+    /// Remove a response nobody is waiting for anymore
+    /// and return whether it had started streaming.
+    fn pop_stale(&mut self, id: u64) -> bool {
+        self.responses.remove(&id);
+        self.partial.remove(&id).is_some()
+    }
+
+    /// Add the next chunk to a streamed response
+    /// unless it would grow larger than `max_size`.
+    fn receive_chunk(
+        &mut self,
+        id: u64,
+        data: Vec<u8>,
+        last: bool,
+        max_size: usize,
+    ) -> ChunkReceived {
+        if !self.responses.contains_key(&id) {
+            return ChunkReceived::Unknown;
+        }
+        let (bytes, received) = self.partial.entry(id).or_insert_with(|| (Vec::new(), 0));
+        if bytes.len().saturating_add(data.len()) > max_size {
+            self.partial.remove(&id);
+            let response = self.responses.remove(&id).expect("checked above");
+            return ChunkReceived::TooLarge(response);
+        }
+        bytes.extend(data);
+        *received += 1;
+        if !last {
+            return ChunkReceived::Partial(*received);
+        }
+        let (bytes, _) = self.partial.remove(&id).expect("inserted above");
+        let response = self.responses.remove(&id).expect("checked above");
+        ChunkReceived::Complete(response, bytes)
+    }
+
     /// Show outstanding responses.
     fn debug(&self) -> (Vec<u64>, u64) {
         (self.responses.keys().copied().collect(), self.index)
@@ -771,6 +1076,29 @@ mod tests {
     use crate::WebsocketListener;
     use url2::url2;
 
+    #[test]
+    fn test_streamed_response_size_limit() {
+        let mut tracker = ResponseTracker::new();
+        let (tx, _rx) = tokio::sync::oneshot::channel();
+        let id = tracker.register(RegisterResponse::new(tx));
+
+        assert!(matches!(
+            tracker.receive_chunk(id, vec![0; 6], false, 10),
+            ChunkReceived::Partial(1)
+        ));
+        // - The next chunk takes the total over the limit
+        assert!(matches!(
+            tracker.receive_chunk(id, vec![0; 6], false, 10),
+            ChunkReceived::TooLarge(_)
+        ));
+        // - The partial response and its request are dropped
+        assert!(tracker.partial.is_empty());
+        assert!(matches!(
+            tracker.receive_chunk(id, vec![0; 1], true, 10),
+            ChunkReceived::Unknown
+        ));
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_register_response() {
         observability::test_run().ok();
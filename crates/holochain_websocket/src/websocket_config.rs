@@ -1,5 +1,24 @@
 //! defines a builder-style config struct for setting up websockets
 
+/// Certificate and private key for serving websockets over tls.
+#[derive(Clone)]
+pub struct WebsocketTlsConfig {
+    /// PEM encoded certificate (chain).
+    pub cert_pem: Vec<u8>,
+
+    /// PEM encoded PKCS #8 private key.
+    pub key_pem: Vec<u8>,
+}
+
+impl std::fmt::Debug for WebsocketTlsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebsocketTlsConfig")
+            .field("cert_pem", &String::from_utf8_lossy(&self.cert_pem))
+            .field("key_pem", &"<redacted>")
+            .finish()
+    }
+}
+
 /// A builder-style config struct for setting up websockets.
 #[derive(Debug)]
 pub struct WebsocketConfig {
@@ -26,6 +45,30 @@ pub struct WebsocketConfig {
 
     /// Maximum number of pending new incoming connections. [default = 255]
     pub max_pending_connections: usize,
+
+    /// Responses larger than this are streamed back to the requester
+    /// in chunks of this many bytes, so it should be well under the
+    /// requester's `max_message_size`. [default = 8M]
+    pub response_chunk_size: usize,
+
+    /// How many chunks of a streamed response can be sent before the
+    /// requester acknowledges receiving them. [default = 4]
+    pub response_chunk_window: usize,
+
+    /// Maximum total size of a response streamed to us in chunks.
+    /// A response that grows past this is canceled and the
+    /// request fails. [default = 256M]
+    pub max_response_size: usize,
+
+    /// Listeners serve `wss://` with this certificate when set.
+    /// Clients connecting to `wss://` trust it in addition to the
+    /// system roots, which allows pinning a self-signed certificate.
+    /// [default = None]
+    pub tls: Option<WebsocketTlsConfig>,
+
+    /// Listeners reject connections whose `Origin` header is not
+    /// in this list when set. "*" allows any origin. [default = None]
+    pub allowed_origins: Option<Vec<String>>,
 }
 
 impl Default for WebsocketConfig {
@@ -38,6 +81,11 @@ impl Default for WebsocketConfig {
             max_message_size: 64 << 20,
             max_frame_size: 16 << 20,
             max_pending_connections: 255,
+            response_chunk_size: 8 << 20,
+            response_chunk_window: 4,
+            max_response_size: 256 << 20,
+            tls: None,
+            allowed_origins: None,
         }
     }
 }
@@ -78,6 +126,37 @@ impl WebsocketConfig {
         self.max_frame_size = max;
         self
     }
+
+    /// Builder-style setter.
+    pub fn response_chunk_size(mut self, size: usize) -> Self {
+        self.response_chunk_size = size;
+        self
+    }
+
+    /// Builder-style setter.
+    pub fn response_chunk_window(mut self, window: usize) -> Self {
+        self.response_chunk_window = window;
+        self
+    }
+
+    /// Builder-style setter.
+    pub fn max_response_size(mut self, max: usize) -> Self {
+        self.max_response_size = max;
+        self
+    }
+
+    /// Builder-style setter. Also switches the scheme to "wss".
+    pub fn tls(mut self, tls: WebsocketTlsConfig) -> Self {
+        self.scheme = "wss";
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Builder-style setter.
+    pub fn allowed_origins(mut self, origins: Vec<String>) -> Self {
+        self.allowed_origins = Some(origins);
+        self
+    }
 }
 
 /// internal helper to convert our configs into tungstenite configs
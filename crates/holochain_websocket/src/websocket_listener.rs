@@ -8,12 +8,21 @@ use std::sync::Arc;
 use stream_cancel::Trigger;
 use stream_cancel::Valve;
 use tracing::instrument;
+use tungstenite::handshake::server::ErrorResponse;
+use tungstenite::handshake::server::Request;
+use tungstenite::handshake::server::Response;
 
 use url2::Url2;
 
 use crate::util::addr_to_url;
+use crate::util::origin_allowed;
+use crate::util::tls_acceptor;
+#[cfg(unix)]
+use crate::util::unix_path_to_url;
 use crate::util::url_to_addr;
+use crate::util::RawSocket;
 use crate::websocket::Websocket;
+use crate::websocket_config::TungsteniteConfigExt;
 use crate::WebsocketConfig;
 use crate::WebsocketError;
 use crate::WebsocketReceiver;
@@ -106,6 +115,35 @@ impl WebsocketListener {
     )> {
         websocket_bind(addr, config).await
     }
+
+    /// Bind to a unix domain socket at `path` to accept incoming connections.
+    /// Any stale socket file at `path` is replaced, and the new socket file
+    /// is only accessible by the current user.
+    /// The socket file is removed once the listener stream is dropped.
+    #[cfg(unix)]
+    pub async fn bind_unix(
+        path: impl AsRef<std::path::Path>,
+        config: Arc<WebsocketConfig>,
+    ) -> WebsocketResult<Self> {
+        let (handle, stream) = Self::bind_unix_with_handle(path, config).await?;
+        Ok(Self {
+            handle,
+            stream: stream.boxed(),
+        })
+    }
+
+    /// Same as [`WebsocketListener::bind_unix`] but gives you a [`ListenerHandle`] to shutdown
+    /// the listener and any open connections.
+    #[cfg(unix)]
+    pub async fn bind_unix_with_handle(
+        path: impl AsRef<std::path::Path>,
+        config: Arc<WebsocketConfig>,
+    ) -> WebsocketResult<(
+        ListenerHandle,
+        impl futures::stream::Stream<Item = ListenerItem>,
+    )> {
+        websocket_bind_unix(path.as_ref(), config).await
+    }
     /// Shutdown the listener stream.
     pub fn close(self) {
         self.handle.close()
@@ -192,6 +230,10 @@ async fn websocket_bind(
     impl futures::stream::Stream<Item = ListenerItem>,
 )> {
     let addr = url_to_addr(&addr, config.scheme).await?;
+    let acceptor = match &config.tls {
+        Some(tls) => Some(tls_acceptor(tls)?),
+        None => None,
+    };
     let socket = match &addr {
         SocketAddr::V4(_) => net2::TcpBuilder::new_v4()?,
         SocketAddr::V6(_) => net2::TcpBuilder::new_v6()?,
@@ -202,17 +244,130 @@ async fn websocket_bind(
     socket.set_nonblocking(true)?;
     let local_addr = addr_to_url(socket.local_addr()?, config.scheme);
     let listener = tokio::net::TcpListener::from_std(socket)?;
-    let listener_stream = tokio_stream::wrappers::TcpListenerStream::new(listener);
+    let scheme = config.scheme;
+    let incoming =
+        tokio_stream::wrappers::TcpListenerStream::new(listener).map(move |socket_result| {
+            let socket = socket_result?;
+            let peer_addr = addr_to_url(socket.peer_addr()?, scheme);
+            let socket: Box<dyn RawSocket> = Box::new(socket);
+            Ok((socket, peer_addr))
+        });
+    Ok(listen(incoming, local_addr, config, acceptor))
+}
+
+#[cfg(unix)]
+async fn websocket_bind_unix(
+    path: &std::path::Path,
+    config: Arc<WebsocketConfig>,
+) -> WebsocketResult<(
+    ListenerHandle,
+    impl futures::stream::Stream<Item = ListenerItem>,
+)> {
+    use std::os::unix::fs::DirBuilderExt;
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::fs::MetadataExt;
+    use std::os::unix::fs::PermissionsExt;
 
+    // Only replace a leftover socket, never some other file.
+    if let Ok(meta) = std::fs::symlink_metadata(path) {
+        if !meta.file_type().is_socket() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("{} exists and is not a socket", path.display()),
+            )
+            .into());
+        }
+    }
+
+    // Bind inside a directory only we can access and restrict the socket
+    // before moving it into place, so nobody else can connect in between.
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => std::path::Path::new("."),
+    };
+    let private_dir = parent.join(format!(".hc-ws-{}", nanoid::nanoid!(8)));
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&private_dir)?;
+    let bound = (|| {
+        let private_path = private_dir.join("s");
+        let listener = tokio::net::UnixListener::bind(&private_path)?;
+        std::fs::set_permissions(&private_path, std::fs::Permissions::from_mode(0o600))?;
+        std::fs::rename(&private_path, path)?;
+        std::io::Result::Ok(listener)
+    })();
+    let _ = std::fs::remove_dir_all(&private_dir);
+    let listener = bound?;
+
+    let meta = std::fs::symlink_metadata(path)?;
+    let socket_file = UnixSocketFile {
+        path: path.to_path_buf(),
+        dev: meta.dev(),
+        ino: meta.ino(),
+    };
+    let local_addr = unix_path_to_url(path);
+    let incoming = tokio_stream::wrappers::UnixListenerStream::new(listener).map({
+        let local_addr = local_addr.clone();
+        move |socket_result| {
+            // The socket file lives as long as the listener.
+            let _ = &socket_file;
+            // Unix socket peers are unnamed, so they are all
+            // identified by the listening socket.
+            let socket: Box<dyn RawSocket> = Box::new(socket_result?);
+            Ok((socket, local_addr.clone()))
+        }
+    });
+    Ok(listen(incoming, local_addr, config, None))
+}
+
+/// Removes the socket file of a unix listener once it is dropped,
+/// unless another socket has replaced it since.
+#[cfg(unix)]
+struct UnixSocketFile {
+    path: std::path::PathBuf,
+    dev: u64,
+    ino: u64,
+}
+
+#[cfg(unix)]
+impl Drop for UnixSocketFile {
+    fn drop(&mut self) {
+        use std::os::unix::fs::MetadataExt;
+        if let Ok(meta) = std::fs::symlink_metadata(&self.path) {
+            if meta.dev() == self.dev && meta.ino() == self.ino {
+                let _ = std::fs::remove_file(&self.path);
+            }
+        }
+    }
+}
+
+/// Upgrade the incoming raw sockets to websockets.
+fn listen(
+    incoming: impl futures::stream::Stream<Item = std::io::Result<(Box<dyn RawSocket>, Url2)>>,
+    local_addr: Url2,
+    config: Arc<WebsocketConfig>,
+    acceptor: Option<tokio_native_tls::TlsAcceptor>,
+) -> (
+    ListenerHandle,
+    impl futures::stream::Stream<Item = ListenerItem>,
+) {
     // Setup proper shutdown
     let (shutdown, valve) = Valve::new();
 
-    let buffered_listener = listener_stream
+    let buffered_listener = incoming
         .map_err(WebsocketError::from)
         .map_ok({
             let config = config.clone();
             let valve = valve.clone();
-            move |socket_result| connect(config.clone(), socket_result, valve.clone())
+            move |(socket, peer_addr)| {
+                connect(
+                    config.clone(),
+                    acceptor.clone(),
+                    socket,
+                    peer_addr,
+                    valve.clone(),
+                )
+            }
         })
         .try_buffer_unordered(config.max_pending_connections);
     tracing::debug!(sever_listening_on = ?local_addr);
@@ -224,13 +379,15 @@ async fn websocket_bind(
         config,
         local_addr,
     };
-    Ok((listener_handle, stream))
+    (listener_handle, stream)
 }
 
-#[instrument(skip(config, socket, valve))]
+#[instrument(skip(config, acceptor, socket, valve))]
 async fn connect(
     config: Arc<WebsocketConfig>,
-    socket: tokio::net::TcpStream,
+    acceptor: Option<tokio_native_tls::TlsAcceptor>,
+    socket: Box<dyn RawSocket>,
+    peer_addr: Url2,
     valve: Valve,
 ) -> WebsocketResult<Pair> {
     // TODO: find alternative to set the keepalive
@@ -239,19 +396,41 @@ async fn connect(
     // )))?;
     tracing::debug!(
         message = "accepted incoming raw socket",
-        remote_addr = %socket.peer_addr()?,
+        remote_addr = %peer_addr,
     );
-    let socket = tokio_tungstenite::accept_async_with_config(
+    let socket: Box<dyn RawSocket> = match acceptor {
+        Some(acceptor) => Box::new(
+            acceptor
+                .accept(socket)
+                .await
+                .map_err(|e| Error::new(ErrorKind::Other, e))?,
+        ),
+        None => socket,
+    };
+    let check_origin = {
+        let config = config.clone();
+        move |req: &Request, res: Response| -> Result<Response, ErrorResponse> {
+            let origin = req
+                .headers()
+                .get(tungstenite::http::header::ORIGIN)
+                .and_then(|o| o.to_str().ok());
+            if origin_allowed(config.allowed_origins.as_deref(), origin) {
+                Ok(res)
+            } else {
+                tracing::warn!(?origin, "rejecting websocket from disallowed origin");
+                let mut res = ErrorResponse::new(Some("origin not allowed".to_string()));
+                *res.status_mut() = tungstenite::http::StatusCode::FORBIDDEN;
+                Err(res)
+            }
+        }
+    };
+    let socket = tokio_tungstenite::accept_hdr_async_with_config(
         socket,
-        Some(tungstenite::protocol::WebSocketConfig {
-            max_send_queue: Some(config.max_send_queue),
-            max_message_size: Some(config.max_message_size),
-            max_frame_size: Some(config.max_frame_size),
-            ..Default::default()
-        }),
+        check_origin,
+        Some(config.to_tungstenite()),
     )
     .await
     .map_err(|e| Error::new(ErrorKind::Other, e))?;
 
-    Websocket::create_ends(config, socket, valve)
+    Websocket::create_ends(config, socket, peer_addr, valve)
 }
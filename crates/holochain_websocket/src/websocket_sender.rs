@@ -95,6 +95,9 @@ pub(crate) enum OutgoingMessage {
     Signal(SerializedBytes),
     Request(SerializedBytes, RegisterResponse, TxStaleRequest),
     Response(Option<SerializedBytes>, u64),
+    ResponseChunk(Vec<u8>, u64, bool),
+    ResponseChunkAck(u64, u64),
+    CancelResponse(u64),
     StaleRequest(u64),
     Pong(Vec<u8>),
     #[allow(dead_code)]
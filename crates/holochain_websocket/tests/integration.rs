@@ -8,6 +8,7 @@ use holochain_websocket::ListenerItem;
 use holochain_websocket::WebsocketConfig;
 use holochain_websocket::WebsocketError;
 use holochain_websocket::WebsocketListener;
+use holochain_websocket::WebsocketTlsConfig;
 use stream_cancel::Tripwire;
 use tracing::Instrument;
 use url2::url2;
@@ -121,6 +122,145 @@ async fn can_connect() {
         .expect("Failed to connect to server");
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn can_connect_tls() {
+    observability::test_run().ok();
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let tls = WebsocketTlsConfig {
+        cert_pem: cert.serialize_pem().unwrap().into_bytes(),
+        key_pem: cert.serialize_private_key_pem().into_bytes(),
+    };
+    let (handle, listener) = WebsocketListener::bind_with_handle(
+        url2!("wss://localhost:0"),
+        Arc::new(WebsocketConfig::default().tls(tls.clone())),
+    )
+    .await
+    .unwrap();
+    assert_eq!("wss", handle.local_addr().scheme());
+    let port = handle.local_addr().port().unwrap();
+    let s_jh = server_recv(Box::pin(listener));
+
+    // the client pins the self-signed certificate
+    let (mut sender, _) = connect(
+        url2!("wss://localhost:{}", port),
+        Arc::new(WebsocketConfig::default().tls(tls)),
+    )
+    .await
+    .expect("Failed to connect to server");
+    sender
+        .signal(TestString("Hey from client".into()))
+        .await
+        .unwrap();
+
+    // a client that doesn't trust the certificate is refused
+    assert!(connect(
+        url2!("wss://localhost:{}", port),
+        Arc::new(WebsocketConfig::default().scheme("wss")),
+    )
+    .await
+    .is_err());
+
+    handle.close();
+    s_jh.await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rejects_disallowed_origin() {
+    observability::test_run().ok();
+    let (handle, mut listener) = WebsocketListener::bind_with_handle(
+        url2!("ws://127.0.0.1:0"),
+        Arc::new(
+            WebsocketConfig::default().allowed_origins(vec!["https://good.example".to_string()]),
+        ),
+    )
+    .await
+    .unwrap();
+    let binding = handle.local_addr().clone();
+    tokio::task::spawn(async move { while let Some(_) = listener.next().await {} });
+
+    let with_origin = |origin: &str| {
+        tungstenite::http::Request::builder()
+            .uri(binding.as_str())
+            .header("Origin", origin)
+            .body(())
+            .unwrap()
+    };
+
+    let err = tokio_tungstenite::connect_async(with_origin("https://evil.example"))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        tungstenite::Error::Http(res) if res.status() == tungstenite::http::StatusCode::FORBIDDEN
+    ));
+    tokio_tungstenite::connect_async(with_origin("https://good.example"))
+        .await
+        .expect("allowed origin should connect");
+
+    // clients without an origin header, i.e. non-browsers, are let through
+    let _ = connect(binding, Arc::new(WebsocketConfig::default()))
+        .await
+        .expect("Failed to connect to server");
+    handle.close();
+}
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread")]
+async fn can_connect_unix() {
+    use std::os::unix::fs::PermissionsExt;
+    observability::test_run().ok();
+    let path = std::env::temp_dir().join(format!("hc-ws-{}.sock", nanoid::nanoid!()));
+    let (handle, mut listener) =
+        WebsocketListener::bind_unix_with_handle(&path, Arc::new(WebsocketConfig::default()))
+            .await
+            .unwrap();
+    assert_eq!("unix", handle.local_addr().scheme());
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(0o600, mode & 0o777);
+
+    tokio::task::spawn(async move {
+        let (_, mut receiver) = listener.next().await.unwrap().unwrap();
+        let (msg, resp) = receiver.next().await.unwrap();
+        let msg: TestString = msg.try_into().unwrap();
+        resp.respond(TestString(format!("echo: {}", msg.0)).try_into().unwrap())
+            .await
+            .unwrap();
+    });
+
+    let (mut sender, _) =
+        holochain_websocket::connect_unix(&path, Arc::new(WebsocketConfig::default()))
+            .await
+            .expect("Failed to connect to server");
+    let resp: TestString = sender.request(TestString("hi".into())).await.unwrap();
+    assert_eq!("echo: hi", resp.0);
+
+    handle.close();
+
+    // binding again replaces a stale socket file
+    let _ = std::fs::remove_file(&path);
+    drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+    let (handle, listener) =
+        WebsocketListener::bind_unix_with_handle(&path, Arc::new(WebsocketConfig::default()))
+            .await
+            .unwrap();
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(0o600, mode & 0o777);
+
+    // the socket file is removed once the listener is dropped
+    handle.close();
+    drop(listener);
+    assert!(!path.exists());
+
+    // other files are never replaced
+    std::fs::write(&path, b"not a socket").unwrap();
+    assert!(
+        WebsocketListener::bind_unix_with_handle(&path, Arc::new(WebsocketConfig::default()))
+            .await
+            .is_err()
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn can_send_signal() {
     observability::test_run().ok();
@@ -452,3 +592,205 @@ async fn cancel_response() {
     c_jh.await.unwrap();
     s_jh.await.unwrap();
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_stream_large_response() {
+    observability::test_run().ok();
+    let (handle, mut listener) = WebsocketListener::bind_with_handle(
+        url2!("ws://127.0.0.1:0"),
+        Arc::new(
+            WebsocketConfig::default()
+                .response_chunk_size(1024)
+                .response_chunk_window(1),
+        ),
+    )
+    .await
+    .unwrap();
+    let large = "a".repeat(200_000);
+    let response = TestString(large.clone());
+    let s_jh = tokio::task::spawn(async move {
+        let (_, mut receiver) = listener.next().await.unwrap().unwrap();
+        let (_, resp) = receiver.next().await.unwrap();
+        resp.respond(response.try_into().unwrap()).await.unwrap();
+    });
+
+    // - The response is larger than the client will accept in one message
+    let binding = handle.local_addr().clone();
+    let (mut sender, _receiver) = connect(
+        binding,
+        Arc::new(
+            WebsocketConfig::default()
+                .max_message_size(64 << 10)
+                .max_frame_size(64 << 10),
+        ),
+    )
+    .instrument(tracing::debug_span!("client:stream_response"))
+    .await
+    .unwrap();
+
+    let resp: TestString = sender
+        .request(TestString("Hey from client".into()))
+        .await
+        .unwrap();
+    assert_eq!(resp.0, large);
+    s_jh.await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn large_response_is_whole_for_clients_without_chunks() {
+    use futures::SinkExt;
+    use holochain_websocket::WireMessage;
+    use std::convert::TryFrom;
+
+    // A request from a client that doesn't know about chunks.
+    #[derive(serde::Serialize, serde::Deserialize, SerializedBytes)]
+    #[serde(tag = "type")]
+    enum OldWireMessage {
+        Request {
+            id: u64,
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        },
+    }
+
+    observability::test_run().ok();
+    let (handle, mut listener) = WebsocketListener::bind_with_handle(
+        url2!("ws://127.0.0.1:0"),
+        Arc::new(
+            WebsocketConfig::default()
+                .response_chunk_size(1024)
+                .response_chunk_window(1),
+        ),
+    )
+    .await
+    .unwrap();
+    let large = "a".repeat(200_000);
+    let response = TestString(large.clone());
+    let s_jh = tokio::task::spawn(async move {
+        let (_, mut receiver) = listener.next().await.unwrap().unwrap();
+        let (_, resp) = receiver.next().await.unwrap();
+        resp.respond(response.try_into().unwrap()).await.unwrap();
+        receiver
+    });
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(handle.local_addr().as_str())
+        .await
+        .unwrap();
+    let data: SerializedBytes = TestString("Hey from client".into()).try_into().unwrap();
+    let request: SerializedBytes = OldWireMessage::Request {
+        id: 0,
+        data: UnsafeBytes::from(data).into(),
+    }
+    .try_into()
+    .unwrap();
+    socket
+        .send(tungstenite::Message::Binary(
+            UnsafeBytes::from(request).into(),
+        ))
+        .await
+        .unwrap();
+
+    // - The response isn't chunked
+    let bytes = loop {
+        match socket.next().await.unwrap().unwrap() {
+            tungstenite::Message::Binary(bytes) => break bytes,
+            _ => continue,
+        }
+    };
+    let msg = WireMessage::try_from(SerializedBytes::from(UnsafeBytes::from(bytes))).unwrap();
+    let data = match msg {
+        WireMessage::Response {
+            id: 0,
+            data: Some(data),
+        } => data,
+        _ => panic!("expected a whole response"),
+    };
+    let resp = TestString::try_from(SerializedBytes::from(UnsafeBytes::from(data))).unwrap();
+    assert_eq!(resp.0, large);
+    s_jh.await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reject_streamed_response_over_max_size() {
+    observability::test_run().ok();
+    let (handle, mut listener) = WebsocketListener::bind_with_handle(
+        url2!("ws://127.0.0.1:0"),
+        Arc::new(
+            WebsocketConfig::default()
+                .response_chunk_size(1024)
+                .response_chunk_window(1),
+        ),
+    )
+    .await
+    .unwrap();
+    let s_jh = tokio::task::spawn(async move {
+        let (_, mut receiver) = listener.next().await.unwrap().unwrap();
+        let (_, resp) = receiver.next().await.unwrap();
+        let response = TestString("a".repeat(200_000));
+        let r = resp.respond(response.try_into().unwrap()).await;
+        // - The client cancels once it has too much
+        assert!(matches!(r, Err(WebsocketError::RespCanceled)));
+        receiver
+    });
+
+    // - The client won't put together a response this large
+    let binding = handle.local_addr().clone();
+    let (mut sender, _receiver) = connect(
+        binding,
+        Arc::new(WebsocketConfig::default().max_response_size(10_000)),
+    )
+    .instrument(tracing::debug_span!("client:reject_streamed_response"))
+    .await
+    .unwrap();
+
+    let r = sender
+        .request::<_, TestString>(TestString("Hey from client".into()))
+        .await;
+    assert!(matches!(r, Err(WebsocketError::FailedToRecvResp)));
+    s_jh.await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cancel_streamed_response() {
+    observability::test_run().ok();
+    let (handle, mut listener) = WebsocketListener::bind_with_handle(
+        url2!("ws://127.0.0.1:0"),
+        Arc::new(
+            WebsocketConfig::default()
+                .response_chunk_size(16)
+                .response_chunk_window(1),
+        ),
+    )
+    .await
+    .unwrap();
+    let s_jh = tokio::task::spawn(async move {
+        let (_, mut receiver) = listener.next().await.unwrap().unwrap();
+        let (_, resp) = receiver.next().await.unwrap();
+        // - Takes far longer to stream than the client will wait
+        let response = TestString("a".repeat(1_000_000));
+        let r = tokio::time::timeout(
+            std::time::Duration::from_secs(10),
+            resp.respond(response.try_into().unwrap()),
+        )
+        .await
+        .expect("streaming should stop when the client gives up");
+        assert!(matches!(r, Err(WebsocketError::RespCanceled)));
+        // - Keep the connection open until the check is done
+        receiver
+    });
+
+    let binding = handle.local_addr().clone();
+    let (mut sender, _receiver) = connect(binding, Arc::new(WebsocketConfig::default()))
+        .instrument(tracing::debug_span!("client:cancel_streamed_response"))
+        .await
+        .unwrap();
+
+    let r = sender
+        .request_timeout::<_, TestString>(
+            TestString("Hey from client".into()),
+            std::time::Duration::from_millis(100),
+        )
+        .await;
+    assert!(matches!(r, Err(WebsocketError::RespTimeout)));
+    s_jh.await.unwrap();
+}
@@ -1,2 +1,255 @@
-#[derive(Default, Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
-pub struct CrdtType;
+//! Types for merging concurrent updates to an entry
+//!
+//! Each entry def declares a [`CrdtType`]. When an entry is read by its entry hash,
+//! the updates to it are followed to the latest versions, and if there is more
+//! than one they are merged according to the [`CrdtType`] into a [`MergedEntry`].
+
+use crate::element::Element;
+use crate::entry::Entry;
+use holo_hash::AgentPubKey;
+use holochain_serialized_bytes::prelude::*;
+use serde_bytes::ByteBuf;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(from = "CrdtTypeCompat")]
+/// How concurrent updates to an app entry are merged when it is read.
+pub enum CrdtType {
+    /// Updates aren't merged.
+    /// A `get` of the entry returns the entry itself even if it has been updated.
+    NoMerge,
+    /// A last writer wins register.
+    /// A `get` of the entry follows its updates and returns the newest
+    /// of the latest versions, by header timestamp and then header hash.
+    LastWriterWins,
+    /// A grow only set. The entry must serialize as a [`GrowOnlySet`].
+    /// A `get` of the entry returns the newest of the latest versions,
+    /// and `get_merged` returns their union.
+    GrowOnlySet,
+    /// A grow only counter. The entry must serialize as a [`GrowOnlyCounter`].
+    /// A `get` of the entry returns the newest of the latest versions,
+    /// and `get_merged` returns the highest count of each agent across them.
+    GrowOnlyCounter,
+}
+
+impl CrdtType {
+    /// Does a `get` of an entry of this type follow its updates?
+    pub fn merges(&self) -> bool {
+        !matches!(self, Self::NoMerge)
+    }
+}
+
+impl Default for CrdtType {
+    fn default() -> Self {
+        Self::NoMerge
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+/// [`CrdtType`] used to be a unit struct,
+/// so entry defs serialized before it was an enum hold a unit value.
+/// Those are still read, as [`CrdtType::NoMerge`].
+enum CrdtTypeCompat {
+    Unit(()),
+    Enum(CrdtTypeEnum),
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename = "CrdtType")]
+enum CrdtTypeEnum {
+    NoMerge,
+    LastWriterWins,
+    GrowOnlySet,
+    GrowOnlyCounter,
+}
+
+impl From<CrdtTypeCompat> for CrdtType {
+    fn from(compat: CrdtTypeCompat) -> Self {
+        match compat {
+            CrdtTypeCompat::Unit(()) | CrdtTypeCompat::Enum(CrdtTypeEnum::NoMerge) => Self::NoMerge,
+            CrdtTypeCompat::Enum(CrdtTypeEnum::LastWriterWins) => Self::LastWriterWins,
+            CrdtTypeCompat::Enum(CrdtTypeEnum::GrowOnlySet) => Self::GrowOnlySet,
+            CrdtTypeCompat::Enum(CrdtTypeEnum::GrowOnlyCounter) => Self::GrowOnlyCounter,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+/// The latest versions of an entry merged according to its [`CrdtType`].
+/// The merged entry is kept apart from the versions it was merged from
+/// because it is not the entry of any of their headers when a
+/// [`GrowOnlySet`] or [`GrowOnlyCounter`] is merged from more than one version.
+pub struct MergedEntry {
+    /// The merged entry.
+    pub entry: Entry,
+    /// The latest versions the entry was merged from, oldest first,
+    /// by header timestamp and then header hash.
+    pub heads: Vec<Element>,
+}
+
+impl MergedEntry {
+    /// An element that wasn't merged with anything.
+    /// Returns `None` if the element has no entry.
+    pub fn from_element(element: Element) -> Option<Self> {
+        let entry = element.entry().as_option()?.clone();
+        Some(Self {
+            entry,
+            heads: vec![element],
+        })
+    }
+
+    /// The newest version the entry was merged from.
+    pub fn newest(&self) -> Option<&Element> {
+        self.heads.last()
+    }
+
+    /// Take the newest version the entry was merged from.
+    pub fn into_newest(mut self) -> Option<Element> {
+        self.heads.pop()
+    }
+}
+
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+/// The content of an entry with [`CrdtType::GrowOnlySet`].
+/// Items are kept serialized so the set can be merged without knowing their type.
+/// An app entry can wrap this in a newtype to give it its own entry def.
+pub struct GrowOnlySet(BTreeSet<ByteBuf>);
+
+impl GrowOnlySet {
+    /// An empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an item to the set.
+    /// Returns whether the item was new.
+    pub fn insert<T: serde::Serialize + std::fmt::Debug>(
+        &mut self,
+        item: &T,
+    ) -> Result<bool, SerializedBytesError> {
+        let item = holochain_serialized_bytes::encode(item)?;
+        Ok(self.0.insert(ByteBuf::from(item)))
+    }
+
+    /// Is this item in the set?
+    pub fn contains<T: serde::Serialize + std::fmt::Debug>(
+        &self,
+        item: &T,
+    ) -> Result<bool, SerializedBytesError> {
+        let item = holochain_serialized_bytes::encode(item)?;
+        Ok(self.0.contains(&ByteBuf::from(item)))
+    }
+
+    /// All the items in the set.
+    pub fn items<T: serde::de::DeserializeOwned>(&self) -> Result<Vec<T>, SerializedBytesError> {
+        self.0
+            .iter()
+            .map(|item| holochain_serialized_bytes::decode(item.as_ref()))
+            .collect()
+    }
+
+    /// The number of items in the set.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Is the set empty?
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Add every item of another set to this one.
+    pub fn merge(&mut self, other: Self) {
+        self.0.extend(other.0);
+    }
+}
+
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+/// The content of an entry with [`CrdtType::GrowOnlyCounter`].
+/// Each agent only increments their own count so concurrent
+/// increments are never lost when versions are merged.
+/// An app entry can wrap this in a newtype to give it its own entry def.
+pub struct GrowOnlyCounter(BTreeMap<AgentPubKey, u64>);
+
+impl GrowOnlyCounter {
+    /// A counter at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add to the count of this agent.
+    pub fn increment(&mut self, agent: AgentPubKey, by: u64) {
+        let count = self.0.entry(agent).or_insert(0);
+        *count = count.saturating_add(by);
+    }
+
+    /// The count of a single agent.
+    pub fn count_of(&self, agent: &AgentPubKey) -> u64 {
+        self.0.get(agent).copied().unwrap_or(0)
+    }
+
+    /// The total of every agent's count.
+    pub fn value(&self) -> u64 {
+        self.0
+            .values()
+            .fold(0u64, |total, count| total.saturating_add(*count))
+    }
+
+    /// Keep the highest count of each agent from this and another counter.
+    pub fn merge(&mut self, other: Self) {
+        for (agent, count) in other.0 {
+            let ours = self.0.entry(agent).or_insert(0);
+            *ours = std::cmp::max(*ours, count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grow_only_set_merge() {
+        let mut a = GrowOnlySet::new();
+        a.insert(&"apple".to_string()).unwrap();
+        a.insert(&"pear".to_string()).unwrap();
+        let mut b = GrowOnlySet::new();
+        b.insert(&"pear".to_string()).unwrap();
+        assert!(b.insert(&"fig".to_string()).unwrap());
+        assert!(!b.insert(&"fig".to_string()).unwrap());
+
+        a.merge(b);
+        assert_eq!(a.len(), 3);
+        assert!(a.contains(&"fig".to_string()).unwrap());
+        let mut items: Vec<String> = a.items().unwrap();
+        items.sort();
+        assert_eq!(items, vec!["apple", "fig", "pear"]);
+    }
+
+    #[test]
+    fn grow_only_counter_merge() {
+        let alice = AgentPubKey::from_raw_36(vec![1; 36]);
+        let bob = AgentPubKey::from_raw_36(vec![2; 36]);
+
+        // Both start from alice at 2
+        let mut base = GrowOnlyCounter::new();
+        base.increment(alice.clone(), 2);
+
+        // Concurrently alice adds 1 and bob adds 5
+        let mut a = base.clone();
+        a.increment(alice.clone(), 1);
+        let mut b = base;
+        b.increment(bob.clone(), 5);
+
+        a.merge(b);
+        assert_eq!(a.count_of(&alice), 3);
+        assert_eq!(a.count_of(&bob), 5);
+        assert_eq!(a.value(), 8);
+    }
+}
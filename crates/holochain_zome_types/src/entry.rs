@@ -40,6 +40,10 @@ pub struct GetOptions {
     /// If it is false you will get whatever is locally
     /// available on this conductor.
     pub strategy: GetStrategy,
+    /// If set, the get asks the network until enough
+    /// authorities agree and fails if they don't.
+    #[serde(default)]
+    pub quorum: Option<crate::quorum::Quorum>,
 }
 
 impl GetOptions {
@@ -54,6 +58,7 @@ impl GetOptions {
     pub fn latest() -> Self {
         Self {
             strategy: GetStrategy::Latest,
+            quorum: None,
         }
     }
     /// Gets the content but does not
@@ -66,8 +71,15 @@ impl GetOptions {
     pub fn content() -> Self {
         Self {
             strategy: GetStrategy::Content,
+            quorum: None,
         }
     }
+    /// Require several authorities to agree on the result.
+    /// See [`crate::quorum::Quorum`].
+    pub fn with_quorum(mut self, quorum: crate::quorum::Quorum) -> Self {
+        self.quorum = Some(quorum);
+        self
+    }
 }
 
 impl Default for GetOptions {
@@ -218,6 +230,28 @@ impl GetInput {
     }
 }
 
+/// Zome IO inner for get_many and get_details_many calls.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct GetManyInput {
+    /// The DHT hashes to get, in the order the results are wanted.
+    pub any_dht_hashes: Vec<holo_hash::AnyDhtHash>,
+    /// Options for every get.
+    pub get_options: crate::entry::GetOptions,
+}
+
+impl GetManyInput {
+    /// Constructor.
+    pub fn new(
+        any_dht_hashes: Vec<holo_hash::AnyDhtHash>,
+        get_options: crate::entry::GetOptions,
+    ) -> Self {
+        Self {
+            any_dht_hashes,
+            get_options,
+        }
+    }
+}
+
 /// Zome IO inner for update.
 #[derive(PartialEq, Debug, Deserialize, Serialize, Clone)]
 pub struct UpdateInput {
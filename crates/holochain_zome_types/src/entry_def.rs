@@ -72,7 +72,7 @@ pub struct EntryDef {
     pub id: EntryDefId,
     /// Public or Private
     pub visibility: EntryVisibility,
-    /// TBD -- Special types of conflict resolution support from Holochain (e.g. Single-Author, )
+    /// How concurrent updates to entries of this type are merged when they are read
     pub crdt_type: CrdtType,
     /// how many validations to receive before considered "network saturated" (MAX value of 50?)
     pub required_validations: RequiredValidations,
@@ -187,7 +187,7 @@ mod tests {
             vec![EntryDef {
                 id: "bar".into(),
                 visibility: EntryVisibility::Public,
-                crdt_type: CrdtType,
+                crdt_type: CrdtType::NoMerge,
                 required_validations: 5.into(),
                 required_validation_type: RequiredValidationType::default(),
             }]
@@ -196,4 +196,42 @@ mod tests {
         let guest_output = ExternIO::encode(&defs_callback_result).unwrap();
         assert_eq!(defs_callback_result, guest_output.into(),);
     }
+
+    #[test]
+    fn crdt_type_unit_form_is_read() {
+        // - How an entry def was serialized when `CrdtType` was a unit struct.
+        #[derive(serde::Serialize)]
+        struct UnitCrdtType;
+        #[derive(serde::Serialize)]
+        struct OldEntryDef {
+            id: super::EntryDefId,
+            visibility: EntryVisibility,
+            crdt_type: UnitCrdtType,
+            required_validations: super::RequiredValidations,
+            required_validation_type: RequiredValidationType,
+        }
+        let old = OldEntryDef {
+            id: "bar".into(),
+            visibility: EntryVisibility::Public,
+            crdt_type: UnitCrdtType,
+            required_validations: 5.into(),
+            required_validation_type: RequiredValidationType::default(),
+        };
+        let bytes = holochain_serialized_bytes::encode(&old).unwrap();
+        let entry_def: EntryDef = holochain_serialized_bytes::decode(&bytes).unwrap();
+        assert_eq!(entry_def.crdt_type, CrdtType::NoMerge);
+
+        for crdt_type in [
+            CrdtType::NoMerge,
+            CrdtType::LastWriterWins,
+            CrdtType::GrowOnlySet,
+            CrdtType::GrowOnlyCounter,
+        ]
+        .iter()
+        {
+            let bytes = holochain_serialized_bytes::encode(crdt_type).unwrap();
+            let decoded: CrdtType = holochain_serialized_bytes::decode(&bytes).unwrap();
+            assert_eq!(&decoded, crdt_type);
+        }
+    }
 }
@@ -439,9 +439,7 @@ fixturator!(
 
 fixturator!(
     CrdtType;
-    curve Empty CrdtType;
-    curve Unpredictable CrdtType;
-    curve Predictable CrdtType;
+    unit variants [ NoMerge LastWriterWins GrowOnlySet GrowOnlyCounter ] empty NoMerge;
 );
 
 fixturator!(
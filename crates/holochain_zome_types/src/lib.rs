@@ -42,6 +42,7 @@ pub mod migrate_agent;
 pub mod post_commit;
 pub mod prelude;
 pub mod query;
+pub mod quorum;
 pub mod request;
 pub mod signal;
 pub mod signature;
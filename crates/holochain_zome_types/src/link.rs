@@ -30,8 +30,61 @@ impl LinkTag {
     pub fn into_inner(self) -> Vec<u8> {
         self.0
     }
+
+    /// New tag for a typed link.
+    /// The tag is [`TYPED_LINK_TAG_PREFIX`], then the length and name of the link type,
+    /// then the payload, so the links of one type can be found by [`Self::typed_prefix`].
+    /// Returns `None` if the name is longer than [`MAX_LINK_TYPE_LEN`] bytes.
+    pub fn typed(link_type: &str, payload: &[u8]) -> Option<Self> {
+        let mut tag = Self::typed_prefix(link_type)?;
+        tag.0.extend_from_slice(payload);
+        Some(tag)
+    }
+
+    /// The start of the tag of every link of this type.
+    /// Returns `None` if the name is longer than [`MAX_LINK_TYPE_LEN`] bytes.
+    pub fn typed_prefix(link_type: &str) -> Option<Self> {
+        if link_type.len() > MAX_LINK_TYPE_LEN {
+            return None;
+        }
+        let mut tag = Vec::with_capacity(TYPED_LINK_TAG_PREFIX.len() + 1 + link_type.len());
+        tag.extend_from_slice(&TYPED_LINK_TAG_PREFIX);
+        tag.push(link_type.len() as u8);
+        tag.extend_from_slice(link_type.as_bytes());
+        Some(Self(tag))
+    }
+
+    /// The name of the link type if this is the tag of a typed link.
+    pub fn link_type(&self) -> Option<&str> {
+        self.split_typed().map(|(link_type, _)| link_type)
+    }
+
+    /// The payload if this is the tag of a typed link.
+    pub fn typed_payload(&self) -> Option<&[u8]> {
+        self.split_typed().map(|(_, payload)| payload)
+    }
+
+    fn split_typed(&self) -> Option<(&str, &[u8])> {
+        let prefix_len = TYPED_LINK_TAG_PREFIX.len();
+        if self.0.len() <= prefix_len || self.0[..prefix_len] != TYPED_LINK_TAG_PREFIX {
+            return None;
+        }
+        let len = self.0[prefix_len] as usize;
+        let start = prefix_len + 1;
+        if self.0.len() < start + len {
+            return None;
+        }
+        let link_type = std::str::from_utf8(&self.0[start..start + len]).ok()?;
+        Some((link_type, &self.0[start + len..]))
+    }
 }
 
+/// The tag of every typed link starts with these bytes, "hdk.link".
+pub const TYPED_LINK_TAG_PREFIX: [u8; 8] = [0x68, 0x64, 0x6b, 0x2e, 0x6c, 0x69, 0x6e, 0x6b];
+
+/// The most bytes in the name of a link type.
+pub const MAX_LINK_TYPE_LEN: usize = u8::MAX as usize;
+
 impl From<Vec<u8>> for LinkTag {
     fn from(b: Vec<u8>) -> Self {
         Self(b)
@@ -73,6 +126,14 @@ pub struct Link {
     pub create_link_hash: HeaderHash,
 }
 
+impl Link {
+    /// The key links are paged by.
+    /// See [`LinkFilter`].
+    pub fn page_key(&self) -> (i64, &HeaderHash) {
+        (self.timestamp.to_sql_ms_lossy(), &self.create_link_hash)
+    }
+}
+
 /// Zome IO inner type for link creation.
 #[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub struct CreateLinkInput {
@@ -99,6 +160,13 @@ impl CreateLinkInput {
 pub struct GetLinksInput {
     pub base_address: holo_hash::EntryHash,
     pub tag_prefix: Option<crate::link::LinkTag>,
+    /// If set, the links are fetched until enough
+    /// authorities agree on them.
+    #[serde(default)]
+    pub quorum: Option<crate::quorum::Quorum>,
+    /// Narrows down and pages the links.
+    #[serde(default)]
+    pub filter: LinkFilter,
 }
 
 impl GetLinksInput {
@@ -109,6 +177,122 @@ impl GetLinksInput {
         Self {
             base_address,
             tag_prefix,
+            quorum: None,
+            filter: LinkFilter::default(),
+        }
+    }
+
+    /// Only get the links matching this filter.
+    pub fn with_filter(mut self, filter: LinkFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Require several authorities to agree on the links.
+    pub fn with_quorum(mut self, quorum: crate::quorum::Quorum) -> Self {
+        self.quorum = Some(quorum);
+        self
+    }
+}
+
+/// Narrows down and pages the links returned by a get links call.
+/// Authorities apply the filter before answering, so only the
+/// matching links are sent over the network.
+///
+/// Times are compared to the millisecond. Links are paged in the order they
+/// were created, and links created in the same millisecond by their hash.
+#[derive(PartialEq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LinkFilter {
+    /// Only links created after this time.
+    pub after: Option<crate::Timestamp>,
+    /// Only links created before this time.
+    pub before: Option<crate::Timestamp>,
+    /// Only links created by one of these agents.
+    /// Empty for links by any agent.
+    pub authors: Vec<holo_hash::AgentPubKey>,
+    /// Return at most this many links.
+    pub limit: Option<u32>,
+    /// Only links after this one.
+    /// Use the last link of a page to get the next page.
+    pub cursor: Option<LinkCursor>,
+}
+
+impl LinkFilter {
+    /// Only links created after this time.
+    pub fn after(mut self, after: crate::Timestamp) -> Self {
+        self.after = Some(after);
+        self
+    }
+
+    /// Only links created before this time.
+    pub fn before(mut self, before: crate::Timestamp) -> Self {
+        self.before = Some(before);
+        self
+    }
+
+    /// Only links created by this agent.
+    /// Can be called more than once to allow several agents.
+    pub fn author(mut self, author: holo_hash::AgentPubKey) -> Self {
+        self.authors.push(author);
+        self
+    }
+
+    /// A page of at most `limit` links after the `cursor`,
+    /// or from the first link if there is no cursor.
+    pub fn page(mut self, limit: u32, cursor: Option<LinkCursor>) -> Self {
+        self.limit = Some(limit);
+        self.cursor = cursor;
+        self
+    }
+
+    /// Are the links limited to a page?
+    pub fn is_paged(&self) -> bool {
+        self.limit.is_some() || self.cursor.is_some()
+    }
+
+    /// Does a link created by `author` at `timestamp` pass the filter?
+    /// The limit is not checked here.
+    pub fn matches(
+        &self,
+        author: &holo_hash::AgentPubKey,
+        timestamp: crate::Timestamp,
+        create_link_hash: &HeaderHash,
+    ) -> bool {
+        let ms = timestamp.to_sql_ms_lossy();
+        self.after
+            .map_or(true, |after| ms > after.to_sql_ms_lossy())
+            && self
+                .before
+                .map_or(true, |before| ms < before.to_sql_ms_lossy())
+            && (self.authors.is_empty() || self.authors.contains(author))
+            && self
+                .cursor
+                .as_ref()
+                .map_or(true, |cursor| (ms, create_link_hash) > cursor.page_key())
+    }
+}
+
+/// The position of a link in a page of links.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct LinkCursor {
+    /// When the link was created.
+    pub timestamp: crate::Timestamp,
+    /// The hash of the link's create header.
+    pub create_link_hash: HeaderHash,
+}
+
+impl LinkCursor {
+    /// The key links are paged by.
+    pub fn page_key(&self) -> (i64, &HeaderHash) {
+        (self.timestamp.to_sql_ms_lossy(), &self.create_link_hash)
+    }
+}
+
+impl From<&Link> for LinkCursor {
+    fn from(link: &Link) -> Self {
+        Self {
+            timestamp: link.timestamp,
+            create_link_hash: link.create_link_hash.clone(),
         }
     }
 }
@@ -164,3 +348,32 @@ impl rusqlite::ToSql for LinkTag {
         Ok(rusqlite::types::ToSqlOutput::Borrowed((&self.0[..]).into()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_link_tag_round_trip() {
+        assert_eq!("hdk.link".as_bytes(), TYPED_LINK_TAG_PREFIX);
+
+        let tag = LinkTag::typed("comment", &[1, 2, 3]).unwrap();
+        assert_eq!(tag.link_type(), Some("comment"));
+        assert_eq!(tag.typed_payload(), Some(&[1, 2, 3][..]));
+        assert!(tag
+            .as_ref()
+            .starts_with(LinkTag::typed_prefix("comment").unwrap().as_ref()));
+        // A longer name with the same start has a different prefix.
+        assert!(!tag
+            .as_ref()
+            .starts_with(LinkTag::typed_prefix("comments").unwrap().as_ref()));
+
+        let empty = LinkTag::typed("like", &[]).unwrap();
+        assert_eq!(empty.link_type(), Some("like"));
+        assert_eq!(empty.typed_payload(), Some(&[][..]));
+
+        assert_eq!(LinkTag::new("untyped").link_type(), None);
+        assert_eq!(LinkTag::new(TYPED_LINK_TAG_PREFIX).link_type(), None);
+        assert!(LinkTag::typed(&"x".repeat(MAX_LINK_TYPE_LEN + 1), &[]).is_none());
+    }
+}
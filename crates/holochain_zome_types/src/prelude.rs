@@ -24,6 +24,7 @@ pub use crate::migrate_agent::*;
 pub use crate::post_commit::*;
 pub use crate::query::ChainQueryFilter as QueryFilter;
 pub use crate::query::*;
+pub use crate::quorum::*;
 pub use crate::request::*;
 pub use crate::signal::*;
 pub use crate::signature::*;
@@ -4,8 +4,11 @@ use crate::header::EntryType;
 use crate::header::Header;
 use crate::header::HeaderType;
 use crate::warrant::Warrant;
+use holo_hash::EntryHash;
 use holo_hash::HeaderHash;
 pub use holochain_serialized_bytes::prelude::*;
+use std::ops::Bound;
+use std::ops::RangeBounds;
 
 /// Query arguments
 #[derive(
@@ -14,19 +17,113 @@ pub use holochain_serialized_bytes::prelude::*;
 #[non_exhaustive]
 pub struct ChainQueryFilter {
     /// The range of source chain sequence numbers to match.
-    /// Inclusive start, exclusive end.
-    // TODO: can we generalize this over RangeBounds to allow unbounded ranges?
-    pub sequence_range: Option<std::ops::Range<u32>>,
-    /// Filter by EntryType
+    pub sequence_range: SequenceRange,
+    /// Filter by any of these EntryTypes
     // NB: if this filter is set, you can't verify the results, so don't
     //     use this in validation
-    pub entry_type: Option<EntryType>,
-    /// Filter by HeaderType
+    pub entry_type: Option<Vec<EntryType>>,
+    /// Filter by any of these entry hashes
     // NB: if this filter is set, you can't verify the results, so don't
     //     use this in validation
-    pub header_type: Option<HeaderType>,
+    pub entry_hashes: Option<Vec<EntryHash>>,
+    /// Filter by any of these HeaderTypes
+    // NB: if this filter is set, you can't verify the results, so don't
+    //     use this in validation
+    pub header_type: Option<Vec<HeaderType>>,
     /// Include the entries in the elements
     pub include_entries: bool,
+    /// Return the newest elements first.
+    /// Only applied by `query` on an agent's own chain.
+    pub order_descending: bool,
+    /// Return at most this many elements, after ordering.
+    /// Only applied by `query` on an agent's own chain.
+    pub limit: Option<u32>,
+}
+
+/// A range of source chain sequence numbers.
+/// Each end can be inclusive, exclusive or unbounded,
+/// and it can be made from any range of `u32` e.g. `3..`, `..=10` or `2..5`.
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct SequenceRange {
+    /// The start of the range.
+    pub start: Bound<u32>,
+    /// The end of the range.
+    pub end: Bound<u32>,
+}
+
+impl SequenceRange {
+    /// Create a range from any range of `u32`.
+    pub fn new<R: RangeBounds<u32>>(range: R) -> Self {
+        Self {
+            start: cloned_bound(range.start_bound()),
+            end: cloned_bound(range.end_bound()),
+        }
+    }
+
+    /// The range of every sequence number.
+    pub fn unbounded() -> Self {
+        Self {
+            start: Bound::Unbounded,
+            end: Bound::Unbounded,
+        }
+    }
+
+    /// Is this range every sequence number?
+    pub fn is_unbounded(&self) -> bool {
+        matches!((self.start, self.end), (Bound::Unbounded, Bound::Unbounded))
+    }
+
+    /// The lowest sequence number in the range, if it has one.
+    /// This can be past the end of the range if the range is empty.
+    pub fn min(&self) -> Option<i64> {
+        match self.start {
+            Bound::Included(start) => Some(start as i64),
+            Bound::Excluded(start) => Some(start as i64 + 1),
+            Bound::Unbounded => None,
+        }
+    }
+
+    /// The highest sequence number in the range, if it has one.
+    /// This can be before the start of the range if the range is empty.
+    pub fn max(&self) -> Option<i64> {
+        match self.end {
+            Bound::Included(end) => Some(end as i64),
+            Bound::Excluded(end) => Some(end as i64 - 1),
+            Bound::Unbounded => None,
+        }
+    }
+}
+
+impl Default for SequenceRange {
+    fn default() -> Self {
+        Self::unbounded()
+    }
+}
+
+impl RangeBounds<u32> for SequenceRange {
+    fn start_bound(&self) -> Bound<&u32> {
+        as_ref_bound(&self.start)
+    }
+
+    fn end_bound(&self) -> Bound<&u32> {
+        as_ref_bound(&self.end)
+    }
+}
+
+fn as_ref_bound(bound: &Bound<u32>) -> Bound<&u32> {
+    match bound {
+        Bound::Included(b) => Bound::Included(b),
+        Bound::Excluded(b) => Bound::Excluded(b),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+fn cloned_bound(bound: Bound<&u32>) -> Bound<u32> {
+    match bound {
+        Bound::Included(b) => Bound::Included(*b),
+        Bound::Excluded(b) => Bound::Excluded(*b),
+        Bound::Unbounded => Bound::Unbounded,
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
@@ -126,21 +223,74 @@ impl ChainQueryFilter {
         }
     }
 
-    /// Filter on sequence range
-    pub fn sequence_range(mut self, sequence_range: std::ops::Range<u32>) -> Self {
-        self.sequence_range = Some(sequence_range);
+    /// Filter on sequence range, e.g. `2..5`, `3..` or `..=10`
+    pub fn sequence_range<R: RangeBounds<u32>>(mut self, sequence_range: R) -> Self {
+        self.sequence_range = SequenceRange::new(sequence_range);
         self
     }
 
-    /// Filter on entry type
+    /// Filter on entry type.
+    /// Call this more than once to match any of several entry types.
     pub fn entry_type(mut self, entry_type: EntryType) -> Self {
-        self.entry_type = Some(entry_type);
+        self.entry_type
+            .get_or_insert_with(Vec::new)
+            .push(entry_type);
+        self
+    }
+
+    /// Filter on any of these entry types
+    pub fn entry_types<I: IntoIterator<Item = EntryType>>(mut self, entry_types: I) -> Self {
+        self.entry_type
+            .get_or_insert_with(Vec::new)
+            .extend(entry_types);
+        self
+    }
+
+    /// Filter on entry hash.
+    /// Call this more than once to match any of several entry hashes.
+    pub fn entry_hash(mut self, entry_hash: EntryHash) -> Self {
+        self.entry_hashes
+            .get_or_insert_with(Vec::new)
+            .push(entry_hash);
         self
     }
 
-    /// Filter on header type
+    /// Filter on any of these entry hashes
+    pub fn entry_hashes<I: IntoIterator<Item = EntryHash>>(mut self, entry_hashes: I) -> Self {
+        self.entry_hashes
+            .get_or_insert_with(Vec::new)
+            .extend(entry_hashes);
+        self
+    }
+
+    /// Filter on header type.
+    /// Call this more than once to match any of several header types.
     pub fn header_type(mut self, header_type: HeaderType) -> Self {
-        self.header_type = Some(header_type);
+        self.header_type
+            .get_or_insert_with(Vec::new)
+            .push(header_type);
+        self
+    }
+
+    /// Filter on any of these header types
+    pub fn header_types<I: IntoIterator<Item = HeaderType>>(mut self, header_types: I) -> Self {
+        self.header_type
+            .get_or_insert_with(Vec::new)
+            .extend(header_types);
+        self
+    }
+
+    /// Return the newest elements first
+    pub fn descending(mut self) -> Self {
+        self.order_descending = true;
+        self
+    }
+
+    /// Return at most `limit` elements.
+    /// Combined with [`Self::descending`] this gets the latest elements,
+    /// e.g. the last 10 posts.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
         self
     }
 
@@ -152,27 +302,45 @@ impl ChainQueryFilter {
 
     /// Perform the boolean check which this filter represents
     pub fn check(&self, header: &Header) -> bool {
-        let check_range = self
-            .sequence_range
-            .as_ref()
-            .map(|range| range.contains(&header.header_seq()))
-            .unwrap_or(true);
+        let check_range = self.sequence_range.contains(&header.header_seq());
         let check_header_type = self
             .header_type
             .as_ref()
-            .map(|header_type| header.header_type() == *header_type)
+            .map(|header_types| header_types.contains(&header.header_type()))
             .unwrap_or(true);
         let check_entry_type = self
             .entry_type
             .as_ref()
-            .map(|entry_type| {
+            .map(|entry_types| {
                 header
                     .entry_type()
-                    .map(|header_entry_type| *header_entry_type == *entry_type)
+                    .map(|header_entry_type| entry_types.contains(header_entry_type))
                     .unwrap_or(false)
             })
             .unwrap_or(true);
-        check_range && check_header_type && check_entry_type
+        let check_entry_hash = self
+            .entry_hashes
+            .as_ref()
+            .map(|entry_hashes| {
+                header
+                    .entry_hash()
+                    .map(|entry_hash| entry_hashes.contains(entry_hash))
+                    .unwrap_or(false)
+            })
+            .unwrap_or(true);
+        check_range && check_header_type && check_entry_type && check_entry_hash
+    }
+
+    /// Apply the ordering and limit of this filter to elements
+    /// which already passed [`Self::check`], in any order.
+    pub fn order_and_limit<T, F: Fn(&T) -> u32>(&self, items: &mut Vec<T>, header_seq: F) {
+        items.sort_by_key(|item| header_seq(item));
+        if self.order_descending {
+            items.reverse();
+        }
+        if let Some(limit) = self.limit {
+            items.truncate(limit as usize);
+        }
     }
 }
 
@@ -186,6 +354,7 @@ mod tests {
     use ::fixt::prelude::*;
 
     use super::ChainQueryFilter;
+    use super::SequenceRange;
 
     /// Create three Headers with various properties.
     /// Also return the EntryTypes used to construct the first two headers.
@@ -298,6 +467,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn filter_by_open_and_inclusive_chain_sequence() {
+        let headers = fixtures();
+
+        let query_1 = ChainQueryFilter::new().sequence_range(3..);
+        let query_2 = ChainQueryFilter::new().sequence_range(..=1);
+        let query_3 = ChainQueryFilter::new().sequence_range(1..=3);
+        let query_4 = ChainQueryFilter::new().sequence_range(..);
+
+        assert_eq!(
+            map_query(&query_1, &headers),
+            [false, false, false, true, true, true].to_vec()
+        );
+        assert_eq!(
+            map_query(&query_2, &headers),
+            [true, true, false, false, false, false].to_vec()
+        );
+        assert_eq!(
+            map_query(&query_3, &headers),
+            [false, true, true, true, false, false].to_vec()
+        );
+        assert_eq!(
+            map_query(&query_4, &headers),
+            [true, true, true, true, true, true].to_vec()
+        );
+        assert_eq!(query_2.sequence_range.min(), None);
+        assert_eq!(query_2.sequence_range.max(), Some(1));
+        assert_eq!(SequenceRange::new(0..0).max(), Some(-1));
+    }
+
+    #[test]
+    fn filter_by_many_types_and_hashes() {
+        let headers = fixtures();
+
+        let query_1 = ChainQueryFilter::new()
+            .header_type(headers[0].header_type())
+            .header_type(headers[2].header_type());
+        let query_2 = ChainQueryFilter::new().entry_types(vec![
+            headers[0].entry_type().unwrap().to_owned(),
+            headers[1].entry_type().unwrap().to_owned(),
+        ]);
+        let query_3 = ChainQueryFilter::new()
+            .entry_hash(headers[0].entry_hash().unwrap().to_owned())
+            .entry_hash(headers[4].entry_hash().unwrap().to_owned());
+
+        assert_eq!(
+            map_query(&query_1, &headers),
+            [true, false, true, true, false, true].to_vec()
+        );
+        assert_eq!(
+            map_query(&query_2, &headers),
+            [true, true, false, true, true, false].to_vec()
+        );
+        assert_eq!(
+            map_query(&query_3, &headers),
+            [true, false, false, false, true, false].to_vec()
+        );
+    }
+
+    #[test]
+    fn order_and_limit() {
+        let mut seqs = vec![3, 0, 5, 1];
+
+        ChainQueryFilter::new().order_and_limit(&mut seqs, |s| *s);
+        assert_eq!(seqs, vec![0, 1, 3, 5]);
+
+        ChainQueryFilter::new()
+            .descending()
+            .limit(2)
+            .order_and_limit(&mut seqs, |s| *s);
+        assert_eq!(seqs, vec![5, 3]);
+    }
+
     #[test]
     fn filter_by_multi() {
         let headers = fixtures();
@@ -0,0 +1,102 @@
+//! Types for asking several authorities to agree on a read
+
+use holochain_serialized_bytes::prelude::*;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+/// How the answers of the authorities are combined.
+pub enum MergePolicy {
+    /// Wait for every authority asked, up to the timeout,
+    /// and merge all their answers so the result holds
+    /// the freshest metadata any of them know about.
+    Freshest,
+    /// Return as soon as the first answers arrive.
+    /// Faster but more likely to miss recent changes.
+    FirstAnswer,
+}
+
+impl Default for MergePolicy {
+    fn default() -> Self {
+        Self::Freshest
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+/// The consistency a read needs from the network.
+/// Useful for critical data like balances or membership
+/// where an answer from a single authority isn't enough.
+///
+/// A read with a quorum always goes to the network,
+/// even if the data is cached or this agent is an authority for it.
+///
+/// Reads are currently only answered by the agents joined to the
+/// same conductor, so a quorum of more than one is rejected
+/// and the timeout and merge policy have no effect yet.
+pub struct Quorum {
+    /// How many authorities must give exactly the same answer.
+    /// Reads with a quorum of zero, or more than one for now, are rejected.
+    pub min_agreeing: u8,
+    /// How long to wait for the authorities to answer.
+    /// Set to `None` for the network default.
+    pub timeout_ms: Option<u64>,
+    /// How the answers are combined.
+    pub merge: MergePolicy,
+}
+
+impl Quorum {
+    /// Require `min_agreeing` authorities to agree,
+    /// waiting for the freshest answer.
+    /// `min_agreeing` must be one until reads reach remote authorities.
+    pub fn new(min_agreeing: u8) -> Self {
+        Self {
+            min_agreeing,
+            timeout_ms: None,
+            merge: MergePolicy::Freshest,
+        }
+    }
+
+    /// Give up waiting for authorities after this long.
+    pub fn timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = Some(timeout_ms);
+        self
+    }
+
+    /// Return as soon as the first answers arrive.
+    pub fn first_answer(mut self) -> Self {
+        self.merge = MergePolicy::FirstAnswer;
+        self
+    }
+}
+
+impl Default for Quorum {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+/// How many authorities answered a read and how many of them agreed.
+pub struct QuorumReport {
+    /// The number of authorities that answered.
+    pub responded: u32,
+    /// The size of the largest group of authorities
+    /// which gave exactly the same answer.
+    pub agreeing: u32,
+}
+
+impl QuorumReport {
+    /// Did enough authorities agree?
+    pub fn is_reached(&self, quorum: &Quorum) -> bool {
+        self.agreeing >= quorum.min_agreeing as u32
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+/// The result of a read along with how the authorities answered.
+/// If the quorum wasn't reached the output is the best available
+/// merge of the answers that did arrive.
+pub struct QuorumOutput<T> {
+    /// The result of the read.
+    pub output: T,
+    /// How the authorities answered.
+    pub report: QuorumReport,
+}
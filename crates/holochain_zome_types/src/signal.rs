@@ -29,3 +29,63 @@ pub struct RemoteSignal {
     /// The signal to send.
     pub signal: crate::ExternIO,
 }
+
+/// Remote signal many agents, waiting for each of them to acknowledge
+/// delivery.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct RemoteSignalWithReceipts {
+    /// Agents to send the signal to.
+    pub agents: Vec<AgentPubKey>,
+    /// The signal to send.
+    pub signal: crate::ExternIO,
+    /// How hard to try delivering the signal.
+    pub options: RemoteSignalOptions,
+}
+
+/// How hard to try delivering a remote signal.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    SerializedBytes,
+)]
+pub struct RemoteSignalOptions {
+    /// How long to wait for each delivery attempt.
+    /// The host's default is used if this is not set.
+    pub timeout: Option<core::time::Duration>,
+    /// How many more times to try an agent after the first attempt fails,
+    /// before returning.
+    pub retries: u8,
+    /// If set, signals which could not be delivered are kept in the
+    /// cell's outbox for this long, and delivery is retried in the
+    /// background until it succeeds or the signal expires.
+    pub outbox_ttl: Option<core::time::Duration>,
+}
+
+/// The outcome of delivering a remote signal to a single agent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum RemoteSignalDelivery {
+    /// The agent received the signal.
+    Delivered,
+    /// The agent could not be reached, or refused the signal.
+    Unreachable,
+    /// The agent did not acknowledge the signal in time.
+    Timeout,
+}
+
+/// The delivery outcome of a remote signal for a single agent.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct RemoteSignalReceipt {
+    /// The agent the signal was sent to.
+    pub agent: AgentPubKey,
+    /// The outcome of the last delivery attempt.
+    pub delivery: RemoteSignalDelivery,
+    /// If the signal was not delivered and has been placed in the outbox,
+    /// the id of the outbox entry.
+    pub outbox_id: Option<u64>,
+}
@@ -334,7 +334,7 @@ impl Timestamp {
     pub fn to_sql_ms_lossy(&self) -> i64 {
         use std::time::Duration;
         let s = Duration::from_secs(self.0.max(0) as u64);
-        let ms = Duration::from_millis(self.1 as u64);
+        let ms = Duration::from_nanos(self.1 as u64);
         let ts = s.checked_add(ms).unwrap_or(s);
         ts.as_millis().clamp(0, i64::MAX as u128) as i64
     }
@@ -377,4 +377,10 @@ pub mod tests {
         let t2 = Timestamp(0, 0) + core::time::Duration::new(0, 1);
         assert_eq!(t2, Ok(Timestamp(0, 1)));
     }
+
+    #[test]
+    fn timestamp_to_sql_ms() {
+        assert_eq!(Timestamp(2, 345_678_999).to_sql_ms_lossy(), 2_345);
+        assert_eq!(Timestamp(-1, 0).to_sql_ms_lossy(), 0);
+    }
 }
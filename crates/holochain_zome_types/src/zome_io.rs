@@ -133,14 +133,32 @@ wasm_io_types! {
 
     fn get_details (zt::entry::GetInput) -> Option<zt::metadata::Details>;
 
+    // Get the details of many hashes at once, in the same order as the hashes.
+    fn get_details_many (zt::entry::GetManyInput) -> Vec<Option<zt::metadata::Details>>;
+
     fn get_link_details (zt::link::GetLinksInput) -> zt::link::LinkDetails;
 
     // Get links by entry hash from the cascade.
     fn get_links (zt::link::GetLinksInput) -> zt::link::Links;
 
+    // Count the live links matching the input, without fetching them.
+    fn count_links (zt::link::GetLinksInput) -> u32;
+
     // Attempt to get a live entry from the cascade.
     fn get (zt::entry::GetInput) -> Option<zt::element::Element>;
 
+    // Attempt to get many live entries at once, in the same order as the hashes.
+    fn get_many (zt::entry::GetManyInput) -> Vec<Option<zt::element::Element>>;
+
+    // Get the latest versions of an entry merged according to its crdt type.
+    fn get_merged (zt::entry::GetInput) -> Option<zt::crdt::MergedEntry>;
+
+    // Get a live entry, reporting how many authorities answered and agreed.
+    fn get_with_quorum (zt::entry::GetInput) -> zt::quorum::QuorumOutput<Option<zt::element::Element>>;
+
+    // Get links, reporting how many authorities answered and agreed.
+    fn get_links_with_quorum (zt::link::GetLinksInput) -> zt::quorum::QuorumOutput<zt::link::Links>;
+
     // Hash an entry on the host.
     fn hash_entry (zt::entry::Entry) -> holo_hash::EntryHash;
 
@@ -153,6 +171,9 @@ wasm_io_types! {
     // Remotely signal many agents without waiting for responses
     fn remote_signal (zt::signal::RemoteSignal) -> ();
 
+    // Remotely signal many agents, returning each agent's delivery outcome
+    fn remote_signal_with_receipts (zt::signal::RemoteSignalWithReceipts) -> Vec<zt::signal::RemoteSignalReceipt>;
+
     // // @todo
     // fn send (()) -> ();
 
@@ -0,0 +1,43 @@
+use kitsune_p2p_bootstrap::*;
+use kitsune_p2p_types::dependencies::observability::tracing;
+use structopt::StructOpt;
+
+/// Option Parsing
+#[derive(structopt::StructOpt, Debug)]
+#[structopt(name = "kitsune-bootstrap")]
+pub struct Opt {
+    /// The interface to bind to.
+    #[structopt(short = "i", long, default_value = "127.0.0.1")]
+    pub interface: std::net::IpAddr,
+
+    /// The port to bind to. 0 lets the OS pick a free port.
+    #[structopt(short = "p", long, default_value = "0")]
+    pub port: u16,
+}
+
+#[tokio::main]
+async fn main() {
+    let _ = tracing::subscriber::set_global_default(
+        tracing_subscriber::FmtSubscriber::builder()
+            .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+            .finish(),
+    );
+
+    if let Err(e) = inner().await {
+        eprintln!("{:?}", e);
+    }
+}
+
+async fn inner() -> BootstrapResult<()> {
+    let opt = Opt::from_args();
+
+    let server = spawn((opt.interface, opt.port).into(), BootstrapStore::default()).await?;
+
+    println!("{}", server.url());
+
+    tokio::signal::ctrl_c().await?;
+
+    server.shutdown();
+
+    Ok(())
+}
@@ -0,0 +1,236 @@
+//! Bootstrap server for kitsune-p2p.
+//!
+//! Speaks the same protocol as the hosted bootstrap service, so private
+//! networks and integration tests can run without any external service.
+//! All requests are POSTs whose `X-Op` header selects the operation,
+//! with messagepack encoded bodies:
+//!
+//! - `put`: store an `AgentInfoSigned`, which must carry a valid signature.
+//! - `random`: return up to `limit` encoded agent infos from a `space`.
+//! - `now`: return the server's current unix time in milliseconds.
+#![deny(missing_docs)]
+
+use futures::future::FutureExt;
+use hyper::{Body, Method, Request, Response, StatusCode};
+use kitsune_p2p::agent_store::{AgentInfo, AgentInfoSigned};
+use kitsune_p2p::KitsuneSpace;
+use kitsune_p2p_types::dependencies::observability::tracing;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+mod store;
+pub use store::*;
+
+/// The HTTP header name for setting the op on POST requests.
+const OP_HEADER: &str = "X-Op";
+/// The header op to put a signed agent info.
+const OP_PUT: &str = "put";
+/// The header op to return our opinion of 'now' in milliseconds.
+const OP_NOW: &str = "now";
+/// The header op to return a random set of agents in a specific space.
+const OP_RANDOM: &str = "random";
+
+/// How far in the future (by our clock) an agent info may claim to
+/// have been signed before we reject it.
+const MAX_CLOCK_SKEW_MS: u64 = 1000 * 60 * 5;
+
+/// Never return more than this many agents from a single `random` op.
+const MAX_RANDOM_LIMIT: u32 = 64;
+
+/// Error type for the bootstrap server.
+#[derive(Debug, thiserror::Error)]
+pub enum BootstrapError {
+    /// The request could not be decoded.
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    /// The agent info was rejected.
+    #[error("Invalid agent info: {0}")]
+    InvalidAgentInfo(String),
+
+    /// IO error, including encoding and decoding errors.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// Http server error.
+    #[error(transparent)]
+    Hyper(#[from] hyper::Error),
+
+    /// Kitsune error.
+    #[error(transparent)]
+    KitsuneP2p(#[from] kitsune_p2p::KitsuneP2pError),
+}
+
+/// Result type for the bootstrap server.
+pub type BootstrapResult<T> = Result<T, BootstrapError>;
+
+/// Struct decoded for the `random` op.
+/// Must match the client's `RandomQuery`.
+#[derive(serde::Deserialize, serde::Serialize)]
+struct RandomQuery {
+    space: Arc<KitsuneSpace>,
+    limit: RandomLimit,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct RandomLimit(u32);
+
+/// A running bootstrap server.
+/// The server is shut down when this handle is dropped.
+pub struct BootstrapServer {
+    local_addr: SocketAddr,
+    shutdown: Option<tokio::sync::oneshot::Sender<()>>,
+}
+
+impl BootstrapServer {
+    /// The address the server is bound to.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// The url to use as the `bootstrap_service` in a `KitsuneP2pConfig`.
+    pub fn url(&self) -> url2::Url2 {
+        url2::url2!("http://{}", self.local_addr)
+    }
+
+    /// Shut down the server.
+    pub fn shutdown(mut self) {
+        self.send_shutdown();
+    }
+
+    fn send_shutdown(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+impl Drop for BootstrapServer {
+    fn drop(&mut self) {
+        self.send_shutdown();
+    }
+}
+
+/// Bind a bootstrap server to the given address, backed by the given store.
+/// Bind to port `0` to let the OS pick a free port,
+/// e.g. for tests, then ask the returned handle for its url.
+pub async fn spawn(addr: SocketAddr, store: BootstrapStore) -> BootstrapResult<BootstrapServer> {
+    let server = hyper::Server::try_bind(&addr)?.serve(hyper::service::make_service_fn(
+        move |_| {
+            let store = store.clone();
+            async move {
+                Ok::<_, std::convert::Infallible>(hyper::service::service_fn(move |req| {
+                    let store = store.clone();
+                    async move {
+                        Ok::<_, std::convert::Infallible>(handle_request(store, req).await)
+                    }
+                }))
+            }
+        },
+    ));
+    let local_addr = server.local_addr();
+    let (shutdown, shutdown_recv) = tokio::sync::oneshot::channel();
+    let server = server.with_graceful_shutdown(shutdown_recv.map(|_| ()));
+    tokio::task::spawn(async move {
+        if let Err(err) = server.await {
+            tracing::error!(?err, "bootstrap server error");
+        }
+    });
+    tracing::info!(%local_addr, "bootstrap server listening");
+    Ok(BootstrapServer {
+        local_addr,
+        shutdown: Some(shutdown),
+    })
+}
+
+async fn handle_request(store: BootstrapStore, req: Request<Body>) -> Response<Body> {
+    match handle_request_inner(store, req).await {
+        Ok(body) => Response::new(Body::from(body)),
+        Err(e) => {
+            tracing::debug!(?e, "rejected bootstrap request");
+            let mut res = Response::new(Body::from(e.to_string()));
+            *res.status_mut() = StatusCode::BAD_REQUEST;
+            res
+        }
+    }
+}
+
+async fn handle_request_inner(
+    store: BootstrapStore,
+    req: Request<Body>,
+) -> BootstrapResult<Vec<u8>> {
+    if req.method() != Method::POST {
+        return Err(BootstrapError::InvalidRequest(format!(
+            "unsupported method: {}",
+            req.method()
+        )));
+    }
+    let op = req
+        .headers()
+        .get(OP_HEADER)
+        .and_then(|op| op.to_str().ok())
+        .map(str::to_string);
+    let body = hyper::body::to_bytes(req.into_body()).await?;
+    let mut out = Vec::new();
+    match op.as_deref() {
+        Some(OP_PUT) => {
+            let signed: AgentInfoSigned = kitsune_p2p_types::codec::rmp_decode(&mut &body[..])?;
+            validate(&signed, now_ms()).await?;
+            store.put(&signed)?;
+            kitsune_p2p_types::codec::rmp_encode(&mut out, ())?;
+        }
+        Some(OP_NOW) => {
+            kitsune_p2p_types::codec::rmp_encode(&mut out, now_ms())?;
+        }
+        Some(OP_RANDOM) => {
+            let RandomQuery {
+                space,
+                limit: RandomLimit(limit),
+            } = kitsune_p2p_types::codec::rmp_decode(&mut &body[..])?;
+            let limit = limit.min(MAX_RANDOM_LIMIT) as usize;
+            let list: Vec<serde_bytes::ByteBuf> = store
+                .random(&space, limit, now_ms())
+                .into_iter()
+                .map(serde_bytes::ByteBuf::from)
+                .collect();
+            kitsune_p2p_types::codec::rmp_encode(&mut out, list)?;
+        }
+        op => {
+            return Err(BootstrapError::InvalidRequest(format!(
+                "unknown op: {:?}",
+                op
+            )))
+        }
+    }
+    Ok(out)
+}
+
+/// Check that a signed agent info is self consistent, correctly signed by
+/// its agent, not signed in the future and not already expired.
+pub async fn validate(signed: &AgentInfoSigned, now_ms: u64) -> BootstrapResult<AgentInfo> {
+    let info = signed
+        .verify()
+        .await
+        .map_err(|e| BootstrapError::InvalidAgentInfo(e.to_string()))?;
+    if info.signed_at_ms() > now_ms.saturating_add(MAX_CLOCK_SKEW_MS) {
+        return Err(BootstrapError::InvalidAgentInfo(
+            "agent info signed in the future".into(),
+        ));
+    }
+    if info.signed_at_ms().saturating_add(info.expires_after_ms()) <= now_ms {
+        return Err(BootstrapError::InvalidAgentInfo(
+            "agent info already expired".into(),
+        ));
+    }
+    Ok(info)
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests;
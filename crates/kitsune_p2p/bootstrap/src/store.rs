@@ -0,0 +1,96 @@
+//! In-memory storage of signed agent info, keyed by space and agent.
+
+use kitsune_p2p::agent_store::{AgentInfo, AgentInfoSigned};
+use kitsune_p2p::{KitsuneAgent, KitsuneSpace};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+/// A single agent info as stored by the bootstrap server.
+struct StoredAgentInfo {
+    /// The unix ms timestamp the agent info was signed at.
+    signed_at_ms: u64,
+    /// The unix ms timestamp after which the agent info is no longer returned.
+    expires_at_ms: u64,
+    /// The messagepack encoded `AgentInfoSigned`, exactly as returned by `random`.
+    encoded: Vec<u8>,
+}
+
+type SpaceStore = HashMap<KitsuneAgent, StoredAgentInfo>;
+
+/// In-memory store of signed agent info for the bootstrap server.
+/// Cheaply cloneable, all clones share the same data.
+#[derive(Clone, Default)]
+pub struct BootstrapStore(Arc<parking_lot::Mutex<HashMap<KitsuneSpace, SpaceStore>>>);
+
+impl BootstrapStore {
+    /// Store an already validated signed agent info.
+    /// If we already hold a more recently signed info for the same agent
+    /// in the same space, the older one is ignored.
+    pub fn put(&self, signed: &AgentInfoSigned) -> crate::BootstrapResult<()> {
+        let info = AgentInfo::try_from(signed)?;
+        let encoded = Vec::<u8>::try_from(signed)?;
+        let stored = StoredAgentInfo {
+            signed_at_ms: info.signed_at_ms(),
+            expires_at_ms: info.signed_at_ms().saturating_add(info.expires_after_ms()),
+            encoded,
+        };
+        let mut lock = self.0.lock();
+        let space = lock.entry(info.as_space_ref().clone()).or_default();
+        match space.get(info.as_agent_ref()) {
+            Some(existing) if existing.signed_at_ms > stored.signed_at_ms => (),
+            _ => {
+                space.insert(info.as_agent_ref().clone(), stored);
+            }
+        }
+        Ok(())
+    }
+
+    /// Up to `limit` encoded agent infos from the space, in random order.
+    /// Expired agent infos are pruned first and never returned.
+    pub fn random(&self, space: &KitsuneSpace, limit: usize, now_ms: u64) -> Vec<Vec<u8>> {
+        self.prune(now_ms);
+        let lock = self.0.lock();
+        let space = match lock.get(space) {
+            Some(space) => space,
+            None => return Vec::new(),
+        };
+        let mut rng = rand::thread_rng();
+        // choose_multiple doesn't randomize the order of the chosen items
+        let mut out: Vec<Vec<u8>> =
+            rand::seq::IteratorRandom::choose_multiple(space.values(), &mut rng, limit)
+                .into_iter()
+                .map(|stored| stored.encoded.clone())
+                .collect();
+        rand::seq::SliceRandom::shuffle(&mut out[..], &mut rng);
+        out
+    }
+
+    /// Remove all agent infos that expired before `now_ms`,
+    /// and any spaces left empty.
+    pub fn prune(&self, now_ms: u64) {
+        let mut lock = self.0.lock();
+        for space in lock.values_mut() {
+            space.retain(|_, stored| stored.expires_at_ms > now_ms);
+        }
+        lock.retain(|_, space| !space.is_empty());
+    }
+
+    /// The number of agents currently stored for a space.
+    pub fn len(&self, space: &KitsuneSpace) -> usize {
+        self.0.lock().get(space).map(|s| s.len()).unwrap_or(0)
+    }
+
+    /// Are there no agents at all stored for a space?
+    pub fn is_empty(&self, space: &KitsuneSpace) -> bool {
+        self.len(space) == 0
+    }
+}
+
+impl std::fmt::Debug for BootstrapStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BootstrapStore")
+            .field("space_count", &self.0.lock().len())
+            .finish()
+    }
+}
@@ -0,0 +1,157 @@
+use super::*;
+use kitsune_p2p::{KitsuneAgent, KitsuneBinType, KitsuneSignature};
+use lair_keystore_api::internal::sign_ed25519::sign_ed25519_keypair_new_from_entropy;
+
+async fn spawn_local() -> (BootstrapServer, BootstrapStore) {
+    let store = BootstrapStore::default();
+    let server = spawn(([127, 0, 0, 1], 0).into(), store.clone())
+        .await
+        .unwrap();
+    (server, store)
+}
+
+async fn do_api<I: serde::Serialize>(
+    server: &BootstrapServer,
+    op: &str,
+    input: I,
+) -> (reqwest::StatusCode, Vec<u8>) {
+    let mut body = Vec::new();
+    kitsune_p2p_types::codec::rmp_encode(&mut body, input).unwrap();
+    let res = reqwest::Client::new()
+        .post(server.url().as_str())
+        .body(body)
+        .header(OP_HEADER, op)
+        .send()
+        .await
+        .unwrap();
+    let status = res.status();
+    (status, res.bytes().await.unwrap().to_vec())
+}
+
+async fn signed_agent_info(
+    space: KitsuneSpace,
+    signed_at_ms: u64,
+    bad_signature: bool,
+) -> AgentInfoSigned {
+    let keypair = sign_ed25519_keypair_new_from_entropy().await.unwrap();
+    let agent = KitsuneAgent::new((*keypair.pub_key.0).clone());
+    let info = AgentInfo::new(
+        space,
+        agent.clone(),
+        vec![url2::url2!("kitsune-quic://127.0.0.1:5778")],
+        signed_at_ms,
+        1000 * 60 * 20,
+    );
+    let mut data = Vec::new();
+    kitsune_p2p_types::codec::rmp_encode(&mut data, &info).unwrap();
+    let mut signature = (*keypair.sign(Arc::new(data.clone())).await.unwrap().0).clone();
+    if bad_signature {
+        signature[0] = signature[0].wrapping_add(1);
+    }
+    AgentInfoSigned::try_new(agent, KitsuneSignature(signature), data).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_now() {
+    let (server, _) = spawn_local().await;
+    let before = now_ms();
+    let (status, body) = do_api(&server, OP_NOW, ()).await;
+    assert!(status.is_success());
+    let remote_now: u64 = kitsune_p2p_types::codec::rmp_decode(&mut &body[..]).unwrap();
+    assert!(remote_now >= before);
+    assert!(remote_now <= now_ms());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_put_and_random() {
+    let (server, store) = spawn_local().await;
+    let space = KitsuneSpace::new(vec![1; 36]);
+    let other_space = KitsuneSpace::new(vec![2; 36]);
+
+    let mut expected = Vec::new();
+    for _ in 0..3 {
+        let signed = signed_agent_info(space.clone(), now_ms(), false).await;
+        let (status, _) = do_api(&server, OP_PUT, &signed).await;
+        assert!(status.is_success());
+        expected.push(signed);
+    }
+    let signed = signed_agent_info(other_space, now_ms(), false).await;
+    let (status, _) = do_api(&server, OP_PUT, &signed).await;
+    assert!(status.is_success());
+    assert_eq!(store.len(&space), 3);
+
+    let random = |limit: u32| {
+        let server = &server;
+        let space = space.clone();
+        async move {
+            let (status, body) = do_api(
+                server,
+                OP_RANDOM,
+                RandomQuery {
+                    space: Arc::new(space),
+                    limit: RandomLimit(limit),
+                },
+            )
+            .await;
+            assert!(status.is_success());
+            let list: Vec<serde_bytes::ByteBuf> =
+                kitsune_p2p_types::codec::rmp_decode(&mut &body[..]).unwrap();
+            list.into_iter()
+                .map(|b| AgentInfoSigned::try_from(&b[..]).unwrap())
+                .collect::<Vec<_>>()
+        }
+    };
+
+    // - only agents from the requested space are returned
+    let mut all = random(10).await;
+    all.sort();
+    expected.sort();
+    assert_eq!(all, expected);
+
+    // - the limit is respected
+    let one = random(1).await;
+    assert_eq!(one.len(), 1);
+    assert!(expected.contains(&one[0]));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_put_rejects_invalid() {
+    let (server, store) = spawn_local().await;
+    let space = KitsuneSpace::new(vec![1; 36]);
+
+    // - bad signature
+    let signed = signed_agent_info(space.clone(), now_ms(), true).await;
+    let (status, _) = do_api(&server, OP_PUT, &signed).await;
+    assert_eq!(status, reqwest::StatusCode::BAD_REQUEST);
+
+    // - signed too far in the future
+    let signed = signed_agent_info(space.clone(), now_ms() + MAX_CLOCK_SKEW_MS * 2, false).await;
+    let (status, _) = do_api(&server, OP_PUT, &signed).await;
+    assert_eq!(status, reqwest::StatusCode::BAD_REQUEST);
+
+    // - already expired
+    let signed = signed_agent_info(space.clone(), 1, false).await;
+    let (status, _) = do_api(&server, OP_PUT, &signed).await;
+    assert_eq!(status, reqwest::StatusCode::BAD_REQUEST);
+
+    // - garbage
+    let (status, _) = do_api(&server, OP_PUT, "not an agent info").await;
+    assert_eq!(status, reqwest::StatusCode::BAD_REQUEST);
+
+    // - unknown op
+    let (status, _) = do_api(&server, "nope", ()).await;
+    assert_eq!(status, reqwest::StatusCode::BAD_REQUEST);
+
+    assert!(store.is_empty(&space));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_store_prunes_expired() {
+    let store = BootstrapStore::default();
+    let space = KitsuneSpace::new(vec![1; 36]);
+    let signed = signed_agent_info(space.clone(), 1000, false).await;
+    store.put(&signed).unwrap();
+    assert_eq!(store.random(&space, 10, 1001).len(), 1);
+    assert!(store.random(&space, 10, 1000 + 1000 * 60 * 20).is_empty());
+    assert!(store.is_empty(&space));
+}
@@ -7,6 +7,7 @@ use futures::future::{BoxFuture, FutureExt};
 use kitsune_p2p::event::MetricDatum;
 use kitsune_p2p::event::MetricQuery;
 use kitsune_p2p::event::MetricQueryAnswer;
+use kitsune_p2p::event::ReachabilityDatum;
 use kitsune_p2p_types::dht_arc::DhtArc;
 use kitsune_p2p_types::tls::*;
 use kitsune_p2p_types::tx2::tx2_utils::*;
@@ -371,6 +372,20 @@ impl AsKdPersist for PersistMem {
         .boxed()
     }
 
+    fn put_reachability_datum(&self, datum: ReachabilityDatum) -> BoxFuture<'static, KdResult<()>> {
+        let inner = self.0.clone();
+        async move {
+            inner
+                .share_mut(|inner, _| {
+                    inner.metric_store.put_reachability_datum(datum);
+                    Ok(())
+                })
+                .map_err(KdError::other)?;
+            Ok(())
+        }
+        .boxed()
+    }
+
     fn query_metrics(&self, query: MetricQuery) -> BoxFuture<'static, KdResult<MetricQueryAnswer>> {
         let inner = self.0.clone();
         async move {
@@ -1,20 +1,33 @@
 //! An in-memory implementation of a metric store.
 //! A real implementation would use a database.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
 
 use kitsune_p2p::event::*;
+use kitsune_p2p::KitsuneAgent;
 use kitsune_p2p_types::dependencies::observability::tracing;
 
 /// An in-memory implementation of a metric store.
 /// A real implementation would use a database.
 #[derive(Default)]
-pub struct KdMetricStore(BTreeSet<MetricDatum>);
+pub struct KdMetricStore {
+    metrics: BTreeSet<MetricDatum>,
+    reachability: HashMap<Arc<KitsuneAgent>, PeerReachability>,
+}
 
 impl KdMetricStore {
     /// Insert metric data into the store
     pub fn put_metric_datum(&mut self, datum: MetricDatum) {
-        self.0.insert(datum);
+        self.metrics.insert(datum);
+    }
+
+    /// Fold a reachability datum into the aggregate for its agent
+    pub fn put_reachability_datum(&mut self, datum: ReachabilityDatum) {
+        self.reachability
+            .entry(datum.agent.clone())
+            .or_insert_with(|| PeerReachability::new(datum.agent.clone()))
+            .apply(&datum);
     }
 
     /// Retrieve metric data from the store
@@ -22,7 +35,7 @@ impl KdMetricStore {
         match query {
             MetricQuery::LastSync { agent } => {
                 let timestamp = self
-                    .0
+                    .metrics
                     .iter()
                     .rev()
                     .find(|metric| metric.agent == agent && metric.kind == MetricKind::QuickGossip)
@@ -34,7 +47,7 @@ impl KdMetricStore {
             } => {
                 tracing::warn!("This \"query\" is untested.");
                 let agent = self
-                    .0
+                    .metrics
                     .iter()
                     .find(|metric| {
                         metric.kind == MetricKind::ConnectError
@@ -43,6 +56,12 @@ impl KdMetricStore {
                     .map(|metric| metric.agent.clone());
                 MetricQueryAnswer::Oldest(agent)
             }
+            MetricQuery::Reachability { agents } => MetricQueryAnswer::Reachability(
+                agents
+                    .iter()
+                    .filter_map(|agent| self.reachability.get(agent).cloned())
+                    .collect(),
+            ),
         }
     }
 }
@@ -5,6 +5,7 @@ use futures::future::BoxFuture;
 use kitsune_p2p::event::MetricDatum;
 use kitsune_p2p::event::MetricQuery;
 use kitsune_p2p::event::MetricQueryAnswer;
+use kitsune_p2p::event::ReachabilityDatum;
 use kitsune_p2p_types::dht_arc::DhtArc;
 use kitsune_p2p_types::tls::TlsConfig;
 use std::future::Future;
@@ -45,6 +46,9 @@ pub trait AsKdPersist: 'static + Send + Sync {
     /// Store agent info
     fn put_metric_datum(&self, datum: MetricDatum) -> BoxFuture<'static, KdResult<()>>;
 
+    /// Store reachability datum
+    fn put_reachability_datum(&self, datum: ReachabilityDatum) -> BoxFuture<'static, KdResult<()>>;
+
     /// Store agent info
     fn query_metrics(&self, query: MetricQuery) -> BoxFuture<'static, KdResult<MetricQueryAnswer>>;
 
@@ -163,6 +167,14 @@ impl KdPersist {
         AsKdPersist::put_metric_datum(&*self.0, datum)
     }
 
+    /// Store reachability datum
+    pub fn store_reachability_datum(
+        &self,
+        datum: ReachabilityDatum,
+    ) -> impl Future<Output = KdResult<()>> + 'static + Send {
+        AsKdPersist::put_reachability_datum(&*self.0, datum)
+    }
+
     /// "Query" metric info
     pub async fn fetch_metrics(
         &self,
@@ -514,6 +514,12 @@ async fn handle_events(
                         .boxed()
                         .into()));
                 }
+                event::KitsuneP2pEvent::PutReachabilityDatum { respond, datum, .. } => {
+                    respond.r(Ok(handle_put_reachability_datum(kdirect.clone(), datum)
+                        .map_err(KitsuneP2pError::other)
+                        .boxed()
+                        .into()));
+                }
                 event::KitsuneP2pEvent::QueryMetrics { respond, query, .. } => {
                     respond.r(Ok(handle_query_metrics(kdirect.clone(), query)
                         .map_err(KitsuneP2pError::other)
@@ -636,6 +642,13 @@ async fn handle_put_metric_datum(kdirect: Arc<Kd1>, datum: MetricDatum) -> KdRes
     kdirect.persist.store_metric_datum(datum).await
 }
 
+async fn handle_put_reachability_datum(
+    kdirect: Arc<Kd1>,
+    datum: ReachabilityDatum,
+) -> KdResult<()> {
+    kdirect.persist.store_reachability_datum(datum).await
+}
+
 async fn handle_query_metrics(
     kdirect: Arc<Kd1>,
     query: MetricQuery,
@@ -17,7 +17,7 @@ pub(crate) enum KitsuneP2pTx2Backend {
 }
 
 pub(crate) struct KitsuneP2pTx2Config {
-    pub backend: KitsuneP2pTx2Backend,
+    pub backends: Vec<KitsuneP2pTx2Backend>,
     pub use_proxy: Option<TxUrl>,
 }
 
@@ -28,6 +28,8 @@ pub struct KitsuneP2pConfig {
     /// list of sub-transports to be included in this pool
     pub transport_pool: Vec<TransportConfig>,
     /// The service used for peers to discover each before they are peers.
+    /// For private networks and tests, this can point at a local
+    /// `kitsune-bootstrap` server from the `kitsune_p2p_bootstrap` crate.
     pub bootstrap_service: Option<Url2>,
     /// Network tuning parameters. These are managed loosely,
     /// as they are subject to change. If you specify a tuning parameter
@@ -58,41 +60,63 @@ fn cnv_bind_to(bind_to: &Option<url2::Url2>) -> TxUrl {
 }
 
 impl KitsuneP2pConfig {
-    /// tx2 is currently designed to use exactly one proxy wrapped transport
-    /// so, convert a bunch of the options from the previous transport
-    /// paradigm into that pattern.
+    /// tx2 is designed to use proxy wrapped transports, so convert a bunch
+    /// of the options from the previous transport paradigm into that pattern.
+    /// Every entry in the transport pool is bound simultaneously,
+    /// but at most one remote proxy may be specified.
     pub(crate) fn to_tx2(&self) -> KitsuneResult<KitsuneP2pTx2Config> {
-        match self.transport_pool.get(0) {
-            Some(TransportConfig::Proxy {
-                sub_transport,
-                proxy_config,
-            }) => {
-                let backend = match &**sub_transport {
-                    TransportConfig::Mem {} => KitsuneP2pTx2Backend::Mem,
-                    TransportConfig::Quic { bind_to, .. } => {
-                        let bind_to = cnv_bind_to(bind_to);
-                        KitsuneP2pTx2Backend::Quic { bind_to }
+        let mut backends = Vec::new();
+        let mut use_proxy: Option<TxUrl> = None;
+
+        for t in self.transport_pool.iter() {
+            let (backend, proxy) = match t {
+                TransportConfig::Proxy {
+                    sub_transport,
+                    proxy_config,
+                } => {
+                    let backend = match &**sub_transport {
+                        TransportConfig::Mem {} => KitsuneP2pTx2Backend::Mem,
+                        TransportConfig::Quic { bind_to, .. } => {
+                            let bind_to = cnv_bind_to(bind_to);
+                            KitsuneP2pTx2Backend::Quic { bind_to }
+                        }
+                        _ => return Err("kitsune tx2 backend must be mem or quic".into()),
+                    };
+                    let proxy = match proxy_config {
+                        ProxyConfig::RemoteProxyClient { proxy_url } => {
+                            Some(proxy_url.clone().into())
+                        }
+                        ProxyConfig::LocalProxyServer { .. } => None,
+                    };
+                    (backend, proxy)
+                }
+                TransportConfig::Quic { bind_to, .. } => {
+                    let bind_to = cnv_bind_to(bind_to);
+                    (KitsuneP2pTx2Backend::Quic { bind_to }, None)
+                }
+                TransportConfig::Mem {} => (KitsuneP2pTx2Backend::Mem, None),
+            };
+
+            if let Some(proxy) = proxy {
+                match &use_proxy {
+                    Some(p) if p != &proxy => {
+                        return Err("kitsune tx2 supports at most one remote proxy".into());
                     }
-                    _ => return Err("kitsune tx2 backend must be mem or quic".into()),
-                };
-                let use_proxy = match proxy_config {
-                    ProxyConfig::RemoteProxyClient { proxy_url } => Some(proxy_url.clone().into()),
-                    ProxyConfig::LocalProxyServer { .. } => None,
-                };
-                Ok(KitsuneP2pTx2Config { backend, use_proxy })
+                    _ => use_proxy = Some(proxy),
+                }
             }
-            Some(TransportConfig::Quic { bind_to, .. }) => {
-                let bind_to = cnv_bind_to(bind_to);
-                Ok(KitsuneP2pTx2Config {
-                    backend: KitsuneP2pTx2Backend::Quic { bind_to },
-                    use_proxy: None,
-                })
-            }
-            None | Some(TransportConfig::Mem {}) => Ok(KitsuneP2pTx2Config {
-                backend: KitsuneP2pTx2Backend::Mem,
-                use_proxy: None,
-            }),
+
+            backends.push(backend);
         }
+
+        if backends.is_empty() {
+            backends.push(KitsuneP2pTx2Backend::Mem);
+        }
+
+        Ok(KitsuneP2pTx2Config {
+            backends,
+            use_proxy,
+        })
     }
 }
 
@@ -170,6 +194,10 @@ pub enum ProxyAcceptConfig {
 pub enum NetworkType {
     /// Via bootstrap server to the WAN
     QuicBootstrap,
-    /// Via MDNS to the LAN
+    /// Via MDNS to the LAN.
+    /// Signed agent info is broadcast for each joined space, and peers
+    /// broadcasting in the same space are added to the peer store.
+    /// If a `bootstrap_service` is also configured it is used as well,
+    /// so peers from both sources are merged.
     QuicMdns,
 }
@@ -4,6 +4,9 @@ impl SimpleBloomMod {
     pub(crate) async fn step_3_initiate_inner(&self) -> KitsuneP2pResult<()> {
         // we have decided to do an initiate check, mark the time
 
+        // we can only initiate with remotes reachable over our transports
+        let local_addrs = self.ep_hnd.local_addrs()?;
+
         // get the remote certs we might want to speak to
         let endpoints: HashMap<GossipTgt, TxUrl> = self.inner.share_mut(|inner, _| {
             inner.last_initiate_check = std::time::Instant::now();
@@ -27,7 +30,7 @@ impl SimpleBloomMod {
                         if let Ok(agent_info) =
                             crate::agent_store::AgentInfo::try_from(agent_info_signed)
                         {
-                            if let Some(url) = agent_info.as_urls_ref().get(0) {
+                            if let Some(url) = agent_info.reachable_urls(&local_addrs).first() {
                                 if let Ok(purl) =
                                     kitsune_p2p_proxy::ProxyUrl::from_full(url.as_str())
                                 {
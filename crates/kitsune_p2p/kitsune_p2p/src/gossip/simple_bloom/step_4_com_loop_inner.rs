@@ -108,7 +108,8 @@ pub(crate) async fn step_4_com_loop_inner_outgoing(
     let con = match how {
         HowToConnect::Con(con) => {
             if con.is_closed() {
-                let url = pick_url_for_cert(inner, &peer_cert)?;
+                let local_addrs = ep_hnd.local_addrs()?;
+                let url = pick_url_for_cert(inner, &local_addrs, &peer_cert)?;
                 ep_hnd.get_connection(url, t).await?
             } else {
                 con
@@ -333,7 +334,11 @@ pub(crate) async fn step_4_com_loop_inner_incoming(
 
 // if the connection is closed while awaiting an outgoing send,
 // we'll need to establish a new one...
-fn pick_url_for_cert(inner: &Share<SimpleBloomModInner>, cert: &Tx2Cert) -> KitsuneResult<TxUrl> {
+fn pick_url_for_cert(
+    inner: &Share<SimpleBloomModInner>,
+    local_addrs: &[TxUrl],
+    cert: &Tx2Cert,
+) -> KitsuneResult<TxUrl> {
     // this is a bit computationally intensive...
     // but, in case there is a split in data recency, safer as a starting point
 
@@ -348,7 +353,7 @@ fn pick_url_for_cert(inner: &Share<SimpleBloomModInner>, cert: &Tx2Cert) -> Kits
             if let MetaOpData::Agent(agent_info_signed) = &**data {
                 use std::convert::TryFrom;
                 if let Ok(agent_info) = crate::agent_store::AgentInfo::try_from(agent_info_signed) {
-                    if let Some(url) = agent_info.as_urls_ref().get(0) {
+                    if let Some(url) = agent_info.reachable_urls(local_addrs).first() {
                         if let Ok(purl) = kitsune_p2p_proxy::ProxyUrl::from_full(url.as_str()) {
                             if &Tx2Cert::from(purl.digest()) != cert {
                                 continue;
@@ -12,6 +12,7 @@ use kitsune_p2p_proxy::ProxyUrl;
 use kitsune_p2p_transport_quic::tx2::*;
 use kitsune_p2p_types::async_lazy::AsyncLazy;
 use kitsune_p2p_types::tx2::tx2_api::*;
+use kitsune_p2p_types::tx2::tx2_multi::*;
 use kitsune_p2p_types::tx2::tx2_pool_promote::*;
 use kitsune_p2p_types::tx2::*;
 use kitsune_p2p_types::*;
@@ -38,7 +39,7 @@ ghost_actor::ghost_chan! {
 }
 
 pub(crate) struct KitsuneP2pActor {
-    this_addr: url2::Url2,
+    this_addrs: Vec<url2::Url2>,
     channel_factory: ghost_actor::actor_builder::GhostActorChannelFactory<Self>,
     internal_sender: ghost_actor::GhostSender<Internal>,
     evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
@@ -66,38 +67,53 @@ impl KitsuneP2pActor {
 
         let tx2_conf = config.to_tx2().map_err(KitsuneP2pError::other)?;
 
-        // set up our backend based on config
-        let (f, bind_to) = match tx2_conf.backend {
-            KitsuneP2pTx2Backend::Mem => {
-                let mut conf = MemConfig::default();
-                conf.tls = Some(tls_config.clone());
-                conf.tuning_params = Some(config.tuning_params.clone());
-                (
-                    tx2_mem_adapter(conf)
-                        .await
-                        .map_err(KitsuneP2pError::other)?,
-                    "none:".into(),
-                )
-            }
-            KitsuneP2pTx2Backend::Quic { bind_to } => {
-                let mut conf = QuicConfig::default();
-                conf.tls = Some(tls_config.clone());
-                conf.tuning_params = Some(config.tuning_params.clone());
-                (
-                    tx2_quic_adapter(conf)
-                        .await
-                        .map_err(KitsuneP2pError::other)?,
-                    bind_to,
-                )
-            }
-        };
+        // set up our backends based on config
+        let mut subs = Vec::new();
+        for backend in tx2_conf.backends {
+            let (f, bind_to) = match backend {
+                KitsuneP2pTx2Backend::Mem => {
+                    let mut conf = MemConfig::default();
+                    conf.tls = Some(tls_config.clone());
+                    conf.tuning_params = Some(config.tuning_params.clone());
+                    (
+                        tx2_mem_adapter(conf)
+                            .await
+                            .map_err(KitsuneP2pError::other)?,
+                        "none:".into(),
+                    )
+                }
+                KitsuneP2pTx2Backend::Quic { bind_to } => {
+                    let mut conf = QuicConfig::default();
+                    conf.tls = Some(tls_config.clone());
+                    conf.tuning_params = Some(config.tuning_params.clone());
+                    (
+                        tx2_quic_adapter(conf)
+                            .await
+                            .map_err(KitsuneP2pError::other)?,
+                        bind_to,
+                    )
+                }
+            };
+
+            // convert to frontend
+            let f = tx2_pool_promote(f, config.tuning_params.clone());
+            subs.push((f, bind_to));
+        }
 
-        // convert to frontend
-        let f = tx2_pool_promote(f, config.tuning_params.clone());
+        // if we have multiple backends, bind them all simultaneously
+        let (f, bind_to) = if subs.len() == 1 {
+            subs.remove(0)
+        } else {
+            (
+                tx2_multi(subs).map_err(KitsuneP2pError::other)?,
+                "none:".into(),
+            )
+        };
 
         // wrap in proxy
         let mut conf = kitsune_p2p_proxy::tx2::ProxyConfig::default();
         conf.tuning_params = Some(config.tuning_params.clone());
+        conf.allow_hole_punch = config.tuning_params.tx2_allow_hole_punch;
         let f = tx2_proxy(f, conf)?;
 
         let metrics = Tx2ApiMetrics::default().set_write_len(|d, l| {
@@ -126,7 +142,8 @@ impl KitsuneP2pActor {
         let ep_hnd = ep.handle().clone();
 
         // if we should be proxying - set up the proxy connect retry / proxy addr
-        let this_addr = if let Some(use_proxy) = tx2_conf.use_proxy {
+        let mut this_addrs: Vec<TxUrl> = Vec::new();
+        if let Some(use_proxy) = tx2_conf.use_proxy {
             let local = ep_hnd.local_addr().map_err(KitsuneP2pError::other)?;
             let this_digest = ProxyUrl::from(local.as_str()).digest();
             let proxy_url = ProxyUrl::from(use_proxy.as_str());
@@ -157,15 +174,18 @@ impl KitsuneP2pActor {
                 }
             });
 
-            ProxyUrl::new(proxy_url.as_base().as_str(), this_digest)
-                .unwrap()
-                .as_str()
-                .into()
-        } else {
-            ep_hnd.local_addr().map_err(KitsuneP2pError::other)?
-        };
+            this_addrs.push(
+                ProxyUrl::new(proxy_url.as_base().as_str(), this_digest)
+                    .unwrap()
+                    .as_str()
+                    .into(),
+            );
+        }
 
-        tracing::info!("this_addr: {}", this_addr);
+        // we are also reachable directly on all our bound transports
+        this_addrs.extend(ep_hnd.local_addrs().map_err(KitsuneP2pError::other)?);
+
+        tracing::info!("this_addrs: {:?}", this_addrs);
 
         let i_s = internal_sender.clone();
         tokio::task::spawn({
@@ -249,7 +269,7 @@ impl KitsuneP2pActor {
         });
 
         Ok(Self {
-            this_addr: this_addr.into(),
+            this_addrs: this_addrs.into_iter().map(|a| a.into()).collect(),
             channel_factory,
             internal_sender,
             evt_sender,
@@ -328,6 +348,13 @@ impl KitsuneP2pEventHandler for KitsuneP2pActor {
         Ok(self.evt_sender.put_metric_datum(datum))
     }
 
+    fn handle_put_reachability_datum(
+        &mut self,
+        datum: ReachabilityDatum,
+    ) -> KitsuneP2pEventHandlerResult<()> {
+        Ok(self.evt_sender.put_reachability_datum(datum))
+    }
+
     fn handle_query_metrics(
         &mut self,
         query: MetricQuery,
@@ -394,8 +421,8 @@ impl ghost_actor::GhostHandler<KitsuneP2p> for KitsuneP2pActor {}
 
 impl KitsuneP2pHandler for KitsuneP2pActor {
     fn handle_list_transport_bindings(&mut self) -> KitsuneP2pHandlerResult<Vec<url2::Url2>> {
-        let this_addr = vec![self.this_addr.clone()];
-        Ok(async move { Ok(this_addr) }.boxed().into())
+        let this_addrs = self.this_addrs.clone();
+        Ok(async move { Ok(this_addrs) }.boxed().into())
     }
 
     fn handle_join(
@@ -405,13 +432,13 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
     ) -> KitsuneP2pHandlerResult<()> {
         let internal_sender = self.internal_sender.clone();
         let space2 = space.clone();
-        let this_addr = self.this_addr.clone();
+        let this_addrs = self.this_addrs.clone();
         let ep_hnd = self.ep_hnd.clone();
         let config = Arc::clone(&self.config);
         let space_sender = match self.spaces.entry(space.clone()) {
             Entry::Occupied(entry) => entry.into_mut(),
             Entry::Vacant(entry) => entry.insert(AsyncLazy::new(async move {
-                let (send, send_inner, evt_recv) = spawn_space(space2, this_addr, ep_hnd, config)
+                let (send, send_inner, evt_recv) = spawn_space(space2, this_addrs, ep_hnd, config)
                     .await
                     .expect("cannot fail to create space");
                 internal_sender
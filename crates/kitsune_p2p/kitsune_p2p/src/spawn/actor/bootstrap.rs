@@ -210,13 +210,17 @@ mod tests {
     use lair_keystore_api::internal::sign_ed25519::sign_ed25519_keypair_new_from_entropy;
     use std::convert::TryInto;
 
-    // TODO - FIXME - davidb
-    // I'm disabling all these tests that depend on outside systems
-    // we need local testing to prove these out in a ci environment.
+    /// Run these tests against a local bootstrap server
+    /// rather than depending on the hosted service.
+    async fn local_bootstrap() -> kitsune_p2p_bootstrap::BootstrapServer {
+        kitsune_p2p_bootstrap::spawn(([127, 0, 0, 1], 0).into(), Default::default())
+            .await
+            .unwrap()
+    }
 
     #[tokio::test(flavor = "multi_thread")]
-    #[ignore = "flaky"]
     async fn test_bootstrap() {
+        let bootstrap = local_bootstrap().await;
         let keypair = sign_ed25519_keypair_new_from_entropy().await.unwrap();
         let space = fixt!(KitsuneSpace);
         let agent = KitsuneAgent::new((*keypair.pub_key.0).clone());
@@ -244,25 +248,19 @@ mod tests {
                 .unwrap();
 
         // Simply hitting the endpoint should be OK.
-        super::put(
-            Some(url2::url2!("{}", crate::config::BOOTSTRAP_SERVICE_DEV)),
-            agent_info_signed,
-        )
-        .await
-        .unwrap();
+        super::put(Some(bootstrap.url()), agent_info_signed)
+            .await
+            .unwrap();
 
         // We should get back an error if we don't have a good signature.
-        assert!(super::put(
-            Some(url2::url2!("{}", crate::config::BOOTSTRAP_SERVICE_DEV)),
-            fixt!(AgentInfoSigned)
-        )
-        .await
-        .is_err());
+        assert!(super::put(Some(bootstrap.url()), fixt!(AgentInfoSigned))
+            .await
+            .is_err());
     }
 
     #[tokio::test(flavor = "multi_thread")]
-    #[ignore = "flaky"]
     async fn test_now() {
+        let bootstrap = local_bootstrap().await;
         let local_now = std::time::SystemTime::now();
         let local_millis: u64 = local_now
             .duration_since(std::time::UNIX_EPOCH)
@@ -272,39 +270,22 @@ mod tests {
             .unwrap();
 
         // We should be able to get a milliseconds timestamp back.
-        let remote_now: u64 = super::now(Some(url2::url2!(
-            "{}",
-            crate::config::BOOTSTRAP_SERVICE_DEV
-        )))
-        .await
-        .unwrap();
+        let remote_now: u64 = super::now(Some(bootstrap.url())).await.unwrap();
         let threshold = 5000;
 
         assert!((remote_now - local_millis) < threshold);
 
         // Now once should return some number and the remote server offset should be set in the
         // NOW_OFFSET_MILLIS once cell.
-        let _: u64 = super::now_once(Some(url2::url2!(
-            "{}",
-            crate::config::BOOTSTRAP_SERVICE_DEV
-        )))
-        .await
-        .unwrap();
+        let _: u64 = super::now_once(Some(bootstrap.url())).await.unwrap();
         assert!(super::NOW_OFFSET_MILLIS.get().is_some());
     }
 
     #[tokio::test(flavor = "multi_thread")]
-    #[ignore = "flaky"]
-    // Fixturator seed: 17591570467001263546
-    // thread 'spawn::actor::bootstrap::tests::test_random' panicked at 'dispatch dropped without returning error', /rustc/d3fb005a39e62501b8b0b356166e515ae24e2e54/src/libstd/macros.rs:13:23
     async fn test_random() {
+        let bootstrap = local_bootstrap().await;
         let space = fixt!(KitsuneSpace, Unpredictable);
-        let now = super::now(Some(url2::url2!(
-            "{}",
-            crate::config::BOOTSTRAP_SERVICE_DEV
-        )))
-        .await
-        .unwrap();
+        let now = super::now(Some(bootstrap.url())).await.unwrap();
 
         let alice = sign_ed25519_keypair_new_from_entropy().await.unwrap();
         let bob = sign_ed25519_keypair_new_from_entropy().await.unwrap();
@@ -329,18 +310,15 @@ mod tests {
             )
             .unwrap();
 
-            super::put(
-                Some(url2::url2!("{}", crate::config::BOOTSTRAP_SERVICE_DEV)),
-                agent_info_signed.clone(),
-            )
-            .await
-            .unwrap();
+            super::put(Some(bootstrap.url()), agent_info_signed.clone())
+                .await
+                .unwrap();
 
             expected.push(agent_info_signed);
         }
 
         let mut random = super::random(
-            Some(url2::url2!("{}", crate::config::BOOTSTRAP_SERVICE_DEV)),
+            Some(bootstrap.url()),
             super::RandomQuery {
                 space: Arc::new(space.clone()),
                 ..Default::default()
@@ -356,7 +334,7 @@ mod tests {
         assert!(random == expected);
 
         let random_single = super::random(
-            Some(url2::url2!("{}", crate::config::BOOTSTRAP_SERVICE_DEV)),
+            Some(bootstrap.url()),
             super::RandomQuery {
                 space: Arc::new(space.clone()),
                 limit: 1.into(),
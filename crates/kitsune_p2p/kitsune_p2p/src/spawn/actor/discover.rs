@@ -2,7 +2,7 @@
 use super::*;
 use crate::agent_store::AgentInfo;
 use ghost_actor::dependencies::must_future::MustBoxFuture;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 
 /// This enum represents the outcomes from peer discovery
@@ -33,9 +33,10 @@ pub(crate) fn peer_discover(
     let ep_hnd = space.ep_hnd.clone();
     let space = space.space.clone();
     async move {
-        // run tx.create_channel an conver success result into our return type
-        let try_connect = |url: url2::Url2| async {
-            let con_hnd = ep_hnd.get_connection(url.clone(), timeout).await?;
+        // try each url we can reach in turn,
+        // convert success result into our return type
+        let try_connect = |urls: Vec<url2::Url2>| async {
+            let (url, con_hnd) = connect_any(&ep_hnd, urls, timeout).await?;
             KitsuneP2pResult::Ok(PeerDiscoverResult::OkRemote { url, con_hnd })
         };
 
@@ -59,12 +60,12 @@ pub(crate) fn peer_discover(
                 .await?
             {
                 let info = types::agent_store::AgentInfo::try_from(&info)?;
-                let url = info
-                    .as_urls_ref()
-                    .get(0)
-                    .ok_or_else(|| KitsuneP2pError::from("no url"))?
-                    .clone();
-                return try_connect(url).await;
+                let local_addrs = ep_hnd.local_addrs()?;
+                let urls = info.reachable_urls(&local_addrs);
+                if urls.is_empty() {
+                    return Err("no reachable url".into());
+                }
+                return try_connect(urls).await;
             }
 
             KitsuneP2pResult::Err("failed to connect".into())
@@ -171,16 +172,33 @@ where
                     let to_agent = Arc::new(node.as_agent_ref().clone());
                     if !sent_to.contains(&to_agent) {
                         sent_to.insert(to_agent.clone());
-                        let url = match node.as_urls_ref().get(0) {
-                            None => continue,
-                            Some(url) => url.clone(),
+                        let local_addrs = match ep_hnd.local_addrs() {
+                            Ok(local_addrs) => local_addrs,
+                            Err(_) => continue,
+                        };
+                        let urls = node.reachable_urls(&local_addrs);
+                        if urls.is_empty() {
+                            continue;
+                        }
+                        let ep_hnd = ep_hnd.clone();
+                        let fut = async move {
+                            connect_any(&ep_hnd, urls, timeout_even_if_none)
+                                .await
+                                .map(|(_, con_hnd)| con_hnd)
                         };
-                        let fut = ep_hnd.get_connection(url, timeout_even_if_none);
                         let mut payload = payload.clone();
                         let accept_result_cb = accept_result_cb.clone();
                         let out = out.clone();
+                        let evt_sender = evt_sender.clone();
                         tokio::task::spawn(async move {
-                            let con_hnd = fut.await?;
+                            let bytes_out = wire_data_len(&payload);
+                            let con_hnd = match fut.await {
+                                Ok(con_hnd) => con_hnd,
+                                Err(e) => {
+                                    record_failure(&evt_sender, to_agent, bytes_out).await;
+                                    return Err(KitsuneP2pError::from(e));
+                                }
+                            };
                             match &mut payload {
                                 wire::Wire::Notify(n) => {
                                     n.to_agent = to_agent.clone();
@@ -190,7 +208,22 @@ where
                                 }
                                 _ => panic!("cannot message {:?}", payload),
                             }
-                            let res = con_hnd.request(&payload, timeout_even_if_none).await?;
+                            let start = std::time::Instant::now();
+                            let res = match con_hnd.request(&payload, timeout_even_if_none).await {
+                                Ok(res) => res,
+                                Err(e) => {
+                                    record_failure(&evt_sender, to_agent, bytes_out).await;
+                                    return Err(KitsuneP2pError::from(e));
+                                }
+                            };
+                            record_success(
+                                &evt_sender,
+                                to_agent.clone(),
+                                start.elapsed(),
+                                bytes_out,
+                                wire_data_len(&res),
+                            )
+                            .await;
                             if let Ok(res) = accept_result_cb(to_agent, res) {
                                 out.lock().await.push(res);
                             }
@@ -222,6 +255,149 @@ where
     .into()
 }
 
+/// Try to connect to a remote peer on each of the given urls in order,
+/// splitting the remaining timeout across the urls not yet attempted.
+pub(crate) async fn connect_any(
+    ep_hnd: &Tx2EpHnd<wire::Wire>,
+    urls: Vec<url2::Url2>,
+    timeout: KitsuneTimeout,
+) -> KitsuneP2pResult<(url2::Url2, Tx2ConHnd<wire::Wire>)> {
+    let count = urls.len();
+    let mut last_err = None;
+    for (idx, url) in urls.into_iter().enumerate() {
+        if timeout.is_expired() {
+            break;
+        }
+        let this_timeout = KitsuneTimeout::new(timeout.time_remaining() / (count - idx) as u32);
+        match ep_hnd.get_connection(url.clone(), this_timeout).await {
+            Ok(con_hnd) => return Ok((url, con_hnd)),
+            Err(e) => {
+                tracing::debug!(%url, ?e, "failed to connect, trying next url");
+                last_err = Some(e);
+            }
+        }
+    }
+    Err(match last_err {
+        Some(e) => e.into(),
+        None => "failed to connect".into(),
+    })
+}
+
+/// The payload size of a wire message, for reachability metrics.
+pub(crate) fn wire_data_len(wire: &wire::Wire) -> u64 {
+    match wire {
+        wire::Wire::Failure(wire::Failure { reason }) => reason.len() as u64,
+        wire::Wire::Call(wire::Call { data, .. })
+        | wire::Wire::CallResp(wire::CallResp { data })
+        | wire::Wire::Notify(wire::Notify { data, .. })
+        | wire::Wire::Gossip(wire::Gossip { data, .. }) => data.len() as u64,
+        wire::Wire::NotifyResp(_) => 0,
+    }
+}
+
+/// Record that a request to a remote agent succeeded.
+pub(crate) async fn record_success(
+    evt_sender: &futures::channel::mpsc::Sender<KitsuneP2pEvent>,
+    agent: Arc<KitsuneAgent>,
+    rtt: std::time::Duration,
+    bytes_out: u64,
+    bytes_in: u64,
+) {
+    record_reachability(
+        evt_sender,
+        agent,
+        ReachabilityOutcome::Success { rtt },
+        bytes_out,
+        bytes_in,
+    )
+    .await
+}
+
+/// Record that we could not reach a remote agent.
+pub(crate) async fn record_failure(
+    evt_sender: &futures::channel::mpsc::Sender<KitsuneP2pEvent>,
+    agent: Arc<KitsuneAgent>,
+    bytes_out: u64,
+) {
+    record_reachability(
+        evt_sender,
+        agent,
+        ReachabilityOutcome::Failure,
+        bytes_out,
+        0,
+    )
+    .await
+}
+
+async fn record_reachability(
+    evt_sender: &futures::channel::mpsc::Sender<KitsuneP2pEvent>,
+    agent: Arc<KitsuneAgent>,
+    outcome: ReachabilityOutcome,
+    bytes_out: u64,
+    bytes_in: u64,
+) {
+    if let Err(e) = evt_sender
+        .put_reachability_datum(ReachabilityDatum {
+            agent,
+            outcome,
+            bytes_out,
+            bytes_in,
+            timestamp: std::time::SystemTime::now(),
+        })
+        .await
+    {
+        tracing::warn!(msg = "failed to record peer reachability", ?e);
+    }
+}
+
+/// Order candidate agents so that healthy, low-latency peers come first,
+/// and agents backing off after recent failures come last.
+/// The sort is stable, so agents we know nothing about keep their
+/// relative (randomized) order.
+pub(crate) async fn rank_by_reachability(
+    evt_sender: &futures::channel::mpsc::Sender<KitsuneP2pEvent>,
+    list: Vec<AgentInfo>,
+) -> Vec<AgentInfo> {
+    let agents = list
+        .iter()
+        .map(|info| Arc::new(info.as_agent_ref().clone()))
+        .collect();
+    let reachability = match evt_sender
+        .query_metrics(MetricQuery::Reachability { agents })
+        .await
+    {
+        Ok(MetricQueryAnswer::Reachability(r)) => r,
+        Ok(_) => return list,
+        Err(e) => {
+            tracing::warn!(msg = "failed to query peer reachability", ?e);
+            return list;
+        }
+    };
+    let now = std::time::SystemTime::now();
+    let mut by_agent: HashMap<Arc<KitsuneAgent>, PeerReachability> = reachability
+        .into_iter()
+        .map(|r| (r.agent.clone(), r))
+        .collect();
+    let mut ranked: Vec<(bool, f64, AgentInfo)> = list
+        .into_iter()
+        .map(|info| {
+            let agent = Arc::new(info.as_agent_ref().clone());
+            let r = by_agent
+                .remove(&agent)
+                .unwrap_or_else(|| PeerReachability::new(agent));
+            (r.is_backing_off(now), r.score(), info)
+        })
+        .collect();
+    ranked.sort_by(|(a_backoff, a_score, _), (b_backoff, b_score, _)| {
+        a_backoff.cmp(b_backoff).then(
+            b_score
+                .partial_cmp(a_score)
+                .unwrap_or(std::cmp::Ordering::Equal),
+        )
+    });
+    ranked.into_iter().map(|(_, _, info)| info).collect()
+}
+
 /// search for agents to contact
 pub(crate) fn get_5_or_less_non_local_agents_near_basis(
     space: Arc<KitsuneSpace>,
@@ -244,6 +420,7 @@ pub(crate) fn get_5_or_less_non_local_agents_near_basis(
         {
             // randomize the results
             rand::seq::SliceRandom::shuffle(&mut list[..], &mut rand::thread_rng());
+            let mut candidates = Vec::new();
             for item in list {
                 if let Ok(info) = AgentInfo::try_from(&item) {
                     if let Ok(is_local) = i_s
@@ -251,10 +428,14 @@ pub(crate) fn get_5_or_less_non_local_agents_near_basis(
                         .await
                     {
                         if !is_local {
-                            out.insert(info);
+                            candidates.push(info);
                         }
                     }
                 }
+            }
+            // prefer healthy, low-latency peers over flaky ones
+            for info in rank_by_reachability(&evt_sender, candidates).await {
+                out.insert(info);
                 if out.len() >= 5 {
                     return Ok(out);
                 }
@@ -5,7 +5,6 @@ use ghost_actor::dependencies::tracing_futures::Instrument;
 use kitsune_p2p_mdns::*;
 use kitsune_p2p_types::codec::{rmp_decode, rmp_encode};
 use std::collections::{HashMap, HashSet};
-use std::sync::atomic::AtomicBool;
 
 /// if the user specifies None or zero (0) for race_timeout_ms
 /// (david.b) this is not currently used
@@ -19,6 +18,9 @@ ghost_actor::ghost_chan! {
         /// Update / publish our agent info
         fn update_agent_info() -> ();
 
+        /// Replace any mdns broadcast of a local agent's info with this one
+        fn update_mdns_broadcast(agent: Arc<KitsuneAgent>, agent_info_signed: crate::types::agent_store::AgentInfoSigned) -> ();
+
         /// see if an agent is locally joined
         fn is_agent_local(agent: Arc<KitsuneAgent>) -> bool;
 
@@ -29,7 +31,7 @@ ghost_actor::ghost_chan! {
 
 pub(crate) async fn spawn_space(
     space: Arc<KitsuneSpace>,
-    this_addr: url2::Url2,
+    this_addrs: Vec<url2::Url2>,
     ep_hnd: Tx2EpHnd<wire::Wire>,
     config: Arc<KitsuneP2pConfig>,
 ) -> KitsuneP2pResult<(
@@ -53,7 +55,7 @@ pub(crate) async fn spawn_space(
 
     tokio::task::spawn(builder.spawn(Space::new(
         space,
-        this_addr,
+        this_addrs,
         i_s.clone(),
         evt_send,
         ep_hnd,
@@ -94,15 +96,14 @@ impl SpaceInternalHandler for Space {
 
     fn handle_update_agent_info(&mut self) -> SpaceInternalHandlerResult<()> {
         let space = self.space.clone();
-        let mut mdns_handles = self.mdns_handles.clone();
+        let i_s = self.i_s.clone();
         let network_type = self.config.network_type.clone();
         let agent_list: Vec<Arc<KitsuneAgent>> = self.local_joined_agents.iter().cloned().collect();
-        let bound_url = self.this_addr.clone();
+        let urls = self.this_addrs.clone();
         let evt_sender = self.evt_sender.clone();
         let bootstrap_service = self.config.bootstrap_service.clone();
         let expires_after = self.config.tuning_params.agent_info_expires_after_ms as u64;
         Ok(async move {
-            let urls = vec![bound_url];
             for agent in agent_list {
                 let agent_info = crate::types::agent_store::AgentInfo::new(
                     (*space).clone(),
@@ -135,41 +136,18 @@ impl SpaceInternalHandler for Space {
                         agent_info_signed: agent_info_signed.clone(),
                     })
                     .await?;
-                // Push to the network as well
-                match network_type {
-                    NetworkType::QuicMdns => {
-                        // Broadcast only valid AgentInfo
-                        if !urls.is_empty() {
-                            // Kill previous broadcast for this space + agent
-                            let key = [space.get_bytes(), agent.get_bytes()].concat();
-                            if let Some(current_handle) = mdns_handles.get(&key) {
-                                mdns_kill_thread(current_handle.to_owned());
-                            }
-                            // Broadcast by using Space as service type and Agent as service name
-                            let space_b64 =
-                                base64::encode_config(&space[..], base64::URL_SAFE_NO_PAD);
-                            let agent_b64 =
-                                base64::encode_config(&agent[..], base64::URL_SAFE_NO_PAD);
-                            //println!("(MDNS) - Broadcasting of Agent {:?} ({}) in space {:?} ({} ; {})",
-                            // agent, agent.get_bytes().len(), space, space.get_bytes().len(), space_b64.len());
-                            // Broadcast rmp encoded agent_info_signed
-                            let mut buffer = Vec::new();
-                            rmp_encode(&mut buffer, &agent_info_signed)?;
-                            tracing::trace!(?space_b64, ?agent_b64);
-                            let handle =
-                                mdns_create_broadcast_thread(space_b64, agent_b64, &buffer);
-                            // store handle in self
-                            mdns_handles.insert(key, handle);
-                        }
-                    }
-                    NetworkType::QuicBootstrap => {
-                        crate::spawn::actor::bootstrap::put(
-                            bootstrap_service.clone(),
-                            agent_info_signed,
-                        )
-                        .await?;
+                // Push to the network as well.
+                // The bootstrap service is used whenever configured,
+                // so LAN peers found over mdns can still reach the WAN.
+                if let NetworkType::QuicMdns = network_type {
+                    // Broadcast only valid AgentInfo
+                    if !urls.is_empty() {
+                        i_s.update_mdns_broadcast(agent.clone(), agent_info_signed.clone())
+                            .await?;
                     }
                 }
+                crate::spawn::actor::bootstrap::put(bootstrap_service.clone(), agent_info_signed)
+                    .await?;
             }
             Ok(())
         }
@@ -177,6 +155,35 @@ impl SpaceInternalHandler for Space {
         .into())
     }
 
+    fn handle_update_mdns_broadcast(
+        &mut self,
+        agent: Arc<KitsuneAgent>,
+        agent_info_signed: crate::types::agent_store::AgentInfoSigned,
+    ) -> SpaceInternalHandlerResult<()> {
+        // The agent may have left while its info was being signed
+        if !self.local_joined_agents.contains(&agent) {
+            return Ok(async move { Ok(()) }.boxed().into());
+        }
+        // Broadcast by using Space as service type and Agent as service name
+        let space_b64 = base64::encode_config(&self.space[..], base64::URL_SAFE_NO_PAD);
+        let agent_b64 = base64::encode_config(&agent[..], base64::URL_SAFE_NO_PAD);
+        let mut buffer = Vec::new();
+        rmp_encode(&mut buffer, &agent_info_signed)?;
+        tracing::trace!(?space_b64, ?agent_b64, len = buffer.len());
+        // Drop the previous broadcast before starting the new one,
+        // so peers never see both at once
+        self.mdns_handles.remove(&agent);
+        match mdns_broadcast(space_b64, agent_b64, &buffer) {
+            Ok(handle) => {
+                self.mdns_handles.insert(agent, handle);
+            }
+            Err(e) => {
+                tracing::error!(msg = "Failed to broadcast agent info over MDNS", ?e);
+            }
+        }
+        Ok(async move { Ok(()) }.boxed().into())
+    }
+
     fn handle_is_agent_local(
         &mut self,
         agent: Arc<KitsuneAgent>,
@@ -217,86 +224,45 @@ impl KitsuneP2pHandler for Space {
         let fut = self.i_s.update_agent_info();
         let i_s = self.i_s.clone();
         let evt_sender = self.evt_sender.clone();
-        match self.config.network_type {
-            NetworkType::QuicMdns => {
-                // Listen to MDNS service that has that space as service type
-                let space_b64 = base64::encode_config(&space[..], base64::URL_SAFE_NO_PAD);
-                //println!("(MDNS) - Agent {:?} ({}) joined space {:?} ({} ; {})", agent, agent.get_bytes().len(), space, space.get_bytes().len(), dna_str.len());
-                if !self.mdns_listened_spaces.contains(&space_b64) {
-                    self.mdns_listened_spaces.insert(space_b64.clone());
-                    tokio::task::spawn(async move {
-                        let stream = mdns_listen(space_b64);
-                        tokio::pin!(stream);
-                        while let Some(maybe_response) = stream.next().await {
-                            match maybe_response {
-                                Ok(response) => {
-                                    tracing::trace!(msg = "Peer found via MDNS", ?response);
-                                    // Decode response
-                                    let remote_agent_vec = base64::decode_config(
-                                        &response.service_name[..],
-                                        base64::URL_SAFE_NO_PAD,
-                                    )
-                                    .expect("Agent base64 decode failed");
-                                    let remote_agent = Arc::new(KitsuneAgent(remote_agent_vec));
-                                    //println!("(MDNS) - Peer found via MDNS: {:?})", *remote_agent);
-                                    let maybe_agent_info_signed =
-                                        rmp_decode(&mut &*response.buffer);
-                                    if let Err(e) = maybe_agent_info_signed {
-                                        tracing::error!(msg = "Failed to decode MDNS peer", ?e);
-                                        continue;
-                                    }
-                                    let remote_agent_info_signed = maybe_agent_info_signed.unwrap();
-                                    //println!("(MDNS) - Found agent_info_signed: {:?})", remote_agent_info_signed);
-                                    // Add to local storage
-                                    let _result = evt_sender
-                                        .put_agent_info_signed(PutAgentInfoSignedEvt {
-                                            space: space.clone(),
-                                            agent: remote_agent,
-                                            agent_info_signed: remote_agent_info_signed,
-                                        })
-                                        .await;
-                                }
-                                Err(e) => {
-                                    tracing::error!(msg = "Failed to get peers from MDNS", ?e);
-                                }
-                            }
-                        }
-                    });
-                }
-            }
-            NetworkType::QuicBootstrap => {
-                let bootstrap_service = self.config.bootstrap_service.clone();
-                if let Some(bootstrap_service) = bootstrap_service {
-                    tokio::task::spawn(async move {
-                        const START_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
-                        const MAX_DELAY: std::time::Duration =
-                            std::time::Duration::from_secs(60 * 60);
-                        let mut delay_len = START_DELAY;
-
-                        loop {
-                            tokio::time::sleep(delay_len).await;
-                            if delay_len <= MAX_DELAY {
-                                delay_len *= 2;
-                            }
-
-                            // TODO - this will make redundant requests to bootstrap server if multiple local agents have joined the same space.
-                            if let Err(e) = super::discover::add_5_or_less_non_local_agents(
-                                space.clone(),
-                                agent.clone(),
-                                i_s.clone(),
-                                evt_sender.clone(),
-                                bootstrap_service.clone(),
-                            )
-                            .await
-                            {
-                                tracing::error!(msg = "Failed to get peers from bootstrap", ?e);
-                            }
-                        }
-                    });
-                }
+        if let NetworkType::QuicMdns = self.config.network_type {
+            // Listen to MDNS service that has that space as service type
+            if self.mdns_listener.is_none() {
+                self.mdns_listener = Some(tokio::task::spawn(mdns_listen_space(
+                    space.clone(),
+                    evt_sender.clone(),
+                )));
             }
         }
+        // Peers from the bootstrap service are merged with those
+        // found over mdns, whenever a bootstrap service is configured
+        let bootstrap_service = self.config.bootstrap_service.clone();
+        if let Some(bootstrap_service) = bootstrap_service {
+            tokio::task::spawn(async move {
+                const START_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+                const MAX_DELAY: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+                let mut delay_len = START_DELAY;
+
+                loop {
+                    tokio::time::sleep(delay_len).await;
+                    if delay_len <= MAX_DELAY {
+                        delay_len *= 2;
+                    }
 
+                    // TODO - this will make redundant requests to bootstrap server if multiple local agents have joined the same space.
+                    if let Err(e) = super::discover::add_5_or_less_non_local_agents(
+                        space.clone(),
+                        agent.clone(),
+                        i_s.clone(),
+                        evt_sender.clone(),
+                        bootstrap_service.clone(),
+                    )
+                    .await
+                    {
+                        tracing::error!(msg = "Failed to get peers from bootstrap", ?e);
+                    }
+                }
+            });
+        }
         Ok(async move { fut.await }.boxed().into())
     }
 
@@ -306,6 +272,12 @@ impl KitsuneP2pHandler for Space {
         agent: Arc<KitsuneAgent>,
     ) -> KitsuneP2pHandlerResult<()> {
         self.local_joined_agents.remove(&agent);
+        self.mdns_handles.remove(&agent);
+        if self.local_joined_agents.is_empty() {
+            if let Some(listener) = self.mdns_listener.take() {
+                listener.abort();
+            }
+        }
         self.gossip_mod.local_agent_leave(agent);
         Ok(async move { Ok(()) }.boxed().into())
     }
@@ -342,7 +314,23 @@ impl KitsuneP2pHandler for Space {
                         to_agent.clone(),
                         payload.into(),
                     );
-                    let res = con_hnd.request(&payload, timeout).await?;
+                    let start = std::time::Instant::now();
+                    let bytes_out = discover::wire_data_len(&payload);
+                    let res = match con_hnd.request(&payload, timeout).await {
+                        Ok(res) => res,
+                        Err(e) => {
+                            discover::record_failure(&evt_sender, to_agent, bytes_out).await;
+                            return Err(e.into());
+                        }
+                    };
+                    discover::record_success(
+                        &evt_sender,
+                        to_agent,
+                        start.elapsed(),
+                        bytes_out,
+                        discover::wire_data_len(&res),
+                    )
+                    .await;
                     match res {
                         wire::Wire::Failure(wire::Failure { reason }) => Err(reason.into()),
                         wire::Wire::CallResp(wire::CallResp { data }) => Ok(data.into()),
@@ -437,26 +425,121 @@ impl KitsuneP2pHandler for Space {
     }
 }
 
+/// Store the agent info of every peer broadcasting in this space over MDNS.
+/// Runs until aborted, restarting the MDNS query if it ever stops.
+async fn mdns_listen_space(
+    space: Arc<KitsuneSpace>,
+    evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
+) {
+    const RESTART_DELAY: std::time::Duration = std::time::Duration::from_secs(5);
+    loop {
+        mdns_listen_space_once(space.clone(), evt_sender.clone()).await;
+        tracing::warn!(msg = "MDNS listener stopped, restarting");
+        tokio::time::sleep(RESTART_DELAY).await;
+    }
+}
+
+async fn mdns_listen_space_once(
+    space: Arc<KitsuneSpace>,
+    evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
+) {
+    let space_b64 = base64::encode_config(&space[..], base64::URL_SAFE_NO_PAD);
+    let stream = mdns_listen(space_b64);
+    tokio::pin!(stream);
+    while let Some(maybe_response) = stream.next().await {
+        let response = match maybe_response {
+            Ok(response) => response,
+            Err(e) => {
+                tracing::error!(msg = "Failed to get peers from MDNS", ?e);
+                continue;
+            }
+        };
+        tracing::trace!(msg = "Peer found via MDNS", ?response);
+        let remote_agent =
+            match base64::decode_config(&response.service_name[..], base64::URL_SAFE_NO_PAD) {
+                Ok(remote_agent) => Arc::new(KitsuneAgent(remote_agent)),
+                Err(e) => {
+                    tracing::warn!(msg = "Failed to decode MDNS peer agent", ?e);
+                    continue;
+                }
+            };
+        let remote_agent_info_signed: crate::types::agent_store::AgentInfoSigned =
+            match rmp_decode(&mut &*response.buffer) {
+                Ok(remote_agent_info_signed) => remote_agent_info_signed,
+                Err(e) => {
+                    tracing::warn!(msg = "Failed to decode MDNS peer", ?e);
+                    continue;
+                }
+            };
+        if remote_agent_info_signed.as_agent_ref() != &*remote_agent {
+            tracing::warn!(
+                msg = "MDNS peer broadcast agent info for another agent",
+                ?remote_agent
+            );
+            continue;
+        }
+        if !mdns_agent_info_is_valid(&space, &remote_agent_info_signed).await {
+            tracing::warn!(
+                msg = "MDNS peer broadcast invalid agent info",
+                ?remote_agent
+            );
+            continue;
+        }
+        // Add to local storage
+        if let Err(e) = evt_sender
+            .put_agent_info_signed(PutAgentInfoSignedEvt {
+                space: space.clone(),
+                agent: remote_agent,
+                agent_info_signed: remote_agent_info_signed,
+            })
+            .await
+        {
+            tracing::error!(msg = "Failed to store MDNS peer", ?e);
+        }
+    }
+}
+
+/// Check that agent info found over MDNS is for this space
+/// and is signed by the agent it describes.
+async fn mdns_agent_info_is_valid(
+    space: &KitsuneSpace,
+    signed: &crate::types::agent_store::AgentInfoSigned,
+) -> bool {
+    match signed.verify().await {
+        Ok(info) => info.as_space_ref() == space,
+        Err(_) => false,
+    }
+}
+
 /// A Kitsune P2p Node can track multiple "spaces" -- Non-interacting namespaced
 /// areas that share common transport infrastructure for communication.
 pub(crate) struct Space {
     pub(crate) space: Arc<KitsuneSpace>,
-    pub(crate) this_addr: url2::Url2,
+    pub(crate) this_addrs: Vec<url2::Url2>,
     pub(crate) i_s: ghost_actor::GhostSender<SpaceInternal>,
     pub(crate) evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
     pub(crate) ep_hnd: Tx2EpHnd<wire::Wire>,
     pub(crate) local_joined_agents: HashSet<Arc<KitsuneAgent>>,
     pub(crate) config: Arc<KitsuneP2pConfig>,
-    mdns_handles: HashMap<Vec<u8>, Arc<AtomicBool>>,
-    mdns_listened_spaces: HashSet<String>,
+    mdns_handles: HashMap<Arc<KitsuneAgent>, MdnsBroadcast>,
+    mdns_listener: Option<tokio::task::JoinHandle<()>>,
     gossip_mod: GossipModule,
 }
 
+impl Drop for Space {
+    fn drop(&mut self) {
+        // the listener task would otherwise outlive the space
+        if let Some(listener) = self.mdns_listener.take() {
+            listener.abort();
+        }
+    }
+}
+
 impl Space {
     /// space constructor
     pub fn new(
         space: Arc<KitsuneSpace>,
-        this_addr: url2::Url2,
+        this_addrs: Vec<url2::Url2>,
         i_s: ghost_actor::GhostSender<SpaceInternal>,
         evt_sender: futures::channel::mpsc::Sender<KitsuneP2pEvent>,
         ep_hnd: Tx2EpHnd<wire::Wire>,
@@ -489,14 +572,14 @@ impl Space {
 
         Self {
             space,
-            this_addr,
+            this_addrs,
             i_s,
             evt_sender,
             ep_hnd,
             local_joined_agents: HashSet::new(),
             config,
             mdns_handles: HashMap::new(),
-            mdns_listened_spaces: HashSet::new(),
+            mdns_listener: None,
             gossip_mod,
         }
     }
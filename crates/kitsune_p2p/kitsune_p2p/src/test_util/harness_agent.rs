@@ -165,6 +165,14 @@ impl KitsuneP2pEventHandler for AgentHarness {
         Ok(async move { Ok(()) }.boxed().into())
     }
 
+    fn handle_put_reachability_datum(
+        &mut self,
+        datum: ReachabilityDatum,
+    ) -> KitsuneP2pEventHandlerResult<()> {
+        self.metric_store.put_reachability_datum(datum);
+        Ok(async move { Ok(()) }.boxed().into())
+    }
+
     fn handle_query_metrics(
         &mut self,
         query: MetricQuery,
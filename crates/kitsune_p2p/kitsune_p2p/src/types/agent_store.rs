@@ -1,5 +1,6 @@
 //! Data structures to be stored in the agent/peer database.
 
+use std::convert::TryFrom;
 use std::convert::TryInto;
 
 use crate::types::KitsuneAgent;
@@ -8,6 +9,7 @@ use crate::types::KitsuneSignature;
 use crate::types::KitsuneSpace;
 use crate::KitsuneBinType;
 use kitsune_p2p_types::dht_arc::DhtArc;
+use kitsune_p2p_types::tx2::tx2_utils::TxUrl;
 use url2::Url2;
 
 /// A list of Urls.
@@ -83,6 +85,36 @@ impl AgentInfoSigned {
     pub fn as_agent_info_ref(&self) -> &[u8] {
         self.agent_info.as_ref()
     }
+
+    /// Decode the agent info, checking that it describes the agent
+    /// it is signed by and that the signature is that agent's.
+    /// The space and times in the agent info are left for the caller to check.
+    pub async fn verify(&self) -> Result<AgentInfo, KitsuneP2pError> {
+        let info = AgentInfo::try_from(self)?;
+        if info.as_agent_ref() != self.as_agent_ref() {
+            return Err("signed agent does not match agent info".into());
+        }
+
+        // the first 32 bytes of a kitsune agent are its ed25519 public key
+        let agent: &[u8] = self.as_agent_ref().as_ref();
+        if agent.len() < 32 {
+            return Err("agent is not a public key".into());
+        }
+        let pub_key: lair_keystore_api::internal::sign_ed25519::SignEd25519PubKey =
+            agent[..32].to_vec().into();
+        let sig: lair_keystore_api::internal::sign_ed25519::SignEd25519Signature =
+            self.as_signature_ref().0.to_vec().into();
+        let data = std::sync::Arc::new(self.agent_info.clone());
+        let valid = pub_key
+            .verify(data, sig)
+            .await
+            .map_err(|e| KitsuneP2pError::from(e.to_string()))?;
+        if !valid {
+            return Err("invalid signature".into());
+        }
+
+        Ok(info)
+    }
 }
 
 impl std::convert::TryFrom<&AgentInfoSigned> for Vec<u8> {
@@ -241,6 +273,29 @@ impl AgentInfo {
         self.as_ref()
     }
 
+    /// The urls of this agent we can reach over any of our bound local
+    /// transport addresses, in the order the agent advertised them.
+    /// Proxy urls are matched by the scheme of the transport they wrap.
+    pub fn reachable_urls(&self, local_addrs: &[TxUrl]) -> Vec<Url2> {
+        fn base_scheme(url: &str) -> String {
+            match kitsune_p2p_proxy::ProxyUrl::from_full(url) {
+                Ok(purl) => purl.as_base().scheme().to_string(),
+                Err(_) => url.split(':').next().unwrap_or_default().to_string(),
+            }
+        }
+
+        let local_schemes = local_addrs
+            .iter()
+            .map(|a| base_scheme(a.as_str()))
+            .collect::<Vec<_>>();
+
+        self.urls
+            .iter()
+            .filter(|u| local_schemes.contains(&base_scheme(u.as_str())))
+            .cloned()
+            .collect()
+    }
+
     /// Accessor for signed_at_ms.
     pub fn signed_at_ms(&self) -> u64 {
         self.signed_at_ms
@@ -1,7 +1,10 @@
 //! Definitions for events emited from the KitsuneP2p actor.
 
 use crate::types::agent_store::AgentInfoSigned;
-use std::{sync::Arc, time::SystemTime};
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 /// Gather a list of op-hashes from our implementor that meet criteria.
 #[derive(Debug)]
@@ -115,6 +118,145 @@ impl Ord for MetricDatum {
     }
 }
 
+/// The outcome of a single attempt to reach a remote agent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReachabilityOutcome {
+    /// The remote agent responded to our request.
+    Success {
+        /// The round trip time of the request.
+        rtt: Duration,
+    },
+
+    /// We were unable to connect to the remote agent,
+    /// or it did not respond within the timeout.
+    Failure,
+}
+
+/// A record of a single attempt to reach a remote agent,
+/// to be aggregated by the client into a `PeerReachability`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReachabilityDatum {
+    /// The agent we were trying to reach
+    pub agent: Arc<super::KitsuneAgent>,
+    /// How the attempt went
+    pub outcome: ReachabilityOutcome,
+    /// Payload bytes we sent to the agent
+    pub bytes_out: u64,
+    /// Payload bytes we received from the agent
+    pub bytes_in: u64,
+    /// The time at which this occurred
+    pub timestamp: SystemTime,
+}
+
+/// Aggregated reachability metrics about a single remote agent,
+/// used to prefer healthy, low-latency peers when selecting targets.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeerReachability {
+    /// The agent these metrics are about
+    pub agent: Arc<super::KitsuneAgent>,
+    /// Exponentially weighted moving average of round trip times,
+    /// if we have ever had a successful response.
+    pub rtt_ewma: Option<Duration>,
+    /// Total count of successful requests
+    pub success_count: u64,
+    /// Total count of failed requests
+    pub failure_count: u64,
+    /// Count of failed requests since the last success
+    pub consecutive_failures: u32,
+    /// Total payload bytes sent to this agent
+    pub bytes_out: u64,
+    /// Total payload bytes received from this agent
+    pub bytes_in: u64,
+    /// The last time a request to this agent failed
+    pub last_failure: Option<SystemTime>,
+}
+
+impl PeerReachability {
+    /// Weight given to each new rtt sample in the moving average,
+    /// expressed as a divisor (i.e. 1/8, as in TCP's SRTT).
+    const RTT_EWMA_DIVISOR: u32 = 8;
+
+    /// Rtt assumed for agents we have never had a response from.
+    const DEFAULT_RTT: Duration = Duration::from_millis(250);
+
+    /// Backoff after the first consecutive failure,
+    /// doubled for each further failure.
+    const BACKOFF_BASE: Duration = Duration::from_secs(5);
+
+    /// Backoff is never longer than this.
+    const BACKOFF_MAX: Duration = Duration::from_secs(60 * 10);
+
+    /// Metrics for an agent we know nothing about yet.
+    pub fn new(agent: Arc<super::KitsuneAgent>) -> Self {
+        Self {
+            agent,
+            rtt_ewma: None,
+            success_count: 0,
+            failure_count: 0,
+            consecutive_failures: 0,
+            bytes_out: 0,
+            bytes_in: 0,
+            last_failure: None,
+        }
+    }
+
+    /// Fold a new datum about this agent into the aggregate.
+    pub fn apply(&mut self, datum: &ReachabilityDatum) {
+        self.bytes_out = self.bytes_out.saturating_add(datum.bytes_out);
+        self.bytes_in = self.bytes_in.saturating_add(datum.bytes_in);
+        match datum.outcome {
+            ReachabilityOutcome::Success { rtt } => {
+                self.success_count = self.success_count.saturating_add(1);
+                self.consecutive_failures = 0;
+                self.rtt_ewma = Some(match self.rtt_ewma {
+                    None => rtt,
+                    Some(avg) => {
+                        (avg * (Self::RTT_EWMA_DIVISOR - 1) + rtt) / Self::RTT_EWMA_DIVISOR
+                    }
+                });
+            }
+            ReachabilityOutcome::Failure => {
+                self.failure_count = self.failure_count.saturating_add(1);
+                self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+                self.last_failure = Some(match self.last_failure {
+                    Some(last) if last > datum.timestamp => last,
+                    _ => datum.timestamp,
+                });
+            }
+        }
+    }
+
+    /// The time until which we should avoid this agent, if it is
+    /// currently failing. Backoff grows exponentially with
+    /// consecutive failures.
+    pub fn backoff_until(&self) -> Option<SystemTime> {
+        if self.consecutive_failures == 0 {
+            return None;
+        }
+        let exp = (self.consecutive_failures - 1).min(16);
+        let backoff = Self::BACKOFF_BASE
+            .checked_mul(1 << exp)
+            .unwrap_or(Self::BACKOFF_MAX)
+            .min(Self::BACKOFF_MAX);
+        self.last_failure.map(|t| t + backoff)
+    }
+
+    /// Is this agent in a backoff period at the given time?
+    pub fn is_backing_off(&self, now: SystemTime) -> bool {
+        matches!(self.backoff_until(), Some(until) if until > now)
+    }
+
+    /// A health score for this agent, higher is better.
+    /// Combines the (smoothed) success ratio with the average rtt,
+    /// so that unknown agents rank between healthy and flaky ones.
+    pub fn score(&self) -> f64 {
+        let ratio = (self.success_count as f64 + 1.0)
+            / (self.success_count as f64 + self.failure_count as f64 + 2.0);
+        let rtt_ms = self.rtt_ewma.unwrap_or(Self::DEFAULT_RTT).as_secs_f64() * 1000.0;
+        ratio / (1.0 + rtt_ms / 100.0)
+    }
+}
+
 /// Different kinds of queries about metric data
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum MetricQuery {
@@ -128,6 +270,11 @@ pub enum MetricQuery {
         /// Agents whose last connection error is earlier than this time will be filtered out.
         last_connect_error_threshold: std::time::SystemTime,
     },
+    /// Aggregated reachability metrics for a set of agents.
+    Reachability {
+        /// The agents to query by
+        agents: Vec<Arc<super::KitsuneAgent>>,
+    },
 }
 
 /// Corresponding response to `MetricQuery`
@@ -137,6 +284,9 @@ pub enum MetricQueryAnswer {
     LastSync(Option<std::time::SystemTime>),
     /// The agent with the oldest last-connection time which satisfies the query.
     Oldest(Option<Arc<super::KitsuneAgent>>),
+    /// Reachability metrics for each queried agent we have data about.
+    /// Agents with no recorded data are omitted.
+    Reachability(Vec<PeerReachability>),
 }
 
 ghost_actor::ghost_chan! {
@@ -155,6 +305,9 @@ ghost_actor::ghost_chan! {
         /// Record a metric datum about an agent.
         fn put_metric_datum(datum: MetricDatum) -> ();
 
+        /// Record the outcome of an attempt to reach an agent.
+        fn put_reachability_datum(datum: ReachabilityDatum) -> ();
+
         /// Ask for metric data.
         fn query_metrics(query: MetricQuery) -> MetricQueryAnswer;
 
@@ -1,20 +1,37 @@
 ///! Crate for discovering Holochain peers over MDNS
 ///! Works by broadcasting a service named `HC_SERVICE_NAME`
-///! and adding base64 encoded data in a TXT record
+///! and adding base64 encoded data in TXT records
+///!
+///! Data too large for a single record is split across several
+///! service instances, and reassembled by the listener.
 ///!
 ///! Uses libmdns crate for broadcasting
 ///! Uses mdns crate for discovery
 use err_derive::Error;
 use mdns::RecordKind;
-use std::time::Duration;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
 use tokio_stream::{Stream, StreamExt};
 
-use std::sync::atomic::{AtomicBool, Ordering};
-
 const HC_SERVICE_PROTOCOL: &str = "._udp";
-const BROADCAST_INTERVAL_SEC: u64 = 8;
 const QUERY_INTERVAL_SEC: u64 = 5;
+/// Max size of a single TXT string, well under the 255 bytes allowed.
 const MAX_TXT_SIZE: usize = 192;
+/// Max raw bytes carried by a single service instance.
+/// Keeps each instance's TXT record at a few hundred bytes,
+/// so responses fit in a single unfragmented packet.
+const MAX_RECORD_SIZE: usize = 384;
+/// Max number of service instances a single broadcast may be split across.
+const MAX_RECORD_COUNT: usize = 32;
+/// How long the chunks of an incomplete broadcast are kept.
+const PENDING_TTL: Duration = Duration::from_secs(QUERY_INTERVAL_SEC * 6);
+/// Max number of incomplete broadcasts kept at once.
+/// The one which started longest ago is dropped to make room.
+const MAX_PENDING: usize = 256;
+/// Prefix of the TXT string describing which chunk a record carries.
+const CHUNK_PREFIX: &str = "c=";
+/// Prefix of the TXT strings carrying the base64 encoded chunk data.
+const DATA_PREFIX: &str = "d=";
 
 #[derive(Debug, Error)]
 pub enum MdnsError {
@@ -22,70 +39,68 @@ pub enum MdnsError {
     Mdns(#[error(source)] mdns::Error),
     #[error(display = "Base64 decoding error {}", _0)]
     Base64(#[error(source)] base64::DecodeError),
+    #[error(display = "IO error {}", _0)]
+    Io(#[error(source)] std::io::Error),
+    #[error(display = "Invalid service type or name: {}", _0)]
+    InvalidName(String),
+    #[error(display = "Data too large to broadcast: {} bytes", _0)]
+    TooLarge(usize),
+    #[error(display = "Invalid record: {}", _0)]
+    InvalidRecord(String),
+}
+
+/// A running mdns broadcast.
+/// The broadcast stops when this handle is dropped.
+pub struct MdnsBroadcast {
+    // services unregister themselves on drop,
+    // so they must be dropped before the responder
+    services: Vec<libmdns::Service>,
+    _responder: libmdns::Responder,
 }
 
-/// Stop thread created by `mdns_create_broadcast_thread()`
-pub fn mdns_kill_thread(can_run: ::std::sync::Arc<AtomicBool>) {
-    can_run.store(false, Ordering::Relaxed);
+impl std::fmt::Debug for MdnsBroadcast {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MdnsBroadcast")
+            .field("record_count", &self.services.len())
+            .finish()
+    }
 }
 
-/// Create a thread that will broadcast a holochain service over mdns
-/// Returns Sender for sending thread termination command
-pub fn mdns_create_broadcast_thread(
+/// Broadcast a holochain service over mdns, with `buffer` as its data.
+/// The buffer is split across as many service instances as needed.
+/// Drop the returned handle to stop broadcasting.
+pub fn mdns_broadcast(
     service_type: String,
     service_name: String,
     buffer: &[u8],
-) -> ::std::sync::Arc<AtomicBool> {
+) -> Result<MdnsBroadcast, MdnsError> {
     let svc_type = format!("_{}{}", service_type, HC_SERVICE_PROTOCOL);
-    // Constraints in libmdns
-    assert!(
-        svc_type.len() < 63,
-        "len = {} ({}) ; {}",
-        svc_type.len(),
-        service_type.len(),
-        service_type
-    );
-    assert!(service_name.len() < 63);
-    // Create Termination command variable
-    let can_run = ::std::sync::Arc::new(AtomicBool::new(true));
-    let can_run_clone = can_run.clone();
-    // Change buffer to base64 string
-    let mut b64 = format!(
-        "u{}",
-        base64::encode_config(buffer, base64::URL_SAFE_NO_PAD)
-    );
-    //println!(
-    //    "Broadcasting service type '{}', named '{}' over mdns ({})",
-    //    svc_type,
-    //    service_name,
-    //    b64.len()
-    //);
-    // Create thread
-    let _handle = tokio::task::spawn(async move {
-        // Split buffer to fix TXT max size
-        let mut substrs = Vec::new();
-        while b64.len() > MAX_TXT_SIZE {
-            let start: String = b64.drain(..MAX_TXT_SIZE).collect();
-            substrs.push(start);
-        }
-        substrs.push(b64);
-        let txts: Vec<_> = substrs.iter().map(AsRef::as_ref).collect();
-        //println!("Entering mdns broadcasting thread...");
-        // Create mdns responder
-
-        let responder = libmdns::Responder::new().unwrap();
-        let _svc = responder.register(svc_type, service_name, 0, &txts);
-        // Loop forever unless termination command received
-        loop {
-            tokio::time::sleep(::std::time::Duration::from_secs(BROADCAST_INTERVAL_SEC)).await;
-            if !can_run_clone.load(Ordering::Relaxed) {
-                //println!("Terminating.");
-                break;
-            }
-        }
-    });
-    // Done
-    can_run
+    // Constraints in libmdns, leaving room for the "-<index>" suffix
+    if svc_type.len() >= 63 {
+        return Err(MdnsError::InvalidName(svc_type));
+    }
+    if service_name.len() >= 63 - 3 || service_name.contains('.') {
+        return Err(MdnsError::InvalidName(service_name));
+    }
+    let records = encode_records(buffer)?;
+    let responder = libmdns::Responder::new()?;
+    let services = records
+        .iter()
+        .enumerate()
+        .map(|(index, txts)| {
+            let txts: Vec<&str> = txts.iter().map(AsRef::as_ref).collect();
+            responder.register(
+                svc_type.clone(),
+                format!("{}-{}", service_name, index),
+                0,
+                &txts,
+            )
+        })
+        .collect();
+    Ok(MdnsBroadcast {
+        services,
+        _responder: responder,
+    })
 }
 
 ///
@@ -97,79 +112,327 @@ pub struct MdnsResponse {
     pub service_name: String,
     /// IP address that responded to the mdns query
     pub addr: std::net::IpAddr,
-    /// Data contained in the TXT record
+    /// Data contained in the TXT records, reassembled
     pub buffer: Vec<u8>,
 }
 
 /// Queries the network for the holochain service.
-/// Returns an iterator over all responses received.
-#[allow(clippy::let_and_return)]
+/// Returns a stream over all complete broadcasts received.
+/// A broadcast split across several records is only returned
+/// once all its records have been received.
 pub fn mdns_listen(service_type: String) -> impl Stream<Item = Result<MdnsResponse, MdnsError>> {
-    //let service_name = format!("{}.local", HC_SERVICE_TYPE);
     let svc_type = format!("_{}{}.local", service_type, HC_SERVICE_PROTOCOL);
-    //println!("MDNS query for service type '{}'", svc_type);
-    let query = mdns::discover::all(svc_type, Duration::from_secs(QUERY_INTERVAL_SEC))
-        .expect("mdns Discover failed");
-    // Get Mdns Response stream
-    let response_stream = query.listen();
-    // Change it into a MdnsResponse stream
-    let mdns_stream = response_stream
-        // Filtering out Empty responses
-        .filter(move |res| {
-            match res {
-                Ok(response) => !response.is_empty() && response.ip_addr().is_some(),
-                Err(_) => true, // Keep errors
-            }
-        })
-        .map(|maybe_response| {
-            if let Err(e) = maybe_response {
-                return Err(MdnsError::Mdns(e));
+    async_stream::stream! {
+        match mdns::discover::all(svc_type, Duration::from_secs(QUERY_INTERVAL_SEC)) {
+            Err(e) => {
+                yield Err(MdnsError::Mdns(e));
             }
-            let response = maybe_response.unwrap();
-            // NOTE: if response.ip_addr() is not te right address,
-            // we should give all A/AAA records found in the answers instead
-            let addr = response.ip_addr().unwrap(); // should have already been filtered out
-            let mut buffer = Vec::new();
-            let mut service_name = String::new();
-            let mut service_type = String::new();
-            //println!("Response Answer count = {}", response.answers.len());
-            for answer in response.answers {
-                match answer.kind {
-                    RecordKind::TXT(txts) => {
-                        //println!("TXT count = {}", txts.len());
-                        let mut b64 = String::new();
-                        for txt in txts {
-                            //println!("Response TXT = {:?}", txt);
-                            b64.push_str(&txt);
+            Ok(query) => {
+                let response_stream = query.listen();
+                tokio::pin!(response_stream);
+                let mut reassembly = Reassembly::default();
+                while let Some(maybe_response) = response_stream.next().await {
+                    let response = match maybe_response {
+                        Err(e) => {
+                            yield Err(MdnsError::Mdns(e));
+                            continue;
                         }
-                        buffer = match base64::decode_config(&b64[1..], base64::URL_SAFE_NO_PAD) {
-                            Err(e) => return Err(MdnsError::Base64(e)),
-                            Ok(s) => s,
+                        Ok(response) => response,
+                    };
+                    // NOTE: if response.ip_addr() is not the right address,
+                    // we should give all A/AAA records found in the answers instead
+                    let addr = match response.ip_addr() {
+                        Some(addr) => addr,
+                        None => continue,
+                    };
+                    // TXT records are usually sent as additional records
+                    // alongside the PTR answer, but may also be answers
+                    for record in response.answers.iter().chain(response.additional.iter()) {
+                        let txts = match &record.kind {
+                            RecordKind::TXT(txts) => txts,
+                            _ => continue,
                         };
+                        let service_name = match instance_service_name(&record.name) {
+                            Some(service_name) => service_name,
+                            None => continue,
+                        };
+                        match reassembly.add(addr, service_name.clone(), txts, Instant::now()) {
+                            Err(e) => {
+                                yield Err(e);
+                            }
+                            Ok(None) => (),
+                            Ok(Some(buffer)) => {
+                                yield Ok(MdnsResponse {
+                                    service_type: service_type.clone(),
+                                    service_name,
+                                    addr,
+                                    buffer,
+                                });
+                            }
+                        }
                     }
-                    // Retrieve service name stored in PTR record
-                    RecordKind::PTR(ptr) => {
-                        //println!("PTR = {}", ptr);
-                        service_name = ptr
-                            .split('.')
-                            .into_iter()
-                            .next()
-                            .expect("Found service without a name")
-                            .to_string();
-                        let names: Vec<&str> = answer.name.split("._").collect();
-                        //println!("answer.name = {}", answer.name);
-                        service_type = names[0][1..].to_string();
-                    }
-                    _ => {}
                 }
             }
-            Ok(MdnsResponse {
-                service_type,
-                service_name,
-                addr,
-                buffer,
-            })
+        }
+    }
+}
+
+/// Extract the broadcast service name from a record name of the form
+/// `<service_name>-<index>._<service_type>._udp.local`.
+fn instance_service_name(record_name: &str) -> Option<String> {
+    let instance = record_name.split('.').next()?;
+    let split = instance.rfind('-')?;
+    Some(instance[..split].to_string())
+}
+
+/// Split a buffer into the TXT strings of each service instance.
+/// Every instance starts with a `c=<index>/<count>/<tag>` string,
+/// followed by `d=<base64>` data strings.
+/// The tag identifies the buffer, so chunks of an old broadcast
+/// are never mixed with chunks of its replacement.
+fn encode_records(buffer: &[u8]) -> Result<Vec<Vec<String>>, MdnsError> {
+    let count = std::cmp::max(1, (buffer.len() + MAX_RECORD_SIZE - 1) / MAX_RECORD_SIZE);
+    if count > MAX_RECORD_COUNT {
+        return Err(MdnsError::TooLarge(buffer.len()));
+    }
+    let tag = {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        buffer.hash(&mut hasher);
+        hasher.finish()
+    };
+    let mut chunks: Vec<&[u8]> = buffer.chunks(MAX_RECORD_SIZE).collect();
+    if chunks.is_empty() {
+        chunks.push(&[]);
+    }
+    Ok(chunks
+        .into_iter()
+        .enumerate()
+        .map(|(index, chunk)| {
+            let b64 = base64::encode_config(chunk, base64::URL_SAFE_NO_PAD);
+            let mut txts = vec![format!("{}{}/{}/{:016x}", CHUNK_PREFIX, index, count, tag)];
+            // base64 is ascii, so splitting on bytes is safe
+            for part in b64.as_bytes().chunks(MAX_TXT_SIZE) {
+                txts.push(format!(
+                    "{}{}",
+                    DATA_PREFIX,
+                    std::str::from_utf8(part).expect("base64 is ascii")
+                ));
+            }
+            txts
+        })
+        .collect())
+}
+
+/// A single decoded service instance.
+#[derive(Debug, PartialEq)]
+struct Chunk {
+    index: usize,
+    count: usize,
+    tag: String,
+    data: Vec<u8>,
+}
+
+fn decode_record(txts: &[String]) -> Result<Chunk, MdnsError> {
+    let invalid = |msg: &str| MdnsError::InvalidRecord(msg.to_string());
+    let mut header = None;
+    let mut b64 = String::new();
+    for txt in txts {
+        if let Some(h) = txt.strip_prefix(CHUNK_PREFIX) {
+            header = Some(h);
+        } else if let Some(d) = txt.strip_prefix(DATA_PREFIX) {
+            b64.push_str(d);
+        }
+    }
+    let mut header = header
+        .ok_or_else(|| invalid("missing chunk header"))?
+        .split('/');
+    let mut next_num = || -> Result<usize, MdnsError> {
+        header
+            .next()
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| invalid("bad chunk header"))
+    };
+    let index = next_num()?;
+    let count = next_num()?;
+    let tag = header
+        .next()
+        .ok_or_else(|| invalid("missing chunk tag"))?
+        .to_string();
+    if count == 0 || count > MAX_RECORD_COUNT || index >= count {
+        return Err(invalid("chunk index out of range"));
+    }
+    let data = base64::decode_config(&b64, base64::URL_SAFE_NO_PAD)?;
+    Ok(Chunk {
+        index,
+        count,
+        tag,
+        data,
+    })
+}
+
+/// Chunks received so far for a single broadcast.
+struct Pending {
+    count: usize,
+    tag: String,
+    started: Instant,
+    chunks: BTreeMap<usize, Vec<u8>>,
+}
+
+/// Collects chunks until a broadcast is complete.
+/// Only the latest broadcast per responder and service name is kept,
+/// and incomplete broadcasts are dropped after [`PENDING_TTL`]
+/// or once there are more than [`MAX_PENDING`] of them.
+#[derive(Default)]
+struct Reassembly(HashMap<(std::net::IpAddr, String), Pending>);
+
+impl Reassembly {
+    /// Add the TXT strings of a record received at `now`.
+    /// Returns the reassembled buffer once the broadcast is complete.
+    fn add(
+        &mut self,
+        addr: std::net::IpAddr,
+        service_name: String,
+        txts: &[String],
+        now: Instant,
+    ) -> Result<Option<Vec<u8>>, MdnsError> {
+        let chunk = decode_record(txts)?;
+        self.0
+            .retain(|_, pending| now.saturating_duration_since(pending.started) < PENDING_TTL);
+        let key = (addr, service_name);
+        if !self.0.contains_key(&key) && self.0.len() >= MAX_PENDING {
+            let oldest = self
+                .0
+                .iter()
+                .min_by_key(|(_, pending)| pending.started)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.0.remove(&oldest);
+            }
+        }
+        let pending = self.0.entry(key.clone()).or_insert_with(|| Pending {
+            count: chunk.count,
+            tag: chunk.tag.clone(),
+            started: now,
+            chunks: BTreeMap::new(),
         });
-    // Done
-    mdns_stream
+        if pending.tag != chunk.tag || pending.count != chunk.count {
+            // the broadcast was replaced, start over
+            *pending = Pending {
+                count: chunk.count,
+                tag: chunk.tag,
+                started: now,
+                chunks: BTreeMap::new(),
+            };
+        }
+        pending.chunks.insert(chunk.index, chunk.data);
+        if pending.chunks.len() < pending.count {
+            return Ok(None);
+        }
+        let pending = self.0.remove(&key).expect("exists");
+        Ok(Some(
+            pending.chunks.into_iter().flat_map(|(_, d)| d).collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reassemble(records: Vec<Vec<String>>) -> Vec<Vec<u8>> {
+        let addr: std::net::IpAddr = [127, 0, 0, 1].into();
+        let mut reassembly = Reassembly::default();
+        records
+            .iter()
+            .filter_map(|txts| {
+                reassembly
+                    .add(addr, "agent".into(), txts, Instant::now())
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_small_buffer_single_record() {
+        let buffer = vec![42; 12];
+        let records = encode_records(&buffer).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(reassemble(records), vec![buffer]);
+    }
+
+    #[test]
+    fn test_large_buffer_reassembled_in_any_order() {
+        let buffer: Vec<u8> = (0..2000).map(|i| (i % 251) as u8).collect();
+        let mut records = encode_records(&buffer).unwrap();
+        assert_eq!(records.len(), 6);
+        for txts in records.iter() {
+            assert!(txts
+                .iter()
+                .all(|t| t.len() <= MAX_TXT_SIZE + DATA_PREFIX.len()));
+        }
+        records.reverse();
+        assert_eq!(reassemble(records), vec![buffer]);
+    }
+
+    #[test]
+    fn test_replaced_broadcast_not_mixed() {
+        let old: Vec<u8> = vec![1; 1000];
+        let new: Vec<u8> = vec![2; 1000];
+        let mut old_records = encode_records(&old).unwrap();
+        let new_records = encode_records(&new).unwrap();
+        // only part of the old broadcast arrives before it is replaced
+        old_records.truncate(2);
+        let records = old_records.into_iter().chain(new_records).collect();
+        assert_eq!(reassemble(records), vec![new]);
+    }
+
+    #[test]
+    fn test_incomplete_broadcasts_dropped() {
+        let addr: std::net::IpAddr = [127, 0, 0, 1].into();
+        let records = encode_records(&[3; 1000]).unwrap();
+        let start = Instant::now();
+
+        // - chunks older than the ttl are dropped
+        let mut reassembly = Reassembly::default();
+        assert!(reassembly
+            .add(addr, "agent".into(), &records[0], start)
+            .unwrap()
+            .is_none());
+        for txts in &records[1..] {
+            assert!(reassembly
+                .add(addr, "agent".into(), txts, start + PENDING_TTL)
+                .unwrap()
+                .is_none());
+        }
+
+        // - at most MAX_PENDING broadcasts are kept, dropping the oldest
+        let mut reassembly = Reassembly::default();
+        for i in 0..=MAX_PENDING {
+            let now = start + Duration::from_millis(i as u64);
+            reassembly
+                .add(addr, format!("agent{}", i), &records[0], now)
+                .unwrap();
+        }
+        assert_eq!(reassembly.0.len(), MAX_PENDING);
+        assert!(!reassembly.0.contains_key(&(addr, "agent0".to_string())));
+    }
+
+    #[test]
+    fn test_too_large() {
+        let buffer = vec![0; MAX_RECORD_SIZE * MAX_RECORD_COUNT + 1];
+        assert!(matches!(
+            encode_records(&buffer),
+            Err(MdnsError::TooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_instance_service_name() {
+        assert_eq!(
+            instance_service_name("abc-d_ef-3._space._udp.local"),
+            Some("abc-d_ef".to_string())
+        );
+        assert_eq!(instance_service_name("noindex._space._udp.local"), None);
+    }
 }
@@ -25,6 +25,13 @@ pub struct ProxyConfig {
     /// If enabled, allow forwarding of messages (proxying)
     /// Default: false.
     pub allow_proxy_fwd: bool,
+
+    /// If enabled, when a peer can only be reached through a proxy,
+    /// try to establish a direct connection to it via hole punching,
+    /// coordinated through that proxy. We keep relaying through the
+    /// proxy if hole punching fails.
+    /// Default: false.
+    pub allow_hole_punch: bool,
 }
 
 impl Default for ProxyConfig {
@@ -32,21 +39,23 @@ impl Default for ProxyConfig {
         Self {
             tuning_params: None,
             allow_proxy_fwd: false,
+            allow_hole_punch: false,
         }
     }
 }
 
 impl ProxyConfig {
     /// into inner contents with default application
-    pub fn split(self) -> KitsuneResult<(KitsuneP2pTuningParams, bool)> {
+    pub fn split(self) -> KitsuneResult<(KitsuneP2pTuningParams, bool, bool)> {
         let ProxyConfig {
             tuning_params,
             allow_proxy_fwd,
+            allow_hole_punch,
         } = self;
 
         let tuning_params = tuning_params.unwrap_or_else(KitsuneP2pTuningParams::default);
 
-        Ok((tuning_params, allow_proxy_fwd))
+        Ok((tuning_params, allow_proxy_fwd, allow_hole_punch))
     }
 }
 
@@ -61,8 +70,22 @@ const PROXY_TYPE_BYTES: usize = 1;
 const DIGEST_BYTES: usize = 32;
 
 const PROXY_FWD_MSG: u8 = 0x30;
+const PROXY_PUNCH_REQ: u8 = 0x31;
+const PROXY_PUNCH_RES: u8 = 0x32;
 const PROXY_ROUTE_ERR: u8 = 0xc0;
 
+/// After hole punching to a peer fails, keep relaying to it
+/// for this long before trying again.
+const PUNCH_RETRY_BACKOFF: std::time::Duration = std::time::Duration::from_secs(60 * 5);
+
+/// How many times to try connecting directly to a peer while hole punching.
+/// The peer dials us at roughly the same time, so our first attempt
+/// may arrive before its nat lets us through.
+const PUNCH_CONNECT_ATTEMPTS: usize = 3;
+
+/// Delay between direct connection attempts while hole punching.
+const PUNCH_CONNECT_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(100);
+
 struct ProxyConHnd {
     uniq: Uniq,
     dir: Tx2ConDir,
@@ -158,9 +181,45 @@ struct ProxyEpInner {
     // these are !OUT CONS! they are returned from api requests / events.
     // these are both INCOMING and OUTGOING
     direct_to_final_peer_con_map: HashMap<Uniq, HashMap<Tx2Cert, ConHnd>>,
+
+    // map peer certs to sub cons connected directly to that peer
+    // these are !SUB CONS! both INCOMING and OUTGOING
+    // preferred over relaying through a proxy,
+    // including direct connections established by hole punching
+    direct_sub_con_map: HashMap<Tx2Cert, ConHnd>,
+
+    // hole punches waiting for the peer's observed address
+    pending_punch_map: HashMap<Tx2Cert, tokio::sync::oneshot::Sender<TxUrl>>,
+
+    // when hole punching to a peer last failed
+    punch_failed_map: HashMap<Tx2Cert, std::time::Instant>,
 }
 
 impl ProxyEpInner {
+    pub fn get_direct_sub_con(&mut self, peer_cert: &Tx2Cert) -> Option<ConHnd> {
+        match self.direct_sub_con_map.get(peer_cert) {
+            Some(sub_con) if !sub_con.is_closed() => Some(sub_con.clone()),
+            Some(_) => {
+                self.direct_sub_con_map.remove(peer_cert);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn set_direct_sub_con(&mut self, sub_con: ConHnd) {
+        match self.direct_sub_con_map.entry(sub_con.peer_cert()) {
+            std::collections::hash_map::Entry::Occupied(mut e) => {
+                if e.get().is_closed() {
+                    e.insert(sub_con);
+                }
+            }
+            std::collections::hash_map::Entry::Vacant(e) => {
+                e.insert(sub_con);
+            }
+        }
+    }
+
     pub fn get_con_hnd(
         &mut self,
         sub_con: ConHnd,
@@ -188,6 +247,8 @@ impl ProxyEpInner {
 }
 
 struct ProxyEpHnd {
+    tuning_params: KitsuneP2pTuningParams,
+    allow_hole_punch: bool,
     sub_ep_hnd: EpHnd,
     local_cert: Tx2Cert,
     logic_hnd: LogicChanHandle<EpEvent>,
@@ -219,22 +280,139 @@ async fn get_con_hnd(
 
 impl ProxyEpHnd {
     pub fn new(
+        tuning_params: KitsuneP2pTuningParams,
+        allow_hole_punch: bool,
         sub_ep_hnd: EpHnd,
         logic_hnd: LogicChanHandle<EpEvent>,
     ) -> KitsuneResult<Arc<ProxyEpHnd>> {
         let local_cert = sub_ep_hnd.local_cert();
         Ok(Arc::new(ProxyEpHnd {
+            tuning_params,
+            allow_hole_punch,
             sub_ep_hnd,
             local_cert,
             logic_hnd,
             inner: Share::new(ProxyEpInner {
                 digest_to_sub_con_map: HashMap::new(),
                 direct_to_final_peer_con_map: HashMap::new(),
+                direct_sub_con_map: HashMap::new(),
+                pending_punch_map: HashMap::new(),
+                punch_failed_map: HashMap::new(),
             }),
         }))
     }
 }
 
+/// Try to establish a direct connection to a peer we currently
+/// reach by relaying through a proxy. Returns None if that fails,
+/// in which case the caller should keep relaying.
+async fn hole_punch(
+    inner: &Share<ProxyEpInner>,
+    sub_ep_hnd: EpHnd,
+    relay_sub_con: ConHnd,
+    local_cert: Tx2Cert,
+    peer_cert: Tx2Cert,
+    timeout: KitsuneTimeout,
+) -> Option<ConHnd> {
+    let (s_addr, r_addr) = tokio::sync::oneshot::channel();
+    let should_punch = inner
+        .share_mut(|i, _| {
+            if let Some(failed_at) = i.punch_failed_map.get(&peer_cert) {
+                if failed_at.elapsed() < PUNCH_RETRY_BACKOFF {
+                    return Ok(false);
+                }
+            }
+            i.pending_punch_map.insert(peer_cert.clone(), s_addr);
+            Ok(true)
+        })
+        .unwrap_or(false);
+    if !should_punch {
+        return None;
+    }
+
+    let res = hole_punch_inner(
+        sub_ep_hnd,
+        relay_sub_con,
+        local_cert,
+        peer_cert.clone(),
+        r_addr,
+        timeout,
+    )
+    .await;
+
+    let _ = inner.share_mut(|i, _| {
+        i.pending_punch_map.remove(&peer_cert);
+        match &res {
+            Ok(sub_con) => {
+                i.punch_failed_map.remove(&peer_cert);
+                i.set_direct_sub_con(sub_con.clone());
+            }
+            Err(_) => {
+                i.punch_failed_map
+                    .insert(peer_cert.clone(), std::time::Instant::now());
+            }
+        }
+        Ok(())
+    });
+
+    match res {
+        Ok(sub_con) => {
+            tracing::debug!(?peer_cert, "hole punch succeeded");
+            Some(sub_con)
+        }
+        Err(err) => {
+            tracing::debug!(?peer_cert, ?err, "hole punch failed, relaying via proxy");
+            None
+        }
+    }
+}
+
+async fn hole_punch_inner(
+    sub_ep_hnd: EpHnd,
+    relay_sub_con: ConHnd,
+    local_cert: Tx2Cert,
+    peer_cert: Tx2Cert,
+    r_addr: tokio::sync::oneshot::Receiver<TxUrl>,
+    timeout: KitsuneTimeout,
+) -> KitsuneResult<ConHnd> {
+    // ask the peer, through the proxy, to dial us,
+    // the proxy fills in the address it sees us connecting from
+    let mut data = PoolBuf::new();
+    data.extend_from_slice(&[PROXY_PUNCH_REQ]);
+    data.extend_from_slice(&peer_cert);
+    data.extend_from_slice(&local_cert);
+    relay_sub_con.write(0.into(), data, timeout).await?;
+
+    // the peer responds with the address the proxy sees it connecting from
+    let peer_addr = timeout
+        .mix(async move {
+            r_addr
+                .await
+                .map_err(|_| KitsuneError::from(KitsuneErrorKind::Closed))
+        })
+        .await?;
+
+    let mut err: KitsuneError = "hole punch not attempted".into();
+    for attempt in 0..PUNCH_CONNECT_ATTEMPTS {
+        if attempt > 0 {
+            tokio::time::sleep(PUNCH_CONNECT_RETRY_DELAY).await;
+        }
+        timeout.ok()?;
+        match sub_ep_hnd.get_connection(peer_addr.clone(), timeout).await {
+            Ok(sub_con) => {
+                // the proxy told us where to connect,
+                // make sure we actually reached the right peer
+                if sub_con.peer_cert() != peer_cert {
+                    return Err("hole punch connected to unexpected peer".into());
+                }
+                return Ok(sub_con);
+            }
+            Err(e) => err = e,
+        }
+    }
+    Err(err)
+}
+
 impl AsEpHnd for ProxyEpHnd {
     fn debug(&self) -> serde_json::Value {
         let addr = self.local_addr();
@@ -275,6 +453,21 @@ impl AsEpHnd for ProxyEpHnd {
         Ok(proxy_addr)
     }
 
+    fn local_addrs(&self) -> KitsuneResult<Vec<TxUrl>> {
+        self.sub_ep_hnd
+            .local_addrs()?
+            .into_iter()
+            .map(|local_addr| {
+                let proxy_addr: TxUrl =
+                    ProxyUrl::new(local_addr.as_str(), self.local_cert.as_digest().clone())
+                        .map_err(KitsuneError::other)?
+                        .as_str()
+                        .into();
+                Ok(proxy_addr)
+            })
+            .collect()
+    }
+
     fn local_cert(&self) -> Tx2Cert {
         self.sub_ep_hnd.local_cert()
     }
@@ -318,10 +511,52 @@ impl AsEpHnd for ProxyEpHnd {
 
         let local_cert = self.local_cert.clone();
         let logic_hnd = self.logic_hnd.clone();
-        let con_fut = self.sub_ep_hnd.get_connection(base_url, timeout);
         let inner = self.inner.clone();
+
+        // prefer any existing direct connection to this peer,
+        // e.g. one established by hole punching
+        let direct = inner.share_mut(|i, _| Ok(i.get_direct_sub_con(&peer_cert)));
+        if let Ok(Some(sub_con)) = direct {
+            return async move {
+                get_con_hnd(&inner, logic_hnd, sub_con, local_cert, peer_cert, true).await
+            }
+            .boxed();
+        }
+
+        let con_fut = self.sub_ep_hnd.get_connection(base_url, timeout);
+        let punch = if self.allow_hole_punch {
+            Some((
+                self.sub_ep_hnd.clone(),
+                self.tuning_params.tx2_hole_punch_timeout_ms as u64,
+            ))
+        } else {
+            None
+        };
         async move {
-            let sub_con = con_fut.await?;
+            let mut sub_con = con_fut.await?;
+            // if the sub con is not to the peer itself, we are relaying
+            // through a proxy, try to get a direct connection instead
+            if sub_con.peer_cert() != peer_cert {
+                if let Some((sub_ep_hnd, punch_ms)) = punch {
+                    // leave at least half the remaining time
+                    // for the relay if punching fails
+                    let punch_ms =
+                        std::cmp::min(punch_ms, timeout.time_remaining().as_millis() as u64 / 2);
+                    let punch_timeout = KitsuneTimeout::from_millis(punch_ms);
+                    if let Some(direct) = hole_punch(
+                        &inner,
+                        sub_ep_hnd,
+                        sub_con.clone(),
+                        local_cert.clone(),
+                        peer_cert.clone(),
+                        punch_timeout,
+                    )
+                    .await
+                    {
+                        sub_con = direct;
+                    }
+                }
+            }
             get_con_hnd(&inner, logic_hnd, sub_con, local_cert, peer_cert, true).await
         }
         .boxed()
@@ -391,11 +626,17 @@ async fn ensure_proxy_register(
 
     // we don't register outgoing connections for proxy-ing
     // that doesn't make any sense.
+    // but any connection can carry direct traffic to its peer.
     if let Tx2ConDir::Outgoing = sub_con.dir() {
+        let _ = inner.share_mut(move |i, _| {
+            i.set_direct_sub_con(sub_con);
+            Ok(())
+        });
         return Ok(());
     }
 
     let _ = inner.share_mut(move |i, _| {
+        i.set_direct_sub_con(sub_con.clone());
         match i.digest_to_sub_con_map.entry(peer_cert.clone()) {
             std::collections::hash_map::Entry::Occupied(mut e) => {
                 if e.get().uniq() != sub_con.uniq() {
@@ -447,17 +688,42 @@ async fn incoming_evt_handle(
                 return;
             };
             match data[0] {
-                PROXY_FWD_MSG => {
+                PROXY_FWD_MSG | PROXY_PUNCH_REQ | PROXY_PUNCH_RES => {
                     const SRC_START: usize = PROXY_TYPE_BYTES + DIGEST_BYTES;
                     const SRC_END: usize = SRC_START + DIGEST_BYTES;
 
                     const DEST_START: usize = PROXY_TYPE_BYTES;
                     const DEST_END: usize = DEST_START + DIGEST_BYTES;
+                    if data.len() < SRC_END {
+                        tracing::error!("Invalid SHORT PROXY FRAME!");
+                        return;
+                    }
+                    let msg_type = data[0];
                     let src_cert = data[SRC_START..SRC_END].to_vec().into();
                     let dest_cert = data[DEST_START..DEST_END].to_vec().into();
                     if dest_cert == hnd.local_cert {
                         // this data is destined for US!
                         data.cheap_move_start(SRC_END);
+                        match msg_type {
+                            PROXY_PUNCH_REQ => {
+                                handle_punch_req(
+                                    tuning_params,
+                                    hnd,
+                                    logic_hnd,
+                                    sub_con,
+                                    local_cert,
+                                    src_cert,
+                                    data,
+                                )
+                                .await;
+                                return;
+                            }
+                            PROXY_PUNCH_RES => {
+                                handle_punch_res(hnd, src_cert, data);
+                                return;
+                            }
+                            _ => (),
+                        }
                         let url = promote_addr(&base_url, &src_cert).unwrap();
                         let con = match get_con_hnd(
                             &hnd.inner,
@@ -480,6 +746,19 @@ async fn incoming_evt_handle(
                         });
                         let _ = logic_hnd.emit(evt).await;
                     } else {
+                        if msg_type != PROXY_FWD_MSG {
+                            // stamp hole punch messages with the address
+                            // we see the sender connecting from,
+                            // which is where the recipient should dial it
+                            let observed = match sub_con.peer_addr() {
+                                Ok(observed) => observed,
+                                Err(_) => return,
+                            };
+                            let mut punch = PoolBuf::new();
+                            punch.extend_from_slice(&data[..SRC_END]);
+                            punch.extend_from_slice(observed.as_str().as_bytes());
+                            data = punch;
+                        }
                         let dest = if !allow_proxy_fwd {
                             tracing::error!("received fwd request on, but proxy fwd is disallowed");
                             Err("proxy fwd disallowed".into())
@@ -506,6 +785,11 @@ async fn incoming_evt_handle(
                             Err(e) => Err(e),
                         } {
                             tracing::warn!("Proxy Fwd Error: {:?}", e);
+                            if msg_type != PROXY_FWD_MSG {
+                                // the hole punch will time out,
+                                // and the sender will keep relaying
+                                return;
+                            }
                             let new_msg_id = if msg_id.is_notify() {
                                 0.into()
                             } else {
@@ -578,6 +862,76 @@ async fn incoming_evt_handle(
     }
 }
 
+/// A peer relaying to us through a proxy wants a direct connection.
+/// Dial it, opening our side of the hole, and let it know where to
+/// dial us back.
+async fn handle_punch_req(
+    tuning_params: &KitsuneP2pTuningParams,
+    hnd: &Arc<ProxyEpHnd>,
+    logic_hnd: &LogicChanHandle<EpEvent>,
+    relay_sub_con: ConHnd,
+    local_cert: Tx2Cert,
+    src_cert: Tx2Cert,
+    data: PoolBuf,
+) {
+    let peer_addr = match parse_observed_addr(&data) {
+        Some(peer_addr) => peer_addr,
+        None => return,
+    };
+
+    if hnd.allow_hole_punch {
+        // if the peer's nat already lets us through,
+        // this is our direct connection
+        let t = KitsuneTimeout::from_millis(tuning_params.tx2_hole_punch_timeout_ms as u64);
+        let con_fut = hnd.sub_ep_hnd.get_connection(peer_addr, t);
+        tokio::task::spawn(async move {
+            if let Err(err) = con_fut.await {
+                tracing::debug!(?err, "hole punch dial failed");
+            }
+        });
+    }
+
+    // the proxy fills in the address it sees us connecting from
+    let mut res = PoolBuf::new();
+    res.extend_from_slice(&[PROXY_PUNCH_RES]);
+    res.extend_from_slice(&src_cert);
+    res.extend_from_slice(&local_cert);
+    let _ = write_to_sub_con(
+        tuning_params,
+        &hnd.inner,
+        logic_hnd,
+        relay_sub_con,
+        0.into(),
+        res,
+    )
+    .await;
+}
+
+/// The peer we are hole punching to told us where to dial it.
+fn handle_punch_res(hnd: &Arc<ProxyEpHnd>, src_cert: Tx2Cert, data: PoolBuf) {
+    let peer_addr = match parse_observed_addr(&data) {
+        Some(peer_addr) => peer_addr,
+        None => return,
+    };
+    if let Ok(Some(s_addr)) = hnd
+        .inner
+        .share_mut(|i, _| Ok(i.pending_punch_map.remove(&src_cert)))
+    {
+        let _ = s_addr.send(peer_addr);
+    }
+}
+
+fn parse_observed_addr(data: &PoolBuf) -> Option<TxUrl> {
+    let addr = std::str::from_utf8(data.as_ref()).ok()?;
+    match url2::try_url2!("{}", addr) {
+        Ok(addr) => Some(addr.into()),
+        Err(err) => {
+            tracing::warn!(?err, "invalid hole punch address");
+            None
+        }
+    }
+}
+
 async fn write_to_sub_con(
     tuning_params: &KitsuneP2pTuningParams,
     inner: &Share<ProxyEpInner>,
@@ -624,6 +978,13 @@ async fn close_connection_inner(
             i.digest_to_sub_con_map.remove(&peer_cert);
         }
 
+        // forget this connection as a direct route to its peer
+        if let Some(sub_con) = i.direct_sub_con_map.get(&peer_cert) {
+            if sub_con.uniq() == direct_peer {
+                i.direct_sub_con_map.remove(&peer_cert);
+            }
+        }
+
         // remove all out cons associated with this exact connection
         Ok(i.direct_to_final_peer_con_map.remove(&direct_peer))
     });
@@ -658,6 +1019,7 @@ impl ProxyEp {
         sub_ep: Ep,
         tuning_params: KitsuneP2pTuningParams,
         allow_proxy_fwd: bool,
+        allow_hole_punch: bool,
     ) -> KitsuneResult<Ep> {
         // this isn't something that needs to be configurable,
         // because it's entirely dependent on the code written here
@@ -668,7 +1030,12 @@ impl ProxyEp {
         let logic_chan = LogicChan::new(LOGIC_CHAN_LIMIT);
         let logic_hnd = logic_chan.handle().clone();
 
-        let hnd = ProxyEpHnd::new(sub_ep.handle().clone(), logic_hnd.clone())?;
+        let hnd = ProxyEpHnd::new(
+            tuning_params.clone(),
+            allow_hole_punch,
+            sub_ep.handle().clone(),
+            logic_hnd.clone(),
+        )?;
 
         let logic = incoming_evt_logic(
             tuning_params,
@@ -708,15 +1075,17 @@ impl AsEp for ProxyEp {
 struct ProxyEpFactory {
     tuning_params: KitsuneP2pTuningParams,
     allow_proxy_fwd: bool,
+    allow_hole_punch: bool,
     sub_fact: EpFactory,
 }
 
 impl ProxyEpFactory {
     pub fn new(sub_fact: EpFactory, config: ProxyConfig) -> KitsuneResult<EpFactory> {
-        let (tuning_params, allow_proxy_fwd) = config.split()?;
+        let (tuning_params, allow_proxy_fwd, allow_hole_punch) = config.split()?;
         let fact: EpFactory = Arc::new(ProxyEpFactory {
             tuning_params,
             allow_proxy_fwd,
+            allow_hole_punch,
             sub_fact,
         });
         Ok(fact)
@@ -732,9 +1101,10 @@ impl AsEpFactory for ProxyEpFactory {
         let tuning_params = self.tuning_params.clone();
         let fut = self.sub_fact.bind(bind_spec, timeout);
         let allow_proxy_fwd = self.allow_proxy_fwd;
+        let allow_hole_punch = self.allow_hole_punch;
         async move {
             let sub_ep = fut.await?;
-            ProxyEp::new(sub_ep, tuning_params, allow_proxy_fwd).await
+            ProxyEp::new(sub_ep, tuning_params, allow_proxy_fwd, allow_hole_punch).await
         }
         .boxed()
     }
@@ -746,16 +1116,28 @@ mod tests {
     use kitsune_p2p_types::tx2::tx2_pool_promote::*;
 
     async fn build_node(
+        s_done: Option<tokio::sync::oneshot::Sender<()>>,
+        expect_err: bool,
+    ) -> (tokio::task::JoinHandle<KitsuneResult<()>>, TxUrl, EpHnd) {
+        build_node_nat(s_done, expect_err, MemNat::None, false).await
+    }
+
+    async fn build_node_nat(
         mut s_done: Option<tokio::sync::oneshot::Sender<()>>,
         expect_err: bool,
+        nat: MemNat,
+        allow_hole_punch: bool,
     ) -> (tokio::task::JoinHandle<KitsuneResult<()>>, TxUrl, EpHnd) {
         let t = KitsuneTimeout::from_millis(5000);
 
-        let f = tx2_mem_adapter(MemConfig::default()).await.unwrap();
+        let mut conf = MemConfig::default();
+        conf.nat = nat;
+        let f = tx2_mem_adapter(conf).await.unwrap();
         let f = tx2_pool_promote(f, Default::default());
 
         let mut conf = ProxyConfig::default();
         conf.allow_proxy_fwd = true;
+        conf.allow_hole_punch = allow_hole_punch;
         let f = tx2_proxy(f, conf).unwrap();
 
         let mut ep = f.bind("none:".into(), t).await.unwrap();
@@ -900,4 +1282,60 @@ mod tests {
 
         futures::future::try_join_all(all_tasks).await.unwrap();
     }
+
+    /// Send "hello" from a node to a target only reachable through a proxy,
+    /// with both nodes behind the given nat.
+    /// Returns true if they ended up directly connected.
+    async fn hole_punch_test(nat: MemNat) -> bool {
+        observability::test_run().ok();
+        let t = KitsuneTimeout::from_millis(5000);
+
+        let (p_join, p_addr, p_ep) = build_node_nat(None, false, MemNat::None, true).await;
+        let (t_join, t_addr, t_ep) = build_node_nat(None, false, nat, true).await;
+
+        // establish proxy connection
+        let _ = t_ep.get_connection(p_addr.clone(), t).await.unwrap();
+        let t_addr_proxy = proxify_addr(&p_addr, &t_addr);
+
+        let (s_done, r_done) = tokio::sync::oneshot::channel();
+        let (n_join, _n_addr, n_ep) = build_node_nat(Some(s_done), false, nat, true).await;
+
+        // whether or not hole punching works, the data gets through
+        let mut data = PoolBuf::new();
+        data.extend_from_slice(b"hello");
+        n_ep.write(t_addr_proxy.clone(), 0.into(), data, t)
+            .await
+            .unwrap();
+        r_done.await.unwrap();
+
+        let con = n_ep.get_connection(t_addr_proxy, t).await.unwrap();
+        let con_host = ProxyUrl::from(con.peer_addr().unwrap().as_str())
+            .as_base()
+            .host_str()
+            .map(str::to_string);
+        let t_host = ProxyUrl::from(t_addr.as_str())
+            .as_base()
+            .host_str()
+            .map(str::to_string);
+
+        n_ep.close(0, "").await;
+        n_join.await.unwrap().unwrap();
+        p_ep.close(0, "").await;
+        t_ep.close(0, "").await;
+        futures::future::try_join_all(vec![p_join, t_join])
+            .await
+            .unwrap();
+
+        con_host == t_host
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_tx2_hole_punch_cone_nat() {
+        assert!(hole_punch_test(MemNat::Cone).await);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_tx2_hole_punch_symmetric_nat_relays() {
+        assert!(!hole_punch_test(MemNat::Symmetric).await);
+    }
 }
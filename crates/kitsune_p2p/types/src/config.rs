@@ -155,6 +155,19 @@ pub mod tuning_params_struct {
         /// multiplied by 2x on every loop)
         /// [Default: 200 ms]
         tx2_initial_connect_retry_delay_ms: usize = 200,
+
+        /// Try to connect directly to peers behind a proxy via hole punching.
+        /// Proxies older than this option close the relay connection
+        /// when asked to punch, so only enable it if every proxy
+        /// you use supports it.
+        /// [Default: false]
+        tx2_allow_hole_punch: bool = false,
+
+        /// How long to spend trying to establish a direct connection
+        /// to a proxied peer via hole punching, before falling back
+        /// to relaying through the proxy.
+        /// [Default: 5 seconds]
+        tx2_hole_punch_timeout_ms: u32 = 1000 * 5,
     }
 
     impl KitsuneP2pTuningParams {
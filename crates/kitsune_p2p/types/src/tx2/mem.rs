@@ -13,9 +13,34 @@ use futures::{
 };
 use once_cell::sync::Lazy;
 use parking_lot::Mutex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic;
 
+/// Simulated NAT behaviour of mem endpoints,
+/// for testing connectivity strategies like hole punching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemNat {
+    /// Not behind a NAT, all incoming connections are accepted.
+    None,
+
+    /// Behind an endpoint-independent ("cone") NAT.
+    /// Incoming connections are only accepted from endpoints
+    /// we have previously attempted to connect to,
+    /// so hole punching succeeds.
+    Cone,
+
+    /// Behind a symmetric NAT.
+    /// Incoming connections are never accepted,
+    /// so hole punching fails and traffic must be relayed.
+    Symmetric,
+}
+
+impl Default for MemNat {
+    fn default() -> Self {
+        MemNat::None
+    }
+}
+
 /// Configuration for MemBackendAdapt
 #[non_exhaustive]
 pub struct MemConfig {
@@ -26,6 +51,10 @@ pub struct MemConfig {
     /// Tuning Params
     /// Default: None = default.
     pub tuning_params: Option<KitsuneP2pTuningParams>,
+
+    /// Simulated NAT behaviour of endpoints bound by this adapter.
+    /// Default: MemNat::None.
+    pub nat: MemNat,
 }
 
 impl Default for MemConfig {
@@ -33,14 +62,19 @@ impl Default for MemConfig {
         Self {
             tls: None,
             tuning_params: None,
+            nat: MemNat::None,
         }
     }
 }
 
 impl MemConfig {
     /// into inner contents with default application
-    pub async fn split(self) -> KitsuneResult<(TlsConfig, KitsuneP2pTuningParams)> {
-        let MemConfig { tls, tuning_params } = self;
+    pub async fn split(self) -> KitsuneResult<(TlsConfig, KitsuneP2pTuningParams, MemNat)> {
+        let MemConfig {
+            tls,
+            tuning_params,
+            nat,
+        } = self;
 
         let tls = match tls {
             None => TlsConfig::new_ephemeral().await?,
@@ -49,7 +83,7 @@ impl MemConfig {
 
         let tuning_params = tuning_params.unwrap_or_else(KitsuneP2pTuningParams::default);
 
-        Ok((tls, tuning_params))
+        Ok((tls, tuning_params, nat))
     }
 }
 
@@ -67,7 +101,39 @@ type ChanRecv = TReceiver<InChan>;
 type ConSend = TSender<Con>;
 type ConRecv = TReceiver<Con>;
 
-type EndpointItem = (ConSend, Active, Tx2Cert);
+/// Simulated NAT state of a single mem endpoint.
+struct MemNatState {
+    nat: MemNat,
+    // ids of remote endpoints we have attempted to connect to
+    pinholes: Mutex<HashSet<u64>>,
+}
+
+impl MemNatState {
+    fn new(nat: MemNat) -> Arc<Self> {
+        Arc::new(Self {
+            nat,
+            pinholes: Mutex::new(HashSet::new()),
+        })
+    }
+
+    /// An outgoing connection attempt opens a pinhole for the remote.
+    fn open_pinhole(&self, remote_id: u64) {
+        if let MemNat::Cone = self.nat {
+            self.pinholes.lock().insert(remote_id);
+        }
+    }
+
+    /// Would an incoming connection from this remote get through?
+    fn accepts(&self, remote_id: u64) -> bool {
+        match self.nat {
+            MemNat::None => true,
+            MemNat::Cone => self.pinholes.lock().contains(&remote_id),
+            MemNat::Symmetric => false,
+        }
+    }
+}
+
+type EndpointItem = (ConSend, Active, Tx2Cert, Arc<MemNatState>);
 static MEM_ENDPOINTS: Lazy<Mutex<HashMap<u64, EndpointItem>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
 
@@ -224,6 +290,7 @@ impl ConRecvAdapt for MemConRecvAdapt {}
 
 struct MemEndpointAdaptInner {
     id: u64,
+    nat: Arc<MemNatState>,
     local_cert: Tx2Cert,
     url: TxUrl,
     ep_active: Active,
@@ -239,13 +306,19 @@ impl Drop for MemEndpointAdaptInner {
 struct MemEndpointAdapt(Mutex<MemEndpointAdaptInner>, Uniq, Tx2Cert);
 
 impl MemEndpointAdapt {
-    pub fn new(c_send: ConSend, id: u64, local_cert: Tx2Cert) -> (Self, Active) {
+    pub fn new(
+        c_send: ConSend,
+        id: u64,
+        nat: Arc<MemNatState>,
+        local_cert: Tx2Cert,
+    ) -> (Self, Active) {
         let url = format!("kitsune-mem://{}", id);
         let ep_active = Active::new();
         (
             Self(
                 Mutex::new(MemEndpointAdaptInner {
                     id,
+                    nat,
                     local_cert: local_cert.clone(),
                     url: url.into(),
                     ep_active: ep_active.clone(),
@@ -293,12 +366,14 @@ impl EndpointAdapt for MemEndpointAdapt {
     }
 
     fn connect(&self, url: TxUrl, timeout: KitsuneTimeout) -> ConFut {
-        let (this_url, local_cert, this_ep_active) = {
+        let (this_id, this_nat, this_url, local_cert, this_ep_active) = {
             let inner = self.0.lock();
             if !inner.ep_active.is_active() {
                 return async move { Err(KitsuneErrorKind::Closed.into()) }.boxed();
             }
             (
+                inner.id,
+                inner.nat.clone(),
                 inner.url.clone(),
                 inner.local_cert.clone(),
                 inner.ep_active.clone(),
@@ -323,9 +398,17 @@ impl EndpointAdapt for MemEndpointAdapt {
                 Ok(id) => id,
             };
 
+            // even a failed attempt opens our pinhole for this remote
+            this_nat.open_pinhole(id);
+
             let (c_send, oth_ep_active, remote_cert) = match MEM_ENDPOINTS.lock().get(&id) {
                 None => return Err(format!("remote not found: {}", url).into()),
-                Some((s, a, d)) => (s.clone(), a.clone(), d.clone()),
+                Some((s, a, d, n)) => {
+                    if !n.accepts(this_id) {
+                        return Err(format!("connection refused by nat: {}", url).into());
+                    }
+                    (s.clone(), a.clone(), d.clone())
+                }
             };
 
             let con_active = Active::new();
@@ -397,13 +480,13 @@ impl EndpointAdapt for MemEndpointAdapt {
 }
 
 /// Memory-based test endpoint adapter for kitsune tx2.
-struct MemBackendAdapt(Tx2Cert);
+struct MemBackendAdapt(Tx2Cert, MemNat);
 
 impl MemBackendAdapt {
     /// Construct a new memory-based test endpoint adapter for kitsune tx2.
     pub async fn new(config: MemConfig) -> KitsuneResult<AdapterFactory> {
-        let (tls, _tuning_params) = config.split().await?;
-        let out: AdapterFactory = Arc::new(Self(tls.cert_digest.into()));
+        let (tls, _tuning_params, nat) = config.split().await?;
+        let out: AdapterFactory = Arc::new(Self(tls.cert_digest.into(), nat));
         Ok(out)
     }
 }
@@ -411,14 +494,16 @@ impl MemBackendAdapt {
 impl BindAdapt for MemBackendAdapt {
     fn bind(&self, _url: TxUrl, timeout: KitsuneTimeout) -> EndpointFut {
         let local_cert = self.0.clone();
+        let nat = MemNatState::new(self.1);
         timeout
             .mix(async move {
                 let id = NEXT_MEM_ID.fetch_add(1, atomic::Ordering::Relaxed);
                 let (c_send, c_recv) = t_chan(32);
-                let (ep, ep_active) = MemEndpointAdapt::new(c_send.clone(), id, local_cert.clone());
+                let (ep, ep_active) =
+                    MemEndpointAdapt::new(c_send.clone(), id, nat.clone(), local_cert.clone());
                 MEM_ENDPOINTS
                     .lock()
-                    .insert(id, (c_send, ep_active.clone(), local_cert));
+                    .insert(id, (c_send, ep_active.clone(), local_cert, nat));
                 let ep: Arc<dyn EndpointAdapt> = Arc::new(ep);
                 let url = ep.local_addr()?;
                 tracing::info!(%url, "bound local endpoint (mem)");
@@ -557,6 +642,44 @@ mod tests {
             .unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_tx2_mem_nat() {
+        let t = KitsuneTimeout::from_millis(5000);
+
+        let open = MemBackendAdapt::new(MemConfig::default()).await.unwrap();
+        let mut conf = MemConfig::default();
+        conf.nat = MemNat::Cone;
+        let cone = MemBackendAdapt::new(conf).await.unwrap();
+        let mut conf = MemConfig::default();
+        conf.nat = MemNat::Symmetric;
+        let symmetric = MemBackendAdapt::new(conf).await.unwrap();
+
+        let (ep_open, _r1) = open.bind("none:".into(), t).await.unwrap();
+        let (ep_cone, _r2) = cone.bind("none:".into(), t).await.unwrap();
+        let (ep_sym, _r3) = symmetric.bind("none:".into(), t).await.unwrap();
+
+        let addr_open = ep_open.local_addr().unwrap();
+        let addr_cone = ep_cone.local_addr().unwrap();
+        let addr_sym = ep_sym.local_addr().unwrap();
+
+        // - everyone can connect out to a node not behind a nat
+        assert!(ep_cone.connect(addr_open.clone(), t).await.is_ok());
+        assert!(ep_sym.connect(addr_open.clone(), t).await.is_ok());
+
+        // - a cone nat only accepts endpoints it has tried to reach
+        assert!(ep_sym.connect(addr_cone.clone(), t).await.is_err());
+        assert!(ep_cone.connect(addr_sym.clone(), t).await.is_err());
+        assert!(ep_sym.connect(addr_cone.clone(), t).await.is_ok());
+
+        // - a symmetric nat never accepts incoming connections
+        assert!(ep_open.connect(addr_sym.clone(), t).await.is_err());
+        assert!(ep_cone.connect(addr_sym, t).await.is_err());
+
+        ep_open.close(0, "").await;
+        ep_cone.close(0, "").await;
+        ep_sym.close(0, "").await;
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_tx2_mem() {
         let t = KitsuneTimeout::from_millis(5000);
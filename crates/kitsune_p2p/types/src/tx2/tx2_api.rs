@@ -423,6 +423,11 @@ impl<C: Codec + 'static + Send + Unpin> Tx2EpHnd<C> {
         self.0.local_addr()
     }
 
+    /// Get all bound local addresses of this endpoint.
+    pub fn local_addrs(&self) -> KitsuneResult<Vec<TxUrl>> {
+        self.0.local_addrs()
+    }
+
     /// Get the local certificate digest.
     pub fn local_cert(&self) -> Tx2Cert {
         self.0.local_cert()
@@ -0,0 +1,351 @@
+#![allow(clippy::new_ret_no_self)]
+//! Bind multiple tx2 transport frontends simultaneously,
+//! presenting them as a single endpoint.
+
+use crate::tx2::tx2_adapter::Uniq;
+use crate::tx2::tx2_pool::*;
+use crate::tx2::tx2_utils::*;
+use crate::tx2::*;
+use crate::*;
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::Stream;
+use std::collections::HashMap;
+
+/// Bind multiple tx2 transport frontends simultaneously.
+/// Each sub factory is paired with the bind spec it should be bound to,
+/// the bind_spec passed to the resulting factory is ignored.
+/// All sub factories must be configured with the same tls certificate.
+/// Outgoing connections are routed to the sub endpoint(s) whose
+/// local address scheme matches the remote url scheme.
+pub fn tx2_multi(subs: Vec<(EpFactory, TxUrl)>) -> KitsuneResult<EpFactory> {
+    if subs.is_empty() {
+        return Err("tx2_multi requires at least one sub factory".into());
+    }
+    let out: EpFactory = Arc::new(MultiFactory(Arc::new(subs)));
+    Ok(out)
+}
+
+// -- private -- //
+
+struct MultiEpHnd {
+    uniq: Uniq,
+    local_cert: Tx2Cert,
+    subs: Arc<Vec<EpHnd>>,
+
+    /// remote url -> index of the sub endpoint that last reached it
+    routes: Share<HashMap<TxUrl, usize>>,
+}
+
+impl MultiEpHnd {
+    /// indexes of the sub endpoints able to reach this remote,
+    /// with any previously successful sub endpoint first
+    fn candidates(&self, remote: &TxUrl) -> Vec<usize> {
+        let scheme = remote.scheme();
+        let mut out = self
+            .subs
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| match s.local_addr() {
+                Ok(addr) => addr.scheme() == scheme,
+                Err(_) => false,
+            })
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        if let Ok(Some(prev)) = self.routes.share_mut(|i, _| Ok(i.get(remote).cloned())) {
+            if let Some(pos) = out.iter().position(|idx| *idx == prev) {
+                out.remove(pos);
+                out.insert(0, prev);
+            }
+        }
+        out
+    }
+}
+
+impl AsEpHnd for MultiEpHnd {
+    fn debug(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "tx2_multi",
+            "state": if self.is_closed() { "closed" } else { "open" },
+            "sub_endpoints": self.subs.iter().map(|s| s.debug()).collect::<Vec<_>>(),
+        })
+    }
+
+    fn uniq(&self) -> Uniq {
+        self.uniq
+    }
+
+    fn local_addr(&self) -> KitsuneResult<TxUrl> {
+        self.subs[0].local_addr()
+    }
+
+    fn local_addrs(&self) -> KitsuneResult<Vec<TxUrl>> {
+        self.subs.iter().map(|s| s.local_addr()).collect()
+    }
+
+    fn local_cert(&self) -> Tx2Cert {
+        self.local_cert.clone()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.subs.iter().all(|s| s.is_closed())
+    }
+
+    fn close(&self, code: u32, reason: &str) -> BoxFuture<'static, ()> {
+        self.routes.close();
+        let all = self
+            .subs
+            .iter()
+            .map(|s| s.close(code, reason))
+            .collect::<Vec<_>>();
+        async move {
+            futures::future::join_all(all).await;
+        }
+        .boxed()
+    }
+
+    fn close_connection(&self, remote: TxUrl, code: u32, reason: &str) -> BoxFuture<'static, ()> {
+        let _ = self.routes.share_mut(|i, _| {
+            i.remove(&remote);
+            Ok(())
+        });
+        let all = self
+            .candidates(&remote)
+            .into_iter()
+            .map(|idx| self.subs[idx].close_connection(remote.clone(), code, reason))
+            .collect::<Vec<_>>();
+        async move {
+            futures::future::join_all(all).await;
+        }
+        .boxed()
+    }
+
+    fn get_connection(
+        &self,
+        remote: TxUrl,
+        timeout: KitsuneTimeout,
+    ) -> BoxFuture<'static, KitsuneResult<ConHnd>> {
+        let candidates = self.candidates(&remote);
+        let subs = self.subs.clone();
+        let routes = self.routes.clone();
+        async move {
+            if candidates.is_empty() {
+                return Err(format!("no bound transport supports {}", remote).into());
+            }
+            let mut last_err = None;
+            for idx in candidates {
+                if timeout.is_expired() {
+                    break;
+                }
+                match subs[idx].get_connection(remote.clone(), timeout).await {
+                    Ok(con) => {
+                        let _ = routes.share_mut(|i, _| {
+                            i.insert(remote, idx);
+                            Ok(())
+                        });
+                        return Ok(con);
+                    }
+                    Err(e) => last_err = Some(e),
+                }
+            }
+            Err(last_err.unwrap_or_else(|| KitsuneErrorKind::TimedOut.into()))
+        }
+        .boxed()
+    }
+}
+
+struct MultiEp {
+    hnd: EpHnd,
+    sub_count: usize,
+    closed_count: usize,
+    evt: futures::stream::SelectAll<Ep>,
+}
+
+impl Stream for MultiEp {
+    type Item = EpEvent;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        loop {
+            match Stream::poll_next(std::pin::Pin::new(&mut self.evt), cx) {
+                std::task::Poll::Ready(Some(EpEvent::EndpointClosed)) => {
+                    // only report closed once all sub endpoints are closed
+                    self.closed_count += 1;
+                    if self.closed_count >= self.sub_count {
+                        return std::task::Poll::Ready(Some(EpEvent::EndpointClosed));
+                    }
+                }
+                r => return r,
+            }
+        }
+    }
+}
+
+impl AsEp for MultiEp {
+    fn handle(&self) -> &EpHnd {
+        &self.hnd
+    }
+}
+
+struct MultiFactory(Arc<Vec<(EpFactory, TxUrl)>>);
+
+impl AsEpFactory for MultiFactory {
+    fn bind(
+        &self,
+        _bind_spec: TxUrl,
+        timeout: KitsuneTimeout,
+    ) -> BoxFuture<'static, KitsuneResult<Ep>> {
+        let all = self
+            .0
+            .iter()
+            .map(|(f, bind_to)| f.bind(bind_to.clone(), timeout))
+            .collect::<Vec<_>>();
+        timeout
+            .mix(async move {
+                let eps = futures::future::join_all(all).await;
+
+                // if any bind failed, close the successful ones
+                if eps.iter().any(|r| r.is_err()) {
+                    let mut err = None;
+                    let mut close = Vec::new();
+                    for r in eps {
+                        match r {
+                            Ok(ep) => close.push(ep.handle().close(0, "multi bind error")),
+                            Err(e) => {
+                                if err.is_none() {
+                                    err = Some(e);
+                                }
+                            }
+                        }
+                    }
+                    futures::future::join_all(close).await;
+                    return Err(err.unwrap());
+                }
+                let eps = eps.into_iter().map(|r| r.unwrap()).collect::<Vec<_>>();
+
+                let local_cert = eps[0].handle().local_cert();
+                if eps.iter().any(|ep| ep.handle().local_cert() != local_cert) {
+                    futures::future::join_all(
+                        eps.iter()
+                            .map(|ep| ep.handle().close(0, "multi cert mismatch")),
+                    )
+                    .await;
+                    return Err("tx2_multi sub endpoints have differing certificates".into());
+                }
+
+                let subs = Arc::new(eps.iter().map(|ep| ep.handle().clone()).collect::<Vec<_>>());
+                let hnd: EpHnd = Arc::new(MultiEpHnd {
+                    uniq: Uniq::default(),
+                    local_cert,
+                    subs,
+                    routes: Share::new(HashMap::new()),
+                });
+
+                let sub_count = eps.len();
+                let ep: Ep = Box::new(MultiEp {
+                    hnd,
+                    sub_count,
+                    closed_count: 0,
+                    evt: futures::stream::select_all(eps),
+                });
+                Ok(ep)
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tls::*;
+    use crate::tx2::tx2_pool_promote::*;
+    use futures::stream::StreamExt;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_tx2_multi() {
+        let t = KitsuneTimeout::from_millis(5000);
+
+        let tls = TlsConfig::new_ephemeral().await.unwrap();
+        let mk_sub = || {
+            let tls = tls.clone();
+            async move {
+                let mut conf = MemConfig::default();
+                conf.tls = Some(tls);
+                let f = tx2_mem_adapter(conf).await.unwrap();
+                (
+                    tx2_pool_promote(f, Default::default()),
+                    TxUrl::from("none:"),
+                )
+            }
+        };
+
+        let fact = tx2_multi(vec![mk_sub().await, mk_sub().await]).unwrap();
+        let mut tgt = fact.bind("none:".into(), t).await.unwrap();
+        let tgt_hnd = tgt.handle().clone();
+
+        let addrs = tgt_hnd.local_addrs().unwrap();
+        assert_eq!(2, addrs.len());
+        assert_ne!(addrs[0], addrs[1]);
+        assert_eq!(addrs[0], tgt_hnd.local_addr().unwrap());
+
+        let tgt_task = tokio::task::spawn(async move {
+            let mut count = 0;
+            while let Some(evt) = tgt.next().await {
+                match evt {
+                    EpEvent::IncomingData(EpIncomingData { con, mut data, .. }) => {
+                        assert_eq!(b"hello", data.as_ref());
+                        data.clear();
+                        data.extend_from_slice(b"world");
+                        con.write(0.into(), data, t).await.unwrap();
+                    }
+                    EpEvent::EndpointClosed => {
+                        count += 1;
+                    }
+                    _ => (),
+                }
+            }
+            count
+        });
+
+        let src_fact = tx2_pool_promote(
+            tx2_mem_adapter(MemConfig::default()).await.unwrap(),
+            Default::default(),
+        );
+
+        // we should be reachable on either bound address
+        for addr in addrs {
+            let mut src = src_fact.bind("none:".into(), t).await.unwrap();
+            let src_hnd = src.handle().clone();
+
+            let mut data = PoolBuf::new();
+            data.extend_from_slice(b"hello");
+            src_hnd.write(addr, 0.into(), data, t).await.unwrap();
+
+            loop {
+                match src.next().await {
+                    Some(EpEvent::IncomingData(EpIncomingData { data, .. })) => {
+                        assert_eq!(b"world", data.as_ref());
+                        break;
+                    }
+                    Some(_) => (),
+                    None => panic!("unexpected src endpoint end"),
+                }
+            }
+
+            src_hnd.close(0, "").await;
+        }
+
+        // unsupported schemes are rejected outright
+        assert!(tgt_hnd
+            .get_connection("kitsune-quic://127.0.0.1:1".into(), t)
+            .await
+            .is_err());
+
+        tgt_hnd.close(0, "").await;
+        assert!(tgt_hnd.is_closed());
+
+        // the closed event should be emitted exactly once
+        assert_eq!(1, tgt_task.await.unwrap());
+    }
+}
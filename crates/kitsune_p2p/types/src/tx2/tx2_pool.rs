@@ -50,6 +50,12 @@ pub trait AsEpHnd: 'static + Send + Sync + Unpin {
     /// Get the bound local address of this endpoint.
     fn local_addr(&self) -> KitsuneResult<TxUrl>;
 
+    /// Get all bound local addresses of this endpoint.
+    /// Endpoints binding multiple transports will return more than one.
+    fn local_addrs(&self) -> KitsuneResult<Vec<TxUrl>> {
+        Ok(vec![self.local_addr()?])
+    }
+
     /// Get the local certificate digest.
     fn local_cert(&self) -> Tx2Cert;
 
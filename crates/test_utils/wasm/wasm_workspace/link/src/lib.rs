@@ -31,6 +31,11 @@ fn get_links(_: ()) -> ExternResult<Links> {
     hdk::prelude::get_links(base()?, None)
 }
 
+#[hdk_extern]
+fn count_links(_: ()) -> ExternResult<u32> {
+    hdk::prelude::count_links(base()?, None, LinkFilter::default())
+}
+
 #[hdk_extern]
 fn delete_all_links(_: ()) -> ExternResult<()> {
     for link in hdk::prelude::get_links(base()?, None)?.into_inner() {
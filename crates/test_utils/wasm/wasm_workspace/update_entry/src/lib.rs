@@ -6,7 +6,10 @@ struct Post(String);
 #[hdk_entry(id = "msg", required_validations = 5)]
 struct Msg(String);
 
-entry_defs![Post::entry_def(), Msg::entry_def()];
+#[hdk_entry(id = "tags", crdt_type = "grow_only_set")]
+struct Tags(GrowOnlySet);
+
+entry_defs![Post::entry_def(), Msg::entry_def(), Tags::entry_def()];
 
 fn post() -> Post {
     Post("foo".into())
@@ -16,6 +19,14 @@ fn msg() -> Msg {
     Msg("hi".into())
 }
 
+fn tags(items: &[&str]) -> ExternResult<Tags> {
+    let mut set = GrowOnlySet::new();
+    for item in items {
+        set.insert(&item.to_string())?;
+    }
+    Ok(Tags(set))
+}
+
 #[hdk_extern]
 fn create_entry(_: ()) -> ExternResult<HeaderHash> {
     hdk::prelude::create_entry(&post())
@@ -41,3 +52,23 @@ fn invalid_update_entry(_: ()) -> ExternResult<HeaderHash> {
     let header_hash = hdk::prelude::create_entry(&post())?;
     hdk::prelude::update_entry(header_hash, &msg())
 }
+
+#[hdk_extern]
+/// Updates the same tags twice so there are two latest versions to merge
+fn create_concurrent_tags(_: ()) -> ExternResult<EntryHash> {
+    let original = tags(&["a"])?;
+    let header_hash = hdk::prelude::create_entry(&original)?;
+    hdk::prelude::update_entry(header_hash.clone(), &tags(&["a", "b"])?)?;
+    hdk::prelude::update_entry(header_hash, &tags(&["a", "c"])?)?;
+    hash_entry(&original)
+}
+
+#[hdk_extern]
+fn get_tags(entry_hash: EntryHash) -> ExternResult<Option<Element>> {
+    get(entry_hash, GetOptions::latest())
+}
+
+#[hdk_extern]
+fn get_merged_tags(entry_hash: EntryHash) -> ExternResult<Option<MergedEntry>> {
+    get_merged(entry_hash, GetOptions::latest())
+}
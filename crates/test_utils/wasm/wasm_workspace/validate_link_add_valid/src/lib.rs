@@ -1,6 +1,41 @@
 use hdk::prelude::*;
 
+#[hdk_link_type(id = "rating")]
+struct Rating {
+    stars: u8,
+}
+
 #[hdk_extern]
 pub fn validate_create_link(_: ValidateCreateLinkData) -> ExternResult<ValidateLinkCallbackResult> {
     Ok(ValidateLinkCallbackResult::Valid)
 }
+
+#[hdk_extern]
+pub fn validate_create_link_rating(
+    validate_create_link_data: ValidateCreateLinkData,
+) -> ExternResult<ValidateLinkCallbackResult> {
+    Ok(
+        match Rating::try_from_link_tag(&validate_create_link_data.link_add.tag)? {
+            Some(Rating { stars }) if stars <= 5 => ValidateLinkCallbackResult::Valid,
+            Some(_) => ValidateLinkCallbackResult::Invalid("ratings are out of five".to_string()),
+            None => ValidateLinkCallbackResult::Invalid("not a rating".to_string()),
+        },
+    )
+}
+
+fn base() -> ExternResult<EntryHash> {
+    Ok(agent_info()?.agent_latest_pubkey.into())
+}
+
+#[hdk_extern]
+fn add_rating(stars: u8) -> ExternResult<HeaderHash> {
+    create_typed_link(base()?, base()?, Rating { stars })
+}
+
+#[hdk_extern]
+fn get_ratings(_: ()) -> ExternResult<Vec<u8>> {
+    Ok(get_typed_links::<Rating>(base()?)?
+        .into_iter()
+        .map(|(_, rating)| rating.stars)
+        .collect())
+}
@@ -1,6 +1,88 @@
 use hdk::prelude::*;
 
+#[hdk_entry(id = "post", validate = true)]
+struct Post(String);
+
+#[hdk_entry(id = "note", visibility = "private", validate = true)]
+struct Note(String);
+
+impl ValidateEntry for Post {
+    fn validate_create(self, _: ValidateData) -> ExternResult<ValidateCallbackResult> {
+        Ok(if self.0.len() <= 280 {
+            ValidateCallbackResult::Valid
+        } else {
+            ValidateCallbackResult::Invalid("Posts are at most 280 characters".to_string())
+        })
+    }
+
+    fn validate_update(
+        self,
+        original: Self,
+        _: ValidateData,
+    ) -> ExternResult<ValidateCallbackResult> {
+        Ok(if original.0.starts_with("final") {
+            ValidateCallbackResult::Invalid("Final posts can't be updated".to_string())
+        } else {
+            ValidateCallbackResult::Valid
+        })
+    }
+
+    fn validate_delete(deleted: Self, _: ValidateData) -> ExternResult<ValidateCallbackResult> {
+        Ok(if deleted.0.starts_with("final") {
+            ValidateCallbackResult::Invalid("Final posts can't be deleted".to_string())
+        } else {
+            ValidateCallbackResult::Valid
+        })
+    }
+}
+
+impl ValidateEntry for Note {
+    fn validate_update(
+        self,
+        original: Self,
+        _: ValidateData,
+    ) -> ExternResult<ValidateCallbackResult> {
+        Ok(if original.0.starts_with("final") {
+            ValidateCallbackResult::Invalid("Final notes can't be updated".to_string())
+        } else {
+            ValidateCallbackResult::Valid
+        })
+    }
+}
+
+entry_defs![Post::entry_def(), Note::entry_def()];
+
 #[hdk_extern]
 fn validate(_: ValidateData) -> ExternResult<ValidateCallbackResult> {
     Ok(ValidateCallbackResult::Valid)
 }
+
+#[hdk_extern]
+fn create_post(text: String) -> ExternResult<HeaderHash> {
+    create_entry(&Post(text))
+}
+
+#[hdk_extern]
+fn update_post((original, text): (HeaderHash, String)) -> ExternResult<HeaderHash> {
+    update_entry(original, &Post(text))
+}
+
+#[hdk_extern]
+fn delete_post(header_hash: HeaderHash) -> ExternResult<HeaderHash> {
+    delete_entry(header_hash)
+}
+
+#[hdk_extern]
+fn create_note(text: String) -> ExternResult<HeaderHash> {
+    create_entry(&Note(text))
+}
+
+#[hdk_extern]
+fn update_note((original, text): (HeaderHash, String)) -> ExternResult<HeaderHash> {
+    update_entry(original, &Note(text))
+}
+
+#[hdk_extern]
+fn delete_note(header_hash: HeaderHash) -> ExternResult<HeaderHash> {
+    delete_entry(header_hash)
+}